@@ -0,0 +1,23 @@
+use std::process::Command;
+
+/// Bake the commit the binary was built from into `YC_GIT_HASH`, with a
+/// `-dirty` suffix for uncommitted changes. Left unset outside a checkout.
+fn main() {
+    let git = |args: &[&str]| {
+        Command::new("git")
+            .args(args)
+            .output()
+            .ok()
+            .filter(|o| o.status.success())
+            .map(|o| String::from_utf8_lossy(&o.stdout).trim().to_string())
+    };
+    println!("cargo:rerun-if-changed=build.rs");
+    if let Some(dir) = git(&["rev-parse", "--absolute-git-dir"]) {
+        println!("cargo:rerun-if-changed={}/HEAD", dir);
+        println!("cargo:rerun-if-changed={}/index", dir);
+    }
+    if let Some(hash) = git(&["rev-parse", "HEAD"]) {
+        let dirty = git(&["status", "--porcelain", "--untracked-files=no"]).is_some_and(|s| !s.is_empty());
+        println!("cargo:rustc-env=YC_GIT_HASH={}{}", hash, if dirty { "-dirty" } else { "" });
+    }
+}
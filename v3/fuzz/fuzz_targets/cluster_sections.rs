@@ -0,0 +1,20 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+
+mod parser;
+
+use parser::{blocks, lang, sections, ParserConfig};
+
+fuzz_target!(|input: (u8, &str)| {
+    let (min_meta_cluster, markdown) = input;
+    // Loose thresholds reach branches the defaults never take.
+    let config = ParserConfig { min_meta_cluster: min_meta_cluster as usize % 8, ..ParserConfig::default() };
+    let lexed = blocks::lex_with(markdown, &lang::Lexicon::english(), &config);
+    let sections = sections::cluster_sections_with(&lexed, &config);
+    let clustered: usize = sections.iter().map(|s| s.blocks.len()).sum();
+    assert_eq!(clustered, lexed.blocks.len(), "every block lands in exactly one section");
+    for s in &sections {
+        assert_eq!(s.blocks.len(), s.spans.len());
+    }
+});
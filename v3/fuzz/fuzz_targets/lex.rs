@@ -0,0 +1,17 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+
+mod parser;
+
+use parser::{blocks, lang, ParserConfig};
+
+fuzz_target!(|markdown: &str| {
+    let lexicon = lang::Lexicon::for_lang(lang::detect_page(markdown));
+    let lexed = blocks::lex_with(markdown, &lexicon, &ParserConfig::default());
+    assert_eq!(lexed.blocks.len(), lexed.spans.len());
+    let lines = markdown.lines().count().max(1);
+    for span in &lexed.spans {
+        assert!(span.start_line >= 1 && span.start_line <= span.end_line && span.end_line <= lines);
+    }
+});
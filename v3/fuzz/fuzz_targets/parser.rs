@@ -0,0 +1,16 @@
+//! The parts of `src/parser` that turn markdown into sections, compiled
+//! standalone under the same module names so their `super::` paths resolve.
+#![allow(dead_code)]
+
+#[path = "../../src/parser/blocks.rs"]
+pub mod blocks;
+#[path = "../../src/parser/config.rs"]
+mod config;
+#[path = "../../src/parser/dates.rs"]
+pub mod dates;
+#[path = "../../src/parser/lang.rs"]
+pub mod lang;
+#[path = "../../src/parser/sections.rs"]
+pub mod sections;
+
+pub use config::ParserConfig;
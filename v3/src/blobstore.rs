@@ -0,0 +1,231 @@
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, OnceLock};
+
+use anyhow::{Context, Result};
+use serde::Deserialize;
+
+/// `[storage]` in `yc.toml`.
+#[derive(Debug, Default, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct StorageConfig {
+    /// Where scraped markdown goes instead of `page_data.markdown`:
+    /// a directory (`data/pages`, `file:///srv/yc`), `s3://bucket/prefix`
+    /// or `gs://bucket/prefix`. Unset keeps markdown in SQLite.
+    pub pages: Option<String>,
+}
+
+/// Raw page storage outside the DB. Keys are relative `/`-separated paths.
+pub trait BlobStore: Send + Sync {
+    fn put(&self, key: &str, data: &[u8]) -> Result<()>;
+    fn get(&self, key: &str) -> Result<Vec<u8>>;
+    fn delete(&self, key: &str) -> Result<()>;
+    /// Location shown in messages, e.g. `s3://bucket/prefix`.
+    fn describe(&self) -> String;
+}
+
+static SHARED: OnceLock<Option<Arc<dyn BlobStore>>> = OnceLock::new();
+
+/// The `[storage] pages` store, if one is configured. Opened the first
+/// time it's needed and kept for the rest of the process.
+pub fn shared() -> Result<Option<Arc<dyn BlobStore>>> {
+    if let Some(store) = SHARED.get() {
+        return Ok(store.clone());
+    }
+    let store = crate::config::get()?.storage.pages.as_deref().map(open).transpose()?;
+    Ok(SHARED.get_or_init(|| store).clone())
+}
+
+pub fn open(location: &str) -> Result<Arc<dyn BlobStore>> {
+    let location = location.trim();
+    match location.split_once("://") {
+        Some(("file", path)) => Ok(Arc::new(LocalDir::new(path))),
+        Some((scheme @ ("s3" | "gs"), rest)) => remote(scheme, rest),
+        Some((scheme, _)) => anyhow::bail!("unsupported storage scheme '{}' in {}", scheme, location),
+        None => Ok(Arc::new(LocalDir::new(location))),
+    }
+}
+
+/// Key for one scrape: `{slug}/{scraped at, UTC}-{digest}.md`, the digest
+/// being the first 16 hex digits of the markdown's SHA-256. It doesn't
+/// depend on the `page_data` row, so a page can be uploaded before that
+/// row is written.
+pub fn page_key(slug: &str, scraped_at: chrono::DateTime<chrono::Utc>, markdown: &[u8]) -> String {
+    key(slug, scraped_at, markdown, "md")
+}
+
+/// Key for a scrape's `--keep-raw` copy, stored as `page_data.markdown_raw`
+/// would hold it (plain or gzip): `{slug}/{scraped at, UTC}-{digest}.raw`.
+pub fn raw_key(slug: &str, scraped_at: chrono::DateTime<chrono::Utc>, stored: &[u8]) -> String {
+    key(slug, scraped_at, stored, "raw")
+}
+
+fn key(slug: &str, scraped_at: chrono::DateTime<chrono::Utc>, data: &[u8], extension: &str) -> String {
+    use sha2::{Digest, Sha256};
+
+    let digest: String = Sha256::digest(data)[..8].iter().map(|b| format!("{:02x}", b)).collect();
+    format!("{}/{}-{}.{}", slug, scraped_at.format("%Y%m%dT%H%M%SZ"), digest, extension)
+}
+
+/// Files under a local directory.
+pub struct LocalDir {
+    root: PathBuf,
+}
+
+impl LocalDir {
+    pub fn new(root: impl AsRef<Path>) -> Self {
+        Self { root: root.as_ref().to_path_buf() }
+    }
+
+    fn path(&self, key: &str) -> Result<PathBuf> {
+        if key.split('/').any(|part| part.is_empty() || part == "." || part == "..") {
+            anyhow::bail!("invalid blob key '{}'", key);
+        }
+        Ok(self.root.join(key))
+    }
+}
+
+impl BlobStore for LocalDir {
+    fn put(&self, key: &str, data: &[u8]) -> Result<()> {
+        let path = self.path(key)?;
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir).with_context(|| format!("creating {}", dir.display()))?;
+        }
+        // Readers never see a half-written file
+        let tmp = path.with_extension("tmp");
+        std::fs::write(&tmp, data).with_context(|| format!("writing {}", tmp.display()))?;
+        std::fs::rename(&tmp, &path).with_context(|| format!("renaming to {}", path.display()))?;
+        Ok(())
+    }
+
+    fn get(&self, key: &str) -> Result<Vec<u8>> {
+        let path = self.path(key)?;
+        std::fs::read(&path).with_context(|| format!("reading {}", path.display()))
+    }
+
+    fn delete(&self, key: &str) -> Result<()> {
+        let path = self.path(key)?;
+        match std::fs::remove_file(&path) {
+            Err(e) if e.kind() != std::io::ErrorKind::NotFound => {
+                Err(e).with_context(|| format!("deleting {}", path.display()))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    fn describe(&self) -> String {
+        self.root.display().to_string()
+    }
+}
+
+#[cfg(not(feature = "blobstore"))]
+fn remote(scheme: &str, _rest: &str) -> Result<Arc<dyn BlobStore>> {
+    anyhow::bail!("{}:// page storage needs a build with the blobstore feature", scheme)
+}
+
+#[cfg(feature = "blobstore")]
+fn remote(scheme: &str, rest: &str) -> Result<Arc<dyn BlobStore>> {
+    use object_store::aws::AmazonS3Builder;
+    use object_store::gcp::GoogleCloudStorageBuilder;
+
+    let (bucket, prefix) = rest.split_once('/').unwrap_or((rest, ""));
+    if bucket.is_empty() {
+        anyhow::bail!("missing bucket in {}://{}", scheme, rest);
+    }
+    // Credentials and region come from the usual AWS_* / GOOGLE_* variables
+    let inner: Arc<dyn object_store::ObjectStore> = match scheme {
+        "s3" => Arc::new(AmazonS3Builder::from_env().with_bucket_name(bucket).build()?),
+        _ => Arc::new(GoogleCloudStorageBuilder::from_env().with_bucket_name(bucket).build()?),
+    };
+    let runtime = tokio::runtime::Builder::new_multi_thread()
+        .worker_threads(1)
+        .enable_all()
+        .build()?;
+    Ok(Arc::new(ObjectStoreBlobs {
+        inner,
+        runtime,
+        url: format!("{}://{}", scheme, rest.trim_end_matches('/')),
+        prefix: prefix.trim_matches('/').to_string(),
+    }))
+}
+
+/// S3 / GCS through `object_store`. Calls come from sync code (SQLite
+/// functions, rayon workers, the async scraper's save path), so requests
+/// run on a runtime of their own, entered from a scoped thread: blocking
+/// on it directly would panic inside the main runtime.
+#[cfg(feature = "blobstore")]
+struct ObjectStoreBlobs {
+    inner: Arc<dyn object_store::ObjectStore>,
+    runtime: tokio::runtime::Runtime,
+    url: String,
+    prefix: String,
+}
+
+#[cfg(feature = "blobstore")]
+impl ObjectStoreBlobs {
+    fn path(&self, key: &str) -> Result<object_store::path::Path> {
+        let full = if self.prefix.is_empty() { key.to_string() } else { format!("{}/{}", self.prefix, key) };
+        Ok(object_store::path::Path::parse(full)?)
+    }
+
+    fn block_on<T: Send>(&self, fut: impl std::future::Future<Output = Result<T>> + Send) -> Result<T> {
+        std::thread::scope(|s| {
+            s.spawn(|| self.runtime.block_on(fut))
+                .join()
+                .map_err(|_| anyhow::anyhow!("blob store request panicked"))?
+        })
+    }
+}
+
+#[cfg(feature = "blobstore")]
+impl BlobStore for ObjectStoreBlobs {
+    fn put(&self, key: &str, data: &[u8]) -> Result<()> {
+        let path = self.path(key)?;
+        let payload = object_store::PutPayload::from(data.to_vec());
+        self.block_on(async { Ok(self.inner.put(&path, payload).await.map(|_| ())?) })
+            .with_context(|| format!("uploading {}/{}", self.url, key))
+    }
+
+    fn get(&self, key: &str) -> Result<Vec<u8>> {
+        let path = self.path(key)?;
+        self.block_on(async { Ok(self.inner.get(&path).await?.bytes().await?.to_vec()) })
+            .with_context(|| format!("downloading {}/{}", self.url, key))
+    }
+
+    fn delete(&self, key: &str) -> Result<()> {
+        let path = self.path(key)?;
+        self.block_on(async {
+            match self.inner.delete(&path).await {
+                Err(object_store::Error::NotFound { .. }) => Ok(()),
+                other => Ok(other?),
+            }
+        })
+        .with_context(|| format!("deleting {}/{}", self.url, key))
+    }
+
+    fn describe(&self) -> String {
+        self.url.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn local_dir_round_trips_and_rejects_escaping_keys() {
+        let root = std::env::temp_dir().join(format!("yc-blobs-{}", std::process::id()));
+        let store = open(&format!("file://{}", root.display())).unwrap();
+        store.put("acme/20240101T000000Z-1.md", b"# Acme").unwrap();
+        assert_eq!(store.get("acme/20240101T000000Z-1.md").unwrap(), b"# Acme");
+        store.delete("acme/20240101T000000Z-1.md").unwrap();
+        store.delete("acme/20240101T000000Z-1.md").unwrap();
+        assert!(store.get("acme/20240101T000000Z-1.md").is_err());
+        assert!(store.put("../outside.md", b"x").is_err());
+        assert!(open("ftp://host/x").is_err());
+        let _ = std::fs::remove_dir_all(&root);
+
+        let at = chrono::DateTime::parse_from_rfc3339("2024-03-05T06:07:08Z").unwrap().to_utc();
+        assert_eq!(page_key("acme", at, b"# Acme"), "acme/20240305T060708Z-7f0d13a031835369.md");
+        assert_eq!(raw_key("acme", at, b"# Acme"), "acme/20240305T060708Z-7f0d13a031835369.raw");
+    }
+}
@@ -0,0 +1,178 @@
+use std::collections::{HashMap, VecDeque};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use anyhow::{bail, Context, Result};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// Cassette file layout version, checked on `--replay`.
+const VERSION: u32 = 1;
+
+/// spider.cloud responses saved by `--record` and served back by
+/// `--replay`, so the scraping path runs without network or credits.
+pub struct Cassette {
+    path: PathBuf,
+    mode: Mode,
+}
+
+enum Mode {
+    /// Interactions in the order their responses arrived.
+    Record(Mutex<Vec<Interaction>>),
+    /// Responses per request in recorded order. The last one stays, so a
+    /// request made more often than recorded gets the final answer again.
+    Replay(Mutex<HashMap<Request, VecDeque<Response>>>),
+}
+
+/// What was asked of spider.cloud; replay matches on all of it.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct Request {
+    /// "scrape" or "crawl"
+    pub kind: String,
+    pub url: String,
+    /// Browser rendering requested
+    #[serde(default)]
+    pub render: bool,
+    /// "markdown" or "html"
+    pub format: String,
+}
+
+/// The response body, or the client error returned instead.
+pub type Response = std::result::Result<Value, String>;
+
+#[derive(Serialize, Deserialize)]
+struct Interaction {
+    #[serde(flatten)]
+    request: Request,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    body: Option<Value>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct File {
+    version: u32,
+    interactions: Vec<Interaction>,
+}
+
+impl Cassette {
+    /// An empty cassette, written to `path` by `save`.
+    pub fn record(path: &Path) -> Cassette {
+        Cassette { path: path.to_path_buf(), mode: Mode::Record(Mutex::new(Vec::new())) }
+    }
+
+    /// A cassette read from a `--record` file.
+    pub fn replay(path: &Path) -> Result<Cassette> {
+        let text = std::fs::read_to_string(path).with_context(|| format!("reading cassette {}", path.display()))?;
+        let file: File =
+            serde_json::from_str(&text).with_context(|| format!("parsing cassette {}", path.display()))?;
+        if file.version != VERSION {
+            bail!("cassette {} is version {}; this build reads version {}", path.display(), file.version, VERSION);
+        }
+        let mut responses: HashMap<Request, VecDeque<Response>> = HashMap::new();
+        for i in file.interactions {
+            let response = match (i.body, i.error) {
+                (_, Some(error)) => Err(error),
+                (body, None) => Ok(body.unwrap_or(Value::Null)),
+            };
+            responses.entry(i.request).or_default().push_back(response);
+        }
+        Ok(Cassette { path: path.to_path_buf(), mode: Mode::Replay(Mutex::new(responses)) })
+    }
+
+    pub fn replaying(&self) -> bool {
+        matches!(self.mode, Mode::Replay(_))
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Note a live response while recording.
+    pub fn add(&self, request: Request, response: &Response) {
+        if let Mode::Record(interactions) = &self.mode {
+            let (body, error) = match response {
+                Ok(body) => (Some(body.clone()), None),
+                Err(e) => (None, Some(e.clone())),
+            };
+            interactions.lock().unwrap().push(Interaction { request, body, error });
+        }
+    }
+
+    /// The next recorded response to `request`. A request the cassette
+    /// doesn't hold comes back as an error, like a failed call would.
+    pub fn take(&self, request: &Request) -> Response {
+        let Mode::Replay(responses) = &self.mode else {
+            return Err("cassette is recording, not replaying".to_string());
+        };
+        let mut responses = responses.lock().unwrap();
+        let queue = responses.get_mut(request).filter(|q| !q.is_empty()).ok_or_else(|| {
+            format!("not in cassette {}: {} {}", self.path.display(), request.kind, request.url)
+        })?;
+        if queue.len() > 1 {
+            queue.pop_front().unwrap()
+        } else {
+            queue[0].clone()
+        }
+    }
+
+    /// Write what was recorded, grouped by request so re-recording the
+    /// same pages gives a small diff. Returns the number of responses.
+    pub fn save(&self) -> Result<usize> {
+        let Mode::Record(interactions) = &self.mode else {
+            return Ok(0);
+        };
+        let mut interactions = std::mem::take(&mut *interactions.lock().unwrap());
+        // Stable, so repeats of one request keep their order
+        interactions.sort_by(|a, b| a.request.cmp(&b.request));
+        let n = interactions.len();
+        let file = File { version: VERSION, interactions };
+        std::fs::write(&self.path, serde_json::to_string_pretty(&file)?)
+            .with_context(|| format!("writing cassette {}", self.path.display()))?;
+        Ok(n)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn request(url: &str, render: bool) -> Request {
+        Request { kind: "scrape".to_string(), url: url.to_string(), render, format: "markdown".to_string() }
+    }
+
+    #[test]
+    fn recorded_responses_replay_in_order() {
+        let path = std::env::temp_dir().join(format!("yc-cassette-test-{}.json", std::process::id()));
+        let recorder = Cassette::record(&path);
+        recorder.add(request("https://x.test/b", false), &Ok(serde_json::json!([{ "content": "B" }])));
+        recorder.add(request("https://x.test/a", false), &Err("429 Too Many Requests".to_string()));
+        recorder.add(request("https://x.test/a", true), &Ok(serde_json::json!([{ "content": "A rendered" }])));
+        recorder.add(request("https://x.test/a", false), &Ok(serde_json::json!([{ "content": "A" }])));
+        assert_eq!(recorder.save().unwrap(), 4);
+
+        let saved = std::fs::read_to_string(&path).unwrap();
+        assert!(saved.find("429").unwrap() < saved.find("\"A\"").unwrap(), "repeats keep their order: {}", saved);
+        let cassette = Cassette::replay(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert!(cassette.replaying());
+
+        let a = request("https://x.test/a", false);
+        assert_eq!(cassette.take(&a), Err("429 Too Many Requests".to_string()));
+        assert_eq!(cassette.take(&a).unwrap()[0]["content"], "A");
+        assert_eq!(cassette.take(&a).unwrap()[0]["content"], "A", "the last response repeats");
+        assert_eq!(cassette.take(&request("https://x.test/a", true)).unwrap()[0]["content"], "A rendered");
+        let missing = cassette.take(&request("https://x.test/c", false)).unwrap_err();
+        assert!(missing.starts_with("not in cassette") && missing.ends_with("scrape https://x.test/c"), "{}", missing);
+    }
+
+    #[test]
+    fn unknown_versions_are_refused() {
+        let path = std::env::temp_dir().join(format!("yc-cassette-version-test-{}.json", std::process::id()));
+        std::fs::write(&path, r#"{"version": 99, "interactions": []}"#).unwrap();
+        let err = Cassette::replay(&path).err().unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert!(err.to_string().contains("version 99"), "{}", err);
+    }
+}
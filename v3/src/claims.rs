@@ -0,0 +1,78 @@
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::thread::JoinHandle;
+use std::time::Duration;
+
+use anyhow::Result;
+use rusqlite::Connection;
+
+use crate::db;
+
+/// How long claims and locks outlive their last renewal. A run that dies
+/// without releasing them frees its pages after this.
+pub const TTL: Duration = Duration::from_secs(10 * 60);
+
+const RENEW_EVERY: Duration = Duration::from_secs(2 * 60);
+
+/// Poll interval while another run holds a lock.
+const LOCK_POLL: Duration = Duration::from_secs(2);
+
+/// Page claims and advisory locks held by one invocation, so concurrent
+/// `scrape` / `run` / `refresh` against the same DB split the queue instead
+/// of scraping the same pages. A background thread with its own connection
+/// renews everything held every couple of minutes and releases it all when
+/// this is dropped.
+pub struct Claims {
+    owner: String,
+    stop: Option<mpsc::Sender<()>>,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl Claims {
+    pub fn start(owner: &str) -> Result<Self> {
+        let conn = db::connect()?;
+        let owner = owner.to_string();
+        let (stop, stopped) = mpsc::channel::<()>();
+        let thread = std::thread::Builder::new().name("claims".to_string()).spawn({
+            let owner = owner.clone();
+            move || {
+                while let Err(RecvTimeoutError::Timeout) = stopped.recv_timeout(RENEW_EVERY) {
+                    if let Err(e) = db::renew_claims(&conn, &owner, TTL) {
+                        tracing::warn!("renewing claims: {:#}", e);
+                    }
+                }
+                if let Err(e) = db::release_claims(&conn, &owner) {
+                    tracing::warn!("releasing claims (they expire on their own): {:#}", e);
+                }
+            }
+        })?;
+        Ok(Claims { owner, stop: Some(stop), thread: Some(thread) })
+    }
+
+    /// The subset of `pages` this run now holds; the rest belong to a run
+    /// that claimed them first.
+    pub fn pages(&self, conn: &Connection, pages: Vec<(i64, String, String)>) -> Result<Vec<(i64, String, String)>> {
+        db::claim_pages(conn, pages, &self.owner, TTL)
+    }
+
+    /// Block until the advisory lock `name` is ours.
+    pub fn lock(&self, conn: &Connection, name: &str) -> Result<()> {
+        let mut waiting = false;
+        while let Some(holder) = db::try_lock(conn, name, &self.owner, TTL)? {
+            if !waiting {
+                tracing::info!("waiting for run {} to finish {}", holder, name);
+                waiting = true;
+            }
+            std::thread::sleep(LOCK_POLL);
+        }
+        Ok(())
+    }
+}
+
+impl Drop for Claims {
+    fn drop(&mut self) {
+        drop(self.stop.take());
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
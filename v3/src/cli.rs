@@ -0,0 +1,2306 @@
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+use chrono::Datelike;
+use clap::{CommandFactory, FromArgMatches, Parser, Subcommand};
+
+use crate::output::out;
+use crate::{
+    blobstore, cassette, claims, config, db, demo, email, enrich, experiment, explain, export, fixups, graph, hooks,
+    linkcheck, manifest, model, output, parser, publish, report, resolve, review, scraper, sink, sitemap, slugs,
+    telemetry, writer,
+};
+
+#[derive(Parser)]
+#[command(name = "yc_scraper", about = "YC company scraper via spider.cloud")]
+struct Cli {
+    #[command(subcommand)]
+    command: Commands,
+    /// Only operate on slugs listed in this file (newline list or CSV with a slug column)
+    #[arg(long, global = true, value_name = "PATH")]
+    slugs_file: Option<PathBuf>,
+    /// Settings file with `[hooks]` and `[parser]` (default: ./yc.toml when present)
+    #[arg(long, global = true, value_name = "PATH")]
+    config: Option<PathBuf>,
+    /// Print nothing but data (JSON lines, CSV) and errors
+    #[arg(short, long, global = true)]
+    quiet: bool,
+    /// Print one JSON result object (counts, errors, timings) instead of text
+    #[arg(long, global = true, conflicts_with = "quiet")]
+    json: bool,
+}
+
+#[derive(Subcommand)]
+enum Commands {
+    /// Fetch sitemap and populate URL queue
+    Init {
+        /// Queue only companies listed for these batches, comma-separated (e.g. W24,S24)
+        #[arg(short, long, value_delimiter = ',')]
+        batch: Vec<model::Batch>,
+    },
+    /// Queue companies from the directory, Launch YC and batch listings before the sitemap lists them
+    Discover {
+        /// Batch listings to check, comma-separated (default: the current and next batch)
+        #[arg(short, long, value_delimiter = ',')]
+        batch: Vec<model::Batch>,
+    },
+    /// Run scrape → process → overview on bundled fixture pages in a temp DB (no API key)
+    Demo {
+        /// Keep the temp DB instead of deleting it afterwards
+        #[arg(long)]
+        keep: bool,
+    },
+    /// Scrape unvisited pages via spider.cloud
+    Scrape {
+        /// Max pages to scrape (default: all unvisited)
+        #[arg(short = 'n', long)]
+        limit: Option<usize>,
+        /// Leave pages whose request failed unvisited, so the next run retries them
+        #[arg(long)]
+        retry_errors: bool,
+        /// Re-fetch pages that come back blocked (captcha, far shorter than usual) with browser rendering
+        #[arg(long)]
+        failover: bool,
+        /// How pages are requested from spider.cloud
+        #[arg(long, value_enum, default_value = "single")]
+        mode: scraper::ScrapeMode,
+        /// Also keep each page before images are stripped, in page_data.markdown_raw (plain, or gzip)
+        #[arg(long, value_enum, num_args = 0..=1, default_missing_value = "plain")]
+        keep_raw: Option<scraper::RawFormat>,
+        /// Page content to request: spider.cloud's markdown, or the raw HTML, parsed without the markdown conversion
+        #[arg(long, value_enum, default_value = "markdown")]
+        return_format: scraper::PageFormat,
+        /// Which unvisited pages go first: discovery order, shuffled, newest batch, or least recently scraped
+        #[arg(long, value_enum, default_value = "id")]
+        order: db::ScrapeOrder,
+        #[command(flatten)]
+        cassette: CassetteArgs,
+    },
+    /// Split scraped markdown into sections
+    Process {
+        /// Output: sqlite (default), jsonl (stdout), or an http(s):// ingest URL
+        #[arg(long, default_value = "sqlite")]
+        sink: sink::SinkKind,
+        /// Max pages to process (default: all unprocessed)
+        #[arg(short = 'n', long)]
+        limit: Option<usize>,
+        /// Also print each extracted company to stdout after the sink stores it
+        #[arg(long, value_enum)]
+        emit: Option<sink::Emit>,
+        /// Only emit; write nothing to the DB (same as --sink jsonl)
+        #[arg(long, requires = "emit", conflicts_with = "sink")]
+        no_db: bool,
+        #[command(flatten)]
+        parallelism: Parallelism,
+    },
+    /// Scrape + process in one pipeline (each page processed immediately after scraping)
+    Run {
+        /// Output for extracted data: sqlite (default), jsonl (stdout), or an http(s):// ingest URL
+        #[arg(long, default_value = "sqlite")]
+        sink: sink::SinkKind,
+        /// Max pages to scrape+process
+        #[arg(short = 'n', long)]
+        limit: Option<usize>,
+        /// Leave pages whose request failed unvisited, so the next run retries them
+        #[arg(long)]
+        retry_errors: bool,
+        /// Re-fetch pages that come back blocked (captcha, far shorter than usual) with browser rendering
+        #[arg(long)]
+        failover: bool,
+        /// How pages are requested from spider.cloud
+        #[arg(long, value_enum, default_value = "single")]
+        mode: scraper::ScrapeMode,
+        /// Also keep each page before images are stripped, in page_data.markdown_raw (plain, or gzip)
+        #[arg(long, value_enum, num_args = 0..=1, default_missing_value = "plain")]
+        keep_raw: Option<scraper::RawFormat>,
+        /// Page content to request: spider.cloud's markdown, or the raw HTML, parsed without the markdown conversion
+        #[arg(long, value_enum, default_value = "markdown")]
+        return_format: scraper::PageFormat,
+        /// Which unvisited pages go first: discovery order, shuffled, newest batch, or least recently scraped
+        #[arg(long, value_enum, default_value = "id")]
+        order: db::ScrapeOrder,
+        #[command(flatten)]
+        cassette: CassetteArgs,
+        #[command(flatten)]
+        parallelism: Parallelism,
+    },
+    /// Re-scrape + process pages the sitemap says changed since our last visit,
+    /// then pages without a lastmod that are older than --days
+    Refresh {
+        /// Output for extracted data: sqlite (default), jsonl (stdout), or an http(s):// ingest URL
+        #[arg(long, default_value = "sqlite")]
+        sink: sink::SinkKind,
+        /// Max pages to refresh
+        #[arg(short = 'n', long)]
+        limit: Option<usize>,
+        /// Age after which pages with no sitemap lastmod are refreshed anyway
+        #[arg(long, default_value = "90")]
+        days: f64,
+        /// Use the lastmod values already stored instead of re-fetching the sitemap
+        #[arg(long)]
+        no_sitemap: bool,
+        /// Also keep each page before images are stripped, in page_data.markdown_raw (plain, or gzip)
+        #[arg(long, value_enum, num_args = 0..=1, default_missing_value = "plain")]
+        keep_raw: Option<scraper::RawFormat>,
+        #[command(flatten)]
+        parallelism: Parallelism,
+    },
+    /// Scrape YC partners page, store partners, match to companies
+    Partners,
+    /// Crawl industry tag pages into tag_catalog and rewrite company tags to the canonical spellings
+    Tags {
+        /// Max tag pages to fetch
+        #[arg(short = 'n', long)]
+        limit: Option<usize>,
+    },
+    /// Follow news redirects (HEAD, bounded), dedup articles by canonical URL, rebuild news_global
+    ResolveNews {
+        /// Max news rows to resolve (default: all unresolved)
+        #[arg(short = 'n', long)]
+        limit: Option<usize>,
+    },
+    /// Follow job apply URLs through YC / Work at a Startup redirects; store final URL + ATS vendor
+    ResolveJobs {
+        /// Max job rows to resolve (default: all unresolved)
+        #[arg(short = 'n', long)]
+        limit: Option<usize>,
+    },
+    /// Scrape job detail pages; split body sections and tag tech stack / benefits chips
+    ScrapeJobs {
+        /// Max job pages to scrape (default: all without details)
+        #[arg(short = 'n', long)]
+        limit: Option<usize>,
+    },
+    /// Show scraping statistics
+    Stats {
+        /// Per-run extraction totals from recent process/run/refresh runs instead
+        #[arg(long)]
+        runs: bool,
+    },
+    /// Inserts, updates and deletes to companies, founders and jobs since a time, as JSON lines
+    Changes {
+        /// UTC start, inclusive: "2024-05-01", "2024-05-01 12:00:00" or RFC 3339
+        #[arg(long)]
+        since: model::Timestamp,
+    },
+    /// Merge founders whose names differ only in case, spacing, or diacritics
+    DedupeFounders {
+        /// List duplicate groups without merging
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Give founderless companies the founders a looser pass finds in their stored founders section
+    RecoverFounders {
+        /// List recovered founders without saving them
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Companies not scraped recently, grouped by batch
+    Stale {
+        /// Age threshold in days since the latest successful scrape
+        #[arg(long, default_value = "90")]
+        days: f64,
+        /// Also write the stale slugs to this file (for --slugs-file)
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+    },
+    /// Reclaim free pages, ANALYZE and REINDEX; prints size before/after
+    Maintain,
+    /// Move markdown already stored in SQLite to the [storage] pages blob store
+    OffloadPages {
+        /// Move at most N scrapes, oldest first
+        #[arg(short = 'n', long)]
+        limit: Option<usize>,
+    },
+    /// Stored regex replacements for text artifacts in extracted columns
+    Fixups {
+        #[command(subcommand)]
+        action: FixupAction,
+    },
+    /// Run two extractor variants over the scraped corpus and report where they disagree
+    Experiment {
+        #[command(subcommand)]
+        action: ExperimentAction,
+    },
+    /// Copy the extracted tables into a typed DuckDB file with keys (`--features duckdb`)
+    /// or an Excel workbook (`--format xlsx`, `--features xlsx`)
+    Export {
+        /// Output format
+        #[arg(long, value_enum, default_value = "duckdb")]
+        format: export::ExportFormat,
+        /// File to write (replaced if it exists)
+        #[arg(long, visible_alias = "duckdb", value_name = "PATH", required_unless_present = "per_company")]
+        out: Option<PathBuf>,
+        /// Instead, write one JSON file per company (company, founders, jobs, news, links, sections) into DIR
+        #[arg(long, value_name = "DIR", conflicts_with_all = ["format", "out"])]
+        per_company: Option<PathBuf>,
+        /// Hash or drop contact details per the `[redact]` policy in yc.toml
+        #[arg(long)]
+        redact: bool,
+    },
+    /// The network of YC companies whose pages link to each other
+    Graph {
+        #[command(subcommand)]
+        action: GraphAction,
+    },
+    /// Write a public dataset bundle: CSVs, data dictionary, license stub, row counts
+    PublishPrep {
+        /// Output directory (default: data/publish/yc-<date>)
+        #[arg(short, long)]
+        out: Option<PathBuf>,
+        /// SPDX license identifier for the LICENSE stub and manifest
+        #[arg(long, default_value = "CC-BY-4.0")]
+        license: String,
+        /// Hash or drop contact details per the `[redact]` policy in yc.toml
+        #[arg(long)]
+        redact: bool,
+    },
+    /// Pages with the longest parse times, with block/section counts
+    Slowest {
+        /// Max rows to display
+        #[arg(short = 'n', long, default_value = "20")]
+        limit: usize,
+    },
+    /// Print a company's latest scraped page with each line's section and lexed blocks
+    Explain {
+        /// Company slug
+        slug: String,
+    },
+    /// Fill derived columns older rows lack (batch season/year, company age, job salary ranges)
+    Backfill {
+        /// Report what would be filled without writing
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Check DB integrity: orphaned rows per foreign key, SQLite quick_check
+    Doctor {
+        /// Delete orphaned rows instead of only reporting them
+        #[arg(long)]
+        repair: bool,
+    },
+    /// Delete a company and everything derived from it (e.g. on a removal request),
+    /// or with --orphans every company whose page left the sitemap
+    Purge {
+        /// Company slug; it is never queued again from the sitemap
+        #[arg(required_unless_present = "orphans", conflicts_with = "orphans")]
+        slug: Option<String>,
+        /// Purge pages no longer in the sitemap (fetches it; they may be re-added if they return)
+        #[arg(long)]
+        orphans: bool,
+        /// Recorded in purged_companies with the slug
+        #[arg(long, requires = "slug")]
+        reason: Option<String>,
+        /// Report what would be deleted without deleting it
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Check external company links and aggregate per-domain health
+    CheckLinks {
+        /// Max links to check (default: all unchecked)
+        #[arg(short = 'n', long)]
+        limit: Option<usize>,
+    },
+    /// Founder Twitter/X profile stats → founder_social_stats, from the [enrich] provider
+    Enrich {
+        /// Max handles to look up (default: all without fresh stats)
+        #[arg(short = 'n', long)]
+        limit: Option<usize>,
+    },
+    /// Booking links (calendly, cal.com, ...) found on company pages
+    Meetings {
+        #[command(subcommand)]
+        action: MeetingsAction,
+    },
+    /// Analysis reports over extracted data (all sections unless one is selected)
+    #[command(args_conflicts_with_subcommands = true)]
+    Report {
+        #[command(subcommand)]
+        view: Option<ReportView>,
+        /// Founders matched to 2+ YC companies (by LinkedIn, Twitter, or name)
+        #[arg(long)]
+        repeat_founders: bool,
+        /// Link domains most associated with inactive companies (needs check-links)
+        #[arg(long)]
+        domain_health: bool,
+        /// Companies failing extraction cross-checks (e.g. founder count mismatch)
+        #[arg(long)]
+        quality: bool,
+        /// Processed companies that still have no founder rows
+        #[arg(long)]
+        missing_founders: bool,
+        /// Email an HTML summary of the last finished run to [email] recipients (`--features email`)
+        #[arg(long, conflicts_with_all = ["repeat_founders", "domain_health", "quality", "missing_founders"])]
+        email: bool,
+    },
+    /// Walk flagged extractions with their source lines and record verdicts/corrections
+    Review {
+        /// Max items to queue
+        #[arg(short = 'n', long, default_value = "50")]
+        limit: usize,
+    },
+    /// Cluster unknown page sections by first line and block shape
+    ExtrasReport {
+        /// Only show patterns seen at this many companies
+        #[arg(long, default_value = "2")]
+        min_companies: usize,
+        /// Max patterns to display
+        #[arg(short = 'n', long, default_value = "30")]
+        limit: usize,
+    },
+    /// Companies overview table
+    Overview {
+        /// Filter by status, comma-separated (Active, Public, Acquired, Inactive)
+        #[arg(short, long, value_delimiter = ',')]
+        status: Vec<model::CompanyStatus>,
+        /// Filter by batch, comma-separated (e.g. "Winter 2024" or W24,S24)
+        #[arg(short, long, value_delimiter = ',')]
+        batch: Vec<model::Batch>,
+        /// Max rows (or groups, with --group-by) to display
+        #[arg(short = 'n', long, default_value = "50")]
+        limit: usize,
+        /// Aggregate matching companies per group instead of listing them
+        #[arg(long, value_enum)]
+        group_by: Option<report::OverviewGroup>,
+        /// Row order
+        #[arg(long, value_enum, default_value = "batch")]
+        sort: report::OverviewSort,
+        /// Only companies with quality_score at least this (0-100)
+        #[arg(long, value_parser = clap::value_parser!(i32).range(0..=100))]
+        min_quality: Option<i32>,
+        /// With --group-by, keep companies whose founding year is flagged founded_year_inconsistent
+        #[arg(long)]
+        include_flagged: bool,
+    },
+}
+
+#[derive(Subcommand)]
+enum ReportView {
+    /// Side-by-side batch metrics: tags, team size, hiring, geography, exits
+    Compare {
+        /// Batch to compare; repeat or comma-separate (e.g. --batch W23 --batch W24)
+        #[arg(short, long, value_delimiter = ',', required = true)]
+        batch: Vec<model::Batch>,
+        /// Output format
+        #[arg(long, value_enum, default_value = "markdown")]
+        format: report::CompareFormat,
+        /// Keep companies whose founding year is flagged founded_year_inconsistent
+        #[arg(long)]
+        include_flagged: bool,
+    },
+    /// Job listing stats (emoji by role bucket, stack and meme keywords); saved to job_stats
+    Jobs,
+    /// How many companies have each quality_score input, and how scores are spread
+    Coverage,
+    /// Survival, exits and team size by batch, batch year or company age (CSV)
+    Cohorts {
+        /// Cohort grouping
+        #[arg(long, value_enum, default_value = "batch")]
+        by: report::CohortBy,
+        /// Keep founding years flagged founded_year_inconsistent (excluded from age cohorts by default)
+        #[arg(long)]
+        include_flagged: bool,
+    },
+}
+
+#[derive(Subcommand)]
+enum FixupAction {
+    /// Show stored rules with the rows each has changed
+    List,
+    /// Store a rule replacing PATTERN with REPLACEMENT in TABLE.COLUMN
+    Add {
+        /// Target column as table.column (e.g. founders.bio)
+        target: String,
+        /// Regex (Rust `regex` syntax)
+        pattern: String,
+        /// Replacement; `$1` inserts a capture group, `$$` a literal `$`
+        #[arg(default_value = "")]
+        replacement: String,
+        /// Why the rule exists
+        #[arg(long)]
+        note: Option<String>,
+    },
+    /// Store the built-in rules (markdown escapes, HTML entities, zero-width characters)
+    Seed,
+    /// Delete a rule
+    Remove { id: i64 },
+    /// Run the rules over their columns
+    Apply {
+        /// Print the changes as a diff and roll them back
+        #[arg(long)]
+        dry_run: bool,
+        /// Only run these rules; repeat or comma-separate
+        #[arg(long, value_delimiter = ',')]
+        id: Vec<i64>,
+    },
+}
+
+#[derive(Subcommand)]
+enum MeetingsAction {
+    /// Links by platform and batch, with dead-link rates from liveness checks
+    Report {
+        /// Check unchecked booking URLs first (results land in link_checks)
+        #[arg(long)]
+        check: bool,
+        /// Max URLs to check with --check (default: all unchecked)
+        #[arg(short = 'n', long, requires = "check")]
+        limit: Option<usize>,
+    },
+}
+
+#[derive(Subcommand)]
+enum GraphAction {
+    /// Write the company reference edges (stdout unless --out)
+    Export {
+        #[arg(long, value_enum, default_value = "csv")]
+        format: graph::GraphFormat,
+        /// File to write instead of stdout
+        #[arg(long, value_name = "PATH")]
+        out: Option<PathBuf>,
+        /// Leave out edges to companies that are not scraped yet
+        #[arg(long)]
+        known_only: bool,
+    },
+}
+
+#[derive(Subcommand)]
+enum ExperimentAction {
+    /// Show registered experiments
+    List,
+    /// Parse the latest scrape of every company with both variants
+    Run {
+        /// Experiment name (see 'experiment list')
+        name: String,
+        /// Max pages to compare (default: all)
+        #[arg(short = 'n', long)]
+        limit: Option<usize>,
+        /// Example slugs shown per differing field
+        #[arg(long, default_value = "3")]
+        examples: usize,
+    },
+}
+
+/// The `yc_scraper` command line. Install a custom extractor registry
+/// (`parser::extract::custom::install`) before calling it to have `process`
+/// store that registry's rows.
+#[tokio::main]
+pub async fn main() -> anyhow::Result<()> {
+    let _telemetry = telemetry::init()?;
+
+    let t0 = Instant::now();
+    let matches = Cli::command().get_matches();
+    let cli = Cli::from_arg_matches(&matches)?;
+    let command_name = matches.subcommand_name().unwrap_or_default().to_string();
+    output::init(match (cli.quiet, cli.json) {
+        (_, true) => output::Mode::Json,
+        (true, false) => output::Mode::Quiet,
+        (false, false) => output::Mode::Human,
+    });
+    if cli.json && writes_data(&cli.command) {
+        anyhow::bail!("'{}' writes its output to stdout; use --quiet instead of --json", command_name);
+    }
+    let result = run(cli).await;
+
+    // stderr, so `process --emit jsonl | jq` sees only JSON on stdout
+    let elapsed = t0.elapsed();
+    if elapsed.as_secs() >= 1 && !output::quiet() {
+        eprintln!("\nDone in {}", format_duration(elapsed));
+    }
+
+    if output::mode() == output::Mode::Json {
+        let outcome = output::finish(&command_name, &result, elapsed);
+        println!("{}", serde_json::to_string(&outcome)?);
+        if !outcome.ok {
+            drop(_telemetry);
+            std::process::exit(1);
+        }
+        return Ok(());
+    }
+    result
+}
+
+/// The subcommand itself; `main` reports how it went.
+async fn run(cli: Cli) -> anyhow::Result<()> {
+    let slugs_file = cli.slugs_file.as_deref();
+    config::set_path(cli.config.clone());
+
+    match cli.command {
+        Commands::Init { batch } => {
+            let conn = db::connect()?;
+            db::init_schema(&conn)?;
+            let mut pages = sitemap::fetch_company_urls().await?;
+            if !batch.is_empty() {
+                pages = filter_to_batches(pages, &batch).await?;
+            }
+            let inserted = db::insert_pages(&conn, &pages)?;
+            output::count("found", pages.len());
+            output::count("inserted", inserted);
+            out!("Inserted {} new company URLs ({} total found)", inserted, pages.len());
+            Ok(())
+        }
+        Commands::Discover { batch } => {
+            let conn = db::connect()?;
+            db::init_schema(&conn)?;
+            let batches = if batch.is_empty() {
+                let current = model::Batch::on(chrono::Utc::now().date_naive());
+                vec![current, current.next()]
+            } else {
+                batch
+            };
+            run_discover(&conn, &batches).await
+        }
+        Commands::Demo { keep } => {
+            let path = std::env::temp_dir().join(format!("yc-demo-{}.sqlite", std::process::id()));
+            // Declared before `conn` so the file outlives the connection
+            let _cleanup = DemoDb { path: path.clone(), keep };
+            let conn = db::connect_at(&path)?;
+            db::init_schema(&conn)?;
+            out!("Demo DB: {}", path.display());
+
+            let scraped = demo::seed(&conn)?;
+            out!("Scraped {} bundled fixture pages (no API key needed).", scraped);
+            let total = db::count_unprocessed(&conn, None)?;
+            out!("Processing {} pages...", total);
+            let counts = process_pages(
+                sink::open(&sink::SinkKind::Sqlite, &conn)?.as_mut(),
+                db::stream_unprocessed(&conn, PROCESS_CHUNK, None),
+                total,
+                &config::get()?.parser,
+                Parallelism::default(),
+            )?;
+            counts.print(false);
+            out!();
+            print_overview(&db::fetch_overview(&conn, &[], &[], None, false, i64::MAX as usize)?);
+
+            if keep {
+                out!("Kept {}", path.display());
+            }
+            Ok(())
+        }
+        Commands::Scrape { limit, retry_errors, failover, mode, keep_raw, return_format, order, cassette } => {
+            let config = config::get()?;
+            let cassette = cassette.open()?;
+            let conn = db::connect()?;
+            db::init_schema(&conn)?;
+            apply_slug_scope(&conn, slugs_file)?;
+            let pages = db::fetch_unvisited(&conn, limit, order)?;
+            if pages.is_empty() {
+                out!("No unvisited pages. Run 'init' first or all pages are scraped.");
+                return Ok(());
+            }
+            let mut info = hooks::RunInfo::new("scrape", db::DB_PATH);
+            let claims = claims::Claims::start(&info.run_id)?;
+            let pages = claims.pages(&conn, pages)?;
+            if pages.is_empty() {
+                out!("Every unvisited page was just claimed by another run.");
+                return Ok(());
+            }
+            info.count("pages", pages.len());
+            manifest::begin(&conn, &info, &config.parser)?;
+            config.hooks.fire(hooks::Phase::PreScrape, &info).await?;
+            out!("Scraping {} pages (streaming to DB)...", pages.len());
+            let t_scrape = Instant::now();
+            let writer = writer::DbWriter::spawn()?;
+            let options = scraper::ScrapeOptions { retry_errors, failover, mode, keep_raw, return_format };
+            let stats = scraper::scrape_pages_streaming(&writer, pages, options, cassette.clone()).await;
+            writer.close().await?;
+            drop(claims);
+            save_cassette(false, cassette.as_deref())?;
+            let stats = stats?;
+            output::timing("scrape", t_scrape.elapsed());
+            record_scrape(&mut info, &stats);
+            out!(
+                "Done: {} scraped ({} ok, {} errors).",
+                stats.total, stats.ok, stats.errors
+            );
+            print_error_breakdown(false, &stats);
+            if stats.invalid > 0 {
+                out!("{} were not company pages (consent wall, empty shell, 404); queued for re-scrape.", stats.invalid);
+            }
+            if stats.renamed > 0 {
+                out!("{} redirected to a renamed company; new slugs queued.", stats.renamed);
+            }
+            manifest::finish(&conn, &info)
+        }
+        Commands::Process { sink, limit, emit, no_db, parallelism } => {
+            let config = config::get()?;
+            let sink = if no_db { sink::SinkKind::Jsonl } else { sink };
+            // A jsonl sink already prints every company
+            let emit = emit.filter(|_| !sink.uses_stdout());
+            let conn = db::connect()?;
+            db::init_schema(&conn)?;
+            apply_slug_scope(&conn, slugs_file)?;
+            let mut info = hooks::RunInfo::new("process", db::DB_PATH);
+            let claims = claims::Claims::start(&info.run_id)?;
+            tokio::task::block_in_place(|| claims.lock(&conn, "process"))?;
+            let total = db::count_unprocessed(&conn, limit)?;
+            if total == 0 {
+                out!("No unprocessed pages. Run 'scrape' first.");
+                return Ok(());
+            }
+            if !no_db {
+                manifest::begin(&conn, &info, &config.parser)?;
+            }
+            let to_stderr = sink.uses_stdout() || emit.is_some();
+            say(to_stderr, format!("Processing {} pages...", total));
+            let t_process = Instant::now();
+            let counts = tokio::task::block_in_place(|| {
+                let pages = db::stream_unprocessed(&conn, parallelism.chunk_size(), limit);
+                let mut out = sink::open(&sink, &conn)?;
+                if let Some(sink::Emit::Jsonl) = emit {
+                    out = sink::tee_jsonl(out, &conn)?;
+                }
+                process_pages(out.as_mut(), pages, total, &config.parser, parallelism)
+            })?;
+            output::timing("process", t_process.elapsed());
+            counts.print(to_stderr);
+            drop(claims);
+            if !no_db {
+                counts.save(&conn, &info, t_process.elapsed())?;
+                manifest::finish(&conn, &info)?;
+            }
+            info.count("pages", total);
+            counts.record(&mut info);
+            config.hooks.fire(hooks::Phase::PostProcess, &info).await
+        }
+        Commands::Run { sink, limit, retry_errors, failover, mode, keep_raw, return_format, order, cassette, parallelism } => {
+            let config = config::get()?;
+            let cassette = cassette.open()?;
+            let conn = db::connect()?;
+            db::init_schema(&conn)?;
+            apply_slug_scope(&conn, slugs_file)?;
+            let pages = db::fetch_unvisited(&conn, limit, order)?;
+            if pages.is_empty() {
+                out!("No unvisited pages. Run 'init' first.");
+                return Ok(());
+            }
+            let mut info = hooks::RunInfo::new("run", db::DB_PATH);
+            info.count("pages", pages.len());
+            manifest::begin(&conn, &info, &config.parser)?;
+            config.hooks.fire(hooks::Phase::PreScrape, &info).await?;
+            let options = scraper::ScrapeOptions { retry_errors, failover, mode, keep_raw, return_format };
+            scrape_and_process(&conn, pages, &sink, &config.hooks, &config.parser, info, options, cassette, parallelism).await
+        }
+        Commands::Refresh { sink, limit, days, no_sitemap, keep_raw, parallelism } => {
+            let config = config::get()?;
+            let conn = db::connect()?;
+            db::init_schema(&conn)?;
+            apply_slug_scope(&conn, slugs_file)?;
+            if !no_sitemap {
+                let pages = sitemap::fetch_company_urls().await?;
+                let dated = pages.iter().filter(|p| p.lastmod.is_some()).count();
+                let inserted = db::insert_pages(&conn, &pages)?;
+                say(
+                    sink.uses_stdout(),
+                    format!("Sitemap: {} pages, {} with lastmod, {} new", pages.len(), dated, inserted),
+                );
+            }
+            let pages = db::fetch_refresh_candidates(&conn, days, limit)?;
+            if pages.is_empty() {
+                out!("Nothing to refresh: no page changed since its last scrape or is older than {} days.", days);
+                return Ok(());
+            }
+            let mut info = hooks::RunInfo::new("refresh", db::DB_PATH);
+            info.count("pages", pages.len());
+            manifest::begin(&conn, &info, &config.parser)?;
+            config.hooks.fire(hooks::Phase::PreScrape, &info).await?;
+            let options = scraper::ScrapeOptions { keep_raw, ..Default::default() };
+            scrape_and_process(&conn, pages, &sink, &config.hooks, &config.parser, info, options, None, parallelism).await
+        }
+        Commands::Overview { status, batch, limit, group_by: Some(by), min_quality, include_flagged, .. } => {
+            let conn = db::connect()?;
+            db::init_schema(&conn)?;
+            apply_slug_scope(&conn, slugs_file)?;
+            let mut rows = db::fetch_overview(&conn, &status, &batch, min_quality, false, i64::MAX as usize)?;
+            rows.retain(|r| include_flagged || !r.founded_year_flagged);
+            output::count("companies", rows.len());
+            if rows.is_empty() {
+                out!("No companies found.");
+                return Ok(());
+            }
+            report::print_overview_groups(&report::group_overview(&rows, by), rows.len(), limit);
+            Ok(())
+        }
+        Commands::Overview { status, batch, limit, group_by: None, sort, min_quality, .. } => {
+            let conn = db::connect()?;
+            db::init_schema(&conn)?;
+            apply_slug_scope(&conn, slugs_file)?;
+            let by_quality = matches!(sort, report::OverviewSort::Quality);
+            let rows = db::fetch_overview(&conn, &status, &batch, min_quality, by_quality, limit)?;
+            output::count("companies", rows.len());
+            if rows.is_empty() {
+                out!("No companies found.");
+                return Ok(());
+            }
+
+            print_overview(&rows);
+            Ok(())
+        }
+        Commands::Partners => {
+            let conn = db::connect()?;
+            db::init_schema(&conn)?;
+            run_partners(&conn).await
+        }
+        Commands::Tags { limit } => {
+            let conn = db::connect()?;
+            db::init_schema(&conn)?;
+            run_tags(&conn, limit).await
+        }
+        Commands::ResolveNews { limit } => {
+            let conn = db::connect()?;
+            db::init_schema(&conn)?;
+            let rows = db::fetch_unresolved_news(&conn, limit)?;
+            if rows.is_empty() {
+                out!("No unresolved news URLs.");
+                return Ok(());
+            }
+            out!("Resolving {} news URLs...", rows.len());
+            let resolved = resolve::resolve_urls(rows).await?;
+            let redirected = resolved.iter().filter(|r| r.redirected).count();
+            let failed = resolved.iter().filter(|r| r.failed).count();
+            let updates: Vec<(i64, String, bool)> = resolved
+                .into_iter()
+                .map(|r| (r.id, r.canonical_url, !r.failed))
+                .collect();
+            db::save_news_canonical(&conn, &updates)?;
+            let duplicates = db::mark_news_duplicates(&conn)?;
+            let articles = db::rebuild_news_global(&conn)?;
+            let funding = db::rebuild_funding_events(&conn)?;
+            output::count("resolved", updates.len());
+            output::count("redirected", redirected);
+            output::count("failed", failed);
+            output::count("duplicates", duplicates);
+            output::count("articles", articles);
+            output::count("funding_events", funding);
+            out!(
+                "Resolved {} URLs ({} redirected, {} failed). Marked {} duplicate news rows; {} distinct articles, {} funding events.",
+                updates.len(),
+                redirected,
+                failed,
+                duplicates,
+                articles,
+                funding
+            );
+            Ok(())
+        }
+        Commands::ResolveJobs { limit } => {
+            let conn = db::connect()?;
+            db::init_schema(&conn)?;
+            apply_slug_scope(&conn, slugs_file)?;
+            let rows = db::fetch_unresolved_apply_urls(&conn, limit)?;
+            if rows.is_empty() {
+                out!("No unresolved job apply URLs.");
+                return Ok(());
+            }
+            out!("Resolving {} apply URLs...", rows.len());
+            let resolved = resolve::resolve_urls(rows).await?;
+            let failed = resolved.iter().filter(|r| r.failed).count();
+            let updates: Vec<(i64, String, Option<&str>, bool)> = resolved
+                .into_iter()
+                .map(|r| {
+                    let vendor = resolve::ats_vendor(&r.canonical_url);
+                    (r.id, r.canonical_url, vendor, !r.failed)
+                })
+                .collect();
+            db::save_apply_resolutions(&conn, &updates)?;
+
+            let mut by_vendor: std::collections::BTreeMap<&str, usize> = std::collections::BTreeMap::new();
+            for (_, _, vendor, ok) in &updates {
+                if *ok {
+                    *by_vendor.entry(vendor.unwrap_or("other")).or_default() += 1;
+                }
+            }
+            output::count("resolved", updates.len() - failed);
+            output::count("failed", failed);
+            out!("Resolved {} apply URLs ({} failed).", updates.len() - failed, failed);
+            for (vendor, n) in &by_vendor {
+                out!("  {:<12} {:>6}", vendor, n);
+            }
+            Ok(())
+        }
+        Commands::ScrapeJobs { limit } => {
+            let conn = db::connect()?;
+            db::init_schema(&conn)?;
+            apply_slug_scope(&conn, slugs_file)?;
+            let jobs = db::fetch_jobs_without_details(&conn, limit)?;
+            if jobs.is_empty() {
+                out!("No job pages left to scrape.");
+                return Ok(());
+            }
+            out!("Scraping {} job pages...", jobs.len());
+            let writer = writer::DbWriter::spawn()?;
+            let stats = scraper::scrape_job_pages(&writer, jobs).await;
+            writer.close().await?;
+            let stats = stats?;
+            output::count("saved", stats.saved);
+            output::count("skipped", stats.skipped);
+            output::count("failed", stats.failed);
+            out!(
+                "Saved {} job pages ({} blank or 404, not parsed; {} failed).",
+                stats.saved,
+                stats.skipped,
+                stats.failed
+            );
+            for (chip, n) in stats.stacks.iter().take(10) {
+                out!("  {:<14} {:>6}", chip, n);
+            }
+            Ok(())
+        }
+        Commands::Review { limit } => {
+            let conn = db::connect()?;
+            db::init_schema(&conn)?;
+            apply_slug_scope(&conn, slugs_file)?;
+            let items = db::fetch_review_queue(&conn, limit)?;
+            if items.is_empty() {
+                out!("Review queue is empty.");
+                return Ok(());
+            }
+            let stats = review::run(&conn, &items, &mut std::io::stdin().lock(), &mut std::io::stdout())?;
+            out!(
+                "\n{} annotations saved, {} skipped, {} left in this batch.",
+                stats.annotated, stats.skipped, stats.remaining
+            );
+            Ok(())
+        }
+        Commands::Fixups { action } => {
+            let conn = db::connect()?;
+            db::init_schema(&conn)?;
+            match action {
+                FixupAction::List => fixups::print_rules(&db::fetch_fixups(&conn)?),
+                FixupAction::Add { target, pattern, replacement, note } => {
+                    let id = fixups::add(&conn, &target, &pattern, &replacement, note.as_deref())?;
+                    out!("Fixup {} stored for {}.", id, target);
+                }
+                FixupAction::Seed => out!("{} built-in fixups added.", fixups::seed(&conn)?),
+                FixupAction::Remove { id } => {
+                    if !db::remove_fixup(&conn, id)? {
+                        anyhow::bail!("no fixup with id {}", id);
+                    }
+                    out!("Fixup {} removed.", id);
+                }
+                FixupAction::Apply { dry_run, id } => {
+                    let changes = fixups::apply(&conn, &id, dry_run)?;
+                    fixups::print_changes(&changes);
+                    let rows: std::collections::HashSet<_> =
+                        changes.iter().map(|c| (c.table.as_str(), c.column.as_str(), c.rowid)).collect();
+                    output::count("changes", changes.len());
+                    output::count("values", rows.len());
+                    if dry_run {
+                        out!("Dry run: {} changes to {} values; nothing written.", changes.len(), rows.len());
+                    } else {
+                        out!("Applied {} changes to {} values.", changes.len(), rows.len());
+                    }
+                }
+            }
+            Ok(())
+        }
+        Commands::Experiment { action } => match action {
+            ExperimentAction::List => {
+                experiment::print_experiments();
+                Ok(())
+            }
+            ExperimentAction::Run { name, limit, examples } => {
+                let exp = experiment::find(&name)?;
+                let conn = db::connect()?;
+                db::init_schema(&conn)?;
+                apply_slug_scope(&conn, slugs_file)?;
+                if db::count_latest(&conn, limit)? == 0 {
+                    out!("No scraped pages. Run 'scrape' first.");
+                    return Ok(());
+                }
+                let (pages, reports) = tokio::task::block_in_place(|| {
+                    let pages = db::stream_latest(&conn, PROCESS_CHUNK, limit);
+                    experiment::run(exp, pages, &config::get()?.parser, examples)
+                })?;
+                experiment::print_report(exp, pages, &reports);
+                Ok(())
+            }
+        },
+        Commands::Enrich { limit } => {
+            let conn = db::connect()?;
+            db::init_schema(&conn)?;
+            apply_slug_scope(&conn, slugs_file)?;
+            let enrichment = &config::get()?.enrich;
+            if enrichment.provider == enrich::Provider::None {
+                out!("No enrichment provider configured; set provider = \"x\" under [enrich] in yc.toml.");
+                return Ok(());
+            }
+            let enricher = enrich::open(enrichment.provider)?;
+            let (looked_up, saved) = tokio::task::block_in_place(|| {
+                enrich::enrich_founders(&conn, enricher.as_ref(), enrichment.max_age_days, limit)
+            })?;
+            output::count("looked_up", looked_up);
+            output::count("saved", saved);
+            out!("Looked up {} founder handles via {}; saved {} profiles.", looked_up, enricher.name(), saved);
+            Ok(())
+        }
+        Commands::CheckLinks { limit } => {
+            let conn = db::connect()?;
+            db::init_schema(&conn)?;
+            apply_slug_scope(&conn, slugs_file)?;
+            let links = db::fetch_unchecked_links(&conn, limit)?;
+            if links.is_empty() {
+                out!("No unchecked links. Run 'process' first or all links are checked.");
+                return Ok(());
+            }
+            out!("Checking {} links...", links.len());
+            let checks = linkcheck::check_links(links).await?;
+            let ok = checks
+                .iter()
+                .filter(|c| c.status.is_some_and(|s| (200..400).contains(&s)))
+                .count();
+            db::save_link_checks(&conn, &checks)?;
+            let domains = db::refresh_domain_health(&conn)?;
+            output::count("checked", checks.len());
+            output::count("ok", ok);
+            output::count("failed", checks.len() - ok);
+            output::count("domains", domains);
+            out!(
+                "Checked {} links ({} ok, {} failed). domain_health: {} domains.",
+                checks.len(),
+                ok,
+                checks.len() - ok,
+                domains
+            );
+            Ok(())
+        }
+        Commands::Meetings { action: MeetingsAction::Report { check, limit } } => {
+            let conn = db::connect()?;
+            db::init_schema(&conn)?;
+            apply_slug_scope(&conn, slugs_file)?;
+            if check {
+                let links = db::fetch_unchecked_meeting_links(&conn, limit)?;
+                if !links.is_empty() {
+                    out!("Checking {} booking links...", links.len());
+                    db::save_link_checks(&conn, &linkcheck::check_links(links).await?)?;
+                    db::refresh_domain_health(&conn)?;
+                }
+            }
+            let rows = db::fetch_meeting_link_statuses(&conn)?;
+            if rows.is_empty() {
+                out!("No meeting links. Run 'process' first.");
+                return Ok(());
+            }
+            report::print_meetings(&rows, &db::fetch_batch_sizes(&conn)?);
+            Ok(())
+        }
+        Commands::Report { view: Some(ReportView::Compare { batch, format, include_flagged }), .. } => {
+            if batch.len() < 2 {
+                anyhow::bail!("report compare needs at least two --batch values");
+            }
+            let conn = db::connect()?;
+            db::init_schema(&conn)?;
+            apply_slug_scope(&conn, slugs_file)?;
+            let summaries = report::compare_batches(&conn, &batch, include_flagged)?;
+            report::print_compare(&batch, &summaries, format);
+            Ok(())
+        }
+        Commands::Report { view: Some(ReportView::Cohorts { by, include_flagged }), .. } => {
+            let conn = db::connect()?;
+            db::init_schema(&conn)?;
+            apply_slug_scope(&conn, slugs_file)?;
+            let rows = db::fetch_cohort_rows(&conn, include_flagged)?;
+            for line in report::cohort_csv(&rows, by, chrono::Utc::now().year()) {
+                println!("{}", line);
+            }
+            Ok(())
+        }
+        Commands::Report { view: Some(ReportView::Coverage), .. } => {
+            let conn = db::connect()?;
+            db::init_schema(&conn)?;
+            apply_slug_scope(&conn, slugs_file)?;
+            let rows = db::fetch_scored_companies(&conn)?;
+            output::count("companies", rows.len());
+            if rows.is_empty() {
+                out!("No companies. Run 'process' first.");
+                return Ok(());
+            }
+            report::print_coverage(&rows);
+            Ok(())
+        }
+        Commands::Report { view: Some(ReportView::Jobs), .. } => {
+            let conn = db::connect()?;
+            db::init_schema(&conn)?;
+            apply_slug_scope(&conn, slugs_file)?;
+            let titles = db::fetch_job_titles(&conn)?;
+            if titles.is_empty() {
+                out!("No job listings. Run 'process' first.");
+                return Ok(());
+            }
+            let stats = report::job_stats(&titles);
+            let markdown = report::render_job_stats(&stats);
+            db::save_job_stats(&conn, &report::job_stat_metrics(&stats, &markdown))?;
+            out!("{}", markdown);
+            Ok(())
+        }
+        Commands::Report { view: None, email: true, .. } => {
+            let conn = db::connect()?;
+            db::init_schema(&conn)?;
+            let Some(summary) = email::summarize(&conn)? else {
+                anyhow::bail!("no finished run to report on yet");
+            };
+            let email_config = &config::get()?.email;
+            let subject = email_config.subject.clone().unwrap_or_else(|| email::subject(&summary));
+            let html = email::render_html(&summary);
+            tokio::task::block_in_place(|| email::send(email_config, &subject, html))?;
+            output::count("new_companies", summary.new_companies.len());
+            output::count("status_changes", summary.status_changes.len());
+            output::count("recipients", email_config.to.len());
+            out!("Sent \"{}\" to {}.", subject, email_config.to.join(", "));
+            Ok(())
+        }
+        Commands::Report { view: None, repeat_founders, domain_health, quality, missing_founders, email: false } => {
+            let conn = db::connect()?;
+            db::init_schema(&conn)?;
+            print_reports(&conn, [repeat_founders, domain_health, quality, missing_founders])
+        }
+        Commands::ExtrasReport { min_companies, limit } => {
+            let conn = db::connect()?;
+            db::init_schema(&conn)?;
+            apply_slug_scope(&conn, slugs_file)?;
+            let clusters = report::extras_clusters(&conn)?;
+            report::print_extras(&clusters, min_companies, limit);
+            Ok(())
+        }
+        Commands::Explain { slug } => {
+            use std::io::IsTerminal;
+
+            let conn = db::connect()?;
+            db::init_schema(&conn)?;
+            let slug = sitemap::canonical_slug(&slug);
+            let Some((markdown, _)) = db::fetch_review_source(&conn, &slug)? else {
+                anyhow::bail!("no scraped page for '{}'", slug);
+            };
+            let color = std::io::stdout().is_terminal() && std::env::var_os("NO_COLOR").is_none();
+            if !output::quiet() {
+                print!("{}", explain::explain(&markdown, &config::get()?.parser, color));
+            }
+            Ok(())
+        }
+        Commands::Slowest { limit } => {
+            let conn = db::connect()?;
+            db::init_schema(&conn)?;
+            apply_slug_scope(&conn, slugs_file)?;
+            let rows = db::fetch_slowest_pages(&conn, limit)?;
+            if rows.is_empty() {
+                out!("No parse timings recorded. Run 'process' first.");
+                return Ok(());
+            }
+            out!(
+                "{:>3} | {:<28} | {:>9} | {:>8} | {:>7} | {:>8} | {:>9}",
+                "#", "Slug", "Parse ms", "Markdown", "Blocks", "Sections", "µs/block"
+            );
+            out!("{}", "-".repeat(90));
+            for (i, r) in rows.iter().enumerate() {
+                out!(
+                    "{:>3} | {:<28} | {:>9.2} | {:>8} | {:>7} | {:>8} | {:>9.1}",
+                    i + 1,
+                    truncate(&r.slug, 28),
+                    r.parse_ms,
+                    format_bytes(r.markdown_bytes),
+                    r.block_count,
+                    r.section_count,
+                    r.parse_ms * 1000.0 / r.block_count.max(1) as f64
+                );
+            }
+            Ok(())
+        }
+        Commands::DedupeFounders { dry_run } => {
+            let conn = db::connect()?;
+            db::init_schema(&conn)?;
+            let groups = db::fetch_duplicate_founders(&conn)?;
+            if groups.is_empty() {
+                out!("No duplicate founders.");
+                return Ok(());
+            }
+            for g in &groups {
+                let names: Vec<String> = g.iter().map(|f| format!("{:?}", f.name)).collect();
+                out!("  {}: {}", g[0].company_slug, names.join(", "));
+            }
+            let extra: usize = groups.iter().map(|g| g.len() - 1).sum();
+            output::count("groups", groups.len());
+            output::count("duplicates", extra);
+            if dry_run {
+                out!("\n{} groups, {} rows would be merged.", groups.len(), extra);
+            } else {
+                let removed = db::merge_duplicate_founders(&conn, &groups)?;
+                out!("\nMerged {} duplicate founder rows in {} groups.", removed, groups.len());
+            }
+            Ok(())
+        }
+        Commands::RecoverFounders { dry_run } => {
+            use parser::{extract::founders, lang};
+
+            let conn = db::connect()?;
+            db::init_schema(&conn)?;
+            apply_slug_scope(&conn, slugs_file)?;
+            let sections = db::fetch_founderless_sections(&conn)?;
+            let mut recovered = Vec::new();
+            for (slug, raw) in &sections {
+                let lexicon = lang::Lexicon::for_lang(lang::detect_page(raw));
+                for f in founders::recover(slug, raw, &lexicon) {
+                    out!("  {}: {} ({})", slug, f.name, f.title.as_deref().unwrap_or("-"));
+                    recovered.push(f);
+                }
+            }
+            let mut companies: Vec<&str> = recovered.iter().map(|f| f.company_slug.as_str()).collect();
+            companies.dedup();
+            output::count("checked", sections.len());
+            output::count("companies", companies.len());
+            output::count("founders", recovered.len());
+            if dry_run {
+                out!(
+                    "\n{} founders would be recovered for {} of {} founderless companies.",
+                    recovered.len(), companies.len(), sections.len()
+                );
+            } else {
+                db::save_extracted(&conn, &[], &recovered, &[], &[], &[])?;
+                db::refresh_quality_scores(&conn, &companies)?;
+                out!(
+                    "\nRecovered {} founders for {} of {} founderless companies. 'report --missing-founders' lists the rest.",
+                    recovered.len(), companies.len(), sections.len()
+                );
+            }
+            Ok(())
+        }
+        Commands::Backfill { dry_run } => {
+            let conn = db::connect()?;
+            db::init_schema(&conn)?;
+            let parser_config = &config::get()?.parser;
+            let filled = db::backfill_derived(&conn, &parser_config.usd_rates, &parser_config.date_formats, dry_run)?;
+            out!("{:<14} {:<42} {:>8}", "Table", "Columns", "Rows");
+            out!("{}", "-".repeat(66));
+            for b in &filled {
+                out!("{:<14} {:<42} {:>8}", b.table, b.columns, b.rows);
+            }
+            let total: usize = filled.iter().map(|b| b.rows).sum();
+            output::count("updates", total);
+            if dry_run {
+                out!("\n{} row updates would be made. Run without --dry-run to write them.", total);
+            } else {
+                out!("\nMade {} row updates.", total);
+            }
+            Ok(())
+        }
+        Commands::Doctor { repair } => {
+            let conn = db::connect()?;
+            db::init_schema(&conn)?;
+            out!("SQLite quick_check: {}", db::quick_check(&conn)?);
+
+            let orphans = db::find_orphans(&conn)?;
+            out!("\n{:<34} {:<18} {:>8}", "Violation", "Table", "Rows");
+            out!("{}", "-".repeat(62));
+            for o in &orphans {
+                out!("{:<34} {:<18} {:>8}", o.violation, o.table, o.count);
+            }
+            let found: usize = orphans.iter().map(|o| o.count).sum();
+            output::count("orphans", found);
+
+            if found == 0 {
+                out!("\nNo orphaned rows.");
+            } else if repair {
+                let deleted = db::delete_orphans(&conn)?;
+                let total: usize = deleted.iter().map(|o| o.count).sum();
+                output::count("deleted", total);
+                out!("\nRepaired: deleted {} orphaned rows.", total);
+            } else {
+                out!("\n{} orphaned rows. Run 'doctor --repair' to delete them.", found);
+            }
+            Ok(())
+        }
+        Commands::Purge { slug, orphans: _, reason, dry_run } => {
+            let conn = db::connect()?;
+            db::init_schema(&conn)?;
+            let slugs = match &slug {
+                Some(slug) => vec![slug.clone()],
+                None => {
+                    let listed = sitemap::fetch_company_urls().await?;
+                    let urls: Vec<String> = listed.into_iter().map(|p| p.url).collect();
+                    let missing = db::pages_missing_from(&conn, &urls)?;
+                    let known: usize = conn.query_row("SELECT COUNT(*) FROM pages", [], |r| r.get(0))?;
+                    if urls.is_empty() || missing.len() * 2 > known {
+                        anyhow::bail!(
+                            "sitemap lists {} pages and {} of {} stored pages are missing from it; \
+                             refusing to purge on what looks like a partial sitemap",
+                            urls.len(),
+                            missing.len(),
+                            known
+                        );
+                    }
+                    missing
+                }
+            };
+            if slugs.is_empty() {
+                out!("Nothing to purge: every stored page is in the sitemap.");
+                return Ok(());
+            }
+            // Only an explicit slug is a removal request; orphans may come back.
+            let tombstone = slug.is_some();
+            let removed = db::purge_companies(&conn, &slugs, tombstone, reason.as_deref(), dry_run)?;
+            if removed.is_empty() {
+                out!("No rows found for {}.", slugs.join(", "));
+                return Ok(());
+            }
+            for (table, n) in &removed {
+                out!("  {:<20} {:>8}", table, n);
+            }
+            let total: usize = removed.iter().map(|(_, n)| n).sum();
+            output::count("companies", slugs.len());
+            output::count("rows", total);
+            let verb = if dry_run { "Would delete" } else { "Deleted" };
+            let whom = match slugs.as_slice() {
+                [one] => one.clone(),
+                many => format!("{} companies", many.len()),
+            };
+            out!("\n{} {} rows for {}.", verb, total, whom);
+            if tombstone && !dry_run {
+                out!("{} will not be re-queued from the sitemap.", slugs[0]);
+            }
+            Ok(())
+        }
+        Commands::Stale { days, output } => {
+            let conn = db::connect()?;
+            db::init_schema(&conn)?;
+            apply_slug_scope(&conn, slugs_file)?;
+            let rows = db::fetch_stale(&conn, days)?;
+            output::count("stale", rows.len());
+            report::print_stale(&rows, days);
+            if let Some(path) = output {
+                let mut text = format!("# {} companies last scraped > {} days ago\n", rows.len(), days);
+                for r in &rows {
+                    text.push_str(&r.slug);
+                    text.push('\n');
+                }
+                std::fs::write(&path, text)?;
+                out!(
+                    "Wrote {} slugs to {} (use with --slugs-file)",
+                    rows.len(),
+                    path.display()
+                );
+            }
+            Ok(())
+        }
+        Commands::PublishPrep { out, license, redact } => {
+            let conn = db::connect()?;
+            db::init_schema(&conn)?;
+            apply_slug_scope(&conn, slugs_file)?;
+            let out = out.unwrap_or_else(|| {
+                PathBuf::from(format!("data/publish/yc-{}", chrono::Utc::now().format("%Y-%m-%d")))
+            });
+            let tables = publish::write_bundle(&conn, &out, &license, redact.then_some(&config::get()?.redact))?;
+            output::count("tables", tables.len());
+            output::count("rows", tables.iter().map(|t| t.rows).sum());
+            out!("{:<18} {:>8} {:>10}", "Table", "Rows", "Size");
+            out!("{}", "-".repeat(38));
+            for t in &tables {
+                out!("{:<18} {:>8} {:>10}", t.table, t.rows, format_bytes(t.bytes as i64));
+            }
+            out!("Wrote {} (CSVs, DATA_DICTIONARY.md, LICENSE, manifest.json)", out.display());
+            Ok(())
+        }
+        Commands::Export { format, out, per_company, redact } => {
+            let conn = db::connect()?;
+            db::init_schema(&conn)?;
+            apply_slug_scope(&conn, slugs_file)?;
+            let redact = redact.then_some(&config::get()?.redact);
+            let (counts, out) = match (per_company, out) {
+                (Some(dir), _) => (export::write_per_company(&conn, &dir, redact)?, dir),
+                (None, Some(out)) => match format {
+                    export::ExportFormat::Duckdb => (export::write_duckdb(&conn, &out, redact)?, out),
+                    export::ExportFormat::Xlsx => (export::write_xlsx(&conn, &out, redact)?, out),
+                },
+                (None, None) => anyhow::bail!("export needs --out or --per-company"),
+            };
+            output::count("tables", counts.len());
+            output::count("rows", counts.iter().map(|(_, rows)| *rows).sum());
+            for (table, rows) in &counts {
+                out!("{:<18} {:>8}", table, rows);
+            }
+            out!("Wrote {}", out.display());
+            Ok(())
+        }
+        Commands::Graph { action: GraphAction::Export { format, out, known_only } } => {
+            let conn = db::connect()?;
+            db::init_schema(&conn)?;
+            apply_slug_scope(&conn, slugs_file)?;
+            let edges = db::fetch_reference_edges(&conn, known_only)?;
+            let (nodes, edges) = match &out {
+                Some(path) => {
+                    let mut file = std::io::BufWriter::new(std::fs::File::create(path)?);
+                    let counts = graph::write(&mut file, &edges, format)?;
+                    std::io::Write::flush(&mut file)?;
+                    counts
+                }
+                None => graph::write(&mut std::io::stdout().lock(), &edges, format)?,
+            };
+            output::count("nodes", nodes);
+            output::count("edges", edges);
+            if let Some(path) = out {
+                out!("{} companies, {} references. Wrote {}", nodes, edges, path.display());
+            }
+            Ok(())
+        }
+        Commands::Maintain => {
+            let conn = db::connect()?;
+            db::init_schema(&conn)?;
+            let before = db::db_size(&conn)?;
+
+            let t = Instant::now();
+            if db::vacuum(&conn)? {
+                out!("Converted to incremental auto-vacuum (full VACUUM).");
+            }
+            db::analyze_and_reindex(&conn)?;
+            let after = db::db_size(&conn)?;
+
+            out!("{:<10} {:>10} {:>8} {:>10}", "", "On disk", "Pages", "Free");
+            for (label, size) in [("Before", &before), ("After", &after)] {
+                out!(
+                    "{:<10} {:>10} {:>8} {:>10}",
+                    label,
+                    format_bytes(size.file_bytes as i64),
+                    size.page_count,
+                    format_bytes(size.free_pages * size.page_size)
+                );
+            }
+            let saved = before.file_bytes.saturating_sub(after.file_bytes);
+            output::count("reclaimed_bytes", saved as usize);
+            out!(
+                "Reclaimed {} in {}",
+                format_bytes(saved as i64),
+                format_duration(t.elapsed())
+            );
+
+            out!("\n{:<36} {:>8} {:>10} {:>10}", "Table / index", "Pages", "Size", "Unused");
+            out!("{}", "-".repeat(67));
+            for u in db::table_usage(&conn)? {
+                out!(
+                    "{:<36} {:>8} {:>10} {:>10}",
+                    u.name,
+                    u.pages,
+                    format_bytes(u.bytes),
+                    format_bytes(u.unused_bytes)
+                );
+            }
+            Ok(())
+        }
+        Commands::OffloadPages { limit } => {
+            let store = blobstore::shared()?
+                .ok_or_else(|| anyhow::anyhow!("set [storage] pages in yc.toml to offload pages"))?;
+            let conn = db::connect()?;
+            db::init_schema(&conn)?;
+            let t = Instant::now();
+            let (moved, bytes) = tokio::task::block_in_place(|| db::offload_pages(&conn, store.as_ref(), limit))?;
+            output::count("pages", moved);
+            output::count("bytes", bytes as usize);
+            out!(
+                "Moved {} pages ({}) to {} in {}",
+                moved,
+                format_bytes(bytes),
+                store.describe(),
+                format_duration(t.elapsed())
+            );
+            if moved > 0 {
+                out!("Run `maintain` to shrink the DB file.");
+            }
+            Ok(())
+        }
+        Commands::Changes { since } => {
+            let conn = db::connect()?;
+            db::init_schema(&conn)?;
+            apply_slug_scope(&conn, slugs_file)?;
+            for event in db::changes(&conn, since)? {
+                println!("{}", serde_json::to_string(&event)?);
+            }
+            Ok(())
+        }
+        Commands::Stats { runs: true } => {
+            let conn = db::connect()?;
+            db::init_schema(&conn)?;
+            print_runs(&db::fetch_process_metrics(&conn, RUNS_SHOWN)?);
+            Ok(())
+        }
+        Commands::Stats { runs: false } => {
+            let conn = db::connect()?;
+            db::init_schema(&conn)?;
+            let s = db::get_stats(&conn)?;
+            print_stats(&s);
+            Ok(())
+        }
+    }
+}
+
+/// Commands whose stdout is their data (JSON lines, CSV, an interactive
+/// session), which `--json` would mix with the result object.
+fn writes_data(command: &Commands) -> bool {
+    match command {
+        Commands::Process { sink, emit, no_db, .. } => sink.uses_stdout() || emit.is_some() || *no_db,
+        Commands::Run { sink, .. } | Commands::Refresh { sink, .. } => sink.uses_stdout(),
+        Commands::Report { view: Some(ReportView::Cohorts { .. }), .. } => true,
+        Commands::Report { view: Some(ReportView::Compare { format, .. }), .. } => {
+            matches!(format, report::CompareFormat::Csv)
+        }
+        Commands::Changes { .. } | Commands::Review { .. } => true,
+        Commands::Graph { action: GraphAction::Export { out: None, .. } } => true,
+        _ => false,
+    }
+}
+
+/// Scope the connection to `--slugs-file` when one was given.
+fn apply_slug_scope(conn: &rusqlite::Connection, slugs_file: Option<&Path>) -> anyhow::Result<()> {
+    if let Some(path) = slugs_file {
+        let slugs = slugs::load(path)?;
+        if !output::quiet() {
+            eprintln!("Scoped to {} slugs from {}", slugs.len(), path.display());
+        }
+        db::set_slug_scope(conn, &slugs)?;
+    }
+    Ok(())
+}
+
+async fn run_partners(conn: &rusqlite::Connection) -> anyhow::Result<()> {
+    use std::collections::{HashMap, HashSet};
+
+    // ── Pass 1: Scrape and store partners ──
+    out!("Fetching https://www.ycombinator.com/people ...");
+    let markdown = scraper::scrape_single_page("https://www.ycombinator.com/people").await?;
+    let partner_rows = parser::extract::partners::parse_partners_page(&markdown);
+    if partner_rows.is_empty() {
+        out!("No partners found in page. Check markdown format.");
+        return Ok(());
+    }
+    let saved = db::save_partners(conn, &partner_rows)?;
+    output::count("partners", saved);
+    out!("Saved {} partners.", saved);
+
+    // Build lookup maps
+    let all_partners = db::fetch_partners(conn)?;
+    let slug_set: HashSet<&str> = all_partners.iter().map(|p| p.slug.as_str()).collect();
+    let name_to_slug: HashMap<String, String> = all_partners
+        .iter()
+        .map(|p| (p.name.to_lowercase(), p.slug.clone()))
+        .collect();
+
+    // ── Pass 2a: URL matching ──
+    // Footer slugs captured at extraction time; only companies without one
+    // need their raw markdown scanned.
+    out!("Matching partners to companies (URL scan)...");
+    let mut url_matches: Vec<db::CompanyPartnerRow> = Vec::new();
+    let mut from_footer: HashSet<String> = HashSet::new();
+    for (company_slug, partner_slug) in db::fetch_partner_slugs(conn)? {
+        if slug_set.contains(partner_slug.as_str()) {
+            from_footer.insert(company_slug.clone());
+            url_matches.push(db::CompanyPartnerRow {
+                company_slug,
+                partner_slug,
+                match_method: "url".to_string(),
+            });
+        }
+    }
+    let pages = db::fetch_scraped_markdown(conn)?;
+    for (company_slug, md) in pages.iter().filter(|(slug, _)| !from_footer.contains(slug)) {
+        let found_slugs = parser::extract::partners::find_partner_urls_in_markdown(md);
+        for ps in found_slugs {
+            if slug_set.contains(ps.as_str()) {
+                url_matches.push(db::CompanyPartnerRow {
+                    company_slug: company_slug.clone(),
+                    partner_slug: ps,
+                    match_method: "url".to_string(),
+                });
+            }
+        }
+    }
+    let url_count = db::save_company_partners(conn, &url_matches)?;
+    out!("  URL matches: {} links saved.", url_count);
+
+    // ── Pass 2b: Name matching (fallback) ──
+    out!("Matching partners to companies (name fallback)...");
+    let unmatched = db::fetch_unmatched_partners(conn)?;
+    let mut name_matches: Vec<db::CompanyPartnerRow> = Vec::new();
+
+    for (company_slug, partner_name) in &unmatched {
+        if let Some(partner_slug) = name_to_slug.get(&partner_name.to_lowercase()) {
+            name_matches.push(db::CompanyPartnerRow {
+                company_slug: company_slug.clone(),
+                partner_slug: partner_slug.clone(),
+                match_method: "name".to_string(),
+            });
+        }
+    }
+    let name_count = db::save_company_partners(conn, &name_matches)?;
+    output::count("url_matches", url_count);
+    output::count("name_matches", name_count);
+    out!("  Name matches: {} links saved.", name_count);
+
+    out!(
+        "\nDone: {} total company-partner links ({} url, {} name).",
+        url_count + name_count,
+        url_count,
+        name_count
+    );
+    Ok(())
+}
+
+/// Sitemap pages of companies listed in the directory for any of `batches`.
+/// The sitemap has no batch, so each batch's listing page is scraped for
+/// the slugs; only slugs the sitemap also has are kept, which leaves out
+/// whatever else the listing links under `/companies/`.
+async fn filter_to_batches(pages: Vec<db::PageUrl>, batches: &[model::Batch]) -> anyhow::Result<Vec<db::PageUrl>> {
+    use anyhow::Context;
+
+    let mut listed = std::collections::HashSet::new();
+    for batch in batches {
+        let md = scraper::scrape_single_page(&sitemap::batch_url(batch))
+            .await
+            .with_context(|| format!("fetching the {} listing", batch))?;
+        let slugs = sitemap::listed_slugs(&md);
+        out!("{}: {} companies listed", batch, slugs.len());
+        listed.extend(slugs);
+    }
+    let pages: Vec<_> = pages.into_iter().filter(|p| listed.contains(&p.slug)).collect();
+    if pages.len() < listed.len() {
+        out!("  {} listed slugs are not in the sitemap and were skipped", listed.len() - pages.len());
+    }
+    Ok(pages)
+}
+
+/// Queue company pages linked from listings that update ahead of the
+/// sitemap, each tagged with the listing it came from. A listing that fails
+/// to load is reported and skipped.
+async fn run_discover(conn: &rusqlite::Connection, batches: &[model::Batch]) -> anyhow::Result<()> {
+    let mut sources = vec![sitemap::DIRECTORY_URL.to_string(), sitemap::LAUNCHES_URL.to_string()];
+    sources.extend(batches.iter().map(sitemap::batch_url));
+
+    let (mut fetched, mut queued) = (0, 0);
+    for url in &sources {
+        let md = match scraper::scrape_single_page(url).await {
+            Ok(md) => md,
+            Err(e) => {
+                out!("{}: {:#}", url, e);
+                output::error(format!("{}: {:#}", url, e));
+                continue;
+            }
+        };
+        fetched += 1;
+        let mut slugs: Vec<String> = sitemap::listed_slugs(&md).into_iter().collect();
+        slugs.sort();
+        let pages: Vec<db::PageUrl> = slugs
+            .into_iter()
+            .map(|slug| db::PageUrl { url: sitemap::company_url(&slug), slug, lastmod: None })
+            .collect();
+        let inserted = db::insert_discovered(conn, &pages, url)?;
+        out!("{}: {} companies listed, {} new", url, pages.len(), inserted);
+        queued += inserted;
+    }
+    if fetched == 0 {
+        anyhow::bail!("none of the {} listings could be fetched", sources.len());
+    }
+    output::count("listings", fetched);
+    output::count("queued", queued);
+    out!("Queued {} newly discovered company pages.", queued);
+    Ok(())
+}
+
+/// Fetch the landing page of every industry tag linked from stored company
+/// pages, plus tags those pages link to, into tag_catalog; then normalize
+/// the tags already stored on companies.
+async fn run_tags(conn: &rusqlite::Connection, limit: Option<usize>) -> anyhow::Result<()> {
+    use parser::extract::tags;
+    use std::collections::{HashSet, VecDeque};
+
+    let mut queue: VecDeque<String> = VecDeque::new();
+    let mut seen: HashSet<String> = HashSet::new();
+    for (_, md) in db::fetch_scraped_markdown(conn)? {
+        for tag in tags::industry_links(&md) {
+            if seen.insert(tags::tag_key(&tag)) {
+                queue.push_back(tag);
+            }
+        }
+    }
+    if queue.is_empty() {
+        out!("No industry tags in scraped pages. Run scrape first.");
+        return Ok(());
+    }
+    out!("Crawling tag pages ({} linked from company pages)...", queue.len());
+
+    let limit = limit.unwrap_or(usize::MAX);
+    let mut rows = Vec::new();
+    let mut failed = Vec::new();
+    while rows.len() + failed.len() < limit {
+        let Some(tag) = queue.pop_front() else { break };
+        let url = tags::tag_url(&tag);
+        match scraper::scrape_single_page(&url).await {
+            Ok(md) => {
+                for linked in tags::industry_links(&md) {
+                    if seen.insert(tags::tag_key(&linked)) {
+                        queue.push_back(linked);
+                    }
+                }
+                rows.push(tags::parse_tag_page(&tag, &md));
+            }
+            Err(e) => {
+                tracing::warn!("{}: {:#}", url, e);
+                failed.push(tag);
+            }
+        }
+    }
+    let saved = db::save_tag_catalog(conn, &rows)?;
+    output::count("tags", saved);
+    output::count("failed", failed.len());
+    out!("Saved {} tags ({} failed, {} not fetched).", saved, failed.len(), queue.len());
+    if !failed.is_empty() {
+        out!("  Failed: {}", failed.join(", "));
+    }
+
+    let catalog = db::fetch_tag_catalog(conn)?;
+    let changed = db::normalize_company_tags(conn, &catalog)?;
+    output::count("companies_normalized", changed);
+    out!("Normalized tags on {} companies ({} tags in catalog).", changed, catalog.len());
+    Ok(())
+}
+
+/// The `demo` database, removed with its WAL files on drop unless `--keep`
+/// was given, so a failed demo doesn't leave it behind either.
+struct DemoDb {
+    path: PathBuf,
+    keep: bool,
+}
+
+impl Drop for DemoDb {
+    fn drop(&mut self) {
+        if self.keep {
+            return;
+        }
+        for suffix in ["", "-wal", "-shm"] {
+            let _ = std::fs::remove_file(format!("{}{}", self.path.display(), suffix));
+        }
+    }
+}
+
+/// Scrape `pages` (streaming to DB), then process whatever is unprocessed.
+/// Shared by `run` and `refresh`; fires post_process and post_run. Pages
+/// another invocation claimed first are left to it, and processing waits
+/// for the `process` lock (see `claims::Claims`).
+#[allow(clippy::too_many_arguments)]
+async fn scrape_and_process(
+    conn: &rusqlite::Connection,
+    pages: Vec<(i64, String, String)>,
+    sink: &sink::SinkKind,
+    hooks: &hooks::Hooks,
+    parser_config: &parser::ParserConfig,
+    mut info: hooks::RunInfo,
+    options: scraper::ScrapeOptions,
+    cassette: Option<std::sync::Arc<cassette::Cassette>>,
+    parallelism: Parallelism,
+) -> anyhow::Result<()> {
+    let to_stderr = sink.uses_stdout();
+    let claims = claims::Claims::start(&info.run_id)?;
+    let queued = pages.len();
+    let pages = claims.pages(conn, pages)?;
+    if pages.len() < queued {
+        say(to_stderr, format!("{} pages are claimed by another run; leaving them to it.", queued - pages.len()));
+    }
+
+    // Phase 1: Scrape (streaming to DB)
+    let t_scrape = Instant::now();
+    say(to_stderr, format!("Pipeline: scraping {} pages (streaming to DB)...", pages.len()));
+    let writer = writer::DbWriter::spawn()?;
+    let stats = scraper::scrape_pages_streaming(&writer, pages, options, cassette.clone()).await;
+    writer.close().await?;
+    save_cassette(to_stderr, cassette.as_deref())?;
+    let stats = stats?;
+    say(
+        to_stderr,
+        format!(
+            "Scraped {} pages ({} ok, {} errors) in {:.1}s",
+            stats.total, stats.ok, stats.errors, t_scrape.elapsed().as_secs_f64()
+        ),
+    );
+    output::timing("scrape", t_scrape.elapsed());
+    record_scrape(&mut info, &stats);
+    print_error_breakdown(to_stderr, &stats);
+    if stats.invalid > 0 {
+        say(to_stderr, format!("{} were not company pages; queued for re-scrape.", stats.invalid));
+    }
+    if stats.renamed > 0 {
+        say(to_stderr, format!("{} redirected to a renamed company; new slugs queued.", stats.renamed));
+    }
+
+    // Phase 2: Process, one run at a time
+    tokio::task::block_in_place(|| claims.lock(conn, "process"))?;
+    let t_process = Instant::now();
+    let total = db::count_unprocessed(conn, None)?;
+    if total == 0 {
+        say(to_stderr, "Nothing to process (all scraped pages had errors).");
+        manifest::finish(conn, &info)?;
+        return hooks.fire(hooks::Phase::PostRun, &info).await;
+    }
+    say(to_stderr, format!("Processing {} pages...", total));
+    let counts = tokio::task::block_in_place(|| {
+        let pages = db::stream_unprocessed(conn, parallelism.chunk_size(), None);
+        process_pages(sink::open(sink, conn)?.as_mut(), pages, total, parser_config, parallelism)
+    })?;
+    say(
+        to_stderr,
+        format!("Processed in {:.1}s", t_process.elapsed().as_secs_f64()),
+    );
+    output::timing("process", t_process.elapsed());
+    counts.print(to_stderr);
+    counts.save(conn, &info, t_process.elapsed())?;
+    manifest::finish(conn, &info)?;
+    drop(claims);
+    counts.record(&mut info);
+    hooks.fire(hooks::Phase::PostProcess, &info).await?;
+    hooks.fire(hooks::Phase::PostRun, &info).await
+}
+
+fn record_scrape(info: &mut hooks::RunInfo, stats: &scraper::ScrapeStats) {
+    info.count("scraped", stats.total);
+    info.count("scrape_ok", stats.ok);
+    info.count("scrape_errors", stats.errors);
+    info.count("scrape_invalid", stats.invalid);
+    info.count("scrape_renamed", stats.renamed);
+}
+
+/// Failed requests per error category, as a table under the scrape summary
+/// and as errors in the `--json` result.
+fn print_error_breakdown(to_stderr: bool, stats: &scraper::ScrapeStats) {
+    if stats.error_categories.is_empty() {
+        return;
+    }
+    say(to_stderr, format!("  {:<12} {:>6} {:>7}", "error", "count", "share"));
+    for (category, n) in &stats.error_categories {
+        output::error(format!("{} pages failed: {}", n, category));
+        say(
+            to_stderr,
+            format!("  {:<12} {:>6} {:>6.1}%", category, n, *n as f64 * 100.0 / stats.total.max(1) as f64),
+        );
+    }
+}
+
+/// Progress message on stdout, or stderr when stdout carries data (`--sink jsonl`).
+/// Nothing under `--quiet` / `--json`.
+fn say(to_stderr: bool, msg: impl std::fmt::Display) {
+    if output::quiet() {
+        return;
+    }
+    if to_stderr {
+        eprintln!("{}", msg);
+    } else {
+        out!("{}", msg);
+    }
+}
+
+#[derive(Default)]
+struct ProcessCounts {
+    companies: usize,
+    founders: usize,
+    news: usize,
+    jobs: usize,
+    links: usize,
+    meeting_links: usize,
+    qa: usize,
+    locations: usize,
+    quality_flags: usize,
+    /// Pages with no founder, news, job, link, meeting, Q&A or location rows.
+    empty_pages: usize,
+    parse_ms: f64,
+}
+
+impl ProcessCounts {
+    fn print(&self, to_stderr: bool) {
+        say(
+            to_stderr,
+            format!(
+                "Saved {} companies, {} founders, {} news, {} jobs, {} links.",
+                self.companies, self.founders, self.news, self.jobs, self.links,
+            ),
+        );
+    }
+
+    fn record(&self, info: &mut hooks::RunInfo) {
+        info.count("companies", self.companies);
+        info.count("founders", self.founders);
+        info.count("news", self.news);
+        info.count("jobs", self.jobs);
+        info.count("links", self.links);
+    }
+
+    /// Store this run in `process_metrics` for `stats --runs`.
+    fn save(&self, conn: &rusqlite::Connection, info: &hooks::RunInfo, elapsed: Duration) -> anyhow::Result<()> {
+        db::save_process_metrics(
+            conn,
+            &db::ProcessMetrics {
+                run_id: info.run_id.clone(),
+                command: info.command.to_string(),
+                pages: self.companies,
+                empty_pages: self.empty_pages,
+                founders: self.founders,
+                news: self.news,
+                jobs: self.jobs,
+                links: self.links,
+                meeting_links: self.meeting_links,
+                qa: self.qa,
+                locations: self.locations,
+                quality_flags: self.quality_flags,
+                parse_ms: self.parse_ms,
+                elapsed_ms: elapsed.as_secs_f64() * 1000.0,
+                finished_at: None,
+            },
+        )
+    }
+}
+
+/// Pages parsed in parallel and handed to the sink per chunk; also the
+/// streaming read size, which bounds memory during `process`.
+const PROCESS_CHUNK: usize = 500;
+
+/// Chunk size under `--low-memory`.
+const LOW_MEMORY_CHUNK: usize = 50;
+
+/// `--record` / `--replay` for `scrape` and `run`.
+#[derive(clap::Args, Clone, Default)]
+struct CassetteArgs {
+    /// Save every spider.cloud response to this JSON file, for --replay
+    #[arg(long, value_name = "PATH", conflicts_with = "replay")]
+    record: Option<PathBuf>,
+    /// Serve spider.cloud responses from a --record file instead (no network, API key or credits)
+    #[arg(long, value_name = "PATH")]
+    replay: Option<PathBuf>,
+}
+
+impl CassetteArgs {
+    fn open(&self) -> anyhow::Result<Option<std::sync::Arc<cassette::Cassette>>> {
+        let cassette = match (&self.record, &self.replay) {
+            (Some(path), _) => cassette::Cassette::record(path),
+            (None, Some(path)) => cassette::Cassette::replay(path)?,
+            (None, None) => return Ok(None),
+        };
+        Ok(Some(std::sync::Arc::new(cassette)))
+    }
+}
+
+/// Write a `--record` cassette, also after a failed scrape, so what did
+/// come back is kept.
+fn save_cassette(to_stderr: bool, cassette: Option<&cassette::Cassette>) -> anyhow::Result<()> {
+    if let Some(cassette) = cassette.filter(|c| !c.replaying()) {
+        let n = cassette.save()?;
+        say(to_stderr, format!("Recorded {} responses to {}", n, cassette.path().display()));
+    }
+    Ok(())
+}
+
+/// Parser threads and chunk size for `process`, `run` and `refresh`,
+/// applied to the whole run rather than per batch.
+#[derive(clap::Args, Clone, Copy, Default)]
+struct Parallelism {
+    /// Parser threads (default: RAYON_NUM_THREADS, else one per core)
+    #[arg(long, value_parser = clap::builder::RangedU64ValueParser::<usize>::new().range(1..))]
+    threads: Option<usize>,
+    /// Pages read and parsed per chunk (default: 500, or 50 with --low-memory)
+    #[arg(long, value_parser = clap::builder::RangedU64ValueParser::<usize>::new().range(1..))]
+    chunk_size: Option<usize>,
+    /// Parse one page at a time in small chunks, for small VMs
+    #[arg(long, conflicts_with = "threads")]
+    low_memory: bool,
+}
+
+impl Parallelism {
+    fn chunk_size(&self) -> usize {
+        let default = if self.low_memory { LOW_MEMORY_CHUNK } else { PROCESS_CHUNK };
+        self.chunk_size.unwrap_or(default)
+    }
+
+    /// Parser threads; 0 lets rayon read RAYON_NUM_THREADS, else use one per core.
+    fn threads(&self) -> usize {
+        if self.low_memory {
+            1
+        } else {
+            self.threads.unwrap_or(0)
+        }
+    }
+
+    /// Pool the parser runs in.
+    fn pool(&self) -> anyhow::Result<rayon::ThreadPool> {
+        Ok(rayon::ThreadPoolBuilder::new().num_threads(self.threads()).build()?)
+    }
+}
+
+fn process_pages(
+    sink: &mut dyn sink::ExtractSink,
+    pages: impl Iterator<Item = anyhow::Result<Vec<db::ScrapedPage>>>,
+    total: usize,
+    parser_config: &parser::ParserConfig,
+    parallelism: Parallelism,
+) -> anyhow::Result<ProcessCounts> {
+    use indicatif::ProgressStyle;
+    use rayon::prelude::*;
+
+    let pool = parallelism.pool()?;
+
+    let pb = output::progress_bar(total as u64);
+    pb.set_style(
+        ProgressStyle::default_bar()
+            .template("{spinner:.green} [{elapsed_precise}] [{bar:40.cyan/blue}] {pos}/{len} ({per_sec})")
+            .unwrap()
+            .progress_chars("#>-"),
+    );
+
+    let mut counts = ProcessCounts::default();
+
+    for chunk in pages {
+        let chunk = chunk?;
+        let results: Vec<_> =
+            pool.install(|| chunk.par_iter().map(|page| parser::process_page(page, parser_config)).collect());
+
+        for data in &results {
+            counts.founders += data.founders.len();
+            counts.news += data.news.len();
+            counts.jobs += data.jobs.len();
+            counts.links += data.links.len();
+            counts.meeting_links += data.meeting_links.len();
+            counts.qa += data.qa.len();
+            counts.locations += data.locations.len();
+            counts.quality_flags += data.quality.len();
+            counts.parse_ms += data.sections.parse_ms;
+            let rows = data.founders.len()
+                + data.news.len()
+                + data.jobs.len()
+                + data.links.len()
+                + data.meeting_links.len()
+                + data.qa.len()
+                + data.locations.len();
+            if rows == 0 {
+                counts.empty_pages += 1;
+            }
+        }
+        counts.companies += results.len();
+
+        sink.write(results)?;
+        pb.inc(chunk.len() as u64);
+    }
+
+    sink.finish()?;
+    pb.finish_and_clear();
+    Ok(counts)
+}
+
+fn print_overview(rows: &[db::OverviewRow]) {
+    // Compact, readable table
+    out!(
+        "{:>3} | {:<24} | {:<12} | {:<8} | {:>5} | {:<20} | {:<16} | {:>4} | {:>3}",
+        "#", "Company", "Batch", "Status", "Size", "Location", "Partner", "Jobs", "Q"
+    );
+    out!("{}", "-".repeat(111));
+
+    for (i, r) in rows.iter().enumerate() {
+        let name = truncate(&r.name, 24);
+        let loc = truncate(&r.location, 20);
+        let partner = truncate(&r.primary_partner, 16);
+        let size = r.team_size.map(|s| s.to_string()).unwrap_or_else(|| "-".into());
+        let quality = r.quality_score.map(|q| q.to_string()).unwrap_or_else(|| "-".into());
+
+        out!(
+            "{:>3} | {:<24} | {:<12} | {:<8} | {:>5} | {:<20} | {:<16} | {:>4} | {:>3}",
+            i + 1, name, r.batch, r.status.map_or("", model::CompanyStatus::as_str), size, loc, partner, r.job_count, quality
+        );
+    }
+
+    // Tags summary (separate section to avoid clutter)
+    let with_tags: Vec<_> = rows.iter().filter(|r| !r.tags.is_empty()).collect();
+    if !with_tags.is_empty() {
+        out!("\n--- Tags ---");
+        for r in &with_tags {
+            out!("  {}: {}", truncate(&r.slug, 24), r.tags);
+        }
+    }
+
+    out!("\n{} companies | slug: /companies/<slug>", rows.len());
+}
+
+/// The `report` sections flagged in `selected` (repeat founders, domain
+/// health, quality, missing founders); all of them when none is.
+fn print_reports(conn: &rusqlite::Connection, selected: [bool; 4]) -> anyhow::Result<()> {
+    let all = !selected.contains(&true);
+    let [repeat_founders, domain_health, quality, missing_founders] = if all { [true; 4] } else { selected };
+    if repeat_founders {
+        report::print_repeat_founders(&report::repeat_founders(conn)?);
+    }
+    if domain_health {
+        if all {
+            out!();
+        }
+        report::print_domain_health(conn)?;
+    }
+    if quality {
+        if all {
+            out!();
+        }
+        report::print_quality(conn)?;
+    }
+    if missing_founders {
+        if all {
+            out!();
+        }
+        report::print_missing_founders(conn)?;
+    }
+    Ok(())
+}
+
+fn print_stats(s: &db::Stats) {
+    let error_rate = if s.scraped > 0 {
+        s.errors as f64 * 100.0 / s.scraped as f64
+    } else {
+        0.0
+    };
+
+    out!("── Pages ──");
+    out!("  Total:     {}", s.total);
+    out!("  Visited:   {}", s.visited);
+    out!("  Unvisited: {}", s.unvisited);
+    out!("  Scraped:   {}", s.scraped);
+    out!("  Errors:    {} ({:.1}%)", s.errors, error_rate);
+    out!("  Processed: {}", s.processed);
+
+    if let Some((p50, p95, p99)) = s.latency_ms {
+        out!("\n── Scrape latency ──");
+        out!("  p50: {}ms | p95: {}ms | p99: {}ms", p50, p95, p99);
+    }
+
+    if !s.error_categories.is_empty() {
+        out!("\n── Errors by category ──");
+        let w = label_width(s.error_categories.iter().map(|(c, _)| *c));
+        for (category, n) in &s.error_categories {
+            out!(
+                "  {:<w$} {:>6} ({:.1}%)",
+                category,
+                n,
+                *n as f64 * 100.0 / s.scraped.max(1) as f64
+            );
+        }
+    }
+
+    if let Some(m) = &s.markdown_sizes {
+        out!("\n── Markdown size ──");
+        out!(
+            "  min: {} | p50: {} | p95: {} | max: {} | total: {}",
+            format_bytes(m.min),
+            format_bytes(m.p50),
+            format_bytes(m.p95),
+            format_bytes(m.max),
+            format_bytes(m.total)
+        );
+        let widest = m.histogram.iter().map(|(_, n)| *n).max().unwrap_or(0).max(1);
+        for &(lower, n) in &m.histogram {
+            let range = if lower == 0 {
+                format!("< {}", format_bytes(db::FIRST_SIZE_BUCKET))
+            } else {
+                format!("{}–{}", format_bytes(lower), format_bytes(lower * 2))
+            };
+            out!("  {:>15} {:>6} {}", range, n, "#".repeat((n * 40).div_ceil(widest)));
+        }
+    }
+
+    if !s.size_by_batch.is_empty() {
+        out!("\n── Average page size by batch ──");
+        let w = label_width(s.size_by_batch.iter().map(|(b, _, _)| b.as_str()));
+        for (batch, pages, avg) in &s.size_by_batch {
+            out!("  {:<w$} {:>6} pages  {:>8}", batch, pages, format_bytes(*avg));
+        }
+    }
+
+    if !s.skipped_kinds.is_empty() {
+        out!("\n── Scraped but not parsed ──");
+        let ws = label_width(s.skipped_kinds.iter().map(|(source, _, _)| *source));
+        let wk = label_width(s.skipped_kinds.iter().map(|(_, kind, _)| kind.as_str()));
+        for (source, kind, n) in &s.skipped_kinds {
+            out!("  {:<ws$} {:<wk$} {:>6}", source, kind, n);
+        }
+    }
+
+    if !s.quality_flags.is_empty() {
+        out!("\n── Extraction quality flags ──");
+        let w = label_width(s.quality_flags.iter().map(|(c, _)| c.as_str()));
+        for (check, n) in &s.quality_flags {
+            out!(
+                "  {:<w$} {:>6} ({:.1}% of processed)",
+                check,
+                n,
+                *n as f64 * 100.0 / s.processed.max(1) as f64
+            );
+        }
+    }
+
+    out!("\n── Rows per table ──");
+    let w = label_width(s.table_rows.iter().map(|(t, _)| t.as_str()));
+    for (table, n) in &s.table_rows {
+        out!("  {:<w$} {:>8}", table, n);
+    }
+}
+
+/// Width of the widest label, so a column lines up whatever the names.
+fn label_width<'a>(labels: impl Iterator<Item = &'a str>) -> usize {
+    labels.map(|l| l.chars().count()).max().unwrap_or(0)
+}
+
+/// Runs listed by `stats --runs`.
+const RUNS_SHOWN: usize = 20;
+
+/// One line per run, oldest first, with per-page rates so extraction drift stands out.
+fn print_runs(runs: &[db::ProcessMetrics]) {
+    if runs.is_empty() {
+        out!("No runs recorded yet. 'process', 'run' and 'refresh' record one each.");
+        return;
+    }
+    out!(
+        "{:<19}  {:<8} {:>6} {:>6}  {:>8} {:>6} {:>6} {:>6} {:>6}  {:>7} {:>8}",
+        "finished", "command", "pages", "empty", "founders", "news", "jobs", "links", "qa", "ms/page", "wall"
+    );
+    for m in runs {
+        let per_page = |n: usize| n as f64 / m.pages.max(1) as f64;
+        out!(
+            "{:<19}  {:<8} {:>6} {:>5.1}%  {:>8.2} {:>6.2} {:>6.2} {:>6.2} {:>6.2}  {:>7.2} {:>8}",
+            m.finished_at.as_deref().unwrap_or("-"),
+            m.command,
+            m.pages,
+            per_page(m.empty_pages) * 100.0,
+            per_page(m.founders),
+            per_page(m.news),
+            per_page(m.jobs),
+            per_page(m.links),
+            per_page(m.qa),
+            m.parse_ms / m.pages.max(1) as f64,
+            format_duration(Duration::from_secs_f64(m.elapsed_ms / 1000.0)),
+        );
+    }
+    out!("\nfounders through qa are rows per page.");
+}
+
+fn format_bytes(n: i64) -> String {
+    let n = n as f64;
+    if n < 1024.0 {
+        format!("{}B", n)
+    } else if n < 1024.0 * 1024.0 {
+        format!("{:.1}KB", n / 1024.0)
+    } else {
+        format!("{:.1}MB", n / (1024.0 * 1024.0))
+    }
+}
+
+fn truncate(s: &str, max: usize) -> String {
+    if s.chars().count() <= max {
+        s.to_string()
+    } else {
+        let truncated: String = s.chars().take(max).collect();
+        format!("{}...", truncated)
+    }
+}
+
+fn format_duration(d: std::time::Duration) -> String {
+    let secs = d.as_secs();
+    if secs < 60 {
+        format!("{:.1}s", d.as_secs_f64())
+    } else if secs < 3600 {
+        format!("{}m {}s", secs / 60, secs % 60)
+    } else {
+        format!("{}h {}m {}s", secs / 3600, (secs % 3600) / 60, secs % 60)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The bundled fixture pages, scraped and processed as `demo` does.
+    fn seeded() -> rusqlite::Connection {
+        let conn = rusqlite::Connection::open_in_memory().unwrap();
+        db::init_schema(&conn).unwrap();
+        demo::seed(&conn).unwrap();
+        let total = db::count_unprocessed(&conn, None).unwrap();
+        process_pages(
+            sink::open(&sink::SinkKind::Sqlite, &conn).unwrap().as_mut(),
+            db::stream_unprocessed(&conn, PROCESS_CHUNK, None),
+            total,
+            &parser::ParserConfig::default(),
+            Parallelism::default(),
+        )
+        .unwrap();
+        conn
+    }
+
+    #[test]
+    fn parallelism_resolves_threads_and_chunk_size() {
+        let parse = |args: &[&str]| {
+            Cli::try_parse_from([&["yc_scraper", "process"], args].concat()).map(|cli| match cli.command {
+                Commands::Process { parallelism, .. } => parallelism,
+                _ => unreachable!(),
+            })
+        };
+        let default = parse(&[]).unwrap();
+        assert_eq!((default.threads(), default.chunk_size()), (0, PROCESS_CHUNK), "0 leaves the count to rayon");
+        let low = parse(&["--low-memory"]).unwrap();
+        assert_eq!((low.threads(), low.chunk_size()), (1, LOW_MEMORY_CHUNK));
+        assert_eq!(low.pool().unwrap().current_num_threads(), 1);
+        assert_eq!(parse(&["--low-memory", "--chunk-size", "10"]).unwrap().chunk_size(), 10);
+        let set = parse(&["--threads", "3", "--chunk-size", "20"]).unwrap();
+        assert_eq!((set.threads(), set.chunk_size()), (3, 20));
+        assert_eq!(set.pool().unwrap().current_num_threads(), 3);
+        assert!(parse(&["--threads", "0"]).is_err());
+        assert!(parse(&["--threads", "2", "--low-memory"]).is_err());
+    }
+
+    #[test]
+    fn demo_db_is_removed_even_when_the_demo_fails() {
+        let path = std::env::temp_dir().join(format!("yc-demo-guard-test-{}.sqlite", std::process::id()));
+        let attempt = || -> anyhow::Result<()> {
+            let _cleanup = DemoDb { path: path.clone(), keep: false };
+            let conn = db::connect_at(&path)?;
+            conn.execute_batch("CREATE TABLE t (x); INSERT INTO t VALUES (1);")?;
+            anyhow::bail!("demo failed")
+        };
+        assert!(attempt().is_err());
+        assert!(!path.exists());
+
+        std::fs::write(&path, "").unwrap();
+        drop(DemoDb { path: path.clone(), keep: true });
+        assert!(path.exists());
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn overview_output() {
+        let conn = seeded();
+        let rows = db::fetch_overview(&conn, &[], &[], None, false, i64::MAX as usize).unwrap();
+        insta::assert_snapshot!("overview", output::capture(|| print_overview(&rows)));
+        let groups = report::group_overview(&rows, report::OverviewGroup::Batch);
+        insta::assert_snapshot!(
+            "overview_by_batch",
+            output::capture(|| report::print_overview_groups(&groups, rows.len(), 10))
+        );
+    }
+
+    #[test]
+    fn stats_output() {
+        let conn = seeded();
+        let stats = db::get_stats(&conn).unwrap();
+        insta::assert_snapshot!("stats", output::capture(|| print_stats(&stats)));
+    }
+
+    #[test]
+    fn report_output() {
+        let conn = seeded();
+        // One row for every section to show
+        conn.execute_batch(
+            "INSERT INTO founders (company_slug, name, name_key, linkedin) VALUES
+                 ('doordash', 'Patrick Collison', 'patrick collison', 'https://linkedin.com/in/patrickcollison');
+             INSERT INTO domain_health (domain, base_domain, checks, ok, success_rate, mean_latency_ms) VALUES
+                 ('linkedin.com', 'linkedin.com', 12, 9, 0.75, 180.0),
+                 ('twitter.com', 'twitter.com', 4, 4, 1.0, NULL);
+             INSERT INTO extraction_quality (company_slug, check_name, expected, actual, detail) VALUES
+                 ('doordash', 'job_count', 6, 5, 'page lists 6 jobs');
+             DELETE FROM founder_experience WHERE company_slug = 'groupahead';
+             UPDATE company_links SET founder_id = NULL WHERE company_slug = 'groupahead';
+             DELETE FROM founders WHERE company_slug = 'groupahead';",
+        )
+        .unwrap();
+        insta::assert_snapshot!("report", output::capture(|| print_reports(&conn, [false; 4]).unwrap()));
+    }
+}
@@ -0,0 +1,108 @@
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+use anyhow::{Context, Result};
+use serde::Deserialize;
+
+use crate::blobstore::StorageConfig;
+use crate::email::EmailConfig;
+use crate::enrich::EnrichConfig;
+use crate::hooks::Hooks;
+use crate::parser::ParserConfig;
+use crate::redact::RedactionPolicy;
+
+/// Looked up in the working directory when `--config` is not given.
+pub const DEFAULT_PATH: &str = "yc.toml";
+
+/// Optional project settings from `yc.toml`.
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Config {
+    #[serde(default)]
+    pub hooks: Hooks,
+    #[serde(default)]
+    pub parser: ParserConfig,
+    #[serde(default)]
+    pub storage: StorageConfig,
+    #[serde(default)]
+    pub redact: RedactionPolicy,
+    #[serde(default)]
+    pub enrich: EnrichConfig,
+    #[serde(default)]
+    pub email: EmailConfig,
+}
+
+static PATH: OnceLock<Option<PathBuf>> = OnceLock::new();
+static LOADED: OnceLock<Config> = OnceLock::new();
+
+/// Remember `--config` for `get`. Nothing is read until a command needs it.
+pub fn set_path(path: Option<PathBuf>) {
+    let _ = PATH.set(path);
+}
+
+/// The settings for this process, loaded on first use. Without `set_path`
+/// (unit tests) this is the empty config.
+pub fn get() -> Result<&'static Config> {
+    if let Some(config) = LOADED.get() {
+        return Ok(config);
+    }
+    let config = match PATH.get() {
+        Some(path) => load(path.as_deref())?,
+        None => Config::default(),
+    };
+    Ok(LOADED.get_or_init(|| config))
+}
+
+/// Load `path`, or `yc.toml` if present. An explicit path must exist;
+/// a missing default file means an empty config.
+pub fn load(path: Option<&Path>) -> Result<Config> {
+    let (path, required) = match path {
+        Some(p) => (p, true),
+        None => (Path::new(DEFAULT_PATH), false),
+    };
+    if !required && !path.exists() {
+        return Ok(Config::default());
+    }
+    let text = std::fs::read_to_string(path).with_context(|| format!("reading {}", path.display()))?;
+    parse(&text).with_context(|| format!("parsing {}", path.display()))
+}
+
+fn parse(text: &str) -> Result<Config> {
+    Ok(toml::from_str(text)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_hooks_and_rejects_typos() {
+        let config = parse(
+            "[hooks]\npre_scrape = \"echo start\"\npost_run = \"dbt run\"\n",
+        )
+        .unwrap();
+        assert_eq!(config.hooks.pre_scrape.as_deref(), Some("echo start"));
+        assert_eq!(config.hooks.post_run.as_deref(), Some("dbt run"));
+        assert!(config.hooks.post_process.is_none());
+
+        assert!(parse("[hooks]\npost_scrape = \"x\"\n").is_err());
+        assert!(parse("").unwrap().hooks.pre_scrape.is_none());
+    }
+
+    #[test]
+    fn parser_thresholds_default_per_field() {
+        let config = parse("[parser]\nmin_meta_cluster = 2\n").unwrap();
+        assert_eq!(config.parser.min_meta_cluster, 2);
+        assert_eq!(config.parser.max_person_name_len, 60);
+        assert_eq!(parse("").unwrap().parser.max_person_name_words, 6);
+        assert!(parse("[parser]\nmin_meta = 2\n").is_err());
+        assert!(parse("").unwrap().storage.pages.is_none());
+        let storage = parse("[storage]\npages = \"s3://bucket/yc\"\n").unwrap().storage;
+        assert_eq!(storage.pages.as_deref(), Some("s3://bucket/yc"));
+        assert!(parse("").unwrap().redact.scrub_text);
+        assert!(parse("[redact]\nsalt = \"x\"\nmode = \"drop\"\n").is_err());
+        let email = parse("[email]\nsmtp_host = \"smtp.test\"\nto = [\"a@test\"]\n").unwrap().email;
+        assert_eq!((email.smtp_host.as_deref(), email.smtp_port, email.to.len()), (Some("smtp.test"), None, 1));
+        assert!(parse("[email]\npassword = \"x\"\n").is_err());
+    }
+}
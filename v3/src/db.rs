@@ -1,11 +1,30 @@
+use std::path::Path;
+use std::sync::Arc;
+
 use anyhow::Result;
-use rusqlite::Connection;
+use rusqlite::types::Value;
+use rusqlite::{Connection, OptionalExtension};
+use serde::Serialize;
+
+use crate::blobstore::BlobStore;
+use crate::model::{Batch, CompanyStatus, Timestamp};
+use crate::parser::extract::quality::{self, ScoreInputs};
+use crate::query::Select;
 
-const DB_PATH: &str = "data/yc.sqlite";
+pub const DB_PATH: &str = "data/yc.sqlite";
 
 pub fn connect() -> Result<Connection> {
-    let conn = Connection::open(DB_PATH)?;
-    conn.execute_batch("PRAGMA journal_mode=WAL; PRAGMA foreign_keys=ON;")?;
+    connect_at(Path::new(DB_PATH))
+}
+
+pub fn connect_at(path: &Path) -> Result<Connection> {
+    let conn = Connection::open(path)?;
+    // Concurrent runs (see `claim_pages`) take turns at the write lock
+    conn.busy_timeout(std::time::Duration::from_secs(30))?;
+    // auto_vacuum only takes effect on new DBs; `maintain` converts old ones.
+    conn.execute_batch(
+        "PRAGMA auto_vacuum=INCREMENTAL; PRAGMA journal_mode=WAL; PRAGMA foreign_keys=ON;",
+    )?;
     Ok(conn)
 }
 
@@ -18,10 +37,22 @@ pub fn init_schema(conn: &Connection) -> Result<()> {
             slug       TEXT NOT NULL,
             visited    BOOLEAN NOT NULL DEFAULT 0,
             visited_at TEXT,
+            lastmod    TEXT,
+            content_retries INTEGER NOT NULL DEFAULT 0,
+            claimed_by TEXT,  -- run scraping this page, until claim_expires
+            claim_expires TEXT,
+            discovered_via TEXT,  -- listing page `discover` found it on; NULL when it came from the sitemap
             created_at TEXT NOT NULL DEFAULT (datetime('now'))
         );
         CREATE INDEX IF NOT EXISTS idx_pages_visited ON pages(visited);
 
+        -- Advisory locks between concurrent invocations (e.g. the process phase)
+        CREATE TABLE IF NOT EXISTS locks (
+            name          TEXT PRIMARY KEY,
+            owner         TEXT NOT NULL,
+            expires_at    TEXT NOT NULL
+        );
+
         CREATE TABLE IF NOT EXISTS page_data (
             id         INTEGER PRIMARY KEY,
             page_id    INTEGER NOT NULL REFERENCES pages(id),
@@ -31,6 +62,15 @@ pub fn init_schema(conn: &Connection) -> Result<()> {
             status     INTEGER,
             error      TEXT,
             latency_ms INTEGER,
+            content_kind TEXT,
+            metadata   TEXT,
+            final_url  TEXT,  -- where a redirect landed, when not the requested URL
+            scrape_meta TEXT,  -- spider.cloud response details (status, content type, title, costs, error body)
+            markdown_raw BLOB,  -- content before image stripping (scrape --keep-raw), UTF-8 or gzip
+            return_format TEXT,  -- 'html' when markdown holds the raw HTML page (--return-format html)
+            blob_key   TEXT,  -- markdown lives in the blob store under this key ([storage] pages)
+            markdown_bytes INTEGER,
+            raw_blob_key TEXT,  -- markdown_raw lives in the blob store under this key ([storage] pages)
             scraped_at TEXT NOT NULL DEFAULT (datetime('now'))
         );
         CREATE INDEX IF NOT EXISTS idx_page_data_slug ON page_data(slug);
@@ -49,9 +89,14 @@ pub fn init_schema(conn: &Connection) -> Result<()> {
             founders_raw TEXT,
             launches     TEXT,
             extras       TEXT,
+            spans        TEXT,
+            parse_ms     REAL,
+            block_count  INTEGER,
+            section_count INTEGER,
             processed_at TEXT NOT NULL DEFAULT (datetime('now'))
         );
         CREATE UNIQUE INDEX IF NOT EXISTS idx_sections_slug ON company_sections(slug);
+        CREATE INDEX IF NOT EXISTS idx_sections_page ON company_sections(page_id);
 
         -- Extracted structured data
         CREATE TABLE IF NOT EXISTS companies (
@@ -62,20 +107,31 @@ pub fn init_schema(conn: &Connection) -> Result<()> {
             batch         TEXT,
             batch_season  TEXT,
             batch_year    INTEGER,
+            -- chronological sort key: year * 10 + 1-4 for Winter..Fall (model::Batch::ordinal)
+            batch_ordinal INTEGER GENERATED ALWAYS AS (batch_year * 10 + CASE batch_season
+                WHEN 'Winter' THEN 1 WHEN 'Spring' THEN 2 WHEN 'Summer' THEN 3 WHEN 'Fall' THEN 4 ELSE 0 END) VIRTUAL,
             status        TEXT CHECK(status IN ('Active','Public','Acquired','Inactive')),
             is_active     BOOLEAN GENERATED ALWAYS AS (status IN ('Active','Public')) STORED,
             homepage      TEXT,
             founded_year  INTEGER,
+            company_age_years INTEGER,
             team_size     INTEGER,
             location      TEXT,
             primary_partner TEXT,
+            primary_partner_slug TEXT,
+            group_partner TEXT,
+            group_partner_slug TEXT,
             tags          TEXT,
-            job_count     INTEGER DEFAULT 0,
+            job_count_extracted INTEGER DEFAULT 0,  -- job links extracted from the jobs section
+            job_count_listed INTEGER,  -- number of jobs the page itself states ('View all 12 jobs')
             linkedin      TEXT,
             twitter       TEXT,
             facebook      TEXT,
             crunchbase    TEXT,
             github        TEXT,
+            description_lang TEXT,
+            renamed_to    TEXT,  -- current slug, once this page redirects to another company
+            quality_score INTEGER,  -- 0-100 completeness (parser::extract::quality::score)
             created_at    TEXT NOT NULL DEFAULT (datetime('now'))
         );
 
@@ -83,11 +139,17 @@ pub fn init_schema(conn: &Connection) -> Result<()> {
             id            INTEGER PRIMARY KEY,
             company_slug  TEXT NOT NULL REFERENCES companies(slug),
             name          TEXT NOT NULL,
+            name_key      TEXT,
             title         TEXT,
             bio           TEXT,
             is_active     BOOLEAN NOT NULL DEFAULT 1,
+            first_seen_at TEXT,  -- scrape time of the first processed page listing this founder
+            departed_at   TEXT,  -- scrape time of the first page listing them under Former Founders; NULL while active
+            departed      BOOLEAN GENERATED ALWAYS AS (departed_at IS NOT NULL) VIRTUAL,
             linkedin      TEXT,
+            linkedin_match TEXT,  -- how a linkedin taken from company_links matched: founder_link | name_in_handle
             twitter       TEXT,
+            source_lines  TEXT,
             UNIQUE(company_slug, name)
         );
         CREATE INDEX IF NOT EXISTS idx_founders_company ON founders(company_slug);
@@ -98,10 +160,36 @@ pub fn init_schema(conn: &Connection) -> Result<()> {
             title         TEXT NOT NULL,
             url           TEXT NOT NULL,
             published     TEXT,
+            canonical_url TEXT,
+            resolved_at   TEXT,
+            source_lines  TEXT,
+            article_id    TEXT,   -- news_global.article_id (hash of canonical_url, else url)
+            duplicate_of  INTEGER,  -- news.id of the same company's row for this article (resolve-news)
             UNIQUE(company_slug, url)
         );
         CREATE INDEX IF NOT EXISTS idx_news_company ON news(company_slug);
 
+        -- One row per article across companies (rebuilt from news after process / resolve-news)
+        CREATE TABLE IF NOT EXISTS news_global (
+            article_id    TEXT PRIMARY KEY,
+            url           TEXT NOT NULL,
+            title         TEXT NOT NULL,
+            published     TEXT,
+            company_count INTEGER NOT NULL
+        );
+
+        -- Funding rounds announced in news titles (rebuilt from news after process / resolve-news)
+        CREATE TABLE IF NOT EXISTS funding_events (
+            id            INTEGER PRIMARY KEY,
+            company_slug  TEXT NOT NULL REFERENCES companies(slug),
+            round         TEXT,   -- 'Pre-Seed', 'Seed', 'Series A', ...; NULL when only an amount is given
+            amount        REAL,   -- in currency
+            currency      TEXT,   -- ISO code
+            announced     TEXT,   -- earliest publish date of the articles reporting it
+            url           TEXT NOT NULL  -- article the round was first read from
+        );
+        CREATE INDEX IF NOT EXISTS idx_funding_company ON funding_events(company_slug);
+
         CREATE TABLE IF NOT EXISTS company_jobs (
             id            INTEGER PRIMARY KEY,
             company_slug  TEXT NOT NULL REFERENCES companies(slug),
@@ -109,18 +197,45 @@ pub fn init_schema(conn: &Connection) -> Result<()> {
             url           TEXT NOT NULL,
             location      TEXT,
             salary        TEXT,
+            salary_currency TEXT,  -- ISO code: USD, EUR, GBP, INR, ...
+            salary_min    REAL,    -- range in salary_currency
+            salary_max    REAL,
+            salary_min_usd INTEGER, -- converted with [parser.usd_rates] / built-in rates
+            salary_max_usd INTEGER,
             experience    TEXT,
             apply_url     TEXT,
+            apply_url_final TEXT,
+            ats_vendor    TEXT,
+            apply_resolved_at TEXT,
+            source_lines  TEXT,
+            tech_stack    TEXT,   -- JSON array of keyword chips (from job_details)
+            benefits      TEXT,   -- JSON array of keyword chips (from job_details)
             UNIQUE(company_slug, url)
         );
         CREATE INDEX IF NOT EXISTS idx_jobs_company ON company_jobs(company_slug);
 
+        -- Job detail pages (scrape-jobs), split into body sections
+        CREATE TABLE IF NOT EXISTS job_details (
+            job_id           INTEGER PRIMARY KEY REFERENCES company_jobs(id),
+            markdown         TEXT NOT NULL,
+            responsibilities TEXT,
+            requirements     TEXT,
+            nice_to_have     TEXT,
+            benefits_text    TEXT,
+            summary          TEXT,
+            content_kind     TEXT,   -- validate::job_content_kind; only 'job' rows are parsed
+            scraped_at       TEXT NOT NULL DEFAULT (datetime('now'))
+        );
+
         CREATE TABLE IF NOT EXISTS company_links (
             id            INTEGER PRIMARY KEY,
             company_slug  TEXT NOT NULL REFERENCES companies(slug),
             url           TEXT NOT NULL,
             domain        TEXT NOT NULL,
             link_type     TEXT,
+            publisher     TEXT,  -- news outlet, for link_type 'press'
+            founder_id    INTEGER REFERENCES founders(id),  -- personal link of this founder
+            source_lines  TEXT,
             UNIQUE(company_slug, url)
         );
         CREATE INDEX IF NOT EXISTS idx_links_company ON company_links(company_slug);
@@ -132,6 +247,7 @@ pub fn init_schema(conn: &Connection) -> Result<()> {
             url           TEXT NOT NULL,
             domain        TEXT NOT NULL,
             link_type     TEXT NOT NULL,
+            source_lines  TEXT,
             UNIQUE(company_slug, url)
         );
         CREATE INDEX IF NOT EXISTS idx_meeting_company ON meeting_links(company_slug);
@@ -154,42 +270,787 @@ pub fn init_schema(conn: &Connection) -> Result<()> {
         );
         CREATE INDEX IF NOT EXISTS idx_cp_company ON company_partners(company_slug);
         CREATE INDEX IF NOT EXISTS idx_cp_partner ON company_partners(partner_slug);
+
+        -- Canonical industry tags from their YC landing pages (`tags`)
+        CREATE TABLE IF NOT EXISTS tag_catalog (
+            tag_key     TEXT PRIMARY KEY,  -- lowercased, single-spaced
+            tag         TEXT NOT NULL,
+            url         TEXT NOT NULL,
+            description TEXT,
+            crawled_at  TEXT NOT NULL DEFAULT (datetime('now'))
+        );
+
+        CREATE TABLE IF NOT EXISTS company_qa (
+            id            INTEGER PRIMARY KEY,
+            company_slug  TEXT NOT NULL REFERENCES companies(slug),
+            question      TEXT NOT NULL,
+            answer        TEXT NOT NULL,
+            source_lines  TEXT,
+            UNIQUE(company_slug, question)
+        );
+        CREATE INDEX IF NOT EXISTS idx_qa_company ON company_qa(company_slug);
+
+        -- Rows from registered extractors (parser/extract/custom.rs), replaced per company on each process
+        CREATE TABLE IF NOT EXISTS custom_rows (
+            id            INTEGER PRIMARY KEY,
+            extractor     TEXT NOT NULL,
+            company_slug  TEXT NOT NULL REFERENCES companies(slug),
+            position      INTEGER NOT NULL,  -- order among the company rows from this extractor
+            data          TEXT NOT NULL,     -- JSON object of the row fields
+            source_lines  TEXT,
+            UNIQUE(extractor, company_slug, position)
+        );
+        CREATE INDEX IF NOT EXISTS idx_custom_rows_company ON custom_rows(company_slug);
+
+        -- Other YC company pages a company page links to (`graph export`);
+        -- to_slug need not be scraped yet
+        CREATE TABLE IF NOT EXISTS company_references (
+            id            INTEGER PRIMARY KEY,
+            from_slug     TEXT NOT NULL REFERENCES companies(slug),
+            to_slug       TEXT NOT NULL,
+            section       TEXT NOT NULL,  -- page section the link was in
+            source_lines  TEXT,
+            UNIQUE(from_slug, to_slug)
+        );
+        CREATE INDEX IF NOT EXISTS idx_references_to ON company_references(to_slug);
+
+        -- Footer locations after the primary one on companies.location
+        CREATE TABLE IF NOT EXISTS company_locations (
+            id            INTEGER PRIMARY KEY,
+            company_slug  TEXT NOT NULL REFERENCES companies(slug),
+            location      TEXT NOT NULL,
+            position      INTEGER NOT NULL,
+            UNIQUE(company_slug, location)
+        );
+        CREATE INDEX IF NOT EXISTS idx_locations_company ON company_locations(company_slug);
+
+        -- Schools and previous employers named in founder bios
+        CREATE TABLE IF NOT EXISTS founder_experience (
+            id            INTEGER PRIMARY KEY,
+            founder_id    INTEGER NOT NULL REFERENCES founders(id),
+            company_slug  TEXT NOT NULL REFERENCES companies(slug),
+            kind          TEXT NOT NULL,  -- education | employer
+            org           TEXT NOT NULL,
+            UNIQUE(founder_id, kind, org)
+        );
+        CREATE INDEX IF NOT EXISTS idx_experience_company ON founder_experience(company_slug);
+        CREATE INDEX IF NOT EXISTS idx_experience_org ON founder_experience(org);
+
+        -- Stored company values replaced by a later extraction
+        CREATE TABLE IF NOT EXISTS field_changes (
+            id            INTEGER PRIMARY KEY,
+            company_slug  TEXT NOT NULL REFERENCES companies(slug),
+            field         TEXT NOT NULL,
+            old_value     TEXT,
+            new_value     TEXT,
+            changed_at    TEXT NOT NULL DEFAULT (datetime('now'))
+        );
+        CREATE INDEX IF NOT EXISTS idx_field_changes_company ON field_changes(company_slug);
+
+        -- Failed cross-checks from the extractor (one row per company + check)
+        CREATE TABLE IF NOT EXISTS extraction_quality (
+            company_slug  TEXT NOT NULL REFERENCES companies(slug),
+            check_name    TEXT NOT NULL,
+            expected      INTEGER,
+            actual        INTEGER,
+            detail        TEXT,
+            checked_at    TEXT NOT NULL DEFAULT (datetime('now')),
+            PRIMARY KEY (company_slug, check_name)
+        );
+
+        -- Manual verdicts from `review` on flagged extractions
+        CREATE TABLE IF NOT EXISTS review_annotations (
+            id            INTEGER PRIMARY KEY,
+            company_slug  TEXT NOT NULL REFERENCES companies(slug),
+            check_name    TEXT NOT NULL,
+            action        TEXT NOT NULL CHECK(action IN ('accept','reject','fix','note')),
+            field         TEXT,
+            value         TEXT,
+            note          TEXT,
+            created_at    TEXT NOT NULL DEFAULT (datetime('now'))
+        );
+        CREATE INDEX IF NOT EXISTS idx_review_company ON review_annotations(company_slug, check_name);
+
+        -- Row changes to companies, founders and jobs for `changes --since`,
+        -- written by the triggers from `create_change_triggers`
+        CREATE TABLE IF NOT EXISTS change_log (
+            id            INTEGER PRIMARY KEY,
+            table_name    TEXT NOT NULL,
+            row_key       TEXT NOT NULL,
+            company_slug  TEXT NOT NULL,
+            op            TEXT NOT NULL CHECK(op IN ('insert','update','delete')),
+            columns       TEXT,
+            changed_at    TEXT NOT NULL DEFAULT (strftime('%Y-%m-%d %H:%M:%f', 'now'))
+        );
+        CREATE INDEX IF NOT EXISTS idx_change_log_at ON change_log(changed_at);
+
+        -- Regex replacements for text artifacts in extracted columns (`fixups apply`)
+        CREATE TABLE IF NOT EXISTS fixups (
+            id            INTEGER PRIMARY KEY,
+            table_name    TEXT NOT NULL,
+            column_name   TEXT NOT NULL,
+            pattern       TEXT NOT NULL,
+            replacement   TEXT NOT NULL DEFAULT '',
+            note          TEXT,
+            rows_changed  INTEGER NOT NULL DEFAULT 0,
+            applied_at    TEXT,
+            created_at    TEXT NOT NULL DEFAULT (datetime('now')),
+            UNIQUE (table_name, column_name, pattern)
+        );
+
+        -- Companies removed with `purge <slug>`; `init` / `refresh` never queue them again
+        CREATE TABLE IF NOT EXISTS purged_companies (
+            slug       TEXT PRIMARY KEY,
+            reason     TEXT,
+            purged_at  TEXT NOT NULL DEFAULT (datetime('now'))
+        );
+
+        -- One row per `process` / `run` / `refresh`: pages parsed, rows per extractor, timing
+        CREATE TABLE IF NOT EXISTS process_metrics (
+            run_id         TEXT PRIMARY KEY,
+            command        TEXT NOT NULL,
+            pages          INTEGER NOT NULL,
+            empty_pages    INTEGER NOT NULL,  -- pages that produced no founder, news, job, link or Q&A rows
+            founders       INTEGER NOT NULL,
+            news           INTEGER NOT NULL,
+            jobs           INTEGER NOT NULL,
+            links          INTEGER NOT NULL,
+            meeting_links  INTEGER NOT NULL,
+            qa             INTEGER NOT NULL,
+            locations      INTEGER NOT NULL,
+            quality_flags  INTEGER NOT NULL,
+            parse_ms       REAL NOT NULL,     -- summed per-page parser time
+            elapsed_ms     REAL NOT NULL,     -- wall time of the process phase
+            finished_at    TEXT NOT NULL DEFAULT (datetime('now'))
+        );
+
+        -- One row per `scrape` / `process` / `run` / `refresh`: what produced the data, for reproducing it
+        CREATE TABLE IF NOT EXISTS run_manifests (
+            run_id         TEXT PRIMARY KEY,
+            command        TEXT NOT NULL,
+            crate_version  TEXT NOT NULL,
+            git_hash       TEXT,              -- commit the binary was built from, NULL outside a checkout
+            parser_config  TEXT NOT NULL,     -- JSON of the effective [parser] settings
+            args           TEXT NOT NULL,     -- JSON array of the command line
+            counts_before  TEXT NOT NULL,     -- JSON object: published table -> rows
+            counts_after   TEXT,              -- NULL while running or when the run failed
+            started_at     TEXT NOT NULL DEFAULT (datetime('now')),
+            finished_at    TEXT
+        );
+
+        -- Job listing analyses from `report jobs` (metric -> value, replaced each run)
+        CREATE TABLE IF NOT EXISTS job_stats (
+            metric TEXT PRIMARY KEY,
+            value  TEXT NOT NULL
+        );
+
+        -- External link health (check-links)
+        CREATE TABLE IF NOT EXISTS link_checks (
+            url         TEXT PRIMARY KEY,
+            domain      TEXT NOT NULL,
+            status      INTEGER,
+            error       TEXT,
+            latency_ms  INTEGER,
+            checked_at  TEXT NOT NULL DEFAULT (datetime('now'))
+        );
+        CREATE INDEX IF NOT EXISTS idx_link_checks_domain ON link_checks(domain);
+
+        -- Public profile stats for founder Twitter/X handles (`enrich`), refetched when stale
+        CREATE TABLE IF NOT EXISTS founder_social_stats (
+            handle      TEXT PRIMARY KEY,  -- lowercase, without @
+            provider    TEXT NOT NULL,     -- enrichment provider that fetched it
+            name        TEXT,
+            bio         TEXT,
+            followers   INTEGER,
+            following   INTEGER,
+            posts       INTEGER,
+            fetched_at  TEXT NOT NULL DEFAULT (datetime('now'))
+        );
+
+        CREATE TABLE IF NOT EXISTS domain_health (
+            domain          TEXT PRIMARY KEY,
+            base_domain     TEXT NOT NULL,
+            checks          INTEGER NOT NULL,
+            ok              INTEGER NOT NULL,
+            success_rate    REAL NOT NULL,
+            mean_latency_ms REAL,
+            common_status   INTEGER,
+            updated_at      TEXT NOT NULL DEFAULT (datetime('now'))
+        );
+        CREATE INDEX IF NOT EXISTS idx_domain_health_base ON domain_health(base_domain);
+
+        -- Founder pairs that appear at more than one company. Matched by
+        -- normalized LinkedIn, Twitter/X, or name (when neither profile conflicts).
+        -- A renamed company's old slug isn't another company.
+        DROP VIEW IF EXISTS repeat_founders;
+        CREATE VIEW repeat_founders AS
+        WITH f AS (
+            SELECT id, company_slug, name,
+                   COALESCE(name_key, lower(trim(name))) AS name_key,
+                   NULLIF(rtrim(replace(replace(lower(linkedin),
+                       'http://', 'https://'), 'https://www.', 'https://'), '/'), '') AS li_key,
+                   NULLIF(rtrim(replace(replace(replace(lower(twitter),
+                       'http://', 'https://'), 'https://www.', 'https://'),
+                       'https://x.com', 'https://twitter.com'), '/'), '') AS tw_key
+            FROM founders
+            WHERE company_slug NOT IN (SELECT slug FROM companies WHERE renamed_to IS NOT NULL)
+        )
+        SELECT a.id AS founder_id, a.name, a.company_slug,
+               b.id AS other_founder_id, b.company_slug AS other_company_slug,
+               'linkedin' AS match_method
+        FROM f a JOIN f b ON b.li_key = a.li_key AND b.company_slug <> a.company_slug
+        UNION
+        SELECT a.id, a.name, a.company_slug, b.id, b.company_slug, 'twitter'
+        FROM f a JOIN f b ON b.tw_key = a.tw_key AND b.company_slug <> a.company_slug
+        UNION
+        SELECT a.id, a.name, a.company_slug, b.id, b.company_slug, 'name'
+        FROM f a JOIN f b ON b.name_key = a.name_key AND b.company_slug <> a.company_slug
+        WHERE (a.li_key IS NULL OR b.li_key IS NULL OR a.li_key = b.li_key)
+          AND (a.tw_key IS NULL OR b.tw_key IS NULL OR a.tw_key = b.tw_key);
         ",
     )?;
+    migrate(conn)?;
+    Ok(())
+}
+
+/// Bring databases created by older versions up to the current schema.
+/// Columns listed here also appear in the CREATE TABLE statements above.
+fn migrate(conn: &Connection) -> Result<()> {
+    add_column(conn, "news", "canonical_url", "TEXT")?;
+    add_column(conn, "news", "resolved_at", "TEXT")?;
+    add_column(conn, "news", "article_id", "TEXT")?;
+    add_column(conn, "news", "duplicate_of", "INTEGER")?;
+    add_column(conn, "founders", "name_key", "TEXT")?;
+    add_column(conn, "companies", "description_lang", "TEXT")?;
+    add_column(conn, "company_sections", "parse_ms", "REAL")?;
+    add_column(conn, "company_sections", "block_count", "INTEGER")?;
+    add_column(conn, "company_sections", "section_count", "INTEGER")?;
+    add_column(conn, "company_sections", "spans", "TEXT")?;
+    add_column(conn, "companies", "primary_partner_slug", "TEXT")?;
+    add_column(conn, "companies", "group_partner", "TEXT")?;
+    add_column(conn, "companies", "group_partner_slug", "TEXT")?;
+    add_column(conn, "companies", "company_age_years", "INTEGER")?;
+    add_column(conn, "pages", "lastmod", "TEXT")?;
+    add_column(conn, "pages", "content_retries", "INTEGER NOT NULL DEFAULT 0")?;
+    add_column(conn, "pages", "claimed_by", "TEXT")?;
+    add_column(conn, "pages", "claim_expires", "TEXT")?;
+    add_column(conn, "pages", "discovered_via", "TEXT")?;
+    add_column(conn, "page_data", "content_kind", "TEXT")?;
+    add_column(conn, "page_data", "metadata", "TEXT")?;
+    add_column(conn, "page_data", "final_url", "TEXT")?;
+    add_column(conn, "page_data", "scrape_meta", "TEXT")?;
+    add_column(conn, "page_data", "markdown_raw", "BLOB")?;
+    add_column(conn, "page_data", "return_format", "TEXT")?;
+    add_column(conn, "page_data", "blob_key", "TEXT")?;
+    add_column(conn, "page_data", "markdown_bytes", "INTEGER")?;
+    add_column(conn, "page_data", "raw_blob_key", "TEXT")?;
+    add_column(conn, "companies", "renamed_to", "TEXT")?;
+    rename_column(conn, "companies", "job_count", "job_count_extracted")?;
+    add_column(conn, "companies", "job_count_listed", "INTEGER")?;
+    add_column(conn, "companies", "quality_score", "INTEGER")?;
+    add_column(conn, "company_jobs", "apply_url_final", "TEXT")?;
+    add_column(conn, "company_jobs", "ats_vendor", "TEXT")?;
+    add_column(conn, "company_jobs", "apply_resolved_at", "TEXT")?;
+    add_column(conn, "company_jobs", "tech_stack", "TEXT")?;
+    add_column(conn, "company_jobs", "benefits", "TEXT")?;
+    add_column(conn, "company_jobs", "salary_currency", "TEXT")?;
+    add_column(conn, "company_jobs", "salary_min", "REAL")?;
+    add_column(conn, "company_jobs", "salary_max", "REAL")?;
+    add_column(conn, "company_jobs", "salary_min_usd", "INTEGER")?;
+    add_column(conn, "company_jobs", "salary_max_usd", "INTEGER")?;
+    add_column(conn, "job_details", "content_kind", "TEXT")?;
+    add_column(conn, "company_links", "founder_id", "INTEGER REFERENCES founders(id)")?;
+    add_column(conn, "company_links", "publisher", "TEXT")?;
+    add_column(conn, "founders", "linkedin_match", "TEXT")?;
+    add_column(conn, "founders", "first_seen_at", "TEXT")?;
+    add_column(conn, "founders", "departed_at", "TEXT")?;
+    add_column(conn, "founders", "departed", "BOOLEAN GENERATED ALWAYS AS (departed_at IS NOT NULL) VIRTUAL")?;
+    add_column(
+        conn,
+        "companies",
+        "batch_ordinal",
+        "INTEGER GENERATED ALWAYS AS (batch_year * 10 + CASE batch_season
+             WHEN 'Winter' THEN 1 WHEN 'Spring' THEN 2 WHEN 'Summer' THEN 3 WHEN 'Fall' THEN 4 ELSE 0 END) VIRTUAL",
+    )?;
+    for table in ["founders", "news", "company_jobs", "company_links", "meeting_links", "company_qa"] {
+        add_column(conn, table, "source_lines", "TEXT")?;
+    }
+    conn.execute_batch(
+        "CREATE INDEX IF NOT EXISTS idx_founders_name_key ON founders(company_slug, name_key);
+         CREATE INDEX IF NOT EXISTS idx_news_article ON news(article_id);
+         CREATE INDEX IF NOT EXISTS idx_links_founder ON company_links(founder_id);",
+    )?;
+    backfill_article_ids(conn)?;
+    backfill_founder_keys(conn)?;
+    backfill_group_partners(conn)?;
+    refresh_company_ages(conn)?;
+    let merged = merge_duplicate_pages(conn)?;
+    if merged > 0 {
+        tracing::info!("Canonicalized {} page URLs (case, trailing slash, unicode)", merged);
+    }
+    create_change_triggers(conn)?;
+    create_views(conn)?;
+    create_functions(conn, crate::blobstore::shared()?)?;
+    Ok(())
+}
+
+/// gzip streams start with these bytes; markdown text never does.
+const GZIP_MAGIC: [u8; 2] = [0x1f, 0x8b];
+
+/// gzip `data` for `page_data.markdown_raw`.
+#[cfg_attr(not(feature = "network"), allow(dead_code))]
+pub fn gzip(data: &[u8]) -> Vec<u8> {
+    use std::io::Write;
+
+    let mut encoder = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+    // Writing to a Vec can't fail
+    encoder.write_all(data).expect("gzip into memory");
+    encoder.finish().expect("gzip into memory")
+}
+
+/// Text of a stored `page_data.markdown_raw`, plain or gzipped.
+pub fn raw_markdown(stored: &[u8]) -> Result<String> {
+    use std::io::Read;
+
+    if !stored.starts_with(&GZIP_MAGIC) {
+        return Ok(String::from_utf8_lossy(stored).into_owned());
+    }
+    let mut text = String::new();
+    flate2::read::GzDecoder::new(stored).read_to_string(&mut text)?;
+    Ok(text)
+}
+
+/// SQL functions queries rely on. `page_markdown(markdown, blob_key)` is the
+/// page's markdown wherever it's stored: the inline column, or the blob
+/// store for pages saved with `[storage] pages` set. `raw_markdown(markdown_raw)`
+/// unpacks the unstripped copy kept with `--keep-raw`, and
+/// `raw_markdown(markdown_raw, raw_blob_key)` also finds it in the blob store.
+pub fn create_functions(conn: &Connection, store: Option<Arc<dyn BlobStore>>) -> Result<()> {
+    use rusqlite::functions::FunctionFlags;
+
+    let fail = |e: anyhow::Error| rusqlite::Error::UserFunctionError(format!("{:#}", e).into());
+    let deterministic = FunctionFlags::SQLITE_UTF8 | FunctionFlags::SQLITE_DETERMINISTIC;
+    conn.create_scalar_function("raw_markdown", 1, deterministic, move |ctx| {
+        let Some(stored) = ctx.get::<Option<Vec<u8>>>(0)? else {
+            return Ok(None);
+        };
+        raw_markdown(&stored).map(Some).map_err(fail)
+    })?;
+
+    let raw_store = std::panic::AssertUnwindSafe(store.clone());
+    conn.create_scalar_function("raw_markdown", 2, FunctionFlags::SQLITE_UTF8, move |ctx| {
+        if let Some(stored) = ctx.get::<Option<Vec<u8>>>(0)? {
+            return raw_markdown(&stored).map(Some).map_err(fail);
+        }
+        let Some(key) = ctx.get::<Option<String>>(1)? else {
+            return Ok(None);
+        };
+        let Some(store) = raw_store.as_ref() else {
+            return Err(fail(anyhow::anyhow!("raw page {} is in the blob store but [storage] pages is not set", key)));
+        };
+        raw_markdown(&store.get(&key).map_err(fail)?).map(Some).map_err(fail)
+    })?;
+
+    let store = std::panic::AssertUnwindSafe(store);
+    conn.create_scalar_function("page_markdown", 2, FunctionFlags::SQLITE_UTF8, move |ctx| {
+        if let Some(md) = ctx.get::<Option<String>>(0)? {
+            return Ok(Some(md));
+        }
+        let Some(key) = ctx.get::<Option<String>>(1)? else {
+            return Ok(None);
+        };
+        let Some(store) = store.as_ref() else {
+            return Err(fail(anyhow::anyhow!("page {} is in the blob store but [storage] pages is not set", key)));
+        };
+        let bytes = store.get(&key).map_err(fail)?;
+        Ok(Some(String::from_utf8_lossy(&bytes).into_owned()))
+    })?;
+    Ok(())
+}
+
+/// Analytics entry points for querying the SQLite file directly. Recreated
+/// on every start, after the migrations, so they track the code. Old slugs
+/// of renamed companies are left out.
+fn create_views(conn: &Connection) -> Result<()> {
+    conn.execute_batch(
+        "DROP VIEW IF EXISTS active_companies;
+         CREATE VIEW active_companies AS
+         SELECT * FROM companies WHERE is_active AND renamed_to IS NULL;
+
+         DROP VIEW IF EXISTS hiring_companies;
+         CREATE VIEW hiring_companies AS
+         SELECT c.slug, c.name, c.batch, c.status, c.location, c.team_size,
+                COUNT(j.id) AS open_jobs,
+                SUM(j.salary IS NOT NULL) AS jobs_with_salary
+         FROM companies c
+         JOIN company_jobs j ON j.company_slug = c.slug
+         WHERE c.renamed_to IS NULL
+         GROUP BY c.slug;
+
+         DROP VIEW IF EXISTS companies_with_founder_links;
+         CREATE VIEW companies_with_founder_links AS
+         SELECT c.slug, c.name, c.batch, c.status,
+                COUNT(f.id) AS founders,
+                SUM(NULLIF(f.linkedin, '') IS NOT NULL) AS founders_with_linkedin,
+                SUM(NULLIF(f.twitter, '') IS NOT NULL) AS founders_with_twitter
+         FROM companies c
+         JOIN founders f ON f.company_slug = c.slug
+         WHERE c.renamed_to IS NULL
+         GROUP BY c.slug
+         HAVING founders_with_linkedin + founders_with_twitter > 0;
+
+         DROP VIEW IF EXISTS founder_departures;
+         CREATE VIEW founder_departures AS
+         SELECT f.id AS founder_id, f.name, f.title, f.company_slug, c.name AS company_name, c.batch,
+                f.first_seen_at, f.departed_at,
+                f.first_seen_at < f.departed_at AS seen_active
+         FROM founders f
+         JOIN companies c ON c.slug = f.company_slug
+         WHERE f.departed AND c.renamed_to IS NULL;
+
+         DROP VIEW IF EXISTS jobs_with_salary;
+         CREATE VIEW jobs_with_salary AS
+         SELECT j.id, j.company_slug, c.name AS company_name, c.batch,
+                j.title, j.location, j.salary, j.salary_currency, j.salary_min_usd, j.salary_max_usd,
+                j.experience, j.url
+         FROM company_jobs j
+         JOIN companies c ON c.slug = j.company_slug
+         WHERE NULLIF(trim(j.salary), '') IS NOT NULL AND c.renamed_to IS NULL;
+",
+    )?;
+    // Companies with a flagged founding year are left out, as in `report compare`
+    conn.execute_batch(&format!(
+        "DROP VIEW IF EXISTS batch_summary;
+         CREATE VIEW batch_summary AS
+         SELECT c.batch, c.batch_year, c.batch_season,
+                COUNT(*) AS companies,
+                SUM(c.status = 'Active') AS active,
+                SUM(c.status = 'Public') AS public,
+                SUM(c.status = 'Acquired') AS acquired,
+                SUM(c.status = 'Inactive') AS inactive,
+                ROUND(AVG(c.team_size), 1) AS avg_team_size,
+                SUM(EXISTS (SELECT 1 FROM company_jobs j WHERE j.company_slug = c.slug)) AS hiring,
+                (SELECT COUNT(*) FROM founders f JOIN companies c2 ON c2.slug = f.company_slug
+                 WHERE c2.batch IS c.batch AND c2.renamed_to IS NULL AND NOT {}) AS founders
+         FROM companies c
+         WHERE c.batch IS NOT NULL AND c.renamed_to IS NULL AND NOT {}
+         GROUP BY c.batch;",
+        founded_year_flagged("c2.slug"),
+        founded_year_flagged("c.slug"),
+    ))?;
+    Ok(())
+}
+
+/// Fold pages whose URL isn't canonical (see `sitemap::canonicalize`) into the
+/// canonical row, or rename them when there is none. Scrape history moves with
+/// the page; rows extracted under the old slug are dropped and rebuilt by the
+/// next `process`. Returns the number of pages changed.
+fn merge_duplicate_pages(conn: &Connection) -> Result<usize> {
+    let pages: Vec<(i64, String, String)> = conn
+        .prepare("SELECT id, url, slug FROM pages ORDER BY id")?
+        .query_map([], |r| Ok((r.get(0)?, r.get(1)?, r.get(2)?)))?
+        .collect::<Result<_, _>>()?;
+    let mut canonical: std::collections::HashMap<String, i64> = pages
+        .iter()
+        .filter(|(_, url, slug)| crate::sitemap::canonicalize(url).is_some_and(|(u, s)| &u == url && &s == slug))
+        .map(|(id, url, _)| (url.clone(), *id))
+        .collect();
+    let changes: Vec<(i64, String, String, String)> = pages
+        .into_iter()
+        .filter_map(|(id, url, slug)| {
+            let (c_url, c_slug) = crate::sitemap::canonicalize(&url)?;
+            (c_url != url || c_slug != slug).then_some((id, slug, c_url, c_slug))
+        })
+        .collect();
+    if changes.is_empty() {
+        return Ok(0);
+    }
+
+    let company_tables: Vec<(&str, &str)> = ORPHAN_CHECKS
+        .iter()
+        .filter(|(_, _, parent, _, _)| *parent == "companies")
+        .map(|&(_, child, _, col, _)| (child, col))
+        .collect();
+    let tx = conn.unchecked_transaction()?;
+    // Children go in ORPHAN_CHECKS order, which deletes founders before the
+    // rows pointing at them; check the keys once, at commit.
+    tx.execute_batch("PRAGMA defer_foreign_keys = ON;")?;
+    for (id, old_slug, c_url, c_slug) in &changes {
+        match canonical.get(c_url) {
+            Some(&keep) => {
+                tx.execute(
+                    "UPDATE pages SET
+                         visited = visited OR (SELECT visited FROM pages WHERE id = ?2),
+                         visited_at = NULLIF(max(COALESCE(visited_at, ''),
+                             COALESCE((SELECT visited_at FROM pages WHERE id = ?2), '')), '')
+                     WHERE id = ?1",
+                    rusqlite::params![keep, id],
+                )?;
+                tx.execute(
+                    "UPDATE page_data SET page_id = ?1, slug = ?2 WHERE page_id = ?3",
+                    rusqlite::params![keep, c_slug, id],
+                )?;
+                tx.execute("DELETE FROM pages WHERE id = ?1", [id])?;
+            }
+            None => {
+                tx.execute(
+                    "UPDATE pages SET url = ?1, slug = ?2 WHERE id = ?3",
+                    rusqlite::params![c_url, c_slug, id],
+                )?;
+                tx.execute(
+                    "UPDATE page_data SET slug = ?1 WHERE page_id = ?2",
+                    rusqlite::params![c_slug, id],
+                )?;
+                canonical.insert(c_url.clone(), *id);
+            }
+        }
+        if old_slug != c_slug {
+            tx.execute("DELETE FROM company_sections WHERE slug = ?1", [old_slug])?;
+            tx.execute(
+                "DELETE FROM job_details WHERE job_id IN (SELECT id FROM company_jobs WHERE company_slug = ?1)",
+                [old_slug],
+            )?;
+            for (table, col) in &company_tables {
+                tx.execute(&format!("DELETE FROM {} WHERE {} = ?1", table, col), [old_slug])?;
+            }
+            tx.execute("DELETE FROM companies WHERE slug = ?1", [old_slug])?;
+        }
+    }
+    tx.commit()?;
+    Ok(changes.len())
+}
+
+/// Rename `from` on databases created before the column got its new name.
+fn rename_column(conn: &Connection, table: &str, from: &str, to: &str) -> Result<()> {
+    let exists = conn
+        .prepare(&format!(
+            "SELECT 1 FROM pragma_table_xinfo('{}') WHERE name = ?1",
+            table
+        ))?
+        .exists([from])?;
+    if exists {
+        conn.execute_batch(&format!("ALTER TABLE {} RENAME COLUMN {} TO {}", table, from, to))?;
+    }
+    Ok(())
+}
+
+fn add_column(conn: &Connection, table: &str, column: &str, decl: &str) -> Result<()> {
+    let exists = conn
+        .prepare(&format!(
+            "SELECT 1 FROM pragma_table_xinfo('{}') WHERE name = ?1",
+            table
+        ))?
+        .exists([column])?;
+    if !exists {
+        conn.execute_batch(&format!("ALTER TABLE {} ADD COLUMN {} {}", table, column, decl))?;
+    }
+    Ok(())
+}
+
+// ── Scope ──
+
+/// Restrict slug-aware queries on this connection to `slugs` (`--slugs-file`).
+/// Lives in a TEMP table, so it ends with the connection.
+pub fn set_slug_scope(conn: &Connection, slugs: &[String]) -> Result<()> {
+    conn.execute_batch(
+        "CREATE TEMP TABLE IF NOT EXISTS scope_slugs (slug TEXT PRIMARY KEY);
+         DELETE FROM temp.scope_slugs;",
+    )?;
+    let tx = conn.unchecked_transaction()?;
+    {
+        let mut stmt = tx.prepare("INSERT OR IGNORE INTO temp.scope_slugs (slug) VALUES (?1)")?;
+        for slug in slugs {
+            stmt.execute([slug])?;
+        }
+    }
+    // A renamed company is in scope under its current slug too
+    tx.execute(
+        "INSERT OR IGNORE INTO temp.scope_slugs (slug)
+         SELECT renamed_to FROM companies
+         WHERE renamed_to IS NOT NULL AND slug IN (SELECT slug FROM temp.scope_slugs)",
+        [],
+    )?;
+    tx.commit()?;
     Ok(())
 }
 
+/// SQL predicate leaving out old slugs of renamed companies in `column`;
+/// their rows are listed under the current slug (`renamed_to`).
+fn current_slug_predicate(column: &str) -> String {
+    format!("{} NOT IN (SELECT slug FROM companies WHERE renamed_to IS NOT NULL)", column)
+}
+
+/// SQL predicate limiting `column` to the active slug scope ("1" when unscoped).
+fn scope_predicate(conn: &Connection, column: &str) -> Result<String> {
+    let scoped = conn
+        .prepare("SELECT 1 FROM temp.sqlite_master WHERE type = 'table' AND name = 'scope_slugs'")?
+        .exists([])?;
+    Ok(if scoped {
+        format!("{} IN (SELECT slug FROM temp.scope_slugs)", column)
+    } else {
+        "1".to_string()
+    })
+}
+
 // ── Scraping ──
 
-pub fn insert_pages(conn: &Connection, pages: &[(String, String)]) -> Result<usize> {
+/// A company page from the sitemap or a listing.
+pub struct PageUrl {
+    pub url: String,
+    pub slug: String,
+    /// Sitemap `<lastmod>` in `datetime('now')` format (UTC).
+    pub lastmod: Option<String>,
+}
+
+/// Queue new pages and record the latest sitemap `lastmod` on known ones.
+/// Purged companies are skipped. Returns the number of new pages.
+pub fn insert_pages(conn: &Connection, pages: &[PageUrl]) -> Result<usize> {
+    insert_pages_via(conn, pages, None)
+}
+
+/// `insert_pages` for pages found on a listing rather than in the sitemap;
+/// new ones record `discovered_via`.
+pub fn insert_discovered(conn: &Connection, pages: &[PageUrl], via: &str) -> Result<usize> {
+    insert_pages_via(conn, pages, Some(via))
+}
+
+fn insert_pages_via(conn: &Connection, pages: &[PageUrl], via: Option<&str>) -> Result<usize> {
     let tx = conn.unchecked_transaction()?;
     let mut count = 0;
     {
-        let mut stmt = tx.prepare("INSERT OR IGNORE INTO pages (url, slug) VALUES (?1, ?2)")?;
-        for (url, slug) in pages {
-            count += stmt.execute(rusqlite::params![url, slug])?;
+        let mut insert = tx.prepare(
+            "INSERT OR IGNORE INTO pages (url, slug, lastmod, discovered_via)
+             SELECT ?1, ?2, ?3, ?4 WHERE NOT EXISTS (SELECT 1 FROM purged_companies WHERE slug = ?2)",
+        )?;
+        let mut lastmod = tx.prepare("UPDATE pages SET lastmod = ?2 WHERE url = ?1 AND lastmod IS NOT ?2")?;
+        for p in pages {
+            let added = insert.execute(rusqlite::params![p.url, p.slug, p.lastmod, via])?;
+            if added == 0 && p.lastmod.is_some() {
+                lastmod.execute(rusqlite::params![p.url, p.lastmod])?;
+            }
+            count += added;
         }
     }
     tx.commit()?;
     Ok(count)
 }
 
+/// Which unvisited pages `scrape` and `run` take first (`--order`).
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, clap::ValueEnum)]
+pub enum ScrapeOrder {
+    /// In the order pages were discovered
+    #[default]
+    Id,
+    /// Shuffled, spreading requests over batches and page ages
+    Random,
+    /// Companies from the latest batch first; pages never processed (new
+    /// to the sitemap) before all of them
+    NewestBatch,
+    /// Never-scraped pages, then the longest since their last scrape
+    StaleFirst,
+}
+
+impl ScrapeOrder {
+    fn sql(self) -> &'static str {
+        match self {
+            ScrapeOrder::Id => "id",
+            ScrapeOrder::Random => "random()",
+            ScrapeOrder::NewestBatch => {
+                "(SELECT batch_ordinal FROM companies c WHERE c.slug = pages.slug) IS NOT NULL,
+                 (SELECT batch_ordinal FROM companies c WHERE c.slug = pages.slug) DESC,
+                 id DESC"
+            }
+            ScrapeOrder::StaleFirst => "visited_at IS NOT NULL, visited_at, id",
+        }
+    }
+}
+
+/// Unvisited pages in scope that no other run holds a live claim on, in
+/// `order`.
 pub fn fetch_unvisited(
     conn: &Connection,
     limit: Option<usize>,
+    order: ScrapeOrder,
 ) -> Result<Vec<(i64, String, String)>> {
-    let sql = match limit {
-        Some(n) => format!(
-            "SELECT id, url, slug FROM pages WHERE visited = 0 ORDER BY id LIMIT {}",
-            n
-        ),
-        None => "SELECT id, url, slug FROM pages WHERE visited = 0 ORDER BY id".to_string(),
-    };
-    let mut stmt = conn.prepare(&sql)?;
-    let rows = stmt
-        .query_map([], |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)))?
-        .collect::<Result<Vec<_>, _>>()?;
-    Ok(rows)
+    Select::new("SELECT id, url, slug FROM pages")
+        .filter("visited = 0")
+        .filter(scope_predicate(conn, "slug")?)
+        .filter(UNCLAIMED)
+        .order_by(order.sql())
+        .limit(limit)
+        .query(conn, |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)))
+}
+
+// ── Claims ──
+
+/// Pages whose claim is missing or expired.
+const UNCLAIMED: &str = "(claim_expires IS NULL OR claim_expires <= datetime('now'))";
+
+fn expiry(ttl: std::time::Duration) -> String {
+    format!("+{} seconds", ttl.as_secs())
+}
+
+/// Claim `pages` for `owner` until `ttl` from now. Each claim is a
+/// conditional update, so when two runs race for a page exactly one gets it;
+/// returns the pages this run won (its own live claims included).
+pub fn claim_pages(
+    conn: &Connection,
+    pages: Vec<(i64, String, String)>,
+    owner: &str,
+    ttl: std::time::Duration,
+) -> Result<Vec<(i64, String, String)>> {
+    let tx = conn.unchecked_transaction()?;
+    let mut won = Vec::with_capacity(pages.len());
+    {
+        let mut stmt = tx.prepare(&format!(
+            "UPDATE pages SET claimed_by = ?2, claim_expires = datetime('now', ?3)
+             WHERE id = ?1 AND (claimed_by = ?2 OR {})",
+            UNCLAIMED
+        ))?;
+        for page in pages {
+            if stmt.execute(rusqlite::params![page.0, owner, expiry(ttl)])? == 1 {
+                won.push(page);
+            }
+        }
+    }
+    tx.commit()?;
+    Ok(won)
+}
+
+/// Take the advisory lock `name` for `owner` unless another owner holds it
+/// and it hasn't expired. Returns the other holder when taken.
+pub fn try_lock(conn: &Connection, name: &str, owner: &str, ttl: std::time::Duration) -> Result<Option<String>> {
+    conn.execute(
+        "INSERT INTO locks (name, owner, expires_at) VALUES (?1, ?2, datetime('now', ?3))
+         ON CONFLICT(name) DO UPDATE SET owner = excluded.owner, expires_at = excluded.expires_at
+         WHERE locks.owner = excluded.owner OR locks.expires_at <= datetime('now')",
+        rusqlite::params![name, owner, expiry(ttl)],
+    )?;
+    let holder: String = conn.query_row("SELECT owner FROM locks WHERE name = ?1", [name], |r| r.get(0))?;
+    Ok((holder != owner).then_some(holder))
+}
+
+/// Push back the expiry of everything `owner` holds.
+pub fn renew_claims(conn: &Connection, owner: &str, ttl: std::time::Duration) -> Result<()> {
+    conn.execute(
+        "UPDATE pages SET claim_expires = datetime('now', ?2) WHERE claimed_by = ?1",
+        rusqlite::params![owner, expiry(ttl)],
+    )?;
+    conn.execute(
+        "UPDATE locks SET expires_at = datetime('now', ?2) WHERE owner = ?1",
+        rusqlite::params![owner, expiry(ttl)],
+    )?;
+    Ok(())
+}
+
+/// Drop every page claim and lock held by `owner`.
+pub fn release_claims(conn: &Connection, owner: &str) -> Result<()> {
+    conn.execute(
+        "UPDATE pages SET claimed_by = NULL, claim_expires = NULL WHERE claimed_by = ?1",
+        [owner],
+    )?;
+    conn.execute("DELETE FROM locks WHERE owner = ?1", [owner])?;
+    Ok(())
 }
 
 pub struct ScrapeRow {
@@ -200,137 +1061,711 @@ pub struct ScrapeRow {
     pub status: Option<i32>,
     pub error: Option<String>,
     pub latency_ms: Option<i64>,
+    /// spider.cloud page metadata (title, description, ...) as JSON.
+    pub metadata: Option<String>,
+    /// URL the response came from, when a redirect moved it off `url`.
+    pub final_url: Option<String>,
+    /// Response details for debugging (final URL, status, content type,
+    /// title, costs, error body) as JSON.
+    pub scrape_meta: Option<String>,
+    /// Content before `strip_images`, as UTF-8 or gzip (see `raw_markdown`).
+    pub markdown_raw: Option<Vec<u8>>,
+    /// `markdown` is the raw HTML page (`--return-format html`).
+    pub html: bool,
 }
 
-
-// ── Processing ──
-
-pub struct ScrapedPage {
-    pub page_data_id: i64,
-    pub slug: String,
-    pub url: String,
-    pub markdown: String,
+impl ScrapeRow {
+    /// The content as the lines the lexer and `validate` read: the
+    /// markdown, or the HTML page rendered by `parser::html::to_text`.
+    pub fn text(&self) -> Option<std::borrow::Cow<'_, str>> {
+        let content = self.markdown.as_deref()?;
+        Some(if self.html {
+            crate::parser::html::to_text(content, &self.url).into()
+        } else {
+            content.into()
+        })
+    }
 }
 
-pub fn fetch_unprocessed(conn: &Connection, limit: Option<usize>) -> Result<Vec<ScrapedPage>> {
-    let sql = format!(
-        "SELECT pd.id, pd.slug, pd.url, pd.markdown
-         FROM page_data pd
-         LEFT JOIN companies c ON c.slug = pd.slug
-         WHERE pd.markdown IS NOT NULL AND c.slug IS NULL
-         ORDER BY pd.id{}",
-        match limit {
-            Some(n) => format!(" LIMIT {}", n),
-            None => String::new(),
-        }
-    );
-    let mut stmt = conn.prepare(&sql)?;
+/// Re-scrapes (with browser rendering) a page gets after returning
+/// something other than a company page, before it's left as is.
+pub const MAX_CONTENT_RETRIES: i64 = 2;
+
+/// Average markdown length of each page's earlier company-page scrapes,
+/// the baseline `validate::block_reason` compares a new scrape against.
+/// HTML scrapes are left out: their size says nothing about the markdown.
+#[cfg_attr(not(feature = "network"), allow(dead_code))]
+pub fn fetch_typical_lengths(conn: &Connection) -> Result<std::collections::HashMap<i64, f64>> {
+    let mut stmt = conn.prepare(
+        "SELECT page_id, AVG(COALESCE(markdown_bytes, LENGTH(markdown))) FROM page_data
+         WHERE (markdown IS NOT NULL OR blob_key IS NOT NULL) AND error IS NULL AND COALESCE(content_kind, 'company') = 'company'
+           AND return_format IS NULL
+         GROUP BY page_id",
+    )?;
     let rows = stmt
-        .query_map([], |row| {
-            Ok(ScrapedPage {
-                page_data_id: row.get(0)?,
-                slug: row.get(1)?,
-                url: row.get(2)?,
-                markdown: row.get(3)?,
-            })
-        })?
-        .collect::<Result<Vec<_>, _>>()?;
+        .query_map([], |r| Ok((r.get(0)?, r.get(1)?)))?
+        .collect::<Result<_, _>>()?;
     Ok(rows)
 }
 
-pub struct SectionRow {
-    pub page_data_id: i64,
-    pub slug: String,
-    pub url: String,
-    pub navbar: Option<String>,
-    pub header: Option<String>,
-    pub description: Option<String>,
-    pub news: Option<String>,
-    pub jobs: Option<String>,
-    pub footer: Option<String>,
-    pub founders_raw: Option<String>,
-    pub launches: Option<String>,
-    pub extras: Option<String>,
+/// Save a single scrape result and mark its page visited, in one
+/// transaction: if the page update fails the page_data insert is rolled back
+/// too, so a retry can't leave a duplicate. Markdown that isn't a company
+/// page (see `validate::content_kind`) is stored with its kind, never
+/// processed, and the page goes back in the queue up to
+/// `MAX_CONTENT_RETRIES` times; so is markdown the scraper flagged as a
+/// block page (error category `blocked`), under that kind. With
+/// `retry_errors`, failed requests are recorded but their page stays
+/// unvisited for the next run. A page that redirected to another company's
+/// page is a rename: it's stored as `renamed`, the new page is queued and
+/// the old company row points at it (see `record_rename`). Returns the
+/// content kind.
+pub fn save_scrape(conn: &Connection, row: &ScrapeRow, retry_errors: bool) -> Result<Option<&'static str>> {
+    save_scrape_with(conn, row, retry_errors, crate::blobstore::shared()?.as_deref())
 }
 
-pub fn save_sections(conn: &Connection, rows: &[SectionRow]) -> Result<()> {
-    let tx = conn.unchecked_transaction()?;
-    {
-        let mut stmt = tx.prepare(
-            "INSERT OR REPLACE INTO company_sections
-             (page_id, slug, url, navbar, header, description, news, jobs, footer, founders_raw, launches, extras)
-             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12)",
-        )?;
-        for r in rows {
-            stmt.execute(rusqlite::params![
-                r.page_data_id, r.slug, r.url, r.navbar, r.header, r.description,
-                r.news, r.jobs, r.footer, r.founders_raw, r.launches, r.extras,
-            ])?;
+/// `save_scrape` with an explicit blob store. With one, the markdown and the
+/// `--keep-raw` copy are uploaded before the transaction opens and only
+/// their keys (and the markdown's size) go in `page_data`; a failed upload
+/// keeps that part inline rather than losing the scrape.
+#[tracing::instrument(name = "db.save_scrape", skip_all, fields(slug = %row.slug))]
+pub fn save_scrape_with(
+    conn: &Connection,
+    row: &ScrapeRow,
+    retry_errors: bool,
+    store: Option<&dyn BlobStore>,
+) -> Result<Option<&'static str>> {
+    let blocked = row.error.as_deref().is_some_and(|e| error_category(row.status, e) == "blocked");
+    let renamed = row
+        .final_url
+        .as_deref()
+        .and_then(crate::sitemap::canonicalize)
+        .filter(|(_, slug)| *slug != row.slug);
+    let kind = row.text().map(|text| match &renamed {
+        Some(_) => crate::validate::RENAMED,
+        None if blocked => "blocked",
+        None => crate::validate::content_kind(&text),
+    });
+    // Uploaded outside the transaction, so a slow store doesn't hold the
+    // write lock
+    let scraped_at = chrono::Utc::now();
+    let upload = |what: &str, key: String, data: &[u8]| {
+        let store = store?;
+        match store.put(&key, data) {
+            Ok(()) => Some(key),
+            Err(e) => {
+                tracing::warn!("{}: keeping {} in SQLite: {:#}", row.slug, what, e);
+                None
+            }
         }
+    };
+    let blob_key = row.markdown.as_ref().and_then(|md| {
+        upload("markdown", crate::blobstore::page_key(&row.slug, scraped_at, md.as_bytes()), md.as_bytes())
+    });
+    let raw_blob_key = row
+        .markdown_raw
+        .as_ref()
+        .and_then(|raw| upload("raw page", crate::blobstore::raw_key(&row.slug, scraped_at, raw), raw));
+    let saved =
+        write_scrape(conn, row, retry_errors, kind, renamed.as_ref(), blob_key.as_deref(), raw_blob_key.as_deref());
+    if let (Err(_), Some(store)) = (&saved, store) {
+        // Nothing points at the uploads; leave no orphans behind
+        for key in blob_key.iter().chain(&raw_blob_key) {
+            let referenced = conn
+                .prepare_cached("SELECT 1 FROM page_data WHERE blob_key = ?1 OR raw_blob_key = ?1")?
+                .exists([key])?;
+            if !referenced {
+                let _ = store.delete(key);
+            }
+        }
+    }
+    saved.map(|()| kind)
+}
+
+/// The `page_data` insert and `pages` bookkeeping of `save_scrape_with`, in
+/// one transaction.
+fn write_scrape(
+    conn: &Connection,
+    row: &ScrapeRow,
+    retry_errors: bool,
+    kind: Option<&'static str>,
+    renamed: Option<&(String, String)>,
+    blob_key: Option<&str>,
+    raw_blob_key: Option<&str>,
+) -> Result<()> {
+    let tx = conn.unchecked_transaction()?;
+    tx.prepare_cached(
+        "INSERT INTO page_data
+         (page_id, url, slug, markdown, status, error, latency_ms, content_kind, metadata, final_url,
+          markdown_bytes, scrape_meta, markdown_raw, return_format, blob_key, raw_blob_key)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16)",
+    )?
+    .execute(rusqlite::params![
+        row.page_id, row.url, row.slug, row.markdown.as_ref().filter(|_| blob_key.is_none()), row.status,
+        row.error, row.latency_ms, kind, row.metadata, row.final_url,
+        row.markdown.as_ref().map(|md| md.len() as i64), row.scrape_meta,
+        row.markdown_raw.as_ref().filter(|_| raw_blob_key.is_none()), row.html.then_some("html"), blob_key,
+        raw_blob_key,
+    ])?;
+    if let (Some((url, slug)), Some(_)) = (renamed, kind) {
+        record_rename(&tx, &row.slug, url, slug)?;
+    } else if kind == Some(crate::validate::COMPANY) {
+        // Serving its own page again: a rename was reverted
+        tx.prepare_cached("UPDATE companies SET renamed_to = NULL WHERE slug = ?1")?
+            .execute([&row.slug])?;
+    }
+    let updated = match kind {
+        None if row.error.is_some() && retry_errors => 1,
+        Some(crate::validate::RENAMED) => tx
+            .prepare_cached(
+                "UPDATE pages SET visited = 1, visited_at = datetime('now'), content_retries = 0
+                 WHERE id = ?1",
+            )?
+            .execute(rusqlite::params![row.page_id])?,
+        Some(k) if k != crate::validate::COMPANY => tx
+            .prepare_cached(
+                "UPDATE pages SET visited = content_retries >= ?2, visited_at = datetime('now'),
+                                  content_retries = MIN(content_retries + 1, ?2)
+                 WHERE id = ?1",
+            )?
+            .execute(rusqlite::params![row.page_id, MAX_CONTENT_RETRIES])?,
+        Some(_) => tx
+            .prepare_cached(
+                "UPDATE pages SET visited = 1, visited_at = datetime('now'), content_retries = 0
+                 WHERE id = ?1",
+            )?
+            .execute(rusqlite::params![row.page_id])?,
+        None => tx
+            .prepare_cached("UPDATE pages SET visited = 1, visited_at = datetime('now') WHERE id = ?1")?
+            .execute(rusqlite::params![row.page_id])?,
+    };
+    if updated != 1 {
+        anyhow::bail!("page {} ({}) not in pages; scrape result not saved", row.page_id, row.slug);
     }
     tx.commit()?;
     Ok(())
 }
 
-// ── Extracted data ──
+/// Point `old_slug` (and slugs already renamed to it) at `new_slug`, so
+/// chains resolve in one hop, and queue the new page unless it's known or
+/// purged.
+fn record_rename(conn: &Connection, old_slug: &str, new_url: &str, new_slug: &str) -> Result<()> {
+    conn.prepare_cached(
+        "INSERT INTO pages (url, slug)
+         SELECT ?1, ?2 WHERE NOT EXISTS (SELECT 1 FROM purged_companies WHERE slug = ?2)
+         ON CONFLICT(url) DO NOTHING",
+    )?
+    .execute([new_url, new_slug])?;
+    conn.prepare_cached("UPDATE companies SET renamed_to = ?2 WHERE slug = ?1 OR renamed_to = ?1")?
+        .execute([old_slug, new_slug])?;
+    // A rename back to an earlier slug would otherwise point it at itself
+    conn.prepare_cached("UPDATE companies SET renamed_to = NULL WHERE slug = ?1")?
+        .execute([new_slug])?;
+    Ok(())
+}
 
-pub struct CompanyRow {
-    pub slug: String,
-    pub url: String,
+/// Pages re-queued after a consent wall, empty shell or listing page; the
+/// scraper renders these in a browser.
+#[cfg_attr(not(feature = "network"), allow(dead_code))]
+pub fn fetch_rescrape_page_ids(conn: &Connection) -> Result<std::collections::HashSet<i64>> {
+    let mut stmt = conn.prepare("SELECT id FROM pages WHERE content_retries > 0")?;
+    let ids = stmt
+        .query_map([], |r| r.get(0))?
+        .collect::<Result<_, _>>()?;
+    Ok(ids)
+}
+
+
+// ── Processing ──
+
+pub struct ScrapedPage {
+    pub page_data_id: i64,
+    pub slug: String,
+    pub url: String,
+    pub markdown: String,
+    /// `<meta name="description">` from the scrape metadata, when requested.
+    pub meta_description: Option<String>,
+    /// `markdown` is the raw HTML page; `process_page` renders it first.
+    pub html: bool,
+}
+
+/// Newest company-page scrape of each slug, within the slug scope.
+fn latest_predicate(conn: &Connection) -> Result<String> {
+    Ok(format!(
+        "(pd.markdown IS NOT NULL OR pd.blob_key IS NOT NULL)
+         AND COALESCE(pd.content_kind, 'company') = 'company'
+         AND pd.id = (SELECT MAX(p2.id) FROM page_data p2
+                      WHERE p2.slug = pd.slug AND (p2.markdown IS NOT NULL OR p2.blob_key IS NOT NULL)
+                        AND COALESCE(p2.content_kind, 'company') = 'company')
+         AND {}",
+        scope_predicate(conn, "pd.slug")?
+    ))
+}
+
+/// Newest scrape of each slug not yet run through `process`: new pages and re-scrapes.
+fn unprocessed_predicate(conn: &Connection) -> Result<String> {
+    Ok(format!(
+        "{} AND NOT EXISTS (SELECT 1 FROM company_sections cs WHERE cs.page_id = pd.id)",
+        latest_predicate(conn)?
+    ))
+}
+
+pub fn count_unprocessed(conn: &Connection, limit: Option<usize>) -> Result<usize> {
+    let sql = format!("SELECT COUNT(*) FROM page_data pd WHERE {}", unprocessed_predicate(conn)?);
+    let n: usize = conn.query_row(&sql, [], |r| r.get(0))?;
+    Ok(limit.map_or(n, |l| n.min(l)))
+}
+
+/// Latest scrapes, processed or not: the corpus `experiment run` parses.
+pub fn count_latest(conn: &Connection, limit: Option<usize>) -> Result<usize> {
+    let sql = format!("SELECT COUNT(*) FROM page_data pd WHERE {}", latest_predicate(conn)?);
+    let n: usize = conn.query_row(&sql, [], |r| r.get(0))?;
+    Ok(limit.map_or(n, |l| n.min(l)))
+}
+
+/// Stream unprocessed pages in `page_data.id` order, `chunk` at a time.
+/// Each chunk is a keyset query (`pd.id > last id`), so only one chunk of
+/// markdown is in memory however large the corpus; pages processed between
+/// chunks don't shift the cursor.
+pub fn stream_unprocessed(conn: &Connection, chunk: usize, limit: Option<usize>) -> ScrapedPages<'_> {
+    ScrapedPages { conn, chunk, after_id: 0, remaining: limit, done: false, processed_too: false }
+}
+
+/// Like `stream_unprocessed`, over the latest scrape of every slug.
+pub fn stream_latest(conn: &Connection, chunk: usize, limit: Option<usize>) -> ScrapedPages<'_> {
+    ScrapedPages { conn, chunk, after_id: 0, remaining: limit, done: false, processed_too: true }
+}
+
+pub struct ScrapedPages<'a> {
+    conn: &'a Connection,
+    chunk: usize,
+    after_id: i64,
+    remaining: Option<usize>,
+    done: bool,
+    processed_too: bool,
+}
+
+impl Iterator for ScrapedPages<'_> {
+    type Item = Result<Vec<ScrapedPage>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let n = self.remaining.map_or(self.chunk, |r| r.min(self.chunk));
+        if self.done || n == 0 {
+            return None;
+        }
+        let pages = match fetch_pages_after(self.conn, self.after_id, n, self.processed_too) {
+            Ok(pages) => pages,
+            Err(e) => {
+                self.done = true;
+                return Some(Err(e));
+            }
+        };
+        let last = pages.last()?;
+        self.after_id = last.page_data_id;
+        self.done = pages.len() < n;
+        if let Some(r) = &mut self.remaining {
+            *r -= pages.len();
+        }
+        Some(Ok(pages))
+    }
+}
+
+fn fetch_pages_after(conn: &Connection, after_id: i64, limit: usize, processed_too: bool) -> Result<Vec<ScrapedPage>> {
+    Select::new(
+        "SELECT pd.id, pd.slug, pd.url, page_markdown(pd.markdown, pd.blob_key),
+                CASE WHEN json_valid(pd.metadata) THEN json_extract(pd.metadata, '$.description') END,
+                pd.return_format IS 'html'
+         FROM page_data pd",
+    )
+    .filter_with("pd.id > ?", after_id)
+    .filter(if processed_too { latest_predicate(conn)? } else { unprocessed_predicate(conn)? })
+    .order_by("pd.id")
+    .limit(Some(limit))
+    .query(conn, |row| {
+        Ok(ScrapedPage {
+            page_data_id: row.get(0)?,
+            slug: row.get(1)?,
+            url: row.get(2)?,
+            markdown: row.get(3)?,
+            meta_description: row.get(4)?,
+            html: row.get(5)?,
+        })
+    })
+}
+
+#[derive(Serialize)]
+pub struct SectionRow {
+    pub page_data_id: i64,
+    pub slug: String,
+    pub url: String,
+    pub navbar: Option<String>,
+    pub header: Option<String>,
+    pub description: Option<String>,
+    pub news: Option<String>,
+    pub jobs: Option<String>,
+    pub footer: Option<String>,
+    pub founders_raw: Option<String>,
+    pub launches: Option<String>,
+    pub extras: Option<String>,
+    /// JSON object of section kind → markdown line span, e.g. {"news": "40-52"}.
+    pub spans: Option<String>,
+    /// Wall time for all three parser passes, filled in by `process_page`.
+    pub parse_ms: f64,
+    pub block_count: usize,
+    pub section_count: usize,
+}
+
+#[tracing::instrument(name = "db.save_sections", skip_all, fields(rows = rows.len()))]
+pub fn save_sections(conn: &Connection, rows: &[SectionRow]) -> Result<()> {
+    let tx = conn.unchecked_transaction()?;
+    {
+        let mut stmt = tx.prepare(
+            "INSERT OR REPLACE INTO company_sections
+             (page_id, slug, url, navbar, header, description, news, jobs, footer, founders_raw, launches, extras,
+              spans, parse_ms, block_count, section_count)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16)",
+        )?;
+        for r in rows {
+            stmt.execute(rusqlite::params![
+                r.page_data_id, r.slug, r.url, r.navbar, r.header, r.description,
+                r.news, r.jobs, r.footer, r.founders_raw, r.launches, r.extras,
+                r.spans, r.parse_ms, r.block_count as i64, r.section_count as i64,
+            ])?;
+        }
+    }
+    tx.commit()?;
+    Ok(())
+}
+
+/// `(slug, extras JSON)` for every processed page with unknown sections.
+pub fn fetch_extras(conn: &Connection) -> Result<Vec<(String, String)>> {
+    let sql = format!(
+        "SELECT slug, extras FROM company_sections WHERE extras IS NOT NULL AND {} AND {} ORDER BY slug",
+        scope_predicate(conn, "slug")?,
+        current_slug_predicate("slug")
+    );
+    let mut stmt = conn.prepare(&sql)?;
+    let rows = stmt.query_map([], |r| Ok((r.get(0)?, r.get(1)?)))?;
+    Ok(rows.collect::<Result<Vec<_>, _>>()?)
+}
+
+pub struct SlowPageRow {
+    pub slug: String,
+    pub parse_ms: f64,
+    pub markdown_bytes: i64,
+    pub block_count: i64,
+    pub section_count: i64,
+}
+
+/// Processed pages with the longest recorded parse times.
+pub fn fetch_slowest_pages(conn: &Connection, limit: usize) -> Result<Vec<SlowPageRow>> {
+    Select::new(
+        "SELECT cs.slug, cs.parse_ms, COALESCE(pd.markdown_bytes, length(pd.markdown), 0),
+                COALESCE(cs.block_count, 0), COALESCE(cs.section_count, 0)
+         FROM company_sections cs
+         JOIN page_data pd ON pd.id = cs.page_id",
+    )
+    .filter("cs.parse_ms IS NOT NULL")
+    .filter(scope_predicate(conn, "cs.slug")?)
+    .order_by("cs.parse_ms DESC")
+    .limit(Some(limit))
+    .query(conn, |r| {
+        Ok(SlowPageRow {
+            slug: r.get(0)?,
+            parse_ms: r.get(1)?,
+            markdown_bytes: r.get(2)?,
+            block_count: r.get(3)?,
+            section_count: r.get(4)?,
+        })
+    })
+}
+
+pub struct StaleRow {
+    pub slug: String,
+    pub batch: Option<String>,
+    pub last_scraped: String,
+    pub age_days: f64,
+}
+
+/// Visited pages worth re-scraping: the sitemap `lastmod` is newer than our
+/// visit, or (without a `lastmod`) the visit is older than `days`. Changed
+/// pages come first, most recently modified first; then the oldest visits.
+/// Pages another run has claimed are left to it.
+pub fn fetch_refresh_candidates(
+    conn: &Connection,
+    days: f64,
+    limit: Option<usize>,
+) -> Result<Vec<(i64, String, String)>> {
+    Select::new("SELECT id, url, slug FROM pages")
+        .filter("visited = 1")
+        .filter(scope_predicate(conn, "slug")?)
+        .filter(UNCLAIMED)
+        .filter_with(
+            "(lastmod > COALESCE(visited_at, '')
+              OR (lastmod IS NULL AND julianday('now') - julianday(COALESCE(visited_at, 0)) > ?))",
+            days,
+        )
+        .order_by("lastmod IS NULL, lastmod DESC, visited_at, id")
+        .limit(limit)
+        .query(conn, |r| Ok((r.get(0)?, r.get(1)?, r.get(2)?)))
+}
+
+/// Companies whose latest successful scrape is older than `days`, oldest first.
+pub fn fetch_stale(conn: &Connection, days: f64) -> Result<Vec<StaleRow>> {
+    let sql = format!(
+        "SELECT pd.slug, c.batch, MAX(pd.scraped_at) AS last,
+                julianday('now') - julianday(MAX(pd.scraped_at)) AS age
+         FROM page_data pd
+         LEFT JOIN companies c ON c.slug = pd.slug
+         WHERE (pd.markdown IS NOT NULL OR pd.blob_key IS NOT NULL) AND {} AND {}
+         GROUP BY pd.slug
+         HAVING age > ?1
+         ORDER BY age DESC, pd.slug",
+        scope_predicate(conn, "pd.slug")?,
+        current_slug_predicate("pd.slug")
+    );
+    let mut stmt = conn.prepare(&sql)?;
+    let rows = stmt
+        .query_map([days], |r| {
+            Ok(StaleRow {
+                slug: r.get(0)?,
+                batch: r.get(1)?,
+                last_scraped: r.get(2)?,
+                age_days: r.get(3)?,
+            })
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(rows)
+}
+
+/// Whether the company in `slug_column` has its founding year flagged
+/// `founded_year_inconsistent`. Aggregate reports leave those out.
+fn founded_year_flagged(slug_column: &str) -> String {
+    format!(
+        "EXISTS (SELECT 1 FROM extraction_quality q
+                 WHERE q.company_slug = {} AND q.check_name = 'founded_year_inconsistent')",
+        slug_column
+    )
+}
+
+/// The company fields cohort metrics are computed from.
+pub struct CohortRow {
+    pub batch: Option<String>,
+    pub status: Option<CompanyStatus>,
+    pub team_size: Option<i32>,
+    pub founded_year: Option<i32>,
+}
+
+/// Cohort inputs for every company in scope. Founding years flagged
+/// `founded_year_inconsistent` come back as None unless `include_flagged`.
+pub fn fetch_cohort_rows(conn: &Connection, include_flagged: bool) -> Result<Vec<CohortRow>> {
+    let sql = format!(
+        "SELECT batch, status, team_size, CASE WHEN ?1 OR NOT {} THEN founded_year END
+         FROM companies WHERE renamed_to IS NULL AND {}",
+        founded_year_flagged("companies.slug"),
+        scope_predicate(conn, "slug")?
+    );
+    let mut stmt = conn.prepare(&sql)?;
+    let rows = stmt
+        .query_map([include_flagged], |r| {
+            Ok(CohortRow {
+                batch: r.get(0)?,
+                status: r.get(1)?,
+                team_size: r.get(2)?,
+                founded_year: r.get(3)?,
+            })
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(rows)
+}
+
+// ── Extracted data ──
+
+#[derive(Serialize)]
+pub struct CompanyRow {
+    pub slug: String,
+    pub url: String,
     pub name: Option<String>,
     pub tagline: Option<String>,
     pub batch: Option<String>,
     pub batch_season: Option<String>,
     pub batch_year: Option<i32>,
-    pub status: Option<String>,
+    pub status: Option<CompanyStatus>,
     pub homepage: Option<String>,
     pub founded_year: Option<i32>,
+    /// Years since `founded_year` at extraction; None when the founding year
+    /// fails the `founded_year_inconsistent` check.
+    pub company_age_years: Option<i32>,
     pub team_size: Option<i32>,
     pub location: Option<String>,
     pub primary_partner: Option<String>,
+    /// `/people/<slug>` of the primary partner, when the footer links the profile.
+    pub primary_partner_slug: Option<String>,
+    /// The footer's "Group Partner", kept apart from the primary partner.
+    pub group_partner: Option<String>,
+    pub group_partner_slug: Option<String>,
     pub tags: Option<String>,
-    pub job_count: i32,
+    /// Job links extracted from the jobs section.
+    pub job_count_extracted: i32,
+    /// Open jobs the page states, to check `job_count_extracted` against.
+    pub job_count_listed: Option<i32>,
     pub linkedin: Option<String>,
     pub twitter: Option<String>,
     pub facebook: Option<String>,
     pub crunchbase: Option<String>,
     pub github: Option<String>,
+    /// ISO 639-3 code of the description text ("eng", "spa"), when detectable.
+    pub description_lang: Option<String>,
 }
 
+#[derive(Serialize)]
 pub struct FounderRow {
     pub company_slug: String,
     pub name: String,
+    /// `parser::names::name_key(name)`; same key = same person within a company.
+    pub name_key: String,
     pub title: Option<String>,
     pub bio: Option<String>,
     pub is_active: bool,
     pub linkedin: Option<String>,
     pub twitter: Option<String>,
+    /// Markdown lines this row was extracted from, e.g. "41-44".
+    pub source_lines: Option<String>,
 }
 
+#[derive(Serialize)]
 pub struct NewsRow {
     pub company_slug: String,
     pub title: String,
     pub url: String,
     pub published: Option<String>,
+    pub source_lines: Option<String>,
 }
 
+#[derive(Serialize)]
 pub struct JobRow {
     pub company_slug: String,
     pub title: String,
     pub url: String,
     pub location: Option<String>,
     pub salary: Option<String>,
+    /// ISO code of the salary range's currency.
+    pub salary_currency: Option<String>,
+    /// Range bounds in that currency.
+    pub salary_min: Option<f64>,
+    pub salary_max: Option<f64>,
+    /// Bounds converted at the configured rate; None for unknown currencies.
+    pub salary_min_usd: Option<i64>,
+    pub salary_max_usd: Option<i64>,
     pub experience: Option<String>,
     pub apply_url: Option<String>,
+    pub source_lines: Option<String>,
 }
 
+#[derive(Serialize)]
 pub struct LinkRow {
     pub company_slug: String,
     pub url: String,
     pub domain: String,
     pub link_type: Option<String>,
+    /// News outlet behind a `press` link.
+    pub publisher: Option<String>,
+    /// `name_key` of the founder this link belongs to; stored as `founder_id`.
+    pub founder_key: Option<String>,
+    pub source_lines: Option<String>,
+}
+
+/// `companies` columns merged field-wise on re-extraction, in `company_values` order.
+const COMPANY_FIELDS: [&str; 25] = [
+    "url", "name", "tagline", "batch", "batch_season", "batch_year", "status", "homepage",
+    "founded_year", "company_age_years", "team_size", "location", "primary_partner",
+    "primary_partner_slug", "group_partner", "group_partner_slug", "tags", "job_count_extracted",
+    "job_count_listed", "linkedin", "twitter", "facebook", "crunchbase", "github", "description_lang",
+];
+
+/// Derived from other fields; advancing them is not a change worth logging.
+const DERIVED_COMPANY_FIELDS: &[&str] = &["company_age_years"];
+
+fn company_values(c: &CompanyRow) -> [Value; 25] {
+    [
+        c.url.clone().into(),
+        c.name.clone().into(),
+        c.tagline.clone().into(),
+        c.batch.clone().into(),
+        c.batch_season.clone().into(),
+        c.batch_year.into(),
+        c.status.map(|s| s.as_str().to_string()).into(),
+        c.homepage.clone().into(),
+        c.founded_year.into(),
+        c.company_age_years.into(),
+        c.team_size.into(),
+        c.location.clone().into(),
+        c.primary_partner.clone().into(),
+        c.primary_partner_slug.clone().into(),
+        c.group_partner.clone().into(),
+        c.group_partner_slug.clone().into(),
+        c.tags.clone().into(),
+        c.job_count_extracted.into(),
+        c.job_count_listed.into(),
+        c.linkedin.clone().into(),
+        c.twitter.clone().into(),
+        c.facebook.clone().into(),
+        c.crunchbase.clone().into(),
+        c.github.clone().into(),
+        c.description_lang.clone().into(),
+    ]
+}
+
+/// Upsert one company without losing data to a degraded re-scrape: NULLs
+/// never replace stored values, and every stored non-NULL value that does
+/// get replaced is logged to `field_changes`.
+fn merge_company(conn: &Connection, c: &CompanyRow) -> Result<()> {
+    let new = company_values(c);
+    let old: Option<Vec<Value>> = conn
+        .prepare_cached(&format!(
+            "SELECT {} FROM companies WHERE slug = ?1",
+            COMPANY_FIELDS.join(", ")
+        ))?
+        .query_row([&c.slug], |row| {
+            (0..COMPANY_FIELDS.len()).map(|i| row.get::<_, Value>(i)).collect()
+        })
+        .optional()?;
+
+    if let Some(old) = &old {
+        let mut log = conn.prepare_cached(
+            "INSERT INTO field_changes (company_slug, field, old_value, new_value)
+             VALUES (?1, ?2, ?3, ?4)",
+        )?;
+        for ((field, before), after) in COMPANY_FIELDS.iter().zip(old).zip(&new) {
+            if *before != Value::Null
+                && *after != Value::Null
+                && before != after
+                && !DERIVED_COMPANY_FIELDS.contains(field)
+            {
+                log.execute(rusqlite::params![c.slug, field, before, after])?;
+            }
+        }
+    }
+
+    let placeholders: Vec<String> = (2..=COMPANY_FIELDS.len() + 1).map(|i| format!("?{}", i)).collect();
+    let merges: Vec<String> = COMPANY_FIELDS
+        .iter()
+        .map(|f| format!("{f} = COALESCE(excluded.{f}, companies.{f})"))
+        .collect();
+    let mut stmt = conn.prepare_cached(&format!(
+        "INSERT INTO companies (slug, {}) VALUES (?1, {})
+         ON CONFLICT(slug) DO UPDATE SET {}",
+        COMPANY_FIELDS.join(", "),
+        placeholders.join(", "),
+        merges.join(", ")
+    ))?;
+    let mut params: Vec<&dyn rusqlite::ToSql> = vec![&c.slug];
+    params.extend(new.iter().map(|v| v as &dyn rusqlite::ToSql));
+    stmt.execute(params.as_slice())?;
+    Ok(())
 }
 
+#[tracing::instrument(name = "db.save_extracted", skip_all, fields(companies = companies.len()))]
 pub fn save_extracted(
     conn: &Connection,
     companies: &[CompanyRow],
@@ -341,250 +1776,2682 @@ pub fn save_extracted(
 ) -> Result<()> {
     let tx = conn.unchecked_transaction()?;
     {
-        let mut c_stmt = tx.prepare(
-            "INSERT OR REPLACE INTO companies
-             (slug, url, name, tagline, batch, batch_season, batch_year, status,
-              homepage, founded_year, team_size, location, primary_partner, tags,
-              job_count, linkedin, twitter, facebook, crunchbase, github)
-             VALUES (?1,?2,?3,?4,?5,?6,?7,?8,?9,?10,?11,?12,?13,?14,?15,?16,?17,?18,?19,?20)",
-        )?;
         for c in companies {
-            c_stmt.execute(rusqlite::params![
-                c.slug, c.url, c.name, c.tagline, c.batch, c.batch_season, c.batch_year,
-                c.status, c.homepage, c.founded_year, c.team_size, c.location,
-                c.primary_partner, c.tags, c.job_count, c.linkedin, c.twitter,
-                c.facebook, c.crunchbase, c.github,
-            ])?;
+            merge_company(&tx, c)?;
         }
 
+        // Skip founders already stored under another spelling of the same name.
+        // `seen` is the scrape time of the page being processed (its sections
+        // are saved first); the earliest one wins, so reprocessing an older
+        // page can only move first_seen_at / departed_at back.
         let mut f_stmt = tx.prepare(
-            "INSERT OR IGNORE INTO founders
-             (company_slug, name, title, bio, is_active, linkedin, twitter)
-             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+            "INSERT INTO founders
+             (company_slug, name, name_key, title, bio, is_active, linkedin, twitter, source_lines,
+              first_seen_at, departed_at)
+             SELECT ?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, seen, CASE WHEN ?6 THEN NULL ELSE seen END
+             FROM (SELECT COALESCE(
+                 (SELECT pd.scraped_at FROM company_sections cs JOIN page_data pd ON pd.id = cs.page_id
+                  WHERE cs.slug = ?1),
+                 datetime('now')
+             ) AS seen)
+             WHERE NOT EXISTS (
+                 SELECT 1 FROM founders
+                 WHERE company_slug = ?1 AND name_key = ?3 AND name <> ?2
+             )
+             ON CONFLICT(company_slug, name) DO UPDATE SET
+                 source_lines = excluded.source_lines,
+                 is_active = excluded.is_active,
+                 first_seen_at = COALESCE(min(first_seen_at, excluded.first_seen_at), first_seen_at, excluded.first_seen_at),
+                 departed_at = CASE WHEN excluded.is_active THEN NULL
+                     ELSE COALESCE(min(departed_at, excluded.departed_at), departed_at, excluded.departed_at) END",
         )?;
         for f in founders {
             f_stmt.execute(rusqlite::params![
-                f.company_slug, f.name, f.title, f.bio, f.is_active, f.linkedin, f.twitter,
+                f.company_slug, f.name, f.name_key, f.title, f.bio, f.is_active,
+                f.linkedin, f.twitter, f.source_lines,
             ])?;
         }
 
         let mut n_stmt = tx.prepare(
-            "INSERT OR IGNORE INTO news (company_slug, title, url, published)
-             VALUES (?1, ?2, ?3, ?4)",
+            "INSERT INTO news (company_slug, title, url, published, source_lines, article_id)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6)
+             ON CONFLICT(company_slug, url) DO UPDATE SET source_lines = excluded.source_lines",
         )?;
         for n in news {
-            n_stmt.execute(rusqlite::params![n.company_slug, n.title, n.url, n.published])?;
+            n_stmt.execute(rusqlite::params![
+                n.company_slug, n.title, n.url, n.published, n.source_lines, article_id(&n.url),
+            ])?;
         }
 
         let mut j_stmt = tx.prepare(
-            "INSERT OR IGNORE INTO company_jobs
-             (company_slug, title, url, location, salary, experience, apply_url)
-             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+            "INSERT INTO company_jobs
+             (company_slug, title, url, location, salary, experience, apply_url, source_lines,
+              salary_currency, salary_min, salary_max, salary_min_usd, salary_max_usd)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13)
+             ON CONFLICT(company_slug, url) DO UPDATE SET
+                 source_lines = excluded.source_lines,
+                 salary = excluded.salary,
+                 salary_currency = excluded.salary_currency,
+                 salary_min = excluded.salary_min,
+                 salary_max = excluded.salary_max,
+                 salary_min_usd = excluded.salary_min_usd,
+                 salary_max_usd = excluded.salary_max_usd",
         )?;
         for j in jobs {
             j_stmt.execute(rusqlite::params![
                 j.company_slug, j.title, j.url, j.location, j.salary, j.experience, j.apply_url,
+                j.source_lines, j.salary_currency, j.salary_min, j.salary_max, j.salary_min_usd,
+                j.salary_max_usd,
             ])?;
         }
 
+        // Founders are written above, so their ids resolve here. A link the
+        // page no longer ties to a founder keeps the one it had (set by
+        // `link_founder_profiles` or an earlier page).
         let mut l_stmt = tx.prepare(
-            "INSERT OR IGNORE INTO company_links (company_slug, url, domain, link_type)
-             VALUES (?1, ?2, ?3, ?4)",
+            "INSERT INTO company_links (company_slug, url, domain, link_type, source_lines, founder_id, publisher)
+             VALUES (?1, ?2, ?3, ?4, ?5, (
+                 SELECT id FROM founders WHERE company_slug = ?1 AND name_key = ?6
+                 ORDER BY id LIMIT 1
+             ), ?7)
+             ON CONFLICT(company_slug, url) DO UPDATE SET
+                 source_lines = excluded.source_lines,
+                 founder_id = COALESCE(excluded.founder_id, company_links.founder_id),
+                 link_type = excluded.link_type, publisher = excluded.publisher",
         )?;
         for l in links {
-            l_stmt.execute(rusqlite::params![l.company_slug, l.url, l.domain, l.link_type])?;
+            l_stmt.execute(rusqlite::params![
+                l.company_slug, l.url, l.domain, l.link_type, l.source_lines, l.founder_key, l.publisher,
+            ])?;
         }
     }
     tx.commit()?;
     Ok(())
 }
 
-// ── Meeting links ──
-
-pub struct MeetingLinkRow {
-    pub company_slug: String,
-    pub url: String,
-    pub domain: String,
-    pub link_type: String, // "calendly", "cal.com", "motion", "hubspot", "other"
-}
+// ── Founder dedup ──
 
-pub fn save_meeting_links(conn: &Connection, rows: &[MeetingLinkRow]) -> Result<()> {
+/// Fill `founders.name_key` for rows written before it existed.
+fn backfill_founder_keys(conn: &Connection) -> Result<()> {
+    let rows: Vec<(i64, String)> = {
+        let mut stmt = conn.prepare("SELECT id, name FROM founders WHERE name_key IS NULL")?;
+        let rows = stmt.query_map([], |r| Ok((r.get(0)?, r.get(1)?)))?;
+        rows.collect::<Result<Vec<_>, _>>()?
+    };
     let tx = conn.unchecked_transaction()?;
     {
-        let mut stmt = tx.prepare(
-            "INSERT OR IGNORE INTO meeting_links (company_slug, url, domain, link_type)
-             VALUES (?1, ?2, ?3, ?4)",
-        )?;
-        for r in rows {
-            stmt.execute(rusqlite::params![r.company_slug, r.url, r.domain, r.link_type])?;
+        let mut stmt = tx.prepare("UPDATE founders SET name_key = ?2 WHERE id = ?1")?;
+        for (id, name) in &rows {
+            stmt.execute(rusqlite::params![id, crate::parser::names::name_key(name)])?;
         }
     }
     tx.commit()?;
     Ok(())
 }
 
-// ── Partners ──
+/// Fill `founders.linkedin` where it is missing from the personal LinkedIn
+/// profiles in the company's `company_links`, for links that sat outside
+/// the founder's card. A link already attributed to the founder
+/// (`founder_id`) matches as `founder_link`; an unattributed one whose
+/// handle names the founder and no one else as `name_in_handle`. Founders
+/// with more than one candidate, and profiles another founder already has,
+/// are left alone. Returns the founders filled.
+pub fn backfill_founder_linkedin(conn: &Connection) -> Result<usize> {
+    let tx = conn.unchecked_transaction()?;
+    let n = link_founder_profiles(&tx)?;
+    tx.commit()?;
+    Ok(n)
+}
 
-pub struct PartnerRow {
-    pub slug: String,
-    pub url: String,
+/// `backfill_founder_linkedin` inside the caller's transaction.
+fn link_founder_profiles(conn: &Connection) -> Result<usize> {
+    use crate::parser::extract::links::{handle_owner, profile_handle};
+    use std::collections::{BTreeMap, HashSet};
+
+    struct Founder {
+        id: i64,
+        name_key: String,
+        linkedin: Option<String>,
+    }
+    struct Profile {
+        id: i64,
+        url: String,
+        handle: String,
+        founder_id: Option<i64>,
+    }
+
+    const MISSING: &str =
+        "SELECT company_slug FROM founders WHERE COALESCE(linkedin, '') = '' AND name_key IS NOT NULL";
+    let mut companies: BTreeMap<String, (Vec<Founder>, Vec<Profile>)> = BTreeMap::new();
+    let mut stmt = conn.prepare(&format!(
+        "SELECT company_slug, id, COALESCE(name_key, ''), NULLIF(linkedin, '') FROM founders
+         WHERE company_slug IN ({MISSING}) ORDER BY id"
+    ))?;
+    let rows = stmt.query_map([], |r| {
+        Ok((r.get::<_, String>(0)?, Founder { id: r.get(1)?, name_key: r.get(2)?, linkedin: r.get(3)? }))
+    })?;
+    for row in rows {
+        let (slug, founder) = row?;
+        companies.entry(slug).or_default().0.push(founder);
+    }
+    let mut stmt = conn.prepare(&format!(
+        "SELECT company_slug, id, url, founder_id FROM company_links
+         WHERE link_type = 'linkedin' AND company_slug IN ({MISSING}) ORDER BY id"
+    ))?;
+    let rows = stmt.query_map([], |r| {
+        Ok((r.get::<_, String>(0)?, r.get::<_, i64>(1)?, r.get::<_, String>(2)?, r.get::<_, Option<i64>>(3)?))
+    })?;
+    for row in rows {
+        let (slug, id, url, founder_id) = row?;
+        let Some(handle) = profile_handle(&url) else { continue };
+        if let Some((_, profiles)) = companies.get_mut(&slug) {
+            profiles.push(Profile { id, url, handle, founder_id });
+        }
+    }
+
+    let mut set_founder =
+        conn.prepare("UPDATE founders SET linkedin = ?2, linkedin_match = ?3 WHERE id = ?1")?;
+    let mut set_link = conn.prepare("UPDATE company_links SET founder_id = ?2 WHERE id = ?1 AND founder_id IS NULL")?;
+    let mut filled = 0;
+    for (founders, profiles) in companies.values() {
+        let keys: Vec<&str> = founders.iter().map(|f| f.name_key.as_str()).filter(|k| !k.is_empty()).collect();
+        let mut taken: HashSet<String> =
+            founders.iter().filter_map(|f| f.linkedin.as_deref().and_then(profile_handle)).collect();
+        for f in founders.iter().filter(|f| f.linkedin.is_none() && !f.name_key.is_empty()) {
+            let free = |p: &&Profile| !taken.contains(&p.handle);
+            let owned: Vec<&Profile> = profiles.iter().filter(free).filter(|p| p.founder_id == Some(f.id)).collect();
+            let (candidates, method) = if owned.is_empty() {
+                let named = profiles
+                    .iter()
+                    .filter(free)
+                    .filter(|p| p.founder_id.is_none() && handle_owner(&p.url, &keys) == Some(f.name_key.as_str()))
+                    .collect();
+                (named, "name_in_handle")
+            } else {
+                (owned, "founder_link")
+            };
+            let [profile] = candidates.as_slice() else { continue };
+            set_founder.execute(rusqlite::params![f.id, profile.url, method])?;
+            set_link.execute(rusqlite::params![profile.id, f.id])?;
+            taken.insert(profile.handle.clone());
+            filled += 1;
+        }
+    }
+    Ok(filled)
+}
+
+pub struct StoredFounder {
+    pub id: i64,
+    pub company_slug: String,
     pub name: String,
+    pub name_key: String,
     pub title: Option<String>,
     pub bio: Option<String>,
+    pub is_active: bool,
+    pub linkedin: Option<String>,
+    pub twitter: Option<String>,
 }
 
-pub struct CompanyPartnerRow {
-    pub company_slug: String,
-    pub partner_slug: String,
-    pub match_method: String, // "url" or "name"
+/// Founders sharing (company_slug, name_key) with at least one other row,
+/// grouped together and ordered by id within each group.
+pub fn fetch_duplicate_founders(conn: &Connection) -> Result<Vec<Vec<StoredFounder>>> {
+    let mut stmt = conn.prepare(
+        "SELECT f.id, f.company_slug, f.name, f.name_key, f.title, f.bio, f.is_active,
+                f.linkedin, f.twitter
+         FROM founders f
+         JOIN (
+             SELECT company_slug, name_key FROM founders
+             WHERE name_key IS NOT NULL
+             GROUP BY company_slug, name_key HAVING COUNT(*) > 1
+         ) d ON d.company_slug = f.company_slug AND d.name_key = f.name_key
+         ORDER BY f.company_slug, f.name_key, f.id",
+    )?;
+    let rows = stmt
+        .query_map([], |r| {
+            Ok(StoredFounder {
+                id: r.get(0)?,
+                company_slug: r.get(1)?,
+                name: r.get(2)?,
+                name_key: r.get(3)?,
+                title: r.get(4)?,
+                bio: r.get(5)?,
+                is_active: r.get(6)?,
+                linkedin: r.get(7)?,
+                twitter: r.get(8)?,
+            })
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let mut groups: Vec<Vec<StoredFounder>> = Vec::new();
+    for f in rows {
+        match groups.last_mut() {
+            Some(g) if g[0].company_slug == f.company_slug && g[0].name_key == f.name_key => {
+                g.push(f)
+            }
+            _ => groups.push(vec![f]),
+        }
+    }
+    Ok(groups)
 }
 
-pub fn save_partners(conn: &Connection, rows: &[PartnerRow]) -> Result<usize> {
+/// Merge each duplicate group into its most complete row (lowest id on ties):
+/// missing fields are filled from the other rows, which are then deleted.
+/// Returns the number of rows removed.
+pub fn merge_duplicate_founders(conn: &Connection, groups: &[Vec<StoredFounder>]) -> Result<usize> {
+    fn filled(f: &StoredFounder) -> usize {
+        [&f.title, &f.bio, &f.linkedin, &f.twitter]
+            .iter()
+            .filter(|v| v.is_some())
+            .count()
+    }
+
     let tx = conn.unchecked_transaction()?;
-    let mut count = 0;
+    let mut removed = 0;
     {
-        let mut stmt = tx.prepare(
-            "INSERT OR REPLACE INTO partners (slug, url, name, title, bio)
-             VALUES (?1, ?2, ?3, ?4, ?5)",
+        // Dates come from the whole group, before the other rows go
+        let mut update = tx.prepare(
+            "UPDATE founders SET title = ?2, bio = ?3, linkedin = ?4, twitter = ?5,
+                                 is_active = ?6,
+                                 first_seen_at = (SELECT MIN(g.first_seen_at) FROM founders g
+                                                  WHERE g.company_slug = founders.company_slug AND g.name_key = founders.name_key),
+                                 departed_at = CASE WHEN ?6 THEN NULL ELSE
+                                     (SELECT MIN(g.departed_at) FROM founders g
+                                      WHERE g.company_slug = founders.company_slug AND g.name_key = founders.name_key) END
+             WHERE id = ?1",
         )?;
-        for r in rows {
-            count += stmt.execute(rusqlite::params![r.slug, r.url, r.name, r.title, r.bio])?;
+        let mut repoint = tx.prepare("UPDATE company_links SET founder_id = ?2 WHERE founder_id = ?1")?;
+        let mut repoint_experience =
+            tx.prepare("UPDATE OR IGNORE founder_experience SET founder_id = ?2 WHERE founder_id = ?1")?;
+        let mut drop_experience = tx.prepare("DELETE FROM founder_experience WHERE founder_id = ?1")?;
+        let mut delete = tx.prepare("DELETE FROM founders WHERE id = ?1")?;
+        for group in groups {
+            let Some(keeper) = group
+                .iter()
+                .max_by(|a, b| filled(a).cmp(&filled(b)).then(b.id.cmp(&a.id)))
+            else {
+                continue;
+            };
+            let others = || group.iter().filter(|f| f.id != keeper.id);
+            let pick = |get: fn(&StoredFounder) -> &Option<String>| {
+                get(keeper)
+                    .clone()
+                    .or_else(|| others().find_map(|f| get(f).clone()))
+            };
+            update.execute(rusqlite::params![
+                keeper.id,
+                pick(|f| &f.title),
+                pick(|f| &f.bio),
+                pick(|f| &f.linkedin),
+                pick(|f| &f.twitter),
+                group.iter().any(|f| f.is_active),
+            ])?;
+            for f in others() {
+                repoint.execute([f.id, keeper.id])?;
+                repoint_experience.execute([f.id, keeper.id])?;
+                drop_experience.execute([f.id])?;
+                removed += delete.execute([f.id])?;
+            }
         }
     }
     tx.commit()?;
-    Ok(count)
+    Ok(removed)
 }
 
-pub fn fetch_partners(conn: &Connection) -> Result<Vec<PartnerRow>> {
-    let mut stmt = conn.prepare("SELECT slug, url, name, title, bio FROM partners")?;
-    let rows = stmt
-        .query_map([], |row| {
-            Ok(PartnerRow {
-                slug: row.get(0)?,
-                url: row.get(1)?,
-                name: row.get(2)?,
-                title: row.get(3)?,
-                bio: row.get(4)?,
-            })
-        })?
-        .collect::<Result<Vec<_>, _>>()?;
-    Ok(rows)
+// ── Founder recovery ──
+
+/// (slug, founders_raw) for processed companies with a founders section but
+/// no founder rows.
+pub fn fetch_founderless_sections(conn: &Connection) -> Result<Vec<(String, String)>> {
+    Select::new("SELECT cs.slug, cs.founders_raw FROM company_sections cs")
+        .filter("cs.founders_raw <> ''")
+        .filter("NOT EXISTS (SELECT 1 FROM founders f WHERE f.company_slug = cs.slug)")
+        .filter(scope_predicate(conn, "cs.slug")?)
+        .order_by("cs.slug")
+        .query(conn, |r| Ok((r.get(0)?, r.get(1)?)))
 }
 
-pub fn save_company_partners(conn: &Connection, rows: &[CompanyPartnerRow]) -> Result<usize> {
+/// A processed company with no founder rows.
+pub struct FounderlessCompany {
+    pub slug: String,
+    pub batch: Option<String>,
+    pub status: Option<String>,
+    /// Characters of founders section text; 0 when the page had none.
+    pub section_chars: usize,
+}
+
+/// Processed companies that still have no founders, those with a founders
+/// section (a parsing miss rather than an empty page) first.
+pub fn fetch_founderless_companies(conn: &Connection) -> Result<Vec<FounderlessCompany>> {
+    Select::new(
+        "SELECT c.slug, c.batch, c.status, COALESCE(length(cs.founders_raw), 0)
+         FROM companies c JOIN company_sections cs ON cs.slug = c.slug",
+    )
+    .filter("c.renamed_to IS NULL")
+    .filter("NOT EXISTS (SELECT 1 FROM founders f WHERE f.company_slug = c.slug)")
+    .filter(scope_predicate(conn, "c.slug")?)
+    .order_by("cs.founders_raw IS NULL, c.slug")
+    .query(conn, |r| {
+        Ok(FounderlessCompany {
+            slug: r.get(0)?,
+            batch: r.get(1)?,
+            status: r.get(2)?,
+            section_chars: r.get::<_, i64>(3)? as usize,
+        })
+    })
+}
+
+// ── News resolution ──
+
+/// News rows whose URL hasn't been resolved yet: (id, url).
+pub fn fetch_unresolved_news(conn: &Connection, limit: Option<usize>) -> Result<Vec<(i64, String)>> {
+    Select::new("SELECT id, url FROM news")
+        .filter("resolved_at IS NULL")
+        .order_by("id")
+        .limit(limit)
+        .query(conn, |row| Ok((row.get(0)?, row.get(1)?)))
+}
+
+/// Store canonical URLs as (id, canonical_url, resolved). Rows whose lookup
+/// failed keep `resolved_at` NULL so the next run retries them.
+pub fn save_news_canonical(conn: &Connection, rows: &[(i64, String, bool)]) -> Result<()> {
     let tx = conn.unchecked_transaction()?;
-    let mut count = 0;
     {
+        let mut url = tx.prepare("SELECT url FROM news WHERE id = ?1")?;
         let mut stmt = tx.prepare(
-            "INSERT OR IGNORE INTO company_partners (company_slug, partner_slug, match_method)
-             VALUES (?1, ?2, ?3)",
+            "UPDATE news
+             SET canonical_url = ?2,
+                 resolved_at = CASE WHEN ?3 THEN datetime('now') END,
+                 article_id = ?4
+             WHERE id = ?1",
         )?;
-        for r in rows {
-            count += stmt.execute(rusqlite::params![
-                r.company_slug, r.partner_slug, r.match_method
-            ])?;
+        for (id, canonical, resolved) in rows {
+            let Some(original) = url.query_row([id], |r| r.get::<_, String>(0)).optional()? else { continue };
+            let article = article_id(article_url(&original, Some(canonical)));
+            stmt.execute(rusqlite::params![id, canonical, resolved, article])?;
         }
     }
     tx.commit()?;
-    Ok(count)
+    Ok(())
 }
 
-/// Fetch company slugs + their raw markdown for partner URL matching.
-pub fn fetch_scraped_markdown(conn: &Connection) -> Result<Vec<(String, String)>> {
-    let mut stmt = conn.prepare(
-        "SELECT pd.slug, pd.markdown
-         FROM page_data pd
-         WHERE pd.markdown IS NOT NULL",
-    )?;
-    let rows = stmt
-        .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?
-        .collect::<Result<Vec<_>, _>>()?;
-    Ok(rows)
+/// Job apply URLs not yet resolved to their final (ATS) destination.
+pub fn fetch_unresolved_apply_urls(conn: &Connection, limit: Option<usize>) -> Result<Vec<(i64, String)>> {
+    Select::new("SELECT id, apply_url FROM company_jobs")
+        .filter("apply_url IS NOT NULL AND apply_resolved_at IS NULL")
+        .filter(scope_predicate(conn, "company_slug")?)
+        .order_by("id")
+        .limit(limit)
+        .query(conn, |row| Ok((row.get(0)?, row.get(1)?)))
 }
 
-/// Fetch companies with primary_partner set but no entry in company_partners yet.
-pub fn fetch_unmatched_partners(conn: &Connection) -> Result<Vec<(String, String)>> {
-    let mut stmt = conn.prepare(
-        "SELECT c.slug, c.primary_partner
-         FROM companies c
-         WHERE c.primary_partner IS NOT NULL
-           AND c.primary_partner != ''
-           AND NOT EXISTS (
-               SELECT 1 FROM company_partners cp WHERE cp.company_slug = c.slug
-           )",
+/// Store (id, final_url, ats_vendor, resolved). Failed lookups keep
+/// `apply_resolved_at` NULL so the next run retries them.
+pub fn save_apply_resolutions(
+    conn: &Connection,
+    rows: &[(i64, String, Option<&str>, bool)],
+) -> Result<()> {
+    let tx = conn.unchecked_transaction()?;
+    {
+        let mut stmt = tx.prepare(
+            "UPDATE company_jobs
+             SET apply_url_final = ?2,
+                 ats_vendor = ?3,
+                 apply_resolved_at = CASE WHEN ?4 THEN datetime('now') END
+             WHERE id = ?1",
+        )?;
+        for (id, final_url, vendor, resolved) in rows {
+            stmt.execute(rusqlite::params![id, final_url, vendor, resolved])?;
+        }
+    }
+    tx.commit()?;
+    Ok(())
+}
+
+/// Hosts redirects land on instead of the article: consent walls and
+/// login or subscription interstitials. Every article behind one resolves
+/// to the same URL.
+const INTERSTITIAL_HOSTS: &[&str] = &[
+    "consent.google.com", "consent.youtube.com", "consent.yahoo.com", "guce.yahoo.com", "guce.aol.com",
+    "accounts.google.com", "login.microsoftonline.com", "myaccount.nytimes.com", "subscribe.wsj.com",
+];
+
+/// Whether a resolved URL names one article: it has a path below the
+/// host's root and isn't an interstitial. Publisher homepages and consent
+/// walls don't, so articles resolving to them aren't the same article.
+fn identifies_article(url: &str) -> bool {
+    let Some((_, rest)) = url.split_once("://") else { return false };
+    let rest = rest.split(['?', '#']).next().unwrap_or(rest);
+    let (host, path) = rest.split_once('/').unwrap_or((rest, ""));
+    let host = host.to_lowercase();
+    !path.trim_matches('/').is_empty()
+        && !host.starts_with("consent.")
+        && !INTERSTITIAL_HOSTS.contains(&host.as_str())
+}
+
+/// The URL an article is known by: the canonical one when it identifies an
+/// article, else the URL the page linked.
+fn article_url<'a>(url: &'a str, canonical: Option<&'a str>) -> &'a str {
+    canonical.filter(|c| identifies_article(c)).unwrap_or(url)
+}
+
+/// Mark news rows that resolve to the same article as another row for the
+/// same company with `duplicate_of`, pointing at the one kept: dated first,
+/// then lowest id. Rows whose canonical URL doesn't identify an article are
+/// never duplicates. Returns the number of rows marked.
+pub fn mark_news_duplicates(conn: &Connection) -> Result<usize> {
+    let rows: Vec<(i64, String, String)> = conn
+        .prepare(
+            "SELECT id, company_slug, canonical_url FROM news WHERE canonical_url IS NOT NULL
+             ORDER BY published IS NULL, id",
+        )?
+        .query_map([], |r| Ok((r.get(0)?, r.get(1)?, r.get(2)?)))?
+        .collect::<Result<_, _>>()?;
+    let mut kept: std::collections::HashMap<(String, String), i64> = Default::default();
+    let mut duplicates = Vec::new();
+    for (id, slug, canonical) in rows {
+        if !identifies_article(&canonical) {
+            continue;
+        }
+        let first = *kept.entry((slug, canonical)).or_insert(id);
+        if first != id {
+            duplicates.push((id, first));
+        }
+    }
+    let tx = conn.unchecked_transaction()?;
+    tx.execute("UPDATE news SET duplicate_of = NULL WHERE duplicate_of IS NOT NULL", [])?;
+    {
+        let mut stmt = tx.prepare("UPDATE news SET duplicate_of = ?2 WHERE id = ?1")?;
+        for (id, first) in &duplicates {
+            stmt.execute([id, first])?;
+        }
+    }
+    tx.commit()?;
+    Ok(duplicates.len())
+}
+
+/// Global article identity: FNV-1a 64 of the URL (the canonical one once
+/// `resolve-news` has run), as hex. Stable across builds, unlike `DefaultHasher`.
+pub fn article_id(url: &str) -> String {
+    let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+    for b in url.trim().trim_end_matches('/').bytes() {
+        hash ^= b as u64;
+        hash = hash.wrapping_mul(0x0100_0000_01b3);
+    }
+    format!("{:016x}", hash)
+}
+
+/// Give news rows stored before `article_id` existed their id.
+fn backfill_article_ids(conn: &Connection) -> Result<()> {
+    let rows: Vec<(i64, String)> = conn
+        .prepare("SELECT id, url, canonical_url FROM news WHERE article_id IS NULL")?
+        .query_map([], |r| {
+            let (url, canonical): (String, Option<String>) = (r.get(1)?, r.get(2)?);
+            Ok((r.get(0)?, article_url(&url, canonical.as_deref()).to_string()))
+        })?
+        .collect::<Result<_, _>>()?;
+    if rows.is_empty() {
+        return Ok(());
+    }
+    let tx = conn.unchecked_transaction()?;
+    {
+        let mut stmt = tx.prepare("UPDATE news SET article_id = ?2 WHERE id = ?1")?;
+        for (id, url) in &rows {
+            stmt.execute(rusqlite::params![id, article_id(url)])?;
+        }
+    }
+    tx.commit()?;
+    rebuild_news_global(conn)?;
+    Ok(())
+}
+
+/// Bring `company_age_years` up to date once the calendar year has turned,
+/// so the stored age reads like one derived from `founded_year`. Companies
+/// without an age (their founding year failed the batch check) keep none.
+fn refresh_company_ages(conn: &Connection) -> Result<()> {
+    conn.execute(
+        "UPDATE companies SET company_age_years = max(0, CAST(strftime('%Y', 'now') AS INTEGER) - founded_year)
+         WHERE company_age_years IS NOT NULL AND founded_year IS NOT NULL
+           AND company_age_years <> max(0, CAST(strftime('%Y', 'now') AS INTEGER) - founded_year)",
+        [],
+    )?;
+    Ok(())
+}
+
+/// Fill `group_partner` for companies extracted before it was a column,
+/// from the footer text `company_sections` kept. Footers without the field
+/// leave it NULL.
+fn backfill_group_partners(conn: &Connection) -> Result<()> {
+    use crate::parser::{blocks, extract::company, lang::Lexicon, ParserConfig};
+
+    let rows: Vec<(String, String)> = conn
+        .prepare(
+            "SELECT c.slug, cs.footer FROM companies c JOIN company_sections cs ON cs.slug = c.slug
+             WHERE c.group_partner IS NULL AND cs.footer LIKE '%Group Partner:%'",
+        )?
+        .query_map([], |r| Ok((r.get(0)?, r.get(1)?)))?
+        .collect::<Result<_, _>>()?;
+    let tx = conn.unchecked_transaction()?;
+    {
+        let mut stmt =
+            tx.prepare("UPDATE companies SET group_partner = ?2, group_partner_slug = ?3 WHERE slug = ?1")?;
+        for (slug, footer) in &rows {
+            let lexed = blocks::lex_with(footer, &Lexicon::english(), &ParserConfig::default());
+            let (name, partner_slug) = company::partner_in(&lexed.blocks, "Group Partner");
+            if name.is_some() {
+                stmt.execute(rusqlite::params![slug, name, partner_slug])?;
+            }
+        }
+    }
+    tx.commit()?;
+    Ok(())
+}
+
+/// Rebuild `news_global` from `news`: one row per article, titled and dated
+/// from its earliest mention, with the number of companies citing it.
+/// Returns the number of articles.
+pub fn rebuild_news_global(conn: &Connection) -> Result<usize> {
+    let tx = conn.unchecked_transaction()?;
+    tx.execute("DELETE FROM news_global", [])?;
+    let articles = tx.execute(
+        "INSERT INTO news_global (article_id, url, title, published, company_count)
+         SELECT n.article_id,
+                COALESCE(MIN(n.canonical_url), MIN(n.url)),
+                (SELECT title FROM news f WHERE f.article_id = n.article_id ORDER BY f.id LIMIT 1),
+                MIN(n.published),
+                COUNT(DISTINCT n.company_slug)
+         FROM news n
+         WHERE n.article_id IS NOT NULL
+         GROUP BY n.article_id",
+        [],
+    )?;
+    tx.commit()?;
+    Ok(articles)
+}
+
+/// Rebuild `funding_events` from news titles (`extract::funding::classify`).
+/// Articles naming the same round of a company are one event, dated by the
+/// earliest and taking the first amount given; unnamed rounds are merged by
+/// amount. Returns the number of events.
+pub fn rebuild_funding_events(conn: &Connection) -> Result<usize> {
+    use crate::parser::extract::funding;
+
+    let mut stmt =
+        conn.prepare("SELECT company_slug, title, url, published FROM news WHERE duplicate_of IS NULL ORDER BY id")?;
+    let news = stmt
+        .query_map([], |r| Ok((r.get::<_, String>(0)?, r.get::<_, String>(1)?, r.get(2)?, r.get(3)?)))?
+        .collect::<rusqlite::Result<Vec<(String, String, String, Option<String>)>>>()?;
+
+    let mut events: Vec<(String, funding::Funding, Option<String>, String)> = Vec::new();
+    for (slug, title, url, published) in news {
+        let Some(found) = funding::classify(&title) else { continue };
+        let announced = published.map(|p| iso_date(&p));
+        let same = |(s, f, _, _): &&mut (String, funding::Funding, Option<String>, String)| {
+            *s == slug
+                && match (&f.round, &found.round) {
+                    (Some(a), Some(b)) => a == b,
+                    (None, None) => f.amount == found.amount,
+                    _ => false,
+                }
+        };
+        match events.iter_mut().find(same) {
+            Some((_, event, date, first_url)) => {
+                if event.amount.is_none() {
+                    event.amount = found.amount;
+                    event.currency = found.currency;
+                }
+                if announced.is_some() && (date.is_none() || announced < *date) {
+                    *date = announced;
+                    *first_url = url;
+                }
+            }
+            None => events.push((slug, found, announced, url)),
+        }
+    }
+
+    let tx = conn.unchecked_transaction()?;
+    tx.execute("DELETE FROM funding_events", [])?;
+    {
+        let mut stmt = tx.prepare(
+            "INSERT INTO funding_events (company_slug, round, amount, currency, announced, url)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+        )?;
+        for (slug, event, announced, url) in &events {
+            stmt.execute(rusqlite::params![slug, event.round, event.amount, event.currency, announced, url])?;
+        }
+    }
+    tx.commit()?;
+    Ok(events.len())
+}
+
+/// "Mar 05, 2024" (the news date format) as "2024-03-05"; anything else as is.
+fn iso_date(date: &str) -> String {
+    crate::parser::dates::iso(date, &[]).unwrap_or_else(|| date.to_string())
+}
+
+// ── Meeting links ──
+
+#[derive(Serialize)]
+pub struct MeetingLinkRow {
+    pub company_slug: String,
+    pub url: String,
+    pub domain: String,
+    pub link_type: String, // "calendly", "cal.com", "motion", "hubspot", "other"
+    pub source_lines: Option<String>,
+}
+
+#[tracing::instrument(name = "db.save_meeting_links", skip_all, fields(rows = rows.len()))]
+pub fn save_meeting_links(conn: &Connection, rows: &[MeetingLinkRow]) -> Result<()> {
+    let tx = conn.unchecked_transaction()?;
+    {
+        let mut stmt = tx.prepare(
+            "INSERT INTO meeting_links (company_slug, url, domain, link_type, source_lines)
+             VALUES (?1, ?2, ?3, ?4, ?5)
+             ON CONFLICT(company_slug, url) DO UPDATE SET source_lines = excluded.source_lines",
+        )?;
+        for r in rows {
+            stmt.execute(rusqlite::params![
+                r.company_slug, r.url, r.domain, r.link_type, r.source_lines,
+            ])?;
+        }
+    }
+    tx.commit()?;
+    Ok(())
+}
+
+// ── Extraction quality ──
+
+#[derive(Serialize)]
+pub struct QualityRow {
+    pub company_slug: String,
+    pub check_name: String,
+    pub expected: i64,
+    pub actual: i64,
+    pub detail: Option<String>,
+}
+
+/// Replace the quality flags for `slugs` with `rows` (re-processing a clean
+/// page clears its old flags).
+pub fn save_quality(conn: &Connection, slugs: &[&str], rows: &[QualityRow]) -> Result<()> {
+    let tx = conn.unchecked_transaction()?;
+    {
+        let mut clear = tx.prepare("DELETE FROM extraction_quality WHERE company_slug = ?1")?;
+        for slug in slugs {
+            clear.execute([slug])?;
+        }
+        let mut stmt = tx.prepare(
+            "INSERT OR REPLACE INTO extraction_quality
+             (company_slug, check_name, expected, actual, detail)
+             VALUES (?1, ?2, ?3, ?4, ?5)",
+        )?;
+        for r in rows {
+            stmt.execute(rusqlite::params![
+                r.company_slug, r.check_name, r.expected, r.actual, r.detail,
+            ])?;
+        }
+    }
+    tx.commit()?;
+    Ok(())
+}
+
+/// Flagged companies, largest discrepancy first.
+pub fn fetch_quality_flags(conn: &Connection, limit: usize) -> Result<Vec<QualityRow>> {
+    Select::new("SELECT company_slug, check_name, expected, actual, detail FROM extraction_quality")
+    .filter(scope_predicate(conn, "company_slug")?)
+    .filter(current_slug_predicate("company_slug"))
+    .order_by("check_name, abs(expected - actual) DESC, company_slug")
+    .limit(Some(limit))
+    .query(conn, |r| {
+        Ok(QualityRow {
+            company_slug: r.get(0)?,
+            check_name: r.get(1)?,
+            expected: r.get(2)?,
+            actual: r.get(3)?,
+            detail: r.get(4)?,
+        })
+    })
+}
+
+// ── Review ──
+
+/// Open review items: quality flags plus companies matched to several
+/// partners (`ambiguous_partner`), minus those given a verdict since the
+/// flag was last raised.
+pub fn fetch_review_queue(conn: &Connection, limit: usize) -> Result<Vec<QualityRow>> {
+    Select::new(
+        "SELECT company_slug, check_name, expected, actual, detail
+         FROM (
+             SELECT company_slug, check_name, expected, actual, detail, checked_at
+             FROM extraction_quality
+             UNION ALL
+             SELECT company_slug, 'ambiguous_partner', 1, COUNT(*),
+                    group_concat(partner_slug || ' (' || match_method || ')', ', '), ''
+             FROM company_partners
+             GROUP BY company_slug
+             HAVING COUNT(*) > 1
+         ) q",
+    )
+    .filter(scope_predicate(conn, "company_slug")?)
+    .filter(
+        "NOT EXISTS (
+             SELECT 1 FROM review_annotations a
+             WHERE a.company_slug = q.company_slug AND a.check_name = q.check_name
+               AND a.action <> 'note' AND a.created_at >= q.checked_at
+         )",
+    )
+    .order_by("check_name, abs(expected - actual) DESC, company_slug")
+    .limit(Some(limit))
+    .query(conn, |r| {
+        Ok(QualityRow {
+            company_slug: r.get(0)?,
+            check_name: r.get(1)?,
+            expected: r.get(2)?,
+            actual: r.get(3)?,
+            detail: r.get(4)?,
+        })
+    })
+}
+
+/// Latest scraped markdown for a company plus the section spans recorded
+/// when it was processed (JSON kind → "start-end"). HTML scrapes come back
+/// rendered, as the lines those spans count.
+pub fn fetch_review_source(conn: &Connection, slug: &str) -> Result<Option<(String, Option<String>)>> {
+    let row: Option<(String, Option<String>, String, bool)> = conn
+        .query_row(
+            "SELECT page_markdown(pd.markdown, pd.blob_key), cs.spans, pd.url, pd.return_format IS 'html'
+             FROM page_data pd
+             LEFT JOIN company_sections cs ON cs.page_id = pd.id
+             WHERE pd.slug = ?1 AND (pd.markdown IS NOT NULL OR pd.blob_key IS NOT NULL)
+             ORDER BY pd.id DESC
+             LIMIT 1",
+            [slug],
+            |r| Ok((r.get(0)?, r.get(1)?, r.get(2)?, r.get(3)?)),
+        )
+        .optional()?;
+    Ok(row.map(|(content, spans, url, html)| {
+        let text = if html { crate::parser::html::to_text(&content, &url) } else { content };
+        (text, spans)
+    }))
+}
+
+pub struct Annotation<'a> {
+    pub company_slug: &'a str,
+    pub check_name: &'a str,
+    pub action: &'a str,
+    pub field: Option<&'a str>,
+    pub value: Option<&'a str>,
+    pub note: Option<&'a str>,
+}
+
+pub fn save_annotation(conn: &Connection, a: &Annotation) -> Result<()> {
+    conn.execute(
+        "INSERT INTO review_annotations (company_slug, check_name, action, field, value, note)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+        rusqlite::params![a.company_slug, a.check_name, a.action, a.field, a.value, a.note],
+    )?;
+    Ok(())
+}
+
+// ── Change feed ──
+
+/// Tables logged to `change_log`: (table, row key column, company slug column).
+const CHANGE_FEED_TABLES: &[(&str, &str, &str)] = &[
+    ("companies", "slug", "slug"),
+    ("founders", "id", "company_slug"),
+    ("company_jobs", "id", "company_slug"),
+];
+
+/// Bookkeeping columns, and derived ones that follow another column
+/// (`batch_ordinal`, `departed`, `company_age_years` ageing each year); an
+/// update touching only these isn't a change.
+const CHANGE_FEED_IGNORED: &[&str] = &["created_at", "source_lines", "batch_ordinal", "departed", "company_age_years"];
+
+/// (Re)create the triggers logging inserts, deletes and real updates to
+/// `change_log`. They are generated from the live columns, so this runs
+/// after the migrations add any. An update that changes the row key is
+/// logged as a delete of the old key and an insert of the new one.
+fn create_change_triggers(conn: &Connection) -> Result<()> {
+    use std::fmt::Write;
+
+    let mut sql = String::new();
+    for (table, key, slug) in CHANGE_FEED_TABLES {
+        let columns: Vec<String> = table_columns(conn, table)?
+            .into_iter()
+            .map(|c| c.name)
+            .filter(|c| !CHANGE_FEED_IGNORED.contains(&c.as_str()))
+            .collect();
+        let differs: Vec<String> = columns.iter().map(|c| format!("OLD.{c} IS NOT NEW.{c}")).collect();
+        let changed: Vec<String> = columns
+            .iter()
+            .map(|c| format!("SELECT '{c}' AS c WHERE OLD.{c} IS NOT NEW.{c}"))
+            .collect();
+        write!(
+            sql,
+            "DROP TRIGGER IF EXISTS {table}_log_insert;
+             CREATE TRIGGER {table}_log_insert AFTER INSERT ON {table} BEGIN
+                 INSERT INTO change_log (table_name, row_key, company_slug, op)
+                 VALUES ('{table}', NEW.{key}, NEW.{slug}, 'insert');
+             END;
+             DROP TRIGGER IF EXISTS {table}_log_delete;
+             CREATE TRIGGER {table}_log_delete AFTER DELETE ON {table} BEGIN
+                 INSERT INTO change_log (table_name, row_key, company_slug, op)
+                 VALUES ('{table}', OLD.{key}, OLD.{slug}, 'delete');
+             END;
+             DROP TRIGGER IF EXISTS {table}_log_update;
+             CREATE TRIGGER {table}_log_update AFTER UPDATE ON {table} WHEN {differs} BEGIN
+                 INSERT INTO change_log (table_name, row_key, company_slug, op)
+                 SELECT '{table}', OLD.{key}, OLD.{slug}, 'delete' WHERE OLD.{key} IS NOT NEW.{key};
+                 INSERT INTO change_log (table_name, row_key, company_slug, op, columns)
+                 SELECT '{table}', NEW.{key}, NEW.{slug},
+                        CASE WHEN OLD.{key} IS NOT NEW.{key} THEN 'insert' ELSE 'update' END,
+                        json_group_array(c)
+                 FROM ({changed});
+             END;
+             ",
+            differs = differs.join(" OR "),
+            changed = changed.join(" UNION ALL "),
+        )?;
+    }
+    conn.execute_batch(&sql)?;
+    Ok(())
+}
+
+#[derive(Debug, Serialize)]
+pub struct ChangeEvent {
+    pub id: i64,
+    pub table: String,
+    /// `slug` for companies, `id` for founders and jobs.
+    pub key: String,
+    pub company_slug: String,
+    /// "insert", "update" or "delete"; re-read the row by key for its values.
+    pub op: String,
+    /// Columns that differ, for updates.
+    pub columns: Option<Vec<String>>,
+    /// UTC, millisecond precision.
+    pub changed_at: String,
+}
+
+/// Change feed entries logged at or after `since`, oldest first. The bound
+/// is inclusive, so a consumer resuming from the last `changed_at` it saw
+/// gets that instant's events again and should skip ids it already has.
+pub fn changes(conn: &Connection, since: Timestamp) -> Result<Vec<ChangeEvent>> {
+    let sql = format!(
+        "SELECT id, table_name, row_key, company_slug, op, columns, changed_at
+         FROM change_log
+         WHERE changed_at >= ?1 AND {}
+         ORDER BY id",
+        scope_predicate(conn, "company_slug")?
+    );
+    let mut stmt = conn.prepare(&sql)?;
+    let rows = stmt
+        .query_map([since.to_sql_string()], |r| {
+            let columns: Option<String> = r.get(5)?;
+            Ok(ChangeEvent {
+                id: r.get(0)?,
+                table: r.get(1)?,
+                key: r.get(2)?,
+                company_slug: r.get(3)?,
+                op: r.get(4)?,
+                columns: columns.and_then(|c| serde_json::from_str(&c).ok()),
+                changed_at: r.get(6)?,
+            })
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(rows)
+}
+
+/// (slug, name, batch) of a company that first appeared in the change log.
+pub type NewCompany = (String, Option<String>, Option<String>);
+
+/// Companies first inserted at or after `since` (a stored UTC timestamp).
+pub fn fetch_new_companies(conn: &Connection, since: &str) -> Result<Vec<NewCompany>> {
+    Select::new("SELECT c.slug, c.name, c.batch FROM change_log l JOIN companies c ON c.slug = l.row_key")
+        .filter("l.table_name = 'companies' AND l.op = 'insert'")
+        .filter("c.renamed_to IS NULL")
+        .filter_with("l.changed_at >= ?", since.to_string())
+        .filter(scope_predicate(conn, "c.slug")?)
+        .order_by("c.slug")
+        .query(conn, |r| Ok((r.get(0)?, r.get(1)?, r.get(2)?)))
+}
+
+/// Replacements of company `field` logged to `field_changes` at or after
+/// `since`, as (slug, old value, new value), oldest first.
+pub fn fetch_field_changes(conn: &Connection, field: &str, since: &str) -> Result<Vec<(String, String, String)>> {
+    Select::new("SELECT company_slug, old_value, new_value FROM field_changes")
+        .filter_with("field = ?", field.to_string())
+        .filter_with("changed_at >= ?", since.to_string())
+        .filter(scope_predicate(conn, "company_slug")?)
+        .order_by("id")
+        .query(conn, |r| Ok((r.get(0)?, r.get(1)?, r.get(2)?)))
+}
+
+// ── Fixups ──
+
+pub struct Fixup {
+    pub id: i64,
+    pub table_name: String,
+    pub column_name: String,
+    pub pattern: String,
+    pub replacement: String,
+    pub note: Option<String>,
+    pub rows_changed: i64,
+    pub applied_at: Option<String>,
+}
+
+pub fn fetch_fixups(conn: &Connection) -> Result<Vec<Fixup>> {
+    let mut stmt = conn.prepare(
+        "SELECT id, table_name, column_name, pattern, replacement, note, rows_changed, applied_at
+         FROM fixups ORDER BY id",
+    )?;
+    let rows = stmt
+        .query_map([], |r| {
+            Ok(Fixup {
+                id: r.get(0)?,
+                table_name: r.get(1)?,
+                column_name: r.get(2)?,
+                pattern: r.get(3)?,
+                replacement: r.get(4)?,
+                note: r.get(5)?,
+                rows_changed: r.get(6)?,
+                applied_at: r.get(7)?,
+            })
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(rows)
+}
+
+/// Fail unless `table.column` exists and holds text; fixup targets are
+/// spliced into SQL, so this is also what keeps them identifiers.
+pub fn check_text_column(conn: &Connection, table: &str, column: &str) -> Result<()> {
+    let cols = table_columns(conn, table)?;
+    if cols.is_empty() {
+        anyhow::bail!("no table named {:?}", table);
+    }
+    match cols.iter().find(|c| c.name == column) {
+        Some(c) if c.decl_type.eq_ignore_ascii_case("TEXT") => Ok(()),
+        Some(c) => anyhow::bail!("{}.{} is {}, not TEXT", table, column, c.decl_type),
+        None => anyhow::bail!("{} has no column {:?}", table, column),
+    }
+}
+
+/// Insert a rule, or return the id of the identical one already stored.
+pub fn add_fixup(
+    conn: &Connection,
+    table: &str,
+    column: &str,
+    pattern: &str,
+    replacement: &str,
+    note: Option<&str>,
+) -> Result<i64> {
+    check_text_column(conn, table, column)?;
+    conn.execute(
+        "INSERT INTO fixups (table_name, column_name, pattern, replacement, note)
+         VALUES (?1, ?2, ?3, ?4, ?5)
+         ON CONFLICT(table_name, column_name, pattern) DO NOTHING",
+        rusqlite::params![table, column, pattern, replacement, note],
+    )?;
+    Ok(conn.query_row(
+        "SELECT id FROM fixups WHERE table_name = ?1 AND column_name = ?2 AND pattern = ?3",
+        [table, column, pattern],
+        |r| r.get(0),
+    )?)
+}
+
+pub fn remove_fixup(conn: &Connection, id: i64) -> Result<bool> {
+    Ok(conn.execute("DELETE FROM fixups WHERE id = ?1", [id])? > 0)
+}
+
+/// Rewrite every non-null value of `table.column` through `f` (None leaves
+/// the value alone). Returns (rowid, before, after) per changed row.
+pub fn rewrite_text_column(
+    conn: &Connection,
+    table: &str,
+    column: &str,
+    mut f: impl FnMut(&str) -> Option<String>,
+) -> Result<Vec<(i64, String, String)>> {
+    check_text_column(conn, table, column)?;
+    let mut changed = Vec::new();
+    {
+        let mut stmt = conn.prepare(&format!(
+            "SELECT rowid, {col} FROM {table} WHERE {col} IS NOT NULL ORDER BY rowid",
+            col = column,
+            table = table
+        ))?;
+        let mut rows = stmt.query([])?;
+        while let Some(row) = rows.next()? {
+            let before: String = row.get(1)?;
+            if let Some(after) = f(&before).filter(|a| *a != before) {
+                changed.push((row.get(0)?, before, after));
+            }
+        }
+    }
+    let mut update = conn.prepare(&format!("UPDATE {} SET {} = ?1 WHERE rowid = ?2", table, column))?;
+    for (rowid, _, after) in &changed {
+        update.execute(rusqlite::params![after, rowid])?;
+    }
+    Ok(changed)
+}
+
+pub fn record_fixup_run(conn: &Connection, id: i64, rows_changed: usize) -> Result<()> {
+    conn.execute(
+        "UPDATE fixups SET rows_changed = rows_changed + ?2, applied_at = datetime('now') WHERE id = ?1",
+        rusqlite::params![id, rows_changed as i64],
+    )?;
+    Ok(())
+}
+
+// ── Q&A ──
+
+#[derive(Serialize)]
+pub struct QaRow {
+    pub company_slug: String,
+    pub question: String,
+    pub answer: String,
+    pub source_lines: Option<String>,
+}
+
+pub fn save_qa(conn: &Connection, rows: &[QaRow]) -> Result<()> {
+    let tx = conn.unchecked_transaction()?;
+    {
+        let mut stmt = tx.prepare(
+            "INSERT INTO company_qa (company_slug, question, answer, source_lines)
+             VALUES (?1, ?2, ?3, ?4)
+             ON CONFLICT(company_slug, question) DO UPDATE SET source_lines = excluded.source_lines",
+        )?;
+        for r in rows {
+            stmt.execute(rusqlite::params![r.company_slug, r.question, r.answer, r.source_lines])?;
+        }
+    }
+    tx.commit()?;
+    Ok(())
+}
+
+// ── Custom extractors ──
+
+#[derive(Debug, Serialize)]
+pub struct CustomRow {
+    pub extractor: &'static str,
+    pub company_slug: String,
+    pub position: usize,
+    pub data: serde_json::Value,
+    pub source_lines: Option<String>,
+}
+
+/// Replace the custom rows of `slugs` with `rows`, across all extractors,
+/// so rows an extractor no longer finds don't linger.
+pub fn save_custom_rows(conn: &Connection, slugs: &[&str], rows: &[CustomRow]) -> Result<()> {
+    let tx = conn.unchecked_transaction()?;
+    {
+        let mut clear = tx.prepare("DELETE FROM custom_rows WHERE company_slug = ?1")?;
+        for slug in slugs {
+            clear.execute([slug])?;
+        }
+        let mut stmt = tx.prepare(
+            "INSERT OR REPLACE INTO custom_rows (extractor, company_slug, position, data, source_lines)
+             VALUES (?1, ?2, ?3, ?4, ?5)",
+        )?;
+        for r in rows {
+            stmt.execute(rusqlite::params![r.extractor, r.company_slug, r.position, r.data.to_string(), r.source_lines])?;
+        }
+    }
+    tx.commit()?;
+    Ok(())
+}
+
+// ── Founder experience ──
+
+#[derive(Serialize)]
+pub struct ExperienceRow {
+    pub company_slug: String,
+    /// `name_key` of the founder whose bio named the org; stored as `founder_id`.
+    pub founder_key: String,
+    pub kind: &'static str,
+    pub org: String,
+}
+
+/// Replace the experience rows of `slugs` with `rows`. Pass only companies
+/// whose page listed founders, so a degraded page keeps the stored rows.
+/// Founders must be saved first, for their ids.
+pub fn save_experience(conn: &Connection, slugs: &[&str], rows: &[ExperienceRow]) -> Result<()> {
+    let tx = conn.unchecked_transaction()?;
+    {
+        let mut clear = tx.prepare("DELETE FROM founder_experience WHERE company_slug = ?1")?;
+        for slug in slugs {
+            clear.execute([slug])?;
+        }
+        let mut stmt = tx.prepare(
+            "INSERT OR IGNORE INTO founder_experience (founder_id, company_slug, kind, org)
+             SELECT id, ?1, ?3, ?4 FROM founders
+             WHERE company_slug = ?1 AND name_key = ?2
+             ORDER BY id LIMIT 1",
+        )?;
+        for r in rows {
+            stmt.execute(rusqlite::params![r.company_slug, r.founder_key, r.kind, r.org])?;
+        }
+    }
+    tx.commit()?;
+    Ok(())
+}
+
+// ── Locations ──
+
+#[derive(Serialize)]
+pub struct LocationRow {
+    pub company_slug: String,
+    pub location: String,
+    /// Order among the footer's Location fields; the primary (0) lives on
+    /// `companies.location`.
+    pub position: i32,
+}
+
+/// Replace the secondary locations of `slugs` with `rows`. Pass only the
+/// companies whose page had a primary location, so a degraded footer keeps
+/// the stored rows (like the field-by-field company merge).
+pub fn save_locations(conn: &Connection, slugs: &[&str], rows: &[LocationRow]) -> Result<()> {
+    let tx = conn.unchecked_transaction()?;
+    {
+        let mut clear = tx.prepare("DELETE FROM company_locations WHERE company_slug = ?1")?;
+        for slug in slugs {
+            clear.execute([slug])?;
+        }
+        let mut stmt = tx.prepare(
+            "INSERT OR REPLACE INTO company_locations (company_slug, location, position)
+             VALUES (?1, ?2, ?3)",
+        )?;
+        for r in rows {
+            stmt.execute(rusqlite::params![r.company_slug, r.location, r.position])?;
+        }
+    }
+    tx.commit()?;
+    Ok(())
+}
+
+// ── References ──
+
+#[derive(Serialize)]
+pub struct ReferenceRow {
+    pub from_slug: String,
+    pub to_slug: String,
+    pub section: String,
+    pub source_lines: Option<String>,
+}
+
+/// Replace the outgoing references of `slugs` with `rows`.
+pub fn save_references(conn: &Connection, slugs: &[&str], rows: &[ReferenceRow]) -> Result<()> {
+    let tx = conn.unchecked_transaction()?;
+    {
+        let mut clear = tx.prepare("DELETE FROM company_references WHERE from_slug = ?1")?;
+        for slug in slugs {
+            clear.execute([slug])?;
+        }
+        let mut stmt = tx.prepare(
+            "INSERT OR IGNORE INTO company_references (from_slug, to_slug, section, source_lines)
+             VALUES (?1, ?2, ?3, ?4)",
+        )?;
+        for r in rows {
+            stmt.execute(rusqlite::params![r.from_slug, r.to_slug, r.section, r.source_lines])?;
+        }
+    }
+    tx.commit()?;
+    Ok(())
+}
+
+/// A `company_references` edge with what is known about both ends.
+pub struct ReferenceEdge {
+    pub from_slug: String,
+    pub from_name: Option<String>,
+    pub from_batch: Option<String>,
+    /// Followed through `companies.renamed_to`.
+    pub to_slug: String,
+    /// `None` until the target page is processed.
+    pub to_name: Option<String>,
+    pub to_batch: Option<String>,
+    pub section: String,
+}
+
+/// Reference edges from companies in the slug scope, ordered by source and
+/// target. `known_only` drops edges to companies not in `companies`.
+pub fn fetch_reference_edges(conn: &Connection, known_only: bool) -> Result<Vec<ReferenceEdge>> {
+    let mut select = Select::new(
+        "SELECT r.from_slug, f.name, f.batch, COALESCE(old.renamed_to, r.to_slug), t.name, t.batch, r.section
+         FROM company_references r
+         JOIN companies f ON f.slug = r.from_slug
+         LEFT JOIN companies old ON old.slug = r.to_slug
+         LEFT JOIN companies t ON t.slug = COALESCE(old.renamed_to, r.to_slug)",
+    )
+    .filter("COALESCE(old.renamed_to, r.to_slug) <> r.from_slug")
+    .filter(scope_predicate(conn, "r.from_slug")?);
+    if known_only {
+        select = select.filter("t.slug IS NOT NULL");
+    }
+    select.order_by("r.from_slug, 4").query(conn, |r| {
+        Ok(ReferenceEdge {
+            from_slug: r.get(0)?,
+            from_name: r.get(1)?,
+            from_batch: r.get(2)?,
+            to_slug: r.get(3)?,
+            to_name: r.get(4)?,
+            to_batch: r.get(5)?,
+            section: r.get(6)?,
+        })
+    })
+}
+
+// ── Job details ──
+
+pub struct JobDetailRow {
+    pub job_id: i64,
+    pub markdown: String,
+    pub content_kind: &'static str,
+    pub responsibilities: Option<String>,
+    pub requirements: Option<String>,
+    pub nice_to_have: Option<String>,
+    pub benefits_text: Option<String>,
+    pub summary: Option<String>,
+    pub tech_stack: Vec<&'static str>,
+    pub benefits: Vec<&'static str>,
+}
+
+/// (job id, job page URL) for listed jobs whose detail page hasn't been scraped.
+pub fn fetch_jobs_without_details(conn: &Connection, limit: Option<usize>) -> Result<Vec<(i64, String)>> {
+    Select::new("SELECT id, url FROM company_jobs")
+        .filter("NOT EXISTS (SELECT 1 FROM job_details d WHERE d.job_id = company_jobs.id)")
+        .filter(scope_predicate(conn, "company_slug")?)
+        .order_by("id")
+        .limit(limit)
+        .query(conn, |row| Ok((row.get(0)?, row.get(1)?)))
+}
+
+/// Store scraped job pages and copy the chips of real postings onto
+/// `company_jobs` as JSON arrays. Blank and 404 pages are kept with their
+/// kind so they aren't fetched again.
+#[cfg_attr(not(feature = "network"), allow(dead_code))]
+pub fn save_job_details(conn: &Connection, rows: &[JobDetailRow]) -> Result<()> {
+    let tx = conn.unchecked_transaction()?;
+    {
+        let mut d_stmt = tx.prepare(
+            "INSERT OR REPLACE INTO job_details
+             (job_id, markdown, responsibilities, requirements, nice_to_have, benefits_text, summary, content_kind)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+        )?;
+        let mut j_stmt = tx.prepare("UPDATE company_jobs SET tech_stack = ?2, benefits = ?3 WHERE id = ?1")?;
+        for r in rows {
+            d_stmt.execute(rusqlite::params![
+                r.job_id, r.markdown, r.responsibilities, r.requirements, r.nice_to_have,
+                r.benefits_text, r.summary, r.content_kind,
+            ])?;
+            if r.content_kind != crate::validate::JOB {
+                continue;
+            }
+            j_stmt.execute(rusqlite::params![
+                r.job_id,
+                serde_json::to_string(&r.tech_stack)?,
+                serde_json::to_string(&r.benefits)?,
+            ])?;
+        }
+    }
+    tx.commit()?;
+    Ok(())
+}
+
+// ── Link health ──
+
+pub struct LinkCheckRow {
+    pub url: String,
+    pub domain: String,
+    pub status: Option<i32>,
+    pub error: Option<String>,
+    pub latency_ms: i64,
+}
+
+/// Distinct unchecked external links (url, domain). Typed social links are
+/// skipped: those platforms block bots and say nothing about company health.
+pub fn fetch_unchecked_links(
+    conn: &Connection,
+    limit: Option<usize>,
+) -> Result<Vec<(String, String)>> {
+    Select::new("SELECT cl.url, MIN(cl.domain) FROM company_links cl")
+        .filter("cl.link_type IS NULL")
+        .filter("NOT EXISTS (SELECT 1 FROM link_checks lc WHERE lc.url = cl.url)")
+        .filter(scope_predicate(conn, "cl.company_slug")?)
+        .group_by("cl.url")
+        .order_by("MIN(cl.id)")
+        .limit(limit)
+        .query(conn, |row| Ok((row.get(0)?, row.get(1)?)))
+}
+
+/// Distinct booking URLs from `meeting_links` not yet in `link_checks`.
+pub fn fetch_unchecked_meeting_links(
+    conn: &Connection,
+    limit: Option<usize>,
+) -> Result<Vec<(String, String)>> {
+    Select::new("SELECT m.url, MIN(m.domain) FROM meeting_links m")
+        .filter("NOT EXISTS (SELECT 1 FROM link_checks lc WHERE lc.url = m.url)")
+        .filter(scope_predicate(conn, "m.company_slug")?)
+        .group_by("m.url")
+        .order_by("MIN(m.id)")
+        .limit(limit)
+        .query(conn, |row| Ok((row.get(0)?, row.get(1)?)))
+}
+
+/// A meeting link with its company's batch and latest liveness check.
+pub struct MeetingLinkStatus {
+    pub company_slug: String,
+    pub batch: Option<String>,
+    pub link_type: String,
+    /// None until `meetings report --check` (or `check-links`) has seen the URL.
+    pub live: Option<bool>,
+}
+
+pub fn fetch_meeting_link_statuses(conn: &Connection) -> Result<Vec<MeetingLinkStatus>> {
+    let sql = format!(
+        "SELECT m.company_slug, c.batch, m.link_type,
+                CASE WHEN lc.url IS NULL THEN NULL
+                     ELSE COALESCE(lc.status BETWEEN 200 AND 399, 0) END
+         FROM meeting_links m
+         JOIN companies c ON c.slug = m.company_slug
+         LEFT JOIN link_checks lc ON lc.url = m.url
+         WHERE c.renamed_to IS NULL AND {}
+         ORDER BY m.id",
+        scope_predicate(conn, "m.company_slug")?
+    );
+    let mut stmt = conn.prepare(&sql)?;
+    let rows = stmt
+        .query_map([], |r| {
+            Ok(MeetingLinkStatus {
+                company_slug: r.get(0)?,
+                batch: r.get(1)?,
+                link_type: r.get(2)?,
+                live: r.get(3)?,
+            })
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(rows)
+}
+
+/// Companies per batch (None for companies without one), within the slug scope.
+pub fn fetch_batch_sizes(conn: &Connection) -> Result<Vec<(Option<String>, usize)>> {
+    let sql = format!(
+        "SELECT batch, COUNT(*) FROM companies WHERE renamed_to IS NULL AND {} GROUP BY batch",
+        scope_predicate(conn, "slug")?
+    );
+    let mut stmt = conn.prepare(&sql)?;
+    let rows = stmt
+        .query_map([], |r| Ok((r.get(0)?, r.get::<_, i64>(1)? as usize)))?
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(rows)
+}
+
+pub fn save_link_checks(conn: &Connection, rows: &[LinkCheckRow]) -> Result<()> {
+    let tx = conn.unchecked_transaction()?;
+    {
+        let mut stmt = tx.prepare(
+            "INSERT OR REPLACE INTO link_checks (url, domain, status, error, latency_ms)
+             VALUES (?1, ?2, ?3, ?4, ?5)",
+        )?;
+        for r in rows {
+            stmt.execute(rusqlite::params![r.url, r.domain, r.status, r.error, r.latency_ms])?;
+        }
+    }
+    tx.commit()?;
+    Ok(())
+}
+
+// ── Social enrichment ──
+
+/// Public profile stats for one social handle (`founder_social_stats`).
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct SocialStats {
+    pub name: Option<String>,
+    pub bio: Option<String>,
+    pub followers: Option<i64>,
+    pub following: Option<i64>,
+    pub posts: Option<i64>,
+}
+
+/// Distinct founder Twitter/X profile URLs in scope.
+pub fn fetch_founder_twitter(conn: &Connection) -> Result<Vec<String>> {
+    Select::new("SELECT DISTINCT twitter FROM founders")
+        .filter("twitter IS NOT NULL AND twitter != ''")
+        .filter(scope_predicate(conn, "company_slug")?)
+        .order_by("twitter")
+        .query(conn, |r| r.get(0))
+}
+
+/// Handles fetched within the last `max_age_days`.
+pub fn fetch_fresh_social_handles(conn: &Connection, max_age_days: u32) -> Result<std::collections::HashSet<String>> {
+    let mut stmt = conn.prepare(
+        "SELECT handle FROM founder_social_stats WHERE fetched_at >= datetime('now', '-' || ?1 || ' days')",
+    )?;
+    let rows = stmt.query_map([max_age_days], |r| r.get(0))?;
+    Ok(rows.collect::<Result<_, _>>()?)
+}
+
+pub fn save_social_stats(conn: &Connection, handle: &str, provider: &str, s: &SocialStats) -> Result<()> {
+    conn.execute(
+        "INSERT OR REPLACE INTO founder_social_stats (handle, provider, name, bio, followers, following, posts)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+        rusqlite::params![handle, provider, s.name, s.bio, s.followers, s.following, s.posts],
+    )?;
+    Ok(())
+}
+
+struct DomainAggregate {
+    domain: String,
+    checks: i64,
+    ok: i64,
+    mean_latency_ms: Option<f64>,
+    common_status: Option<i32>,
+}
+
+/// Rebuild `domain_health` from all `link_checks`. Returns the domain count.
+pub fn refresh_domain_health(conn: &Connection) -> Result<usize> {
+    let aggregates: Vec<DomainAggregate> = {
+        let mut stmt = conn.prepare(
+            "SELECT l.domain, COUNT(*),
+                    COALESCE(SUM(l.status BETWEEN 200 AND 399), 0),
+                    AVG(l.latency_ms),
+                    (SELECT l2.status FROM link_checks l2 WHERE l2.domain = l.domain
+                     GROUP BY l2.status ORDER BY COUNT(*) DESC, l2.status LIMIT 1)
+             FROM link_checks l
+             GROUP BY l.domain",
+        )?;
+        let rows = stmt.query_map([], |r| {
+            Ok(DomainAggregate {
+                domain: r.get(0)?,
+                checks: r.get(1)?,
+                ok: r.get(2)?,
+                mean_latency_ms: r.get(3)?,
+                common_status: r.get(4)?,
+            })
+        })?;
+        rows.collect::<Result<Vec<_>, _>>()?
+    };
+
+    let tx = conn.unchecked_transaction()?;
+    tx.execute("DELETE FROM domain_health", [])?;
+    {
+        let mut stmt = tx.prepare(
+            "INSERT INTO domain_health
+             (domain, base_domain, checks, ok, success_rate, mean_latency_ms, common_status)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+        )?;
+        for a in &aggregates {
+            let base = crate::parser::extract::links::base_domain(&a.domain);
+            let rate = a.ok as f64 / a.checks.max(1) as f64;
+            stmt.execute(rusqlite::params![
+                a.domain, base, a.checks, a.ok, rate, a.mean_latency_ms, a.common_status
+            ])?;
+        }
+    }
+    tx.commit()?;
+    Ok(aggregates.len())
+}
+
+pub struct DomainDeathRow {
+    pub base_domain: String,
+    pub companies: usize,
+    pub inactive: usize,
+    pub checks: i64,
+    pub success_rate: f64,
+    pub mean_latency_ms: Option<f64>,
+}
+
+/// Registrable domains ranked by the share of linking companies that are Inactive.
+pub fn fetch_domain_death_report(
+    conn: &Connection,
+    min_companies: usize,
+    limit: usize,
+) -> Result<Vec<DomainDeathRow>> {
+    let mut stmt = conn.prepare(
+        "WITH h AS (
+             SELECT base_domain, SUM(checks) AS checks, SUM(ok) AS ok,
+                    SUM(mean_latency_ms * checks) / SUM(checks) AS latency
+             FROM domain_health GROUP BY base_domain
+         ), c AS (
+             SELECT dh.base_domain,
+                    COUNT(DISTINCT cl.company_slug) AS companies,
+                    COUNT(DISTINCT CASE WHEN co.status = 'Inactive' THEN cl.company_slug END) AS inactive
+             FROM company_links cl
+             JOIN domain_health dh ON dh.domain = cl.domain
+             JOIN companies co ON co.slug = cl.company_slug
+             WHERE co.renamed_to IS NULL
+             GROUP BY dh.base_domain
+         )
+         SELECT c.base_domain, c.companies, c.inactive, h.checks,
+                h.ok * 1.0 / h.checks, h.latency
+         FROM c JOIN h ON h.base_domain = c.base_domain
+         WHERE c.companies >= ?1
+         ORDER BY c.inactive * 1.0 / c.companies DESC, c.companies DESC
+         LIMIT ?2",
+    )?;
+    let rows = stmt
+        .query_map(rusqlite::params![min_companies, limit], |row| {
+            Ok(DomainDeathRow {
+                base_domain: row.get(0)?,
+                companies: row.get(1)?,
+                inactive: row.get(2)?,
+                checks: row.get(3)?,
+                success_rate: row.get(4)?,
+                mean_latency_ms: row.get(5)?,
+            })
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(rows)
+}
+
+// ── Partners ──
+
+pub struct PartnerRow {
+    pub slug: String,
+    pub url: String,
+    pub name: String,
+    pub title: Option<String>,
+    pub bio: Option<String>,
+}
+
+pub struct CompanyPartnerRow {
+    pub company_slug: String,
+    pub partner_slug: String,
+    pub match_method: String, // "url" or "name"
+}
+
+pub fn save_partners(conn: &Connection, rows: &[PartnerRow]) -> Result<usize> {
+    let tx = conn.unchecked_transaction()?;
+    let mut count = 0;
+    {
+        let mut stmt = tx.prepare(
+            "INSERT OR REPLACE INTO partners (slug, url, name, title, bio)
+             VALUES (?1, ?2, ?3, ?4, ?5)",
+        )?;
+        for r in rows {
+            count += stmt.execute(rusqlite::params![r.slug, r.url, r.name, r.title, r.bio])?;
+        }
+    }
+    tx.commit()?;
+    Ok(count)
+}
+
+pub fn fetch_partners(conn: &Connection) -> Result<Vec<PartnerRow>> {
+    let mut stmt = conn.prepare("SELECT slug, url, name, title, bio FROM partners")?;
+    let rows = stmt
+        .query_map([], |row| {
+            Ok(PartnerRow {
+                slug: row.get(0)?,
+                url: row.get(1)?,
+                name: row.get(2)?,
+                title: row.get(3)?,
+                bio: row.get(4)?,
+            })
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(rows)
+}
+
+pub fn save_company_partners(conn: &Connection, rows: &[CompanyPartnerRow]) -> Result<usize> {
+    let tx = conn.unchecked_transaction()?;
+    let mut count = 0;
+    {
+        let mut stmt = tx.prepare(
+            "INSERT OR IGNORE INTO company_partners (company_slug, partner_slug, match_method)
+             VALUES (?1, ?2, ?3)",
+        )?;
+        for r in rows {
+            count += stmt.execute(rusqlite::params![
+                r.company_slug, r.partner_slug, r.match_method
+            ])?;
+        }
+    }
+    tx.commit()?;
+    Ok(count)
+}
+
+/// Fetch company slugs + their raw markdown for partner URL matching.
+pub fn fetch_scraped_markdown(conn: &Connection) -> Result<Vec<(String, String)>> {
+    let mut stmt = conn.prepare(
+        "SELECT pd.slug, page_markdown(pd.markdown, pd.blob_key)
+         FROM page_data pd
+         WHERE pd.markdown IS NOT NULL OR pd.blob_key IS NOT NULL",
+    )?;
+    let rows = stmt
+        .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(rows)
+}
+
+/// Fetch (company_slug, partner_slug) for companies whose footer linked a
+/// partner profile: the primary partner's, else the group partner's.
+pub fn fetch_partner_slugs(conn: &Connection) -> Result<Vec<(String, String)>> {
+    let mut stmt = conn.prepare(
+        "SELECT slug, COALESCE(primary_partner_slug, group_partner_slug) FROM companies
+         WHERE COALESCE(primary_partner_slug, group_partner_slug) IS NOT NULL",
+    )?;
+    let rows = stmt
+        .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(rows)
+}
+
+/// Fetch companies with a partner name (primary, else group) but no entry
+/// in company_partners yet.
+pub fn fetch_unmatched_partners(conn: &Connection) -> Result<Vec<(String, String)>> {
+    let mut stmt = conn.prepare(
+        "SELECT c.slug, COALESCE(NULLIF(c.primary_partner, ''), c.group_partner)
+         FROM companies c
+         WHERE COALESCE(NULLIF(c.primary_partner, ''), NULLIF(c.group_partner, '')) IS NOT NULL
+           AND NOT EXISTS (
+               SELECT 1 FROM company_partners cp WHERE cp.company_slug = c.slug
+           )",
+    )?;
+    let rows = stmt
+        .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(rows)
+}
+
+// ── Tag catalog ──
+
+pub struct TagCatalogRow {
+    pub tag: String,
+    pub url: String,
+    pub description: Option<String>,
+}
+
+pub fn save_tag_catalog(conn: &Connection, rows: &[TagCatalogRow]) -> Result<usize> {
+    let tx = conn.unchecked_transaction()?;
+    let mut count = 0;
+    {
+        let mut stmt = tx.prepare(
+            "INSERT INTO tag_catalog (tag_key, tag, url, description) VALUES (?1, ?2, ?3, ?4)
+             ON CONFLICT(tag_key) DO UPDATE SET
+                 tag = excluded.tag, url = excluded.url,
+                 description = COALESCE(excluded.description, tag_catalog.description),
+                 crawled_at = datetime('now')",
+        )?;
+        for r in rows {
+            let key = crate::parser::extract::tags::tag_key(&r.tag);
+            count += stmt.execute(rusqlite::params![key, r.tag, r.url, r.description])?;
+        }
+    }
+    tx.commit()?;
+    Ok(count)
+}
+
+/// tag_key → canonical tag.
+pub fn fetch_tag_catalog(conn: &Connection) -> Result<std::collections::HashMap<String, String>> {
+    let mut stmt = conn.prepare("SELECT tag_key, tag FROM tag_catalog")?;
+    let rows = stmt
+        .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?
+        .collect::<Result<_, _>>()?;
+    Ok(rows)
+}
+
+/// Rewrite `companies.tags` already stored with catalog spellings. Returns
+/// the number of companies changed.
+pub fn normalize_company_tags(conn: &Connection, catalog: &std::collections::HashMap<String, String>) -> Result<usize> {
+    let tx = conn.unchecked_transaction()?;
+    let mut count = 0;
+    {
+        let mut select = tx.prepare("SELECT slug, tags FROM companies WHERE tags IS NOT NULL")?;
+        let rows = select
+            .query_map([], |row| Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?)))?
+            .collect::<Result<Vec<_>, _>>()?;
+        let mut update = tx.prepare("UPDATE companies SET tags = ?2 WHERE slug = ?1")?;
+        for (slug, tags) in rows {
+            let normalized = crate::parser::extract::tags::normalize_list(&tags, catalog);
+            if normalized != tags {
+                count += update.execute(rusqlite::params![slug, normalized])?;
+            }
+        }
+    }
+    tx.commit()?;
+    Ok(count)
+}
+
+// ── Overview ──
+
+pub struct OverviewRow {
+    pub slug: String,
+    pub name: String,
+    pub batch: String,
+    pub status: Option<CompanyStatus>,
+    pub team_size: Option<i32>,
+    pub location: String,
+    pub primary_partner: String,
+    pub tags: String,
+    pub job_count: i32,
+    pub quality_score: Option<i32>,
+    /// Founding year flagged `founded_year_inconsistent`; left out of
+    /// `overview --group-by` and `report compare` by default.
+    pub founded_year_flagged: bool,
+}
+
+/// Companies matching the filters, newest batch first, or best-scored
+/// first with `by_quality`. `min_quality` drops rows scored below it, and
+/// rows not scored yet.
+pub fn fetch_overview(
+    conn: &Connection,
+    statuses: &[CompanyStatus],
+    batches: &[Batch],
+    min_quality: Option<i32>,
+    by_quality: bool,
+    limit: usize,
+) -> Result<Vec<OverviewRow>> {
+    let mut select = Select::new(&format!(
+        "SELECT slug, COALESCE(name,''), COALESCE(batch,''), status,
+                team_size, COALESCE(location,''), COALESCE(primary_partner, group_partner, ''),
+                COALESCE(tags,''), job_count_extracted, quality_score, {}
+         FROM companies",
+        founded_year_flagged("companies.slug")
+    ))
+    .any_of("status", statuses.iter().map(|s| s.as_str().to_string()))
+    .any_of("batch", batches.iter().map(|b| b.to_string()))
+    .filter(scope_predicate(conn, "slug")?)
+    // Renamed slugs are listed under their current slug
+    .filter("renamed_to IS NULL");
+    if let Some(min) = min_quality {
+        select = select.filter_with("quality_score >= ?", min);
+    }
+    select
+        .order_by(if by_quality { "quality_score IS NULL, quality_score DESC, slug" } else { "batch_ordinal DESC, slug" })
+        .limit(Some(limit))
+        .query(conn, |row| {
+            Ok(OverviewRow {
+                slug: row.get(0)?,
+                name: row.get(1)?,
+                batch: row.get(2)?,
+                status: row.get(3)?,
+                team_size: row.get(4)?,
+                location: row.get(5)?,
+                primary_partner: row.get(6)?,
+                tags: row.get(7)?,
+                job_count: row.get(8)?,
+                quality_score: row.get(9)?,
+                founded_year_flagged: row.get(10)?,
+            })
+        })
+}
+
+// ── Quality score ──
+
+/// `parser::extract::quality::score` inputs of each company, from the
+/// stored row, its founders and its description section.
+const SCORE_INPUTS: &str = "SELECT c.slug, c.batch, c.quality_score,
+        c.name IS NOT NULL, c.batch IS NOT NULL, c.status IS NOT NULL,
+        (SELECT COUNT(*) FROM founders f WHERE f.company_slug = c.slug),
+        (c.linkedin IS NOT NULL) + (c.twitter IS NOT NULL) + (c.facebook IS NOT NULL)
+            + (c.crunchbase IS NOT NULL) + (c.github IS NOT NULL),
+        COALESCE(LENGTH(cs.description), 0)
+ FROM companies c LEFT JOIN company_sections cs ON cs.slug = c.slug";
+
+pub struct ScoredCompany {
+    pub slug: String,
+    pub batch: Option<String>,
+    /// As stored; None until the company is scored.
+    pub quality_score: Option<i32>,
+    pub inputs: ScoreInputs,
+}
+
+fn scored_company(r: &rusqlite::Row) -> rusqlite::Result<ScoredCompany> {
+    Ok(ScoredCompany {
+        slug: r.get(0)?,
+        batch: r.get(1)?,
+        quality_score: r.get(2)?,
+        inputs: ScoreInputs {
+            name: r.get(3)?,
+            batch: r.get(4)?,
+            status: r.get(5)?,
+            founders: r.get(6)?,
+            socials: r.get(7)?,
+            description_chars: r.get(8)?,
+        },
+    })
+}
+
+/// Recompute `companies.quality_score` for `slugs` from what is stored
+/// now, after their rows, founders or sections changed.
+pub fn refresh_quality_scores(conn: &Connection, slugs: &[&str]) -> Result<usize> {
+    let mut fetch = conn.prepare_cached(&format!("{} WHERE c.slug = ?1", SCORE_INPUTS))?;
+    let mut update = conn.prepare_cached("UPDATE companies SET quality_score = ?2 WHERE slug = ?1")?;
+    let mut n = 0;
+    for slug in slugs {
+        if let Some(c) = fetch.query_row([slug], scored_company).optional()? {
+            n += update.execute(rusqlite::params![slug, quality::score(&c.inputs)])?;
+        }
+    }
+    Ok(n)
+}
+
+/// Companies in scope with their score inputs, for `report coverage`.
+pub fn fetch_scored_companies(conn: &Connection) -> Result<Vec<ScoredCompany>> {
+    Select::new(SCORE_INPUTS)
+        .filter(scope_predicate(conn, "c.slug")?)
+        .filter("c.renamed_to IS NULL")
+        .order_by("c.slug")
+        .query(conn, scored_company)
+}
+
+// ── Integrity ──
+
+/// (violation, child table, parent table, child column, parent column)
+const ORPHAN_CHECKS: &[(&str, &str, &str, &str, &str)] = &[
+    ("page_data without page", "page_data", "pages", "page_id", "id"),
+    ("sections without page_data", "company_sections", "page_data", "page_id", "id"),
+    ("experience without company", "founder_experience", "companies", "company_slug", "slug"),
+    ("founders without company", "founders", "companies", "company_slug", "slug"),
+    ("experience without founder", "founder_experience", "founders", "founder_id", "id"),
+    ("news without company", "news", "companies", "company_slug", "slug"),
+    ("global articles without news", "news_global", "news", "article_id", "article_id"),
+    ("funding events without company", "funding_events", "companies", "company_slug", "slug"),
+    ("jobs without company", "company_jobs", "companies", "company_slug", "slug"),
+    ("job details without job", "job_details", "company_jobs", "job_id", "id"),
+    ("links without company", "company_links", "companies", "company_slug", "slug"),
+    ("meeting links without company", "meeting_links", "companies", "company_slug", "slug"),
+    ("Q&A without company", "company_qa", "companies", "company_slug", "slug"),
+    ("locations without company", "company_locations", "companies", "company_slug", "slug"),
+    ("references without company", "company_references", "companies", "from_slug", "slug"),
+    ("custom rows without company", "custom_rows", "companies", "company_slug", "slug"),
+    ("quality flags without company", "extraction_quality", "companies", "company_slug", "slug"),
+    ("field changes without company", "field_changes", "companies", "company_slug", "slug"),
+    ("review annotations without company", "review_annotations", "companies", "company_slug", "slug"),
+    ("partner matches without company", "company_partners", "companies", "company_slug", "slug"),
+    ("partner matches without partner", "company_partners", "partners", "partner_slug", "slug"),
+];
+
+pub struct OrphanCount {
+    pub violation: &'static str,
+    pub table: &'static str,
+    pub count: usize,
+}
+
+fn orphan_predicate(child: &str, parent: &str, child_col: &str, parent_col: &str) -> String {
+    format!(
+        "NOT EXISTS (SELECT 1 FROM {parent} p WHERE p.{parent_col} = {child}.{child_col})"
+    )
+}
+
+/// Count rows whose foreign key points at a missing parent, per violation type.
+pub fn find_orphans(conn: &Connection) -> Result<Vec<OrphanCount>> {
+    let mut out = Vec::with_capacity(ORPHAN_CHECKS.len());
+    for &(violation, child, parent, child_col, parent_col) in ORPHAN_CHECKS {
+        let sql = format!(
+            "SELECT COUNT(*) FROM {} WHERE {}",
+            child,
+            orphan_predicate(child, parent, child_col, parent_col)
+        );
+        let count: usize = conn.query_row(&sql, [], |r| r.get(0))?;
+        out.push(OrphanCount { violation, table: child, count });
+    }
+    Ok(out)
+}
+
+/// Delete orphan rows in one transaction, repeating until no pass removes
+/// anything (deleting orphan page_data can orphan its sections). Returns
+/// deleted counts per violation type.
+pub fn delete_orphans(conn: &Connection) -> Result<Vec<OrphanCount>> {
+    let tx = conn.unchecked_transaction()?;
+    tx.execute_batch("PRAGMA defer_foreign_keys = ON;")?;
+    let mut totals: Vec<OrphanCount> = ORPHAN_CHECKS
+        .iter()
+        .map(|&(violation, table, ..)| OrphanCount { violation, table, count: 0 })
+        .collect();
+    loop {
+        let mut pass = 0;
+        for (i, &(_, child, parent, child_col, parent_col)) in ORPHAN_CHECKS.iter().enumerate() {
+            let sql = format!(
+                "DELETE FROM {} WHERE {}",
+                child,
+                orphan_predicate(child, parent, child_col, parent_col)
+            );
+            let n = tx.execute(&sql, [])?;
+            totals[i].count += n;
+            pass += n;
+        }
+        if pass == 0 {
+            break;
+        }
+    }
+    tx.commit()?;
+    Ok(totals)
+}
+
+// ── Purge ──
+
+/// Slugs of stored pages whose URL is not in `sitemap_urls`.
+pub fn pages_missing_from(conn: &Connection, sitemap_urls: &[String]) -> Result<Vec<String>> {
+    let listed: std::collections::HashSet<&str> = sitemap_urls.iter().map(String::as_str).collect();
+    let mut stmt = conn.prepare("SELECT url, slug FROM pages ORDER BY slug")?;
+    let rows = stmt.query_map([], |r| Ok((r.get::<_, String>(0)?, r.get::<_, String>(1)?)))?;
+    let mut slugs = Vec::new();
+    for row in rows {
+        let (url, slug) = row?;
+        if !listed.contains(url.as_str()) {
+            slugs.push(slug);
+        }
+    }
+    Ok(slugs)
+}
+
+/// Delete `slugs` and everything derived from them (pages and scrapes,
+/// sections, extracted rows, job details, review notes, social stats of
+/// founder handles no other company lists) in one transaction,
+/// returning rows removed per table. With `tombstone`, the slugs are recorded
+/// in `purged_companies` (with `reason`) so the sitemap never re-queues them.
+/// Markdown in the blob store is deleted once the transaction commits. A dry
+/// run rolls back and reports what would be removed.
+pub fn purge_companies(
+    conn: &Connection,
+    slugs: &[String],
+    tombstone: bool,
+    reason: Option<&str>,
+    dry_run: bool,
+) -> Result<Vec<(&'static str, usize)>> {
+    // Children of companies, from the integrity checks, so new tables are covered.
+    let company_tables: Vec<(&'static str, &'static str)> = ORPHAN_CHECKS
+        .iter()
+        .filter(|(_, _, parent, _, _)| *parent == "companies")
+        .map(|&(_, child, _, col, _)| (child, col))
+        .collect();
+    let mut counts: Vec<(&'static str, usize)> = Vec::new();
+    let mut add = |table: &'static str, n: usize| match counts.iter_mut().find(|(t, _)| *t == table) {
+        Some((_, total)) => *total += n,
+        None => counts.push((table, n)),
+    };
+
+    let tx = conn.unchecked_transaction()?;
+    tx.execute_batch("PRAGMA defer_foreign_keys = ON;")?;
+    let mut articles: Vec<String> = Vec::new();
+    let mut handles: std::collections::BTreeSet<String> = Default::default();
+    let mut blob_keys: Vec<String> = Vec::new();
+    for slug in slugs {
+        let mut stmt = tx.prepare("SELECT twitter FROM founders WHERE company_slug = ?1 AND twitter IS NOT NULL")?;
+        for url in stmt.query_map([slug], |r| r.get::<_, String>(0))? {
+            handles.extend(crate::enrich::twitter_handle(&url?));
+        }
+        let mut stmt = tx.prepare(
+            "SELECT blob_key FROM page_data WHERE slug = ?1 AND blob_key IS NOT NULL
+             UNION ALL
+             SELECT raw_blob_key FROM page_data WHERE slug = ?1 AND raw_blob_key IS NOT NULL",
+        )?;
+        for key in stmt.query_map([slug], |r| r.get(0))? {
+            blob_keys.push(key?);
+        }
+        let mut stmt = tx.prepare("SELECT DISTINCT article_id FROM news WHERE company_slug = ?1 AND article_id IS NOT NULL")?;
+        for id in stmt.query_map([slug], |r| r.get(0))? {
+            articles.push(id?);
+        }
+        add(
+            "job_details",
+            tx.execute(
+                "DELETE FROM job_details WHERE job_id IN (SELECT id FROM company_jobs WHERE company_slug = ?1)",
+                [slug],
+            )?,
+        );
+        for &(table, col) in &company_tables {
+            add(table, tx.execute(&format!("DELETE FROM {} WHERE {} = ?1", table, col), [slug])?);
+        }
+        // Edges into the company too; the integrity checks only cover from_slug
+        add("company_references", tx.execute("DELETE FROM company_references WHERE to_slug = ?1", [slug])?);
+        add("companies", tx.execute("DELETE FROM companies WHERE slug = ?1", [slug])?);
+        add("company_sections", tx.execute("DELETE FROM company_sections WHERE slug = ?1", [slug])?);
+        add("page_data", tx.execute("DELETE FROM page_data WHERE slug = ?1", [slug])?);
+        add("pages", tx.execute("DELETE FROM pages WHERE slug = ?1", [slug])?);
+        if tombstone {
+            tx.execute(
+                "INSERT OR REPLACE INTO purged_companies (slug, reason) VALUES (?1, ?2)",
+                rusqlite::params![slug, reason],
+            )?;
+        }
+    }
+    // Articles other companies still cite stay, with a lower count.
+    for id in &articles {
+        let cited: usize =
+            tx.query_row("SELECT COUNT(DISTINCT company_slug) FROM news WHERE article_id = ?1", [id], |r| r.get(0))?;
+        if cited == 0 {
+            add("news_global", tx.execute("DELETE FROM news_global WHERE article_id = ?1", [id])?);
+        } else {
+            tx.execute(
+                "UPDATE news_global SET company_count = ?2 WHERE article_id = ?1",
+                rusqlite::params![id, cited],
+            )?;
+        }
+    }
+    // Handles another company's founder still lists keep their stats.
+    let mut stmt = tx.prepare("SELECT twitter FROM founders WHERE twitter IS NOT NULL")?;
+    for url in stmt.query_map([], |r| r.get::<_, String>(0))? {
+        if let Some(handle) = crate::enrich::twitter_handle(&url?) {
+            handles.remove(&handle);
+        }
+    }
+    drop(stmt);
+    for handle in &handles {
+        add("founder_social_stats", tx.execute("DELETE FROM founder_social_stats WHERE handle = ?1", [handle])?);
+    }
+    add("page blobs", blob_keys.len());
+    if !dry_run {
+        tx.commit()?;
+        if let Some(store) = crate::blobstore::shared()? {
+            for key in &blob_keys {
+                if let Err(e) = store.delete(key) {
+                    tracing::warn!("{:#}", e);
+                }
+            }
+        }
+    }
+    counts.retain(|(_, n)| *n > 0);
+    Ok(counts)
+}
+
+/// SQLite's own structural check; "ok" when healthy.
+pub fn quick_check(conn: &Connection) -> Result<String> {
+    let mut stmt = conn.prepare("PRAGMA quick_check")?;
+    let msgs = stmt
+        .query_map([], |r| r.get::<_, String>(0))?
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(msgs.join("; "))
+}
+
+// ── Maintenance ──
+
+pub struct DbSize {
+    /// Main file plus WAL, as on disk.
+    pub file_bytes: u64,
+    pub page_size: i64,
+    pub page_count: i64,
+    pub free_pages: i64,
+}
+
+pub fn db_size(conn: &Connection) -> Result<DbSize> {
+    let file_bytes = [DB_PATH.to_string(), format!("{}-wal", DB_PATH)]
+        .iter()
+        .filter_map(|p| std::fs::metadata(p).ok())
+        .map(|m| m.len())
+        .sum();
+    Ok(DbSize {
+        file_bytes,
+        page_size: conn.query_row("PRAGMA page_size", [], |r| r.get(0))?,
+        page_count: conn.query_row("PRAGMA page_count", [], |r| r.get(0))?,
+        free_pages: conn.query_row("PRAGMA freelist_count", [], |r| r.get(0))?,
+    })
+}
+
+pub struct TableUsage {
+    /// Table or index name.
+    pub name: String,
+    pub pages: i64,
+    pub bytes: i64,
+    /// Bytes allocated in those pages but not holding data.
+    pub unused_bytes: i64,
+}
+
+/// Page usage per table and index from the `dbstat` virtual table, largest first.
+pub fn table_usage(conn: &Connection) -> Result<Vec<TableUsage>> {
+    let mut stmt = conn.prepare(
+        "SELECT name, COUNT(*), SUM(pgsize), SUM(unused)
+         FROM dbstat
+         GROUP BY name
+         ORDER BY SUM(pgsize) DESC, name",
+    )?;
+    let rows = stmt
+        .query_map([], |r| {
+            Ok(TableUsage {
+                name: r.get(0)?,
+                pages: r.get(1)?,
+                bytes: r.get(2)?,
+                unused_bytes: r.get(3)?,
+            })
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(rows)
+}
+
+/// Reclaim free pages. DBs created before incremental auto-vacuum get a
+/// one-time full VACUUM to switch modes; returns true when that happened.
+pub fn vacuum(conn: &Connection) -> Result<bool> {
+    let mode: i64 = conn.query_row("PRAGMA auto_vacuum", [], |r| r.get(0))?;
+    let converted = mode != 2;
+    if converted {
+        conn.execute_batch("PRAGMA auto_vacuum=INCREMENTAL; VACUUM;")?;
+    } else {
+        conn.execute_batch("PRAGMA incremental_vacuum;")?;
+    }
+    Ok(converted)
+}
+
+/// Refresh planner statistics and rebuild every index, then fold the WAL
+/// back into the main file so the on-disk size is final.
+pub fn analyze_and_reindex(conn: &Connection) -> Result<()> {
+    conn.execute_batch("ANALYZE; REINDEX; PRAGMA optimize;")?;
+    conn.query_row("PRAGMA wal_checkpoint(TRUNCATE)", [], |_| Ok(()))?;
+    Ok(())
+}
+
+/// Move inline markdown and `--keep-raw` copies of up to `limit` scrapes
+/// (oldest first) into `store`, keyed by their scrape time, one upload and
+/// update at a time so an interrupted run loses nothing. `vacuum` afterwards
+/// to shrink the file. Returns (pages moved, bytes moved).
+pub fn offload_pages(conn: &Connection, store: &dyn BlobStore, limit: Option<usize>) -> Result<(usize, i64)> {
+    type Row = (i64, String, String, Option<String>, Option<Vec<u8>>);
+    let rows: Vec<Row> = Select::new("SELECT id, slug, scraped_at, markdown, markdown_raw FROM page_data")
+        .filter("markdown IS NOT NULL OR markdown_raw IS NOT NULL")
+        .order_by("id")
+        .limit(limit)
+        .query(conn, |r| Ok((r.get(0)?, r.get(1)?, r.get(2)?, r.get(3)?, r.get(4)?)))?;
+    let (mut moved, mut bytes) = (0, 0);
+    for (id, slug, scraped_at, markdown, raw) in rows {
+        let at = chrono::NaiveDateTime::parse_from_str(&scraped_at, "%Y-%m-%d %H:%M:%S")
+            .map(|t| t.and_utc())
+            .unwrap_or_else(|_| chrono::Utc::now());
+        if let Some(markdown) = markdown {
+            let key = crate::blobstore::page_key(&slug, at, markdown.as_bytes());
+            store.put(&key, markdown.as_bytes())?;
+            conn.prepare_cached(
+                "UPDATE page_data SET markdown = NULL, blob_key = ?2, markdown_bytes = ?3 WHERE id = ?1",
+            )?
+            .execute(rusqlite::params![id, key, markdown.len() as i64])?;
+            bytes += markdown.len() as i64;
+        }
+        if let Some(raw) = raw {
+            let key = crate::blobstore::raw_key(&slug, at, &raw);
+            store.put(&key, &raw)?;
+            conn.prepare_cached("UPDATE page_data SET markdown_raw = NULL, raw_blob_key = ?2 WHERE id = ?1")?
+                .execute(rusqlite::params![id, key])?;
+            bytes += raw.len() as i64;
+        }
+        moved += 1;
+    }
+    Ok((moved, bytes))
+}
+
+// ── Export ──
+
+pub struct ColumnInfo {
+    pub name: String,
+    pub decl_type: String,
+    pub not_null: bool,
+    pub primary_key: bool,
+}
+
+/// Declared columns of `table`, including stored generated columns.
+pub fn table_columns(conn: &Connection, table: &str) -> Result<Vec<ColumnInfo>> {
+    let mut stmt = conn.prepare(&format!("PRAGMA table_xinfo({})", table))?;
+    let cols = stmt
+        .query_map([], |row| {
+            Ok((
+                ColumnInfo {
+                    name: row.get(1)?,
+                    decl_type: row.get(2)?,
+                    not_null: row.get(3)?,
+                    primary_key: row.get::<_, i64>(5)? > 0,
+                },
+                row.get::<_, i64>(6)?,
+            ))
+        })?
+        .filter_map(|r| match r {
+            // hidden = 1 marks virtual-table internals, not real columns
+            Ok((_, 1)) => None,
+            other => Some(other.map(|(c, _)| c)),
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(cols)
+}
+
+/// `column REFERENCES parent(parent_column)` declared on a table.
+#[cfg(feature = "duckdb")]
+pub struct ForeignKey {
+    pub column: String,
+    pub parent: String,
+    pub parent_column: String,
+}
+
+#[cfg(feature = "duckdb")]
+pub fn foreign_keys(conn: &Connection, table: &str) -> Result<Vec<ForeignKey>> {
+    let mut stmt = conn.prepare(&format!("PRAGMA foreign_key_list({})", table))?;
+    let keys = stmt
+        .query_map([], |row| {
+            Ok(ForeignKey {
+                parent: row.get(2)?,
+                column: row.get(3)?,
+                parent_column: row.get(4)?,
+            })
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(keys)
+}
+
+/// Stream `columns` of `table` in rowid order (companies newest batch
+/// first), honoring the slug scope when `slug_column` is given and leaving
+/// out old slugs of renamed companies; `news_global` follows `news`.
+/// Returns the number of rows visited.
+pub fn for_each_row(
+    conn: &Connection,
+    table: &str,
+    columns: &[&str],
+    slug_column: Option<&str>,
+    mut f: impl FnMut(&rusqlite::Row) -> Result<()>,
+) -> Result<usize> {
+    let scope = match (table, slug_column) {
+        (_, Some(col)) => format!("{} AND {}", scope_predicate(conn, col)?, current_slug_predicate(col)),
+        // Articles go with the companies citing them
+        ("news_global", None) => format!(
+            "article_id IN (SELECT article_id FROM news WHERE {} AND {})",
+            scope_predicate(conn, "company_slug")?,
+            current_slug_predicate("company_slug")
+        ),
+        (_, None) => "1".to_string(),
+    };
+    let order = match table {
+        "companies" => "batch_ordinal DESC, slug",
+        _ => "rowid",
+    };
+    let sql = format!(
+        "SELECT {} FROM {} WHERE {} ORDER BY {}",
+        columns.join(", "),
+        table,
+        scope,
+        order
+    );
+    let mut stmt = conn.prepare(&sql)?;
+    let mut rows = stmt.query([])?;
+    let mut n = 0;
+    while let Some(row) = rows.next()? {
+        f(row)?;
+        n += 1;
+    }
+    Ok(n)
+}
+
+// ── Reports ──
+
+/// One edge of the `repeat_founders` view, with both companies' batches.
+#[derive(Clone)]
+pub struct RepeatFounderRow {
+    pub founder_id: i64,
+    pub name: String,
+    pub company_slug: String,
+    pub batch: Option<String>,
+    pub other_founder_id: i64,
+    pub other_company_slug: String,
+    pub other_batch: Option<String>,
+    pub match_method: String,
+}
+
+pub fn fetch_repeat_founders(conn: &Connection) -> Result<Vec<RepeatFounderRow>> {
+    let mut stmt = conn.prepare(
+        "SELECT rf.founder_id, rf.name, rf.company_slug, c1.batch,
+                rf.other_founder_id, rf.other_company_slug, c2.batch, rf.match_method
+         FROM repeat_founders rf
+         LEFT JOIN companies c1 ON c1.slug = rf.company_slug
+         LEFT JOIN companies c2 ON c2.slug = rf.other_company_slug",
+    )?;
+    let rows = stmt
+        .query_map([], |row| {
+            Ok(RepeatFounderRow {
+                founder_id: row.get(0)?,
+                name: row.get(1)?,
+                company_slug: row.get(2)?,
+                batch: row.get(3)?,
+                other_founder_id: row.get(4)?,
+                other_company_slug: row.get(5)?,
+                other_batch: row.get(6)?,
+                match_method: row.get(7)?,
+            })
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(rows)
+}
+
+/// Titles of every job listing in scope.
+pub fn fetch_job_titles(conn: &Connection) -> Result<Vec<String>> {
+    let sql = format!(
+        "SELECT title FROM company_jobs WHERE {} AND {} ORDER BY id",
+        scope_predicate(conn, "company_slug")?,
+        current_slug_predicate("company_slug")
+    );
+    let mut stmt = conn.prepare(&sql)?;
+    let rows = stmt
+        .query_map([], |row| row.get(0))?
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(rows)
+}
+
+/// Replace the contents of `job_stats` with `metrics`.
+pub fn save_job_stats(conn: &Connection, metrics: &[(String, String)]) -> Result<()> {
+    let tx = conn.unchecked_transaction()?;
+    tx.execute("DELETE FROM job_stats", [])?;
+    {
+        let mut stmt = tx.prepare("INSERT OR REPLACE INTO job_stats (metric, value) VALUES (?1, ?2)")?;
+        for (metric, value) in metrics {
+            stmt.execute(rusqlite::params![metric, value])?;
+        }
+    }
+    tx.commit()?;
+    Ok(())
+}
+
+// ── Process metrics ──
+
+/// Extraction totals for one processing run (`process_metrics`).
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct ProcessMetrics {
+    pub run_id: String,
+    pub command: String,
+    pub pages: usize,
+    pub empty_pages: usize,
+    pub founders: usize,
+    pub news: usize,
+    pub jobs: usize,
+    pub links: usize,
+    pub meeting_links: usize,
+    pub qa: usize,
+    pub locations: usize,
+    pub quality_flags: usize,
+    pub parse_ms: f64,
+    pub elapsed_ms: f64,
+    /// Set by the database on insert.
+    pub finished_at: Option<String>,
+}
+
+pub fn save_process_metrics(conn: &Connection, m: &ProcessMetrics) -> Result<()> {
+    conn.execute(
+        "INSERT OR REPLACE INTO process_metrics
+         (run_id, command, pages, empty_pages, founders, news, jobs, links, meeting_links, qa,
+          locations, quality_flags, parse_ms, elapsed_ms)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14)",
+        rusqlite::params![
+            m.run_id, m.command, m.pages, m.empty_pages, m.founders, m.news, m.jobs, m.links,
+            m.meeting_links, m.qa, m.locations, m.quality_flags, m.parse_ms, m.elapsed_ms,
+        ],
+    )?;
+    Ok(())
+}
+
+/// The `limit` most recent runs, oldest first.
+pub fn fetch_process_metrics(conn: &Connection, limit: usize) -> Result<Vec<ProcessMetrics>> {
+    let mut stmt = conn.prepare(
+        "SELECT * FROM (
+             SELECT run_id, command, pages, empty_pages, founders, news, jobs, links, meeting_links,
+                    qa, locations, quality_flags, parse_ms, elapsed_ms, finished_at, rowid AS seq
+             FROM process_metrics ORDER BY seq DESC LIMIT ?1
+         ) ORDER BY seq",
     )?;
-    let rows = stmt
-        .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?
-        .collect::<Result<Vec<_>, _>>()?;
-    Ok(rows)
+    let rows = stmt.query_map([limit], process_metrics_row)?;
+    Ok(rows.collect::<Result<Vec<_>, _>>()?)
 }
 
-// ── Overview ──
+/// Metrics of one run; `None` for a run that processed nothing (`scrape`).
+pub fn fetch_run_metrics(conn: &Connection, run_id: &str) -> Result<Option<ProcessMetrics>> {
+    Ok(conn
+        .query_row(
+            "SELECT run_id, command, pages, empty_pages, founders, news, jobs, links, meeting_links,
+                    qa, locations, quality_flags, parse_ms, elapsed_ms, finished_at
+             FROM process_metrics WHERE run_id = ?1",
+            [run_id],
+            process_metrics_row,
+        )
+        .optional()?)
+}
 
-pub struct OverviewRow {
-    pub slug: String,
-    pub name: String,
-    pub batch: String,
-    pub status: String,
-    pub team_size: Option<i32>,
-    pub location: String,
-    pub primary_partner: String,
-    pub tags: String,
-    pub job_count: i32,
+fn process_metrics_row(r: &rusqlite::Row) -> rusqlite::Result<ProcessMetrics> {
+    Ok(ProcessMetrics {
+        run_id: r.get(0)?,
+        command: r.get(1)?,
+        pages: r.get(2)?,
+        empty_pages: r.get(3)?,
+        founders: r.get(4)?,
+        news: r.get(5)?,
+        jobs: r.get(6)?,
+        links: r.get(7)?,
+        meeting_links: r.get(8)?,
+        qa: r.get(9)?,
+        locations: r.get(10)?,
+        quality_flags: r.get(11)?,
+        parse_ms: r.get(12)?,
+        elapsed_ms: r.get(13)?,
+        finished_at: r.get(14)?,
+    })
 }
 
-pub fn fetch_overview(
+// ── Run manifests ──
+
+/// What produced one run's data (`run_manifests`). The JSON columns stay
+/// text here; `manifest` builds and reads them.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct RunManifest {
+    pub run_id: String,
+    pub command: String,
+    pub crate_version: String,
+    pub git_hash: Option<String>,
+    pub parser_config: String,
+    pub args: String,
+    pub counts_before: String,
+    pub counts_after: Option<String>,
+    /// Set by the database on insert.
+    pub started_at: Option<String>,
+    pub finished_at: Option<String>,
+}
+
+pub fn insert_run_manifest(conn: &Connection, m: &RunManifest) -> Result<()> {
+    conn.execute(
+        "INSERT OR REPLACE INTO run_manifests
+         (run_id, command, crate_version, git_hash, parser_config, args, counts_before)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+        rusqlite::params![m.run_id, m.command, m.crate_version, m.git_hash, m.parser_config, m.args, m.counts_before],
+    )?;
+    Ok(())
+}
+
+pub fn finish_run_manifest(conn: &Connection, run_id: &str, counts_after: &str) -> Result<()> {
+    conn.execute(
+        "UPDATE run_manifests SET counts_after = ?2, finished_at = datetime('now') WHERE run_id = ?1",
+        rusqlite::params![run_id, counts_after],
+    )?;
+    Ok(())
+}
+
+/// The most recently finished run, if any run has finished.
+pub fn fetch_latest_run_manifest(conn: &Connection) -> Result<Option<RunManifest>> {
+    Ok(conn
+        .query_row(
+            "SELECT run_id, command, crate_version, git_hash, parser_config, args, counts_before,
+                    counts_after, started_at, finished_at
+             FROM run_manifests WHERE finished_at IS NOT NULL
+             ORDER BY finished_at DESC, rowid DESC LIMIT 1",
+            [],
+            |r| {
+                Ok(RunManifest {
+                    run_id: r.get(0)?,
+                    command: r.get(1)?,
+                    crate_version: r.get(2)?,
+                    git_hash: r.get(3)?,
+                    parser_config: r.get(4)?,
+                    args: r.get(5)?,
+                    counts_before: r.get(6)?,
+                    counts_after: r.get(7)?,
+                    started_at: r.get(8)?,
+                    finished_at: r.get(9)?,
+                })
+            },
+        )
+        .optional()?)
+}
+
+/// Row count of each of `tables`, in order.
+pub fn count_rows(conn: &Connection, tables: &[&str]) -> Result<Vec<(String, usize)>> {
+    tables
+        .iter()
+        .map(|t| {
+            let n: usize = conn.query_row(&format!("SELECT COUNT(*) FROM \"{}\"", t), [], |r| r.get(0))?;
+            Ok((t.to_string(), n))
+        })
+        .collect()
+}
+
+// ── Backfill ──
+
+/// Rows whose derived columns `backfill_derived` filled in.
+pub struct Backfilled {
+    pub table: &'static str,
+    pub columns: &'static str,
+    pub rows: usize,
+}
+
+/// Fill derived columns left NULL by older code paths from the columns
+/// they derive from: batch season/year from `batch` and back, company age
+/// from the founding year, quality scores, job salary ranges from the
+/// listing text, and founder LinkedIn profiles from `company_links`.
+/// Existing values are never overwritten. With `dry_run` nothing is kept.
+pub fn backfill_derived(
     conn: &Connection,
-    status: Option<&str>,
-    batch: Option<&str>,
-    limit: usize,
-) -> Result<Vec<OverviewRow>> {
-    let mut conditions = Vec::new();
-    let mut params: Vec<Box<dyn rusqlite::types::ToSql>> = Vec::new();
+    usd_rates: &std::collections::BTreeMap<String, f64>,
+    date_formats: &[String],
+    dry_run: bool,
+) -> Result<Vec<Backfilled>> {
+    use crate::parser::dates;
+    use crate::parser::extract::salary;
+    use chrono::Datelike;
+
+    let tx = conn.unchecked_transaction()?;
+    let mut out = Vec::new();
 
-    if let Some(s) = status {
-        conditions.push(format!("status = ?{}", params.len() + 1));
-        params.push(Box::new(s.to_string()));
+    // "Winter 2024" or "W24" → batch_season, batch_year
+    let rows: Vec<(String, String)> = tx
+        .prepare(
+            "SELECT slug, batch FROM companies
+             WHERE batch IS NOT NULL AND (batch_season IS NULL OR batch_year IS NULL)",
+        )?
+        .query_map([], |r| Ok((r.get(0)?, r.get(1)?)))?
+        .collect::<Result<_, _>>()?;
+    let mut n = 0;
+    {
+        let mut update = tx.prepare(
+            "UPDATE companies SET batch_season = COALESCE(batch_season, ?2), batch_year = COALESCE(batch_year, ?3)
+             WHERE slug = ?1",
+        )?;
+        for (slug, batch) in rows {
+            if let Ok(b) = batch.parse::<Batch>() {
+                n += update.execute(rusqlite::params![slug, b.season.as_str(), b.year])?;
+            }
+        }
     }
-    if let Some(b) = batch {
-        conditions.push(format!("batch = ?{}", params.len() + 1));
-        params.push(Box::new(b.to_string()));
+    out.push(Backfilled { table: "companies", columns: "batch_season, batch_year", rows: n });
+
+    let n = tx.execute(
+        "UPDATE companies SET batch = batch_season || ' ' || batch_year
+         WHERE batch IS NULL AND batch_season IS NOT NULL AND batch_year IS NOT NULL",
+        [],
+    )?;
+    out.push(Backfilled { table: "companies", columns: "batch", rows: n });
+
+    // Same rule as extraction: no age for a founding year the batch
+    // contradicts. Ages stored in an earlier year are brought up to date.
+    let rows: Vec<(String, i32, Option<i32>, Option<i32>)> = tx
+        .prepare("SELECT slug, founded_year, batch_year, company_age_years FROM companies WHERE founded_year IS NOT NULL")?
+        .query_map([], |r| Ok((r.get(0)?, r.get(1)?, r.get(2)?, r.get(3)?)))?
+        .collect::<Result<_, _>>()?;
+    let this_year = chrono::Utc::now().year();
+    let mut n = 0;
+    {
+        let mut update = tx.prepare("UPDATE companies SET company_age_years = ?2 WHERE slug = ?1")?;
+        for (slug, founded, batch_year, stored) in rows {
+            let age = quality::founded_year_bound(founded, batch_year).is_none().then(|| (this_year - founded).max(0));
+            if age != stored {
+                n += update.execute(rusqlite::params![slug, age])?;
+            }
+        }
     }
+    out.push(Backfilled { table: "companies", columns: "company_age_years", rows: n });
 
-    let where_clause = if conditions.is_empty() {
-        String::new()
-    } else {
-        format!(" WHERE {}", conditions.join(" AND "))
-    };
+    let unscored: Vec<String> = tx
+        .prepare("SELECT slug FROM companies WHERE quality_score IS NULL")?
+        .query_map([], |r| r.get(0))?
+        .collect::<Result<_, _>>()?;
+    let unscored: Vec<&str> = unscored.iter().map(String::as_str).collect();
+    let n = refresh_quality_scores(&tx, &unscored)?;
+    out.push(Backfilled { table: "companies", columns: "quality_score", rows: n });
 
-    let sql = format!(
-        "SELECT slug, COALESCE(name,''), COALESCE(batch,''), COALESCE(status,''),
-                team_size, COALESCE(location,''), COALESCE(primary_partner,''),
-                COALESCE(tags,''), job_count
-         FROM companies{}
-         ORDER BY batch_year DESC, slug
-         LIMIT {}",
-        where_clause, limit
-    );
+    let rows: Vec<(i64, String)> = tx
+        .prepare("SELECT id, salary FROM company_jobs WHERE salary IS NOT NULL AND salary_currency IS NULL")?
+        .query_map([], |r| Ok((r.get(0)?, r.get(1)?)))?
+        .collect::<Result<_, _>>()?;
+    let mut n = 0;
+    {
+        let mut update = tx.prepare(
+            "UPDATE company_jobs SET salary_currency = ?2, salary_min = ?3, salary_max = ?4 WHERE id = ?1",
+        )?;
+        for (id, text) in rows {
+            if let Some(s) = salary::parse(&text) {
+                n += update.execute(rusqlite::params![id, s.currency, s.min, s.max])?;
+            }
+        }
+    }
+    out.push(Backfilled { table: "company_jobs", columns: "salary_currency, salary_min, salary_max", rows: n });
 
-    let mut stmt = conn.prepare(&sql)?;
-    let param_refs: Vec<&dyn rusqlite::types::ToSql> = params.iter().map(|p| p.as_ref()).collect();
-    let rows = stmt
-        .query_map(param_refs.as_slice(), |row| {
-            Ok(OverviewRow {
-                slug: row.get(0)?,
-                name: row.get(1)?,
-                batch: row.get(2)?,
-                status: row.get(3)?,
-                team_size: row.get(4)?,
-                location: row.get(5)?,
-                primary_partner: row.get(6)?,
-                tags: row.get(7)?,
-                job_count: row.get(8)?,
-            })
-        })?
-        .collect::<Result<Vec<_>, _>>()?;
-    Ok(rows)
+    let rows: Vec<(i64, String, Option<f64>, Option<f64>)> = tx
+        .prepare(
+            "SELECT id, salary_currency, salary_min, salary_max FROM company_jobs
+             WHERE salary_currency IS NOT NULL AND salary_min_usd IS NULL AND salary_max_usd IS NULL",
+        )?
+        .query_map([], |r| Ok((r.get(0)?, r.get(1)?, r.get(2)?, r.get(3)?)))?
+        .collect::<Result<_, _>>()?;
+    let mut n = 0;
+    {
+        let mut update = tx.prepare("UPDATE company_jobs SET salary_min_usd = ?2, salary_max_usd = ?3 WHERE id = ?1")?;
+        for (id, currency, min, max) in rows {
+            let Some(rate) = salary::usd_rate(&currency, usd_rates) else { continue };
+            let usd = |v: Option<f64>| v.map(|v| (v * rate).round() as i64);
+            if min.is_some() || max.is_some() {
+                n += update.execute(rusqlite::params![id, usd(min), usd(max)])?;
+            }
+        }
+    }
+    out.push(Backfilled { table: "company_jobs", columns: "salary_min_usd, salary_max_usd", rows: n });
+
+    let n = link_founder_profiles(&tx)?;
+    out.push(Backfilled { table: "founders", columns: "linkedin, linkedin_match", rows: n });
+
+    // Founders stored before the dates were kept: the processed page is the
+    // earliest observation left
+    let n = tx.execute(
+        "UPDATE founders SET
+             first_seen_at = seen,
+             departed_at = COALESCE(departed_at, CASE WHEN is_active THEN NULL ELSE seen END)
+         FROM (SELECT cs.slug, pd.scraped_at AS seen
+               FROM company_sections cs JOIN page_data pd ON pd.id = cs.page_id) p
+         WHERE p.slug = founders.company_slug AND founders.first_seen_at IS NULL",
+        [],
+    )?;
+    out.push(Backfilled { table: "founders", columns: "first_seen_at, departed_at", rows: n });
+
+    // News dates stored as the page wrote them, before they were parsed
+    let rows: Vec<(i64, String)> = tx
+        .prepare(
+            "SELECT id, published FROM news
+             WHERE published IS NOT NULL AND published NOT GLOB '[0-9][0-9][0-9][0-9]-[0-9][0-9]-[0-9][0-9]'",
+        )?
+        .query_map([], |r| Ok((r.get(0)?, r.get(1)?)))?
+        .collect::<Result<_, _>>()?;
+    let mut n = 0;
+    {
+        let mut update = tx.prepare("UPDATE news SET published = ?2 WHERE id = ?1")?;
+        for (id, text) in rows {
+            if let Some(date) = dates::iso(&text, date_formats) {
+                n += update.execute(rusqlite::params![id, date])?;
+            }
+        }
+    }
+    if n > 0 {
+        tx.execute(
+            "UPDATE news_global SET published =
+                 (SELECT MIN(n.published) FROM news n WHERE n.article_id = news_global.article_id)",
+            [],
+        )?;
+    }
+    out.push(Backfilled { table: "news", columns: "published", rows: n });
+
+    if !dry_run {
+        tx.commit()?;
+    }
+    Ok(out)
 }
 
 // ── Stats ──
@@ -596,6 +4463,75 @@ pub struct Stats {
     pub scraped: usize,
     pub errors: usize,
     pub processed: usize,
+    /// Scrape latency (p50, p95, p99) in ms; None before any page is scraped.
+    pub latency_ms: Option<(i64, i64, i64)>,
+    /// Error count per category, most frequent first.
+    pub error_categories: Vec<(&'static str, usize)>,
+    pub markdown_sizes: Option<SizeSummary>,
+    /// (batch, processed pages, average markdown bytes), newest batch first,
+    /// unknown batches last.
+    pub size_by_batch: Vec<(String, usize, i64)>,
+    /// Row count for every table in the DB, alphabetical.
+    pub table_rows: Vec<(String, usize)>,
+    /// Flagged companies per extraction_quality check.
+    pub quality_flags: Vec<(String, usize)>,
+    /// Scraped pages that weren't parsed: ("pages" | "job pages", content kind, count).
+    pub skipped_kinds: Vec<(&'static str, String, usize)>,
+}
+
+/// Distribution of stored markdown sizes in bytes.
+pub struct SizeSummary {
+    pub min: i64,
+    pub p50: i64,
+    pub p95: i64,
+    pub max: i64,
+    pub total: i64,
+    /// (lower bound, pages) per `size_histogram` bucket, smallest first.
+    pub histogram: Vec<(i64, usize)>,
+}
+
+/// Upper bound of the first `size_histogram` bucket; each later one doubles.
+pub const FIRST_SIZE_BUCKET: i64 = 1024;
+
+/// Exponential buckets over ascending sizes: [0, 1KB), [1KB, 2KB), [2KB, 4KB)
+/// and so on up to the largest, as (lower bound, count). Empty buckets
+/// between the smallest and largest are kept so gaps show.
+pub fn size_histogram(sorted: &[i64]) -> Vec<(i64, usize)> {
+    let bucket = |size: i64| -> usize {
+        if size < FIRST_SIZE_BUCKET {
+            0
+        } else {
+            (size / FIRST_SIZE_BUCKET).ilog2() as usize + 1
+        }
+    };
+    let (Some(&min), Some(&max)) = (sorted.first(), sorted.last()) else {
+        return Vec::new();
+    };
+    let (first, last) = (bucket(min), bucket(max));
+    let mut counts = vec![0; last - first + 1];
+    for &size in sorted {
+        counts[bucket(size) - first] += 1;
+    }
+    let lower = |i: usize| if i == 0 { 0 } else { FIRST_SIZE_BUCKET << (i - 1) };
+    counts.into_iter().enumerate().map(|(i, n)| (lower(first + i), n)).collect()
+}
+
+/// Bucket a failed scrape into a coarse category: by its HTTP status when
+/// the request got one, else by the kind the scraper tags the error with
+/// (`timeout: …`, `connect: …`, `blocked: …`).
+pub fn error_category(status: Option<i32>, error: &str) -> &'static str {
+    match status {
+        Some(429) => return "rate_limit",
+        Some(401 | 403) => return "auth",
+        Some(500..=599) => return "server",
+        _ => {}
+    }
+    match error.split_once(':').map(|(kind, _)| kind) {
+        Some("blocked") => "blocked",
+        Some("timeout") => "timeout",
+        Some("connect") => "network",
+        _ => "other",
+    }
 }
 
 pub fn get_stats(conn: &Connection) -> Result<Stats> {
@@ -610,6 +4546,104 @@ pub fn get_stats(conn: &Connection) -> Result<Stats> {
     )?;
     let processed: usize =
         conn.query_row("SELECT COUNT(*) FROM companies", [], |r| r.get(0))?;
+
+    let latencies = query_i64s(
+        conn,
+        "SELECT latency_ms FROM page_data WHERE latency_ms IS NOT NULL ORDER BY latency_ms",
+    )?;
+    let latency_ms = if latencies.is_empty() {
+        None
+    } else {
+        Some((
+            percentile(&latencies, 50.0),
+            percentile(&latencies, 95.0),
+            percentile(&latencies, 99.0),
+        ))
+    };
+
+    let mut by_category: std::collections::HashMap<&'static str, usize> =
+        std::collections::HashMap::new();
+    {
+        let mut stmt = conn.prepare("SELECT status, error FROM page_data WHERE error IS NOT NULL")?;
+        let rows = stmt.query_map([], |r| Ok((r.get(0)?, r.get::<_, String>(1)?)))?;
+        for row in rows {
+            let (status, e) = row?;
+            *by_category.entry(error_category(status, &e)).or_default() += 1;
+        }
+    }
+    let mut error_categories: Vec<_> = by_category.into_iter().collect();
+    error_categories.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(b.0)));
+
+    let sizes = query_i64s(
+        conn,
+        "SELECT COALESCE(markdown_bytes, LENGTH(markdown)) FROM page_data
+         WHERE markdown IS NOT NULL OR blob_key IS NOT NULL ORDER BY 1",
+    )?;
+    let markdown_sizes = match (sizes.first(), sizes.last()) {
+        (Some(&min), Some(&max)) => Some(SizeSummary {
+            min,
+            p50: percentile(&sizes, 50.0),
+            p95: percentile(&sizes, 95.0),
+            max,
+            total: sizes.iter().sum(),
+            histogram: size_histogram(&sizes),
+        }),
+        _ => None,
+    };
+
+    // The page each company was last processed from
+    let mut size_by_batch: Vec<(String, usize, i64)> = {
+        let mut stmt = conn.prepare(
+            "SELECT COALESCE(c.batch, 'Unknown batch'), COUNT(*),
+                    CAST(AVG(COALESCE(pd.markdown_bytes, LENGTH(pd.markdown))) AS INTEGER)
+             FROM company_sections cs
+             JOIN page_data pd ON pd.id = cs.page_id
+             JOIN companies c ON c.slug = cs.slug
+             WHERE c.renamed_to IS NULL
+             GROUP BY 1",
+        )?;
+        let rows = stmt.query_map([], |r| Ok((r.get(0)?, r.get(1)?, r.get(2)?)))?;
+        rows.collect::<Result<Vec<_>, _>>()?
+    };
+    size_by_batch.sort_by_key(|(batch, ..)| std::cmp::Reverse(batch.parse::<Batch>().ok().map(Batch::ordinal)));
+
+    let table_names: Vec<String> = {
+        let mut stmt = conn.prepare(
+            "SELECT name FROM sqlite_master
+             WHERE type = 'table' AND name NOT LIKE 'sqlite_%'
+             ORDER BY name",
+        )?;
+        let rows = stmt.query_map([], |r| r.get(0))?;
+        rows.collect::<Result<Vec<_>, _>>()?
+    };
+    let mut table_rows = Vec::with_capacity(table_names.len());
+    for name in table_names {
+        let n: usize =
+            conn.query_row(&format!("SELECT COUNT(*) FROM \"{}\"", name), [], |r| r.get(0))?;
+        table_rows.push((name, n));
+    }
+
+    let quality_flags: Vec<(String, usize)> = {
+        let mut stmt = conn.prepare(
+            "SELECT check_name, COUNT(*) FROM extraction_quality
+             GROUP BY check_name ORDER BY check_name",
+        )?;
+        let rows = stmt.query_map([], |r| Ok((r.get(0)?, r.get(1)?)))?;
+        rows.collect::<Result<Vec<_>, _>>()?
+    };
+
+    let mut skipped_kinds = Vec::new();
+    for (source, sql) in [
+        ("pages", "SELECT content_kind, COUNT(*) FROM page_data WHERE content_kind <> 'company' GROUP BY 1 ORDER BY 2 DESC, 1"),
+        ("job pages", "SELECT content_kind, COUNT(*) FROM job_details WHERE content_kind <> 'job' GROUP BY 1 ORDER BY 2 DESC, 1"),
+    ] {
+        let mut stmt = conn.prepare(sql)?;
+        let rows = stmt.query_map([], |r| Ok((source, r.get(0)?, r.get(1)?)))?;
+        for row in rows {
+            skipped_kinds.push(row?);
+        }
+    }
+
     Ok(Stats {
         total,
         visited,
@@ -617,5 +4651,1226 @@ pub fn get_stats(conn: &Connection) -> Result<Stats> {
         scraped,
         errors,
         processed,
+        latency_ms,
+        error_categories,
+        markdown_sizes,
+        size_by_batch,
+        table_rows,
+        quality_flags,
+        skipped_kinds,
     })
 }
+
+fn query_i64s(conn: &Connection, sql: &str) -> Result<Vec<i64>> {
+    let mut stmt = conn.prepare(sql)?;
+    let rows = stmt
+        .query_map([], |r| r.get(0))?
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(rows)
+}
+
+/// Nearest-rank percentile over an ascending slice. Panics on empty input.
+pub fn percentile(sorted: &[i64], p: f64) -> i64 {
+    let rank = ((p / 100.0) * sorted.len() as f64).ceil() as usize;
+    sorted[rank.clamp(1, sorted.len()) - 1]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn percentile_nearest_rank() {
+        let v: Vec<i64> = (1..=100).collect();
+        assert_eq!(percentile(&v, 50.0), 50);
+        assert_eq!(percentile(&v, 95.0), 95);
+        assert_eq!(percentile(&v, 99.0), 99);
+        assert_eq!(percentile(&[7], 99.0), 7);
+    }
+
+    #[test]
+    fn size_histogram_doubles_from_one_kb() {
+        let sizes = [10, 1023, 1024, 3000, 4095, 20_000];
+        assert_eq!(
+            size_histogram(&sizes),
+            [(0, 2), (1024, 1), (2048, 2), (4096, 0), (8192, 0), (16384, 1)]
+        );
+        assert_eq!(size_histogram(&[5000, 6000]), [(4096, 2)]);
+        assert!(size_histogram(&[]).is_empty());
+    }
+
+    #[test]
+    fn error_categories() {
+        assert_eq!(error_category(Some(429), "Too Many Requests"), "rate_limit");
+        assert_eq!(error_category(Some(502), "Bad Gateway"), "server");
+        assert_eq!(error_category(Some(403), "Forbidden"), "auth");
+        assert_eq!(error_category(None, "timeout: operation timed out"), "timeout");
+        assert_eq!(error_category(None, "connect: error trying to connect"), "network");
+        assert_eq!(error_category(None, "blocked: block page (captcha)"), "blocked");
+        assert_eq!(error_category(None, "rate of 500 pages exceeded the plan"), "other", "the message text isn't read");
+    }
+
+    fn company(name: &str, team_size: Option<i32>, location: Option<&str>) -> CompanyRow {
+        CompanyRow {
+            slug: "acme".to_string(),
+            url: "https://www.ycombinator.com/companies/acme".to_string(),
+            name: Some(name.to_string()),
+            tagline: None,
+            batch: Some("Winter 2024".to_string()),
+            batch_season: Some("Winter".to_string()),
+            batch_year: Some(2024),
+            status: Some(CompanyStatus::Active),
+            homepage: None,
+            founded_year: None,
+            company_age_years: None,
+            team_size,
+            location: location.map(str::to_string),
+            primary_partner: None,
+            primary_partner_slug: None,
+            group_partner: None,
+            group_partner_slug: None,
+            tags: None,
+            job_count_extracted: 0,
+            job_count_listed: None,
+            linkedin: None,
+            twitter: None,
+            facebook: None,
+            crunchbase: None,
+            github: None,
+            description_lang: None,
+        }
+    }
+
+    #[test]
+    fn reextraction_merges_fields_and_logs_overwrites() {
+        let conn = Connection::open_in_memory().unwrap();
+        init_schema(&conn).unwrap();
+        save_extracted(&conn, &[company("Acme", Some(12), Some("SF"))], &[], &[], &[], &[]).unwrap();
+        // Degraded re-scrape: footer missing, name changed
+        save_extracted(&conn, &[company("Acme Labs", None, None)], &[], &[], &[], &[]).unwrap();
+
+        let (name, team_size, location): (String, i32, String) = conn
+            .query_row("SELECT name, team_size, location FROM companies WHERE slug = 'acme'", [], |r| {
+                Ok((r.get(0)?, r.get(1)?, r.get(2)?))
+            })
+            .unwrap();
+        assert_eq!((name.as_str(), team_size, location.as_str()), ("Acme Labs", 12, "SF"));
+
+        let changes: Vec<(String, String, String)> = conn
+            .prepare("SELECT field, old_value, new_value FROM field_changes")
+            .unwrap()
+            .query_map([], |r| Ok((r.get(0)?, r.get(1)?, r.get(2)?)))
+            .unwrap()
+            .collect::<Result<_, _>>()
+            .unwrap();
+        assert_eq!(changes, vec![("name".to_string(), "Acme".to_string(), "Acme Labs".to_string())]);
+    }
+
+    #[test]
+    fn interstitial_scrapes_are_requeued_not_processed() {
+        let conn = Connection::open_in_memory().unwrap();
+        init_schema(&conn).unwrap();
+        conn.execute("INSERT INTO pages (id, url, slug) VALUES (1, 'u/acme', 'acme')", []).unwrap();
+        let scrape = |markdown: &str| ScrapeRow {
+            page_id: 1,
+            url: "u/acme".to_string(),
+            slug: "acme".to_string(),
+            markdown: Some(markdown.to_string()),
+            status: Some(200),
+            error: None,
+            latency_ms: Some(1),
+            metadata: None,
+            final_url: None,
+            scrape_meta: None,
+            markdown_raw: None,
+            html: false,
+        };
+        let wall = format!("We use cookies.\n\n[Accept all cookies](#)\n{}", "x".repeat(300));
+
+        for attempt in 1..=MAX_CONTENT_RETRIES {
+            assert_eq!(save_scrape(&conn, &scrape(&wall), false).unwrap(), Some("consent_wall"));
+            assert_eq!(fetch_unvisited(&conn, None, ScrapeOrder::Id).unwrap().len(), 1, "attempt {}", attempt);
+        }
+        assert_eq!(fetch_rescrape_page_ids(&conn).unwrap().into_iter().collect::<Vec<_>>(), vec![1]);
+        save_scrape(&conn, &scrape(&wall), false).unwrap();
+        assert!(fetch_unvisited(&conn, None, ScrapeOrder::Id).unwrap().is_empty(), "gives up after the retries");
+        assert_eq!(count_unprocessed(&conn, None).unwrap(), 0);
+
+        assert_eq!(save_scrape(&conn, &scrape("### Acme\n\nFounded:2020\n"), false).unwrap(), Some("company"));
+        assert_eq!(count_unprocessed(&conn, None).unwrap(), 1);
+        assert!(fetch_rescrape_page_ids(&conn).unwrap().is_empty());
+
+        // Flagged by the scraper as a block page: stored, not processed, re-queued
+        let mut blocked = scrape("### Acme\n\nFounded:2020\n");
+        blocked.error = Some("blocked: 24 bytes, usually 4000".to_string());
+        assert_eq!(save_scrape(&conn, &blocked, false).unwrap(), Some("blocked"));
+        assert_eq!(fetch_unvisited(&conn, None, ScrapeOrder::Id).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn redirects_to_another_slug_record_renames() {
+        let conn = Connection::open_in_memory().unwrap();
+        init_schema(&conn).unwrap();
+        let base = "https://www.ycombinator.com/companies/";
+        conn.execute_batch(&format!(
+            "INSERT INTO pages (id, url, slug) VALUES (1, '{0}old-name', 'old-name'), (2, '{0}mid-name', 'mid-name');
+             INSERT INTO companies (slug, url, name, batch) VALUES
+                 ('old-name', '{0}old-name', 'Old', 'W20'), ('mid-name', '{0}mid-name', 'Mid', 'W20');",
+            base
+        ))
+        .unwrap();
+        let scrape = |page_id: i64, slug: &str, final_url: Option<&str>| ScrapeRow {
+            page_id,
+            url: format!("{}{}", base, slug),
+            slug: slug.to_string(),
+            markdown: Some("### New Name\n\nFounded:2020\n".to_string()),
+            status: Some(200),
+            error: None,
+            latency_ms: Some(1),
+            metadata: None,
+            final_url: final_url.map(str::to_string),
+            scrape_meta: None,
+            markdown_raw: None,
+            html: false,
+        };
+        let renamed_to = |slug: &str| -> Option<String> {
+            conn.query_row("SELECT renamed_to FROM companies WHERE slug = ?1", [slug], |r| r.get(0)).unwrap()
+        };
+
+        // Same page, trailing slash: not a rename
+        let same = scrape(1, "old-name", Some(&format!("{}Old-Name/", base)));
+        assert_eq!(save_scrape(&conn, &same, false).unwrap(), Some(crate::validate::COMPANY));
+
+        let moved = scrape(1, "old-name", Some(&format!("{}mid-name", base)));
+        assert_eq!(save_scrape(&conn, &moved, false).unwrap(), Some(crate::validate::RENAMED));
+        assert_eq!(renamed_to("old-name").as_deref(), Some("mid-name"));
+
+        // mid-name renamed again: old-name follows to the current slug, which gets queued
+        let moved = scrape(2, "mid-name", Some(&format!("{}new-name", base)));
+        save_scrape(&conn, &moved, false).unwrap();
+        assert_eq!(renamed_to("old-name").as_deref(), Some("new-name"));
+        assert_eq!(renamed_to("mid-name").as_deref(), Some("new-name"));
+        let queued: Vec<String> =
+            fetch_unvisited(&conn, None, ScrapeOrder::Id).unwrap().into_iter().map(|(_, _, s)| s).collect();
+        assert_eq!(queued, ["new-name"]);
+        let overview = fetch_overview(&conn, &[], &[], None, false, 10).unwrap();
+        assert!(overview.is_empty(), "renamed slugs are not listed");
+        let exported = for_each_row(&conn, "companies", &["slug"], Some("slug"), |_| Ok(())).unwrap();
+        assert_eq!(exported, 0, "nor exported");
+        assert!(fetch_batch_sizes(&conn).unwrap().is_empty());
+
+        // Serving its own page again reverts the rename
+        save_scrape(&conn, &scrape(2, "mid-name", None), false).unwrap();
+        assert_eq!(renamed_to("mid-name"), None);
+        assert_eq!(count_unprocessed(&conn, None).unwrap(), 2, "renamed scrapes are never processed");
+
+        // Scoping to an old slug brings in its current one
+        set_slug_scope(&conn, &["old-name".to_string()]).unwrap();
+        let scoped: Vec<String> = conn
+            .prepare("SELECT slug FROM temp.scope_slugs ORDER BY slug")
+            .unwrap()
+            .query_map([], |r| r.get(0))
+            .unwrap()
+            .collect::<Result<_, _>>()
+            .unwrap();
+        assert_eq!(scoped, ["new-name", "old-name"]);
+    }
+
+    #[test]
+    fn markdown_in_the_blob_store_reads_like_inline_markdown() {
+        let root = std::env::temp_dir().join(format!("yc-db-blobs-{}", std::process::id()));
+        let store: Arc<dyn BlobStore> = Arc::new(crate::blobstore::LocalDir::new(&root));
+        let conn = Connection::open_in_memory().unwrap();
+        init_schema(&conn).unwrap();
+        create_functions(&conn, Some(store.clone())).unwrap();
+        conn.execute_batch("INSERT INTO pages (id, url, slug) VALUES (1, 'u/acme', 'acme'), (2, 'u/beta', 'beta');")
+            .unwrap();
+        let scrape = |page_id: i64, slug: &str| ScrapeRow {
+            page_id,
+            url: format!("u/{}", slug),
+            slug: slug.to_string(),
+            markdown: Some(format!("### {}\n\nFounded:2020\n", slug)),
+            status: Some(200),
+            error: None,
+            latency_ms: Some(1),
+            metadata: None,
+            final_url: None,
+            scrape_meta: None,
+            markdown_raw: Some(gzip(format!("raw {}", slug).as_bytes())),
+            html: false,
+        };
+        save_scrape_with(&conn, &scrape(1, "acme"), false, Some(store.as_ref())).unwrap();
+        // A row that fails to save takes its upload with it
+        assert!(save_scrape_with(&conn, &scrape(9, "ghost"), false, Some(store.as_ref())).is_err());
+        assert!(!root.join("ghost").read_dir().is_ok_and(|mut d| d.next().is_some()));
+        save_scrape_with(&conn, &scrape(2, "beta"), false, None).unwrap();
+        let (inline, key, bytes): (Option<String>, Option<String>, i64) = conn
+            .query_row("SELECT markdown, blob_key, markdown_bytes FROM page_data WHERE slug = 'acme'", [], |r| {
+                Ok((r.get(0)?, r.get(1)?, r.get(2)?))
+            })
+            .unwrap();
+        assert_eq!(inline, None);
+        assert!(key.as_deref().is_some_and(|k| k.starts_with("acme/") && k.ends_with(".md")), "{:?}", key);
+        assert_eq!(bytes, "### acme\n\nFounded:2020\n".len() as i64);
+        let raw = |conn: &Connection| -> Vec<(bool, String)> {
+            conn.prepare("SELECT markdown_raw IS NULL, raw_markdown(markdown_raw, raw_blob_key) FROM page_data ORDER BY slug")
+                .unwrap()
+                .query_map([], |r| Ok((r.get(0)?, r.get(1)?)))
+                .unwrap()
+                .collect::<Result<_, _>>()
+                .unwrap()
+        };
+        assert_eq!(raw(&conn), [(true, "raw acme".to_string()), (false, "raw beta".to_string())]);
+
+        let read = |conn: &Connection| -> Result<Vec<(String, String)>> {
+            let mut pages = Vec::new();
+            for chunk in stream_unprocessed(conn, 10, None) {
+                pages.extend(chunk?.into_iter().map(|p| (p.slug, p.markdown)));
+            }
+            Ok(pages)
+        };
+        let pages = read(&conn).unwrap();
+        assert_eq!(pages.len(), 2);
+        assert!(pages.iter().all(|(slug, md)| md.starts_with(&format!("### {}", slug))));
+
+        assert_eq!(offload_pages(&conn, store.as_ref(), None).unwrap().0, 1);
+        assert_eq!(offload_pages(&conn, store.as_ref(), None).unwrap().0, 0);
+        assert_eq!(raw(&conn), [(true, "raw acme".to_string()), (true, "raw beta".to_string())]);
+        let (md, _) = fetch_review_source(&conn, "beta").unwrap().unwrap();
+        assert!(md.starts_with("### beta"));
+
+        create_functions(&conn, None).unwrap();
+        assert!(read(&conn).is_err(), "blob keys need a configured store");
+        let _ = std::fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn raw_markdown_reads_plain_and_gzipped_copies() {
+        let conn = Connection::open_in_memory().unwrap();
+        init_schema(&conn).unwrap();
+        conn.execute_batch("INSERT INTO pages (id, url, slug) VALUES (1, 'u/acme', 'acme'), (2, 'u/beta', 'beta');")
+            .unwrap();
+        let raw = "### Acme\n\n![Acme logo](https://cdn.test/acme.png)\n\nFounded:2020\n";
+        let scrape = |page_id: i64, slug: &str, stored: Option<Vec<u8>>| ScrapeRow {
+            page_id,
+            url: format!("u/{}", slug),
+            slug: slug.to_string(),
+            markdown: Some("### Acme\n\nFounded:2020\n".to_string()),
+            status: Some(200),
+            error: None,
+            latency_ms: Some(1),
+            metadata: None,
+            final_url: None,
+            scrape_meta: None,
+            markdown_raw: stored,
+            html: false,
+        };
+        let packed = gzip(raw.as_bytes());
+        assert!(packed.starts_with(&GZIP_MAGIC));
+        save_scrape(&conn, &scrape(1, "acme", Some(raw.as_bytes().to_vec())), false).unwrap();
+        save_scrape(&conn, &scrape(2, "beta", Some(packed)), false).unwrap();
+        let read: Vec<Option<String>> = conn
+            .prepare("SELECT raw_markdown(markdown_raw) FROM page_data ORDER BY id")
+            .unwrap()
+            .query_map([], |r| r.get(0))
+            .unwrap()
+            .collect::<Result<_, _>>()
+            .unwrap();
+        assert_eq!(read, [Some(raw.to_string()), Some(raw.to_string())]);
+        let none: Option<String> = conn.query_row("SELECT raw_markdown(NULL)", [], |r| r.get(0)).unwrap();
+        assert_eq!(none, None);
+    }
+
+    #[test]
+    fn concurrent_runs_split_pages_and_take_turns_at_locks() {
+        let conn = Connection::open_in_memory().unwrap();
+        init_schema(&conn).unwrap();
+        conn.execute_batch("INSERT INTO pages (id, url, slug) VALUES (1, 'u/a', 'a'), (2, 'u/b', 'b'), (3, 'u/c', 'c');")
+            .unwrap();
+        let ttl = std::time::Duration::from_secs(600);
+        let ids = |pages: &[(i64, String, String)]| -> Vec<i64> { pages.iter().map(|p| p.0).collect() };
+
+        let first = fetch_unvisited(&conn, Some(2), ScrapeOrder::Id).unwrap();
+        assert_eq!(ids(&claim_pages(&conn, first.clone(), "run-1", ttl).unwrap()), [1, 2]);
+        // A second run that listed the same pages before the claim loses them
+        assert!(claim_pages(&conn, first, "run-2", ttl).unwrap().is_empty());
+        let rest = fetch_unvisited(&conn, None, ScrapeOrder::Id).unwrap();
+        assert_eq!(ids(&claim_pages(&conn, rest, "run-2", ttl).unwrap()), [3]);
+        assert!(fetch_unvisited(&conn, None, ScrapeOrder::Id).unwrap().is_empty());
+
+        assert_eq!(try_lock(&conn, "process", "run-1", ttl).unwrap(), None);
+        assert_eq!(try_lock(&conn, "process", "run-2", ttl).unwrap().as_deref(), Some("run-1"));
+        release_claims(&conn, "run-1").unwrap();
+        assert_eq!(try_lock(&conn, "process", "run-2", ttl).unwrap(), None);
+        assert_eq!(ids(&fetch_unvisited(&conn, None, ScrapeOrder::Id).unwrap()), [1, 2]);
+
+        // Claims of a run that died lapse after their ttl
+        renew_claims(&conn, "run-2", std::time::Duration::ZERO).unwrap();
+        assert_eq!(ids(&fetch_unvisited(&conn, None, ScrapeOrder::Id).unwrap()), [1, 2, 3]);
+        assert_eq!(try_lock(&conn, "process", "run-1", ttl).unwrap(), None);
+    }
+
+    #[test]
+    fn scrape_orders() {
+        let conn = Connection::open_in_memory().unwrap();
+        init_schema(&conn).unwrap();
+        conn.execute_batch(
+            "INSERT INTO pages (id, url, slug, visited_at) VALUES
+                 (1, 'u/old', 'old', '2024-01-01 00:00:00'), (2, 'u/new', 'new', '2024-06-01 00:00:00'),
+                 (3, 'u/mid', 'mid', NULL), (4, 'u/fresh', 'fresh', NULL), (5, 'u/mid2', 'mid2', '2023-01-01 00:00:00');
+             INSERT INTO companies (slug, url, batch_season, batch_year) VALUES
+                 ('old', 'u/old', 'Summer', 2012), ('new', 'u/new', 'Winter', 2024),
+                 ('mid', 'u/mid', 'Summer', 2020), ('mid2', 'u/mid2', 'Fall', 2020);",
+        )
+        .unwrap();
+        let ids = |order| -> Vec<i64> {
+            fetch_unvisited(&conn, None, order).unwrap().into_iter().map(|p| p.0).collect()
+        };
+        assert_eq!(ids(ScrapeOrder::Id), [1, 2, 3, 4, 5]);
+        assert_eq!(ids(ScrapeOrder::NewestBatch), [4, 2, 5, 3, 1]);
+        assert_eq!(ids(ScrapeOrder::StaleFirst), [3, 4, 5, 1, 2]);
+        let mut shuffled = ids(ScrapeOrder::Random);
+        shuffled.sort();
+        assert_eq!(shuffled, [1, 2, 3, 4, 5]);
+    }
+
+    #[test]
+    fn batch_ordinal_sorts_seasons_within_a_year() {
+        let conn = Connection::open_in_memory().unwrap();
+        init_schema(&conn).unwrap();
+        // Dropped and re-added, as for a database created before the column
+        conn.execute_batch("ALTER TABLE companies DROP COLUMN batch_ordinal").unwrap();
+        init_schema(&conn).unwrap();
+        init_schema(&conn).unwrap();
+        conn.execute_batch(
+            "INSERT INTO companies (slug, url, batch_season, batch_year) VALUES
+                 ('w', 'u1', 'Winter', 2024), ('f', 'u2', 'Fall', 2024), ('x', 'u3', 'Spring', 2024),
+                 ('s', 'u4', 'Summer', 2024), ('old', 'u5', 'Fall', 2023), ('none', 'u6', NULL, NULL);",
+        )
+        .unwrap();
+        for season in crate::model::Season::ALL {
+            let ordinal: i32 = conn
+                .query_row("SELECT batch_ordinal FROM companies WHERE batch_season = ?1 AND batch_year = 2024", [season.as_str()], |r| r.get(0))
+                .unwrap();
+            assert_eq!(ordinal, Batch { season, year: 2024 }.ordinal(), "{:?}", season);
+        }
+        let overview = fetch_overview(&conn, &[], &[], None, false, 10).unwrap();
+        let slugs: Vec<&str> = overview.iter().map(|r| r.slug.as_str()).collect();
+        assert_eq!(slugs, ["f", "s", "x", "w", "old", "none"]);
+    }
+
+    #[test]
+    fn failed_scrapes_stay_queued_with_retry_errors() {
+        let conn = Connection::open_in_memory().unwrap();
+        init_schema(&conn).unwrap();
+        conn.execute("INSERT INTO pages (id, url, slug) VALUES (1, 'u/acme', 'acme')", []).unwrap();
+        let failed = |page_id| ScrapeRow {
+            page_id,
+            url: "u/acme".to_string(),
+            slug: "acme".to_string(),
+            markdown: None,
+            status: None,
+            error: Some("HTTP 503".to_string()),
+            latency_ms: Some(1),
+            metadata: None,
+            final_url: None,
+            scrape_meta: None,
+            markdown_raw: None,
+            html: false,
+        };
+        let attempts = |conn: &Connection| -> i64 {
+            conn.query_row("SELECT COUNT(*) FROM page_data", [], |r| r.get(0)).unwrap()
+        };
+
+        save_scrape(&conn, &failed(1), true).unwrap();
+        assert_eq!(fetch_unvisited(&conn, None, ScrapeOrder::Id).unwrap().len(), 1);
+        save_scrape(&conn, &failed(1), false).unwrap();
+        assert!(fetch_unvisited(&conn, None, ScrapeOrder::Id).unwrap().is_empty());
+        assert_eq!(attempts(&conn), 2);
+
+        // No page to mark: the page_data insert is rolled back with it
+        assert!(save_scrape(&conn, &failed(99), false).is_err());
+        assert_eq!(attempts(&conn), 2);
+    }
+
+    #[test]
+    fn refresh_prefers_pages_changed_since_visit() {
+        let conn = Connection::open_in_memory().unwrap();
+        init_schema(&conn).unwrap();
+        conn.execute_batch(
+            "INSERT INTO pages (id, url, slug, visited, visited_at) VALUES
+                 (1, 'u/old', 'old', 1, datetime('now', '-200 days')),
+                 (2, 'u/changed', 'changed', 1, '2024-01-01 00:00:00'),
+                 (3, 'u/unchanged', 'unchanged', 1, '2024-06-01 00:00:00'),
+                 (4, 'u/fresh', 'fresh', 1, datetime('now', '-1 days')),
+                 (5, 'u/new', 'new', 0, NULL);",
+        )
+        .unwrap();
+        let lastmod = |slug: &str, lastmod: &str| PageUrl {
+            url: format!("u/{}", slug),
+            slug: slug.to_string(),
+            lastmod: Some(lastmod.to_string()),
+        };
+        let added = insert_pages(
+            &conn,
+            &[lastmod("changed", "2024-03-01 00:00:00"), lastmod("unchanged", "2024-02-01 00:00:00")],
+        )
+        .unwrap();
+        assert_eq!(added, 0);
+
+        let slugs: Vec<String> = fetch_refresh_candidates(&conn, 90.0, None)
+            .unwrap()
+            .into_iter()
+            .map(|(_, _, slug)| slug)
+            .collect();
+        assert_eq!(slugs, vec!["changed", "old"]);
+        assert_eq!(fetch_refresh_candidates(&conn, 90.0, Some(1)).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn migration_renames_job_count() {
+        let conn = Connection::open_in_memory().unwrap();
+        init_schema(&conn).unwrap();
+        // As created before the column was renamed
+        conn.execute_batch(
+            "ALTER TABLE companies RENAME COLUMN job_count_extracted TO job_count;
+             INSERT INTO companies (slug, url, job_count) VALUES ('acme', 'x', 4);",
+        )
+        .unwrap();
+        init_schema(&conn).unwrap();
+        init_schema(&conn).unwrap();
+        let n: i32 = conn.query_row("SELECT job_count_extracted FROM companies", [], |r| r.get(0)).unwrap();
+        assert_eq!(n, 4);
+    }
+
+    #[test]
+    fn startup_refreshes_stale_company_ages() {
+        let conn = Connection::open_in_memory().unwrap();
+        init_schema(&conn).unwrap();
+        conn.execute_batch(
+            "INSERT INTO companies (slug, url, founded_year, company_age_years) VALUES
+                 ('a', 'x', 2010, 3), ('b', 'y', 2010, NULL);",
+        )
+        .unwrap();
+        init_schema(&conn).unwrap();
+        let ages: Vec<Option<i32>> = conn
+            .prepare("SELECT company_age_years FROM companies ORDER BY slug")
+            .unwrap()
+            .query_map([], |r| r.get(0))
+            .unwrap()
+            .collect::<Result<_, _>>()
+            .unwrap();
+        let age = chrono::Datelike::year(&chrono::Utc::now()) - 2010;
+        assert_eq!(ages, vec![Some(age), None], "a flagged founding year stays without an age");
+    }
+
+    #[test]
+    fn migration_fills_founder_name_keys() {
+        let conn = Connection::open_in_memory().unwrap();
+        init_schema(&conn).unwrap();
+        // As stored before name_key existed
+        conn.execute_batch(
+            "INSERT INTO companies (slug, url) VALUES ('acme', 'x');
+             INSERT INTO founders (company_slug, name) VALUES ('acme', 'Jane Doe');",
+        )
+        .unwrap();
+        init_schema(&conn).unwrap();
+        let key: Option<String> = conn.query_row("SELECT name_key FROM founders", [], |r| r.get(0)).unwrap();
+        assert_eq!(key, Some(crate::parser::names::name_key("Jane Doe")));
+    }
+
+    #[test]
+    fn migration_fills_group_partner_from_stored_footer() {
+        let conn = Connection::open_in_memory().unwrap();
+        init_schema(&conn).unwrap();
+        conn.execute_batch(
+            "INSERT INTO pages (id, url, slug) VALUES (1, 'x', 'acme'), (2, 'y', 'beta'), (3, 'z', 'gamma');
+             INSERT INTO page_data (id, page_id, url, slug) VALUES (1, 1, 'x', 'acme'), (2, 2, 'y', 'beta'), (3, 3, 'z', 'gamma');
+             INSERT INTO companies (slug, url, primary_partner) VALUES
+                 ('acme', 'x', NULL), ('beta', 'y', 'Tom Blomfield'), ('gamma', 'z', NULL);
+             INSERT INTO company_sections (page_id, slug, url, footer) VALUES
+                 (1, 'acme', 'x', 'Founded:2020\nGroup Partner:Dalton Caldwell\n[Dalton Caldwell](https://www.ycombinator.com/people/dalton-caldwell)'),
+                 (2, 'beta', 'y', 'Founded:2021\nPrimary Partner:Tom Blomfield'),
+                 (3, 'gamma', 'z', 'Founded:2022\nGroup Partner:');",
+        )
+        .unwrap();
+        init_schema(&conn).unwrap();
+        let partners: Vec<(String, Option<String>, Option<String>)> = conn
+            .prepare("SELECT slug, group_partner, group_partner_slug FROM companies ORDER BY slug")
+            .unwrap()
+            .query_map([], |r| Ok((r.get(0)?, r.get(1)?, r.get(2)?)))
+            .unwrap()
+            .collect::<Result<_, _>>()
+            .unwrap();
+        assert_eq!(
+            partners,
+            vec![
+                ("acme".to_string(), Some("Dalton Caldwell".to_string()), Some("dalton-caldwell".to_string())),
+                ("beta".to_string(), None, None),
+                ("gamma".to_string(), None, None),
+            ]
+        );
+        // Matching falls back to the group partner
+        assert_eq!(fetch_partner_slugs(&conn).unwrap(), vec![("acme".to_string(), "dalton-caldwell".to_string())]);
+        let mut unmatched = fetch_unmatched_partners(&conn).unwrap();
+        unmatched.sort();
+        assert_eq!(
+            unmatched,
+            vec![
+                ("acme".to_string(), "Dalton Caldwell".to_string()),
+                ("beta".to_string(), "Tom Blomfield".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn migration_merges_non_canonical_pages() {
+        let conn = Connection::open_in_memory().unwrap();
+        init_schema(&conn).unwrap();
+        conn.execute_batch(
+            "INSERT INTO pages (id, url, slug, visited, visited_at) VALUES
+                 (1, 'https://www.ycombinator.com/companies/Airbnb', 'Airbnb', 1, '2024-01-01 00:00:00'),
+                 (2, 'https://www.ycombinator.com/companies/airbnb', 'airbnb', 0, NULL),
+                 (3, 'https://www.ycombinator.com/companies/Stripe/', 'Stripe', 0, NULL);
+             INSERT INTO page_data (id, page_id, url, slug, markdown) VALUES
+                 (10, 1, 'https://www.ycombinator.com/companies/Airbnb', 'Airbnb', '# Airbnb');
+             INSERT INTO company_sections (page_id, slug, url) VALUES (10, 'Airbnb', 'x');
+             INSERT INTO companies (slug, url) VALUES ('Airbnb', 'x');
+             INSERT INTO founders (company_slug, name) VALUES ('Airbnb', 'Brian Chesky');",
+        )
+        .unwrap();
+        assert_eq!(merge_duplicate_pages(&conn).unwrap(), 2);
+
+        let pages: Vec<(i64, String, String, bool)> = conn
+            .prepare("SELECT id, url, slug, visited FROM pages ORDER BY id")
+            .unwrap()
+            .query_map([], |r| Ok((r.get(0)?, r.get(1)?, r.get(2)?, r.get(3)?)))
+            .unwrap()
+            .collect::<Result<_, _>>()
+            .unwrap();
+        assert_eq!(
+            pages,
+            vec![
+                (2, "https://www.ycombinator.com/companies/airbnb".to_string(), "airbnb".to_string(), true),
+                (3, "https://www.ycombinator.com/companies/stripe".to_string(), "stripe".to_string(), false),
+            ]
+        );
+        let (page_id, slug): (i64, String) = conn
+            .query_row("SELECT page_id, slug FROM page_data WHERE id = 10", [], |r| Ok((r.get(0)?, r.get(1)?)))
+            .unwrap();
+        assert_eq!((page_id, slug.as_str()), (2, "airbnb"));
+        let stale: i64 = conn
+            .query_row(
+                "SELECT (SELECT COUNT(*) FROM companies) + (SELECT COUNT(*) FROM founders)
+                      + (SELECT COUNT(*) FROM company_sections)",
+                [],
+                |r| r.get(0),
+            )
+            .unwrap();
+        assert_eq!(stale, 0, "extracted rows under the old slug are dropped for re-processing");
+        assert_eq!(count_unprocessed(&conn, None).unwrap(), 1);
+        assert_eq!(merge_duplicate_pages(&conn).unwrap(), 0);
+    }
+
+    #[test]
+    fn merged_pages_drop_founder_linked_rows() {
+        let conn = Connection::open_in_memory().unwrap();
+        init_schema(&conn).unwrap();
+        conn.execute_batch(
+            "INSERT INTO pages (id, url, slug) VALUES (1, 'https://www.ycombinator.com/companies/Airbnb', 'Airbnb');
+             INSERT INTO page_data (id, page_id, url, slug) VALUES
+                 (10, 1, 'https://www.ycombinator.com/companies/Airbnb', 'Airbnb');
+             INSERT INTO company_sections (page_id, slug, url) VALUES (10, 'Airbnb', 'x');
+             INSERT INTO companies (slug, url) VALUES ('Airbnb', 'x');
+             INSERT INTO founders (id, company_slug, name) VALUES (1, 'Airbnb', 'Brian Chesky');
+             INSERT INTO founder_experience (founder_id, company_slug, kind, org) VALUES (1, 'Airbnb', 'education', 'RISD');
+             INSERT INTO company_links (company_slug, url, domain, link_type, founder_id)
+                 VALUES ('Airbnb', 'https://linkedin.com/in/brianchesky', 'linkedin.com', 'linkedin', 1);
+             INSERT INTO company_jobs (id, company_slug, title, url) VALUES (1, 'Airbnb', 'Engineer', 'j/1');
+             INSERT INTO job_details (job_id, markdown) VALUES (1, '# Engineer');",
+        )
+        .unwrap();
+        assert_eq!(merge_duplicate_pages(&conn).unwrap(), 1);
+        let left: i64 = conn
+            .query_row(
+                "SELECT (SELECT COUNT(*) FROM founders) + (SELECT COUNT(*) FROM founder_experience)
+                      + (SELECT COUNT(*) FROM company_links) + (SELECT COUNT(*) FROM job_details)",
+                [],
+                |r| r.get(0),
+            )
+            .unwrap();
+        assert_eq!(left, 0);
+        assert!(conn.prepare("PRAGMA foreign_key_check").unwrap().query([]).unwrap().next().unwrap().is_none());
+    }
+
+    #[test]
+    fn news_shared_across_companies_is_one_article() {
+        let conn = Connection::open_in_memory().unwrap();
+        init_schema(&conn).unwrap();
+        conn.execute_batch("INSERT INTO companies (slug, url) VALUES ('acquirer', 'u/acquirer'), ('target', 'u/target');")
+            .unwrap();
+        let news = |slug: &str, url: &str, published: Option<&str>| NewsRow {
+            company_slug: slug.to_string(),
+            title: format!("{} news", slug),
+            url: url.to_string(),
+            published: published.map(str::to_string),
+            source_lines: None,
+        };
+        let rows = [
+            news("acquirer", "https://techcrunch.com/acq", Some("2024-03-01")),
+            news("target", "https://techcrunch.com/acq/", None),
+            news("target", "https://example.com/amp/acq", None),
+            news("target", "https://example.com/other", None),
+        ];
+        save_extracted(&conn, &[], &[], &rows, &[], &[]).unwrap();
+        assert_eq!(rebuild_news_global(&conn).unwrap(), 3);
+
+        let amp_id: i64 = conn
+            .query_row("SELECT id FROM news WHERE url = 'https://example.com/amp/acq'", [], |r| r.get(0))
+            .unwrap();
+        save_news_canonical(&conn, &[(amp_id, "https://techcrunch.com/acq".to_string(), true)]).unwrap();
+        assert_eq!(rebuild_news_global(&conn).unwrap(), 2);
+        let (title, published, companies): (String, Option<String>, i64) = conn
+            .query_row(
+                "SELECT title, published, company_count FROM news_global WHERE article_id = ?1",
+                [article_id("https://techcrunch.com/acq")],
+                |r| Ok((r.get(0)?, r.get(1)?, r.get(2)?)),
+            )
+            .unwrap();
+        assert_eq!((title.as_str(), published.as_deref(), companies), ("acquirer news", Some("2024-03-01"), 2));
+    }
+
+    #[test]
+    fn only_article_canonicals_mark_duplicates() {
+        let conn = Connection::open_in_memory().unwrap();
+        init_schema(&conn).unwrap();
+        conn.execute("INSERT INTO companies (slug, url) VALUES ('acme', 'u/acme')", []).unwrap();
+        let news = |url: &str, published: Option<&str>| NewsRow {
+            company_slug: "acme".to_string(),
+            title: url.to_string(),
+            url: url.to_string(),
+            published: published.map(str::to_string),
+            source_lines: None,
+        };
+        let urls = [
+            "https://yahoo.com/a",
+            "https://yahoo.com/b",
+            "https://wsj.com/x",
+            "https://wsj.com/y",
+            "https://t.co/1",
+            "https://t.co/2",
+        ];
+        let published = [None, None, None, None, None, Some("2024-01-01")];
+        let rows: Vec<NewsRow> = urls.iter().zip(published).map(|(u, p)| news(u, p)).collect();
+        save_extracted(&conn, &[], &[], &rows, &[], &[]).unwrap();
+        let id = |url: &str| -> i64 {
+            conn.query_row("SELECT id FROM news WHERE url = ?1", [url], |r| r.get(0)).unwrap()
+        };
+        let resolved = [
+            ("https://yahoo.com/a", "https://consent.yahoo.com/v2/collectConsent"),
+            ("https://yahoo.com/b", "https://consent.yahoo.com/v2/collectConsent"),
+            ("https://wsj.com/x", "https://www.wsj.com/"),
+            ("https://wsj.com/y", "https://www.wsj.com/"),
+            ("https://t.co/1", "https://techcrunch.com/story"),
+            ("https://t.co/2", "https://techcrunch.com/story"),
+        ];
+        let updates: Vec<_> = resolved.iter().map(|(u, c)| (id(u), c.to_string(), true)).collect();
+        save_news_canonical(&conn, &updates).unwrap();
+
+        assert_eq!(mark_news_duplicates(&conn).unwrap(), 1);
+        assert_eq!(mark_news_duplicates(&conn).unwrap(), 1, "re-marking is idempotent");
+        let marked: Vec<(String, i64)> = conn
+            .prepare("SELECT url, duplicate_of FROM news WHERE duplicate_of IS NOT NULL")
+            .unwrap()
+            .query_map([], |r| Ok((r.get(0)?, r.get(1)?)))
+            .unwrap()
+            .collect::<Result<_, _>>()
+            .unwrap();
+        assert_eq!(marked, vec![("https://t.co/1".to_string(), id("https://t.co/2"))], "the dated row is kept");
+        assert_eq!(conn.query_row("SELECT COUNT(*) FROM news", [], |r| r.get::<_, i64>(0)).unwrap(), 6);
+        assert_eq!(rebuild_news_global(&conn).unwrap(), 5, "interstitial canonicals keep their articles apart");
+    }
+
+    #[test]
+    fn reprocessed_jobs_keep_salary_text_and_bounds_together() {
+        let conn = Connection::open_in_memory().unwrap();
+        init_schema(&conn).unwrap();
+        conn.execute("INSERT INTO companies (slug, url) VALUES ('acme', 'u/acme')", []).unwrap();
+        let job = |salary: &str, min: f64, max: f64| JobRow {
+            company_slug: "acme".to_string(),
+            title: "Engineer".to_string(),
+            url: "j/1".to_string(),
+            location: None,
+            salary: Some(salary.to_string()),
+            salary_currency: Some("USD".to_string()),
+            salary_min: Some(min),
+            salary_max: Some(max),
+            salary_min_usd: Some(min as i64),
+            salary_max_usd: Some(max as i64),
+            experience: None,
+            apply_url: None,
+            source_lines: None,
+        };
+        save_extracted(&conn, &[], &[], &[], &[job("$100K – $120K", 100_000.0, 120_000.0)], &[]).unwrap();
+        save_extracted(&conn, &[], &[], &[], &[job("$130K – $150K", 130_000.0, 150_000.0)], &[]).unwrap();
+        let (salary, min): (String, f64) = conn
+            .query_row("SELECT salary, salary_min FROM company_jobs WHERE url = 'j/1'", [], |r| Ok((r.get(0)?, r.get(1)?)))
+            .unwrap();
+        assert_eq!((salary.as_str(), min), ("$130K – $150K", 130_000.0));
+    }
+
+    #[test]
+    fn funding_events_merge_articles_about_the_same_round() {
+        let conn = Connection::open_in_memory().unwrap();
+        init_schema(&conn).unwrap();
+        conn.execute("INSERT INTO companies (slug, url) VALUES ('acme', 'u/acme')", []).unwrap();
+        let news = |title: &str, url: &str, published: Option<&str>| NewsRow {
+            company_slug: "acme".to_string(),
+            title: title.to_string(),
+            url: url.to_string(),
+            published: published.map(str::to_string),
+            source_lines: None,
+        };
+        let rows = [
+            news("Acme's Series A, explained", "https://a.com/1", Some("Jun 02, 2023")),
+            news("Acme raises $12M Series A", "https://b.com/2", Some("May 07, 2023")),
+            news("Acme raises $3M", "https://c.com/3", None),
+            news("Acme launches v2", "https://d.com/4", Some("Jan 10, 2024")),
+        ];
+        save_extracted(&conn, &[], &[], &rows, &[], &[]).unwrap();
+        assert_eq!(rebuild_funding_events(&conn).unwrap(), 2);
+        let events: Vec<String> = conn
+            .prepare(
+                "SELECT concat_ws(' ', COALESCE(round, '-'), amount, currency, COALESCE(announced, '-'), url)
+                 FROM funding_events ORDER BY id",
+            )
+            .unwrap()
+            .query_map([], |r| r.get(0))
+            .unwrap()
+            .collect::<rusqlite::Result<_>>()
+            .unwrap();
+        assert_eq!(
+            events,
+            ["Series A 12000000.0 USD 2023-05-07 https://b.com/2", "- 3000000.0 USD - https://c.com/3"]
+        );
+    }
+
+    #[test]
+    fn founder_linkedin_backfills_from_company_links() {
+        let conn = Connection::open_in_memory().unwrap();
+        init_schema(&conn).unwrap();
+        conn.execute_batch(
+            "INSERT INTO companies (slug, url) VALUES ('acme', 'u/acme');
+             INSERT INTO founders (id, company_slug, name, name_key, linkedin) VALUES
+                 (1, 'acme', 'Jane Doe', 'jane doe', NULL),
+                 (2, 'acme', 'Bob Smith', 'bob smith', ''),
+                 (3, 'acme', 'Ann Lee', 'ann lee', 'https://www.linkedin.com/in/annlee/'),
+                 (4, 'acme', 'Max Muster', 'max muster', NULL);
+             INSERT INTO company_links (company_slug, url, domain, link_type, founder_id) VALUES
+                 ('acme', 'https://linkedin.com/in/jd-42', 'linkedin.com', 'linkedin', 1),
+                 ('acme', 'https://linkedin.com/in/bob-smith-123', 'linkedin.com', 'linkedin', NULL),
+                 ('acme', 'https://linkedin.com/in/annlee', 'linkedin.com', 'linkedin', NULL),
+                 ('acme', 'https://linkedin.com/company/acme', 'linkedin.com', 'linkedin', NULL),
+                 ('acme', 'https://linkedin.com/in/maxmuster', 'linkedin.com', 'linkedin', NULL),
+                 ('acme', 'https://linkedin.com/in/max-muster-2', 'linkedin.com', 'linkedin', NULL);",
+        )
+        .unwrap();
+        assert_eq!(backfill_founder_linkedin(&conn).unwrap(), 2);
+        let founders: Vec<String> = conn
+            .prepare("SELECT concat_ws(' ', id, COALESCE(linkedin, '-'), linkedin_match) FROM founders ORDER BY id")
+            .unwrap()
+            .query_map([], |r| r.get(0))
+            .unwrap()
+            .collect::<rusqlite::Result<_>>()
+            .unwrap();
+        assert_eq!(
+            founders,
+            [
+                "1 https://linkedin.com/in/jd-42 founder_link",
+                "2 https://linkedin.com/in/bob-smith-123 name_in_handle",
+                "3 https://www.linkedin.com/in/annlee/",
+                "4 -",
+            ]
+        );
+        let bob_link: Option<i64> = conn
+            .query_row("SELECT founder_id FROM company_links WHERE url LIKE '%bob-smith%'", [], |r| r.get(0))
+            .unwrap();
+        assert_eq!(bob_link, Some(2));
+        assert_eq!(backfill_founder_linkedin(&conn).unwrap(), 0);
+    }
+
+    #[test]
+    fn founder_departures_date_from_the_first_page_listing_them() {
+        let conn = Connection::open_in_memory().unwrap();
+        init_schema(&conn).unwrap();
+        conn.execute_batch(
+            "INSERT INTO companies (slug, url, name, batch) VALUES ('acme', 'u/acme', 'Acme', 'W24');
+             INSERT INTO pages (id, url, slug) VALUES (1, 'u/acme', 'acme');
+             INSERT INTO page_data (id, page_id, url, slug, scraped_at) VALUES
+                 (1, 1, 'u/acme', 'acme', '2024-01-05 09:00:00'),
+                 (2, 1, 'u/acme', 'acme', '2024-06-01 09:00:00'),
+                 (3, 1, 'u/acme', 'acme', '2024-09-01 09:00:00');",
+        )
+        .unwrap();
+        let founder = |name: &str, is_active: bool| FounderRow {
+            company_slug: "acme".into(),
+            name: name.into(),
+            name_key: name.to_lowercase(),
+            title: None,
+            bio: None,
+            is_active,
+            linkedin: None,
+            twitter: None,
+            source_lines: None,
+        };
+        let process = |page_id: i64, founders: &[FounderRow]| {
+            conn.execute(
+                "INSERT INTO company_sections (page_id, slug, url) VALUES (?1, 'acme', 'u/acme')
+                 ON CONFLICT(slug) DO UPDATE SET page_id = excluded.page_id",
+                [page_id],
+            )
+            .unwrap();
+            save_extracted(&conn, &[], founders, &[], &[], &[]).unwrap();
+        };
+        process(1, &[founder("Jane Doe", true), founder("Max Roe", true)]);
+        process(2, &[founder("Jane Doe", true), founder("Max Roe", false)]);
+        process(3, &[founder("Jane Doe", true), founder("Max Roe", false), founder("Ann Lee", false)]);
+        process(2, &[founder("Jane Doe", true), founder("Max Roe", false)]);
+
+        let timeline: Vec<(String, String, Option<String>, bool)> = conn
+            .prepare("SELECT name, first_seen_at, departed_at, departed FROM founders ORDER BY id")
+            .unwrap()
+            .query_map([], |r| Ok((r.get(0)?, r.get(1)?, r.get(2)?, r.get(3)?)))
+            .unwrap()
+            .collect::<rusqlite::Result<_>>()
+            .unwrap();
+        let row = |name: &str, seen: &str, departed: Option<&str>| {
+            (name.to_string(), seen.to_string(), departed.map(str::to_string), departed.is_some())
+        };
+        assert_eq!(
+            timeline,
+            [
+                row("Jane Doe", "2024-01-05 09:00:00", None),
+                row("Max Roe", "2024-01-05 09:00:00", Some("2024-06-01 09:00:00")),
+                row("Ann Lee", "2024-09-01 09:00:00", Some("2024-09-01 09:00:00")),
+            ]
+        );
+        let departures: Vec<(String, bool)> = conn
+            .prepare("SELECT name, seen_active FROM founder_departures ORDER BY founder_id")
+            .unwrap()
+            .query_map([], |r| Ok((r.get(0)?, r.get(1)?)))
+            .unwrap()
+            .collect::<rusqlite::Result<_>>()
+            .unwrap();
+        assert_eq!(departures, [("Max Roe".to_string(), true), ("Ann Lee".to_string(), false)]);
+
+        process(3, &[founder("Max Roe", true)]);
+        let back: Option<String> =
+            conn.query_row("SELECT departed_at FROM founders WHERE name = 'Max Roe'", [], |r| r.get(0)).unwrap();
+        assert_eq!(back, None, "listed as a current founder again");
+    }
+
+    #[test]
+    fn founder_links_resolve_and_follow_merges() {
+        let conn = Connection::open_in_memory().unwrap();
+        init_schema(&conn).unwrap();
+        conn.execute("INSERT INTO companies (slug, url) VALUES ('acme', 'u/acme')", []).unwrap();
+        let founder = FounderRow {
+            company_slug: "acme".into(),
+            name: "Jane Doe".into(),
+            name_key: "jane doe".into(),
+            title: None,
+            bio: None,
+            is_active: true,
+            linkedin: None,
+            twitter: None,
+            source_lines: None,
+        };
+        let link = |url: &str, founder_key: Option<&str>| LinkRow {
+            company_slug: "acme".into(),
+            url: url.into(),
+            domain: "linkedin.com".into(),
+            link_type: Some("linkedin".into()),
+            publisher: None,
+            founder_key: founder_key.map(str::to_string),
+            source_lines: None,
+        };
+        let links = [
+            link("https://linkedin.com/in/janedoe", Some("jane doe")),
+            link("https://linkedin.com/company/acme", None),
+            link("https://linkedin.com/in/someone", Some("not listed")),
+        ];
+        save_extracted(&conn, &[], &[founder], &[], &[], &links).unwrap();
+        let founder_ids = |conn: &Connection| -> Vec<Option<i64>> {
+            let mut stmt = conn.prepare("SELECT founder_id FROM company_links ORDER BY id").unwrap();
+            stmt.query_map([], |r| r.get(0)).unwrap().collect::<Result<_, _>>().unwrap()
+        };
+        let jane: i64 = conn.query_row("SELECT id FROM founders", [], |r| r.get(0)).unwrap();
+        assert_eq!(founder_ids(&conn), vec![Some(jane), None, None]);
+        let experience = |org: &str| ExperienceRow {
+            company_slug: "acme".into(),
+            founder_key: "jane doe".into(),
+            kind: "employer",
+            org: org.into(),
+        };
+        save_experience(&conn, &["acme"], &[experience("Google"), experience("Stripe")]).unwrap();
+        save_experience(&conn, &["acme"], &[experience("Google")]).unwrap();
+        let experience_founders = |conn: &Connection| -> Vec<(i64, String)> {
+            let mut stmt = conn.prepare("SELECT founder_id, org FROM founder_experience ORDER BY org").unwrap();
+            stmt.query_map([], |r| Ok((r.get(0)?, r.get(1)?))).unwrap().collect::<Result<_, _>>().unwrap()
+        };
+        assert_eq!(experience_founders(&conn), vec![(jane, "Google".to_string())], "re-processing replaces rows");
+
+        // A duplicate spelling that wins the merge takes over the link
+        conn.execute(
+            "INSERT INTO founders (company_slug, name, name_key, bio) VALUES ('acme', 'Jane  Doe', 'jane doe', 'CEO')",
+            [],
+        )
+        .unwrap();
+        let groups = fetch_duplicate_founders(&conn).unwrap();
+        assert_eq!(merge_duplicate_founders(&conn, &groups).unwrap(), 1);
+        let keeper: i64 = conn.query_row("SELECT id FROM founders", [], |r| r.get(0)).unwrap();
+        assert_ne!(keeper, jane);
+        assert_eq!(founder_ids(&conn), vec![Some(keeper), None, None]);
+        assert_eq!(experience_founders(&conn), vec![(keeper, "Google".to_string())]);
+
+        // Re-processing a page that no longer ties the link to her keeps it
+        save_extracted(&conn, &[], &[], &[], &[], &[link("https://linkedin.com/in/janedoe", None)]).unwrap();
+        assert_eq!(founder_ids(&conn), vec![Some(keeper), None, None]);
+    }
+
+    #[test]
+    fn process_metrics_keep_recent_runs() {
+        let conn = Connection::open_in_memory().unwrap();
+        init_schema(&conn).unwrap();
+        for (i, founders) in [10, 12, 9].into_iter().enumerate() {
+            let m = ProcessMetrics {
+                run_id: format!("run-{}", i),
+                command: "process".into(),
+                pages: 5,
+                founders,
+                parse_ms: 1.5,
+                ..Default::default()
+            };
+            save_process_metrics(&conn, &m).unwrap();
+        }
+        let runs = fetch_process_metrics(&conn, 2).unwrap();
+        let ids: Vec<(&str, usize)> = runs.iter().map(|m| (m.run_id.as_str(), m.founders)).collect();
+        assert_eq!(ids, vec![("run-1", 12), ("run-2", 9)]);
+        assert!(runs[0].finished_at.is_some());
+        assert_eq!(runs[0].parse_ms, 1.5);
+    }
+
+    #[test]
+    fn purge_removes_company_and_blocks_requeue() {
+        let conn = Connection::open_in_memory().unwrap();
+        init_schema(&conn).unwrap();
+        conn.execute_batch(
+            "INSERT INTO pages (id, url, slug) VALUES (1, 'u/acme', 'acme'), (2, 'u/beta', 'beta');
+             INSERT INTO page_data (id, page_id, url, slug, markdown) VALUES (1, 1, 'u/acme', 'acme', 'md');
+             INSERT INTO company_sections (page_id, slug, url) VALUES (1, 'acme', 'u/acme');
+             INSERT INTO companies (slug, url) VALUES ('acme', 'u/acme'), ('beta', 'u/beta');
+             INSERT INTO founders (id, company_slug, name, twitter) VALUES
+                 (1, 'acme', 'Jane Doe', 'https://x.com/janedoe'),
+                 (2, 'acme', 'Sam Lee', 'https://twitter.com/samlee'),
+                 (3, 'beta', 'Sam Lee', 'https://x.com/SamLee');
+             INSERT INTO founder_social_stats (handle, provider) VALUES ('janedoe', 'x'), ('samlee', 'x');
+             INSERT INTO company_links (company_slug, url, domain, founder_id)
+                 VALUES ('acme', 'https://linkedin.com/in/jane', 'linkedin.com', 1);
+             INSERT INTO company_jobs (id, company_slug, title, url) VALUES (1, 'acme', 'Eng', 'u/j');
+             INSERT INTO job_details (job_id, markdown) VALUES (1, 'job');
+             INSERT INTO news (company_slug, title, url, article_id)
+                 VALUES ('acme', 'A', 'https://t.co/a', 'a'), ('beta', 'A', 'https://t.co/a', 'a'),
+                        ('acme', 'B', 'https://t.co/b', 'b');
+             INSERT INTO company_references (from_slug, to_slug, section) VALUES ('acme', 'beta', 'news'), ('beta', 'acme', 'news');",
+        )
+        .unwrap();
+        rebuild_news_global(&conn).unwrap();
+        let count = |sql: &str| -> i64 { conn.query_row(sql, [], |r| r.get(0)).unwrap() };
+
+        let preview = purge_companies(&conn, &["acme".into()], true, Some("founder request"), true).unwrap();
+        assert!(preview.contains(&("founders", 2)) && preview.contains(&("news_global", 1)), "{:?}", preview);
+        assert!(preview.contains(&("founder_social_stats", 1)), "{:?}", preview);
+        assert_eq!(count("SELECT COUNT(*) FROM founders"), 3, "dry run keeps everything");
+
+        let removed = purge_companies(&conn, &["acme".into()], true, Some("founder request"), false).unwrap();
+        assert_eq!(removed, preview);
+        for table in ["pages", "page_data", "company_sections"] {
+            assert_eq!(count(&format!("SELECT COUNT(*) FROM {} WHERE slug = 'acme'", table)), 0, "{}", table);
+        }
+        for table in ["founders", "company_links", "company_jobs", "news"] {
+            assert_eq!(count(&format!("SELECT COUNT(*) FROM {} WHERE company_slug = 'acme'", table)), 0, "{}", table);
+        }
+        assert_eq!(count("SELECT COUNT(*) FROM job_details"), 0);
+        assert_eq!(count("SELECT COUNT(*) FROM company_references"), 0, "edges from and to acme");
+        assert_eq!(count("SELECT COUNT(*) FROM companies"), 1);
+        assert_eq!(count("SELECT company_count FROM news_global WHERE article_id = 'a'"), 1);
+        let stats: String = conn.query_row("SELECT group_concat(handle) FROM founder_social_stats", [], |r| r.get(0)).unwrap();
+        assert_eq!(stats, "samlee", "a handle beta's founder lists too stays");
+        assert!(find_orphans(&conn).unwrap().iter().all(|o| o.count == 0));
+
+        let page = |url: &str, slug: &str| PageUrl { url: url.into(), slug: slug.into(), lastmod: None };
+        assert_eq!(insert_pages(&conn, &[page("u/acme", "acme"), page("u/gamma", "gamma")]).unwrap(), 1);
+        let urls: Vec<String> = vec!["u/gamma".into()];
+        assert_eq!(pages_missing_from(&conn, &urls).unwrap(), vec!["beta".to_string()]);
+
+        let listing = "https://www.ycombinator.com/launches";
+        assert_eq!(insert_discovered(&conn, &[page("u/acme", "acme"), page("u/delta", "delta")], listing).unwrap(), 1);
+        assert_eq!(insert_pages(&conn, &[page("u/delta", "delta")]).unwrap(), 0);
+        let via: Option<String> =
+            conn.query_row("SELECT discovered_via FROM pages WHERE slug = 'delta'", [], |r| r.get(0)).unwrap();
+        assert_eq!(via.as_deref(), Some(listing));
+    }
+
+    #[test]
+    fn backfill_fills_only_missing_derived_columns() {
+        let conn = Connection::open_in_memory().unwrap();
+        init_schema(&conn).unwrap();
+        conn.execute_batch(
+            "INSERT INTO companies (slug, url, batch, batch_season, batch_year, founded_year) VALUES
+                 ('a', 'u/a', 'W24', NULL, NULL, 2023),
+                 ('b', 'u/b', NULL, 'Summer', 2013, NULL),
+                 ('c', 'u/c', 'Winter 2015', 'Winter', 2015, 2020),
+                 ('d', 'u/d', 'sometime', NULL, NULL, NULL);
+             INSERT INTO companies (slug, url, founded_year, company_age_years, quality_score) VALUES
+                 ('e', 'u/e', 2010, 3, 0);
+             INSERT INTO company_jobs (company_slug, title, url, salary) VALUES
+                 ('a', 'Engineer', 'j/1', '€50K – €70K'),
+                 ('a', 'Designer', 'j/2', 'San Francisco, CA');
+             INSERT INTO founders (company_slug, name, name_key, is_active) VALUES
+                 ('a', 'Jane Doe', 'jane doe', 1), ('a', 'Max Roe', 'max roe', 0);
+             INSERT INTO company_links (company_slug, url, domain, link_type) VALUES
+                 ('a', 'https://linkedin.com/in/janedoe', 'linkedin.com', 'linkedin');
+             INSERT INTO pages (id, url, slug) VALUES (1, 'u/a', 'a');
+             INSERT INTO page_data (id, page_id, url, slug, scraped_at) VALUES (1, 1, 'u/a', 'a', '2024-03-01 10:00:00');
+             INSERT INTO company_sections (page_id, slug, url) VALUES (1, 'a', 'u/a');
+             INSERT INTO news (company_slug, title, url, published, article_id) VALUES
+                 ('a', 'A', 'https://t.co/a', 'May 07, 2023', 'a'),
+                 ('a', 'B', 'https://t.co/b', '2023-06-01', 'b'),
+                 ('a', 'C', 'https://t.co/c', 'last spring', 'c');",
+        )
+        .unwrap();
+        rebuild_news_global(&conn).unwrap();
+        let rows = |filled: &[Backfilled]| filled.iter().map(|b| b.rows).collect::<Vec<_>>();
+        let rates = std::collections::BTreeMap::from([("EUR".to_string(), 1.1)]);
+
+        assert_eq!(rows(&backfill_derived(&conn, &rates, &[], true).unwrap()), [1, 1, 2, 4, 1, 1, 1, 2, 1]);
+        let count = |sql: &str| -> i64 { conn.query_row(sql, [], |r| r.get(0)).unwrap() };
+        assert_eq!(count("SELECT COUNT(*) FROM companies WHERE batch_year IS NOT NULL"), 2, "dry run keeps nothing");
+
+        assert_eq!(rows(&backfill_derived(&conn, &rates, &[], false).unwrap()), [1, 1, 2, 4, 1, 1, 1, 2, 1]);
+        let a: (String, i64) =
+            conn.query_row("SELECT batch_season, batch_year FROM companies WHERE slug = 'a'", [], |r| Ok((r.get(0)?, r.get(1)?))).unwrap();
+        assert_eq!(a, ("Winter".to_string(), 2024));
+        assert_eq!(count("SELECT batch = 'Summer 2013' FROM companies WHERE slug = 'b'"), 1);
+        assert_eq!(count("SELECT company_age_years IS NULL FROM companies WHERE slug = 'c'"), 1, "2020 fails the batch check");
+        let age = chrono::Datelike::year(&chrono::Utc::now()) as i64 - 2010;
+        assert_eq!(count("SELECT company_age_years FROM companies WHERE slug = 'e'"), age, "a stale age is refreshed");
+        assert_eq!(count("SELECT salary_max_usd FROM company_jobs WHERE url = 'j/1'"), 77_000);
+        assert_eq!(count("SELECT quality_score FROM companies WHERE slug = 'b'"), 15, "scored after its batch is filled");
+        assert_eq!(count("SELECT COUNT(*) FROM founders WHERE first_seen_at = '2024-03-01 10:00:00'"), 2);
+        assert_eq!(count("SELECT departed_at = '2024-03-01 10:00:00' FROM founders WHERE name = 'Max Roe'"), 1);
+        assert_eq!(count("SELECT departed FROM founders WHERE name = 'Jane Doe'"), 0);
+        assert_eq!(count("SELECT published = '2023-05-07' FROM news_global WHERE article_id = 'a'"), 1);
+        assert_eq!(count("SELECT published = 'last spring' FROM news WHERE title = 'C'"), 1, "unparsed text stays");
+        assert_eq!(rows(&backfill_derived(&conn, &rates, &[], false).unwrap()), [0, 0, 0, 0, 0, 0, 0, 0, 0]);
+    }
+
+    #[test]
+    fn quality_score_reads_founders_and_description() {
+        let conn = Connection::open_in_memory().unwrap();
+        init_schema(&conn).unwrap();
+        conn.execute_batch(
+            "INSERT INTO companies (slug, url, name, status, twitter, github) VALUES
+                 ('acme', 'u/acme', 'Acme', 'Active', 'https://x.com/acme', 'https://github.com/acme'),
+                 ('stub', 'u/stub', NULL, NULL, NULL, NULL);
+             INSERT INTO founders (company_slug, name) VALUES ('acme', 'Jane Doe');
+             INSERT INTO pages (id, url, slug) VALUES (1, 'u/acme', 'acme');
+             INSERT INTO page_data (id, page_id, url, slug, status) VALUES (1, 1, 'u/acme', 'acme', 200);",
+        )
+        .unwrap();
+        conn.execute(
+            "INSERT INTO company_sections (page_id, slug, url, description) VALUES (1, 'acme', 'u/acme', ?1)",
+            ["x".repeat(150)],
+        )
+        .unwrap();
+        assert_eq!(refresh_quality_scores(&conn, &["acme", "stub", "gone"]).unwrap(), 2);
+        let scores: Vec<(String, Option<i32>)> = fetch_scored_companies(&conn)
+            .unwrap()
+            .into_iter()
+            .map(|c| (c.slug, c.quality_score))
+            .collect();
+        // name 15 + status 10 + founder 25 + 2 of 3 socials 10 + half a description 10
+        assert_eq!(scores, [("acme".to_string(), Some(70)), ("stub".to_string(), Some(0))]);
+
+        let overview = fetch_overview(&conn, &[], &[], Some(1), true, 10).unwrap();
+        assert_eq!(overview.iter().map(|r| r.slug.as_str()).collect::<Vec<_>>(), ["acme"]);
+    }
+
+    #[test]
+    fn change_feed_logs_real_changes_only() {
+        let conn = Connection::open_in_memory().unwrap();
+        init_schema(&conn).unwrap();
+        let start: Timestamp = "2000-01-01".parse().unwrap();
+        conn.execute_batch(
+            "INSERT INTO companies (slug, url, tagline) VALUES ('acme', 'u/acme', 'Payroll');
+             INSERT INTO founders (id, company_slug, name) VALUES (7, 'acme', 'Jane Doe');
+             UPDATE companies SET tagline = 'Payroll' WHERE slug = 'acme';
+             UPDATE founders SET source_lines = '3-4' WHERE id = 7;
+             UPDATE companies SET tagline = 'Payroll for teams', team_size = 4 WHERE slug = 'acme';
+             DELETE FROM founders WHERE id = 7;",
+        )
+        .unwrap();
+
+        let events: Vec<(String, String, String, Option<Vec<String>>)> = changes(&conn, start)
+            .unwrap()
+            .into_iter()
+            .map(|e| (e.table, e.key, e.op, e.columns))
+            .collect();
+        let cols = |c: &[&str]| Some(c.iter().map(|s| s.to_string()).collect());
+        assert_eq!(
+            events,
+            vec![
+                ("companies".into(), "acme".into(), "insert".into(), None),
+                ("founders".into(), "7".into(), "insert".into(), None),
+                ("companies".into(), "acme".into(), "update".into(), cols(&["tagline", "team_size"])),
+                ("founders".into(), "7".into(), "delete".into(), None),
+            ]
+        );
+        assert!(changes(&conn, "2999-01-01".parse().unwrap()).unwrap().is_empty());
+    }
+
+    #[test]
+    fn analytics_views() {
+        let conn = Connection::open_in_memory().unwrap();
+        init_schema(&conn).unwrap();
+        conn.execute_batch(
+            "INSERT INTO companies (slug, url, name, batch, batch_year, batch_season, status, team_size) VALUES
+                 ('acme', 'u/acme', 'Acme', 'Winter 2024', 2024, 'Winter', 'Active', 10),
+                 ('gone', 'u/gone', 'Gone', 'Winter 2024', 2024, 'Winter', 'Inactive', 2),
+                 ('late', 'u/late', 'Late', 'Winter 2024', 2024, 'Winter', 'Active', 90);
+             INSERT INTO extraction_quality (company_slug, check_name, expected, actual)
+                 VALUES ('late', 'founded_year_inconsistent', 2025, 2031);
+             INSERT INTO founders (company_slug, name, linkedin) VALUES
+                 ('acme', 'Jane Doe', 'https://linkedin.com/in/jane'), ('acme', 'John Roe', NULL),
+                 ('gone', 'Max Moe', ''), ('late', 'Ann Lee', NULL);
+             INSERT INTO company_jobs (company_slug, title, url, salary) VALUES
+                 ('acme', 'Engineer', '/jobs/1', '$120K - $150K'), ('acme', 'Designer', '/jobs/2', NULL);",
+        )
+        .unwrap();
+        let one = |sql: &str| -> String { conn.query_row(sql, [], |r| r.get::<_, String>(0)).unwrap() };
+        assert_eq!(one("SELECT group_concat(slug) FROM active_companies"), "acme,late");
+        assert_eq!(one("SELECT slug || ':' || open_jobs || ':' || jobs_with_salary FROM hiring_companies"), "acme:2:1");
+        assert_eq!(
+            one("SELECT group_concat(slug || ':' || founders_with_linkedin) FROM companies_with_founder_links"),
+            "acme:1"
+        );
+        assert_eq!(one("SELECT group_concat(title) FROM jobs_with_salary"), "Engineer");
+        assert_eq!(
+            one("SELECT companies || '/' || active || '/' || inactive || '/' || avg_team_size || '/' || hiring || '/' || founders
+                 FROM batch_summary"),
+            "2/1/1/6.0/1/3",
+            "the flagged founding year is left out"
+        );
+    }
+}
@@ -0,0 +1,72 @@
+use anyhow::Result;
+use rusqlite::Connection;
+
+use crate::db::{self, ScrapeRow};
+
+/// Company pages bundled into the binary, as spider.cloud returned them.
+const FIXTURES: &[(&str, &str)] = &[
+    ("stripe", include_str!("../tests/fixtures/stripe.md")),
+    ("doordash", include_str!("../tests/fixtures/doordash.md")),
+    ("groupahead", include_str!("../tests/fixtures/groupahead.md")),
+];
+
+/// Queue the fixture pages and store them as scraped, through the same
+/// writes a real scrape makes. Returns the number of pages stored.
+pub fn seed(conn: &Connection) -> Result<usize> {
+    let pages: Vec<db::PageUrl> = FIXTURES
+        .iter()
+        .map(|(slug, _)| db::PageUrl {
+            url: format!("https://www.ycombinator.com/companies/{}", slug),
+            slug: slug.to_string(),
+            lastmod: None,
+        })
+        .collect();
+    db::insert_pages(conn, &pages)?;
+
+    let mut stored = 0;
+    for (page_id, url, slug) in db::fetch_unvisited(conn, None, db::ScrapeOrder::Id)? {
+        let Some((_, markdown)) = FIXTURES.iter().find(|(s, _)| *s == slug) else {
+            continue;
+        };
+        db::save_scrape(
+            conn,
+            &ScrapeRow {
+                page_id,
+                url,
+                slug,
+                markdown: Some(markdown.to_string()),
+                status: Some(200),
+                error: None,
+                latency_ms: Some(0),
+                metadata: None,
+                final_url: None,
+                scrape_meta: None,
+                markdown_raw: None,
+                html: false,
+            },
+            false,
+        )?;
+        stored += 1;
+    }
+    Ok(stored)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn seeded_pages_are_ready_to_process() {
+        let conn = Connection::open_in_memory().unwrap();
+        db::init_schema(&conn).unwrap();
+        assert_eq!(seed(&conn).unwrap(), FIXTURES.len());
+        assert!(db::fetch_unvisited(&conn, None, db::ScrapeOrder::Id).unwrap().is_empty());
+        let chunks = db::stream_unprocessed(&conn, 2, None).collect::<Result<Vec<_>>>().unwrap();
+        assert_eq!(chunks.iter().map(Vec::len).collect::<Vec<_>>(), vec![2, 1]);
+        let limited = db::stream_unprocessed(&conn, 2, Some(1)).collect::<Result<Vec<_>>>().unwrap();
+        assert_eq!(limited.iter().map(Vec::len).collect::<Vec<_>>(), vec![1]);
+        assert_eq!(db::count_unprocessed(&conn, None).unwrap(), FIXTURES.len());
+        let pages: Vec<_> = chunks.into_iter().flatten().collect();
+        assert!(pages.iter().any(|p| p.slug == "stripe"));
+    }
+}
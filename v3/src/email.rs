@@ -0,0 +1,227 @@
+use std::collections::BTreeMap;
+use std::fmt::Write;
+
+use anyhow::Result;
+use rusqlite::Connection;
+use serde::Deserialize;
+
+use crate::db::{self, NewCompany, ProcessMetrics, RunManifest};
+
+/// `[email]` in `yc.toml`: where `report --email` sends the run summary.
+/// The SMTP password comes from `SMTP_PASSWORD`, never the file.
+#[derive(Debug, Default, Clone, Deserialize)]
+#[serde(deny_unknown_fields, default)]
+pub struct EmailConfig {
+    pub smtp_host: Option<String>,
+    /// 465 connects over TLS, anything else upgrades with STARTTLS (default 587).
+    pub smtp_port: Option<u16>,
+    /// Log in as this user with `SMTP_PASSWORD`; unset sends without auth.
+    pub username: Option<String>,
+    /// Sender, e.g. "YC scraper <bot@example.com>".
+    pub from: Option<String>,
+    pub to: Vec<String>,
+    /// Defaults to one naming the run and what changed.
+    pub subject: Option<String>,
+}
+
+#[cfg_attr(not(feature = "email"), allow(dead_code))]
+const DEFAULT_SMTP_PORT: u16 = 587;
+
+/// What the latest finished run did, for the email.
+pub struct RunSummary {
+    pub manifest: RunManifest,
+    /// (table, rows before, rows after) for the published tables.
+    pub counts: Vec<(String, i64, i64)>,
+    pub metrics: Option<ProcessMetrics>,
+    /// Companies first stored during the run.
+    pub new_companies: Vec<NewCompany>,
+    /// (slug, old status, new status).
+    pub status_changes: Vec<(String, String, String)>,
+}
+
+/// Summary of the most recently finished scrape / process / run / refresh;
+/// `None` before any has finished.
+pub fn summarize(conn: &Connection) -> Result<Option<RunSummary>> {
+    let Some(manifest) = db::fetch_latest_run_manifest(conn)? else {
+        return Ok(None);
+    };
+    let since = manifest.started_at.clone().unwrap_or_default();
+    let parse = |s: Option<&str>| -> BTreeMap<String, i64> {
+        s.and_then(|s| serde_json::from_str(s).ok()).unwrap_or_default()
+    };
+    let before = parse(Some(manifest.counts_before.as_str()));
+    let after = parse(manifest.counts_after.as_deref());
+    let counts = after
+        .iter()
+        .map(|(table, &n)| (table.clone(), before.get(table).copied().unwrap_or(0), n))
+        .collect();
+    Ok(Some(RunSummary {
+        counts,
+        metrics: db::fetch_run_metrics(conn, &manifest.run_id)?,
+        new_companies: db::fetch_new_companies(conn, &since)?,
+        status_changes: db::fetch_field_changes(conn, "status", &since)?,
+        manifest,
+    }))
+}
+
+/// "YC scraper: refresh finished 2024-05-01 02:10:00 (+12 companies, 3 status changes)"
+pub fn subject(summary: &RunSummary) -> String {
+    let m = &summary.manifest;
+    format!(
+        "YC scraper: {} finished {} (+{} companies, {} status changes)",
+        m.command,
+        m.finished_at.as_deref().unwrap_or("-"),
+        summary.new_companies.len(),
+        summary.status_changes.len()
+    )
+}
+
+/// The summary as a self-contained HTML page (inline styles only, as mail
+/// clients drop stylesheets).
+pub fn render_html(summary: &RunSummary) -> String {
+    const TABLE: &str = "border-collapse:collapse;font-family:sans-serif;font-size:14px";
+    const CELL: &str = "border:1px solid #ddd;padding:4px 8px";
+    let m = &summary.manifest;
+    let mut html = String::new();
+    let row = |html: &mut String, cells: &[String]| {
+        html.push_str("<tr>");
+        for c in cells {
+            let _ = write!(html, "<td style=\"{}\">{}</td>", CELL, c);
+        }
+        html.push_str("</tr>\n");
+    };
+
+    let _ = writeln!(html, "<html><body style=\"font-family:sans-serif\">");
+    let _ = writeln!(html, "<h2>{} run {}</h2>", escape(&m.command), escape(&m.run_id));
+    let _ = writeln!(
+        html,
+        "<p>{} → {} · yc_scraper {}{}</p>",
+        escape(m.started_at.as_deref().unwrap_or("-")),
+        escape(m.finished_at.as_deref().unwrap_or("-")),
+        escape(&m.crate_version),
+        m.git_hash.as_deref().map(|h| format!(" ({})", escape(h))).unwrap_or_default()
+    );
+
+    let _ = writeln!(html, "<h3>Rows</h3>\n<table style=\"{}\">", TABLE);
+    row(&mut html, &["<b>Table</b>".into(), "<b>Before</b>".into(), "<b>After</b>".into(), "<b>Change</b>".into()]);
+    for (table, before, after) in &summary.counts {
+        let delta = after - before;
+        let change = if delta == 0 { String::new() } else { format!("{:+}", delta) };
+        row(&mut html, &[escape(table), before.to_string(), after.to_string(), change]);
+    }
+    html.push_str("</table>\n");
+
+    if let Some(p) = &summary.metrics {
+        let _ = writeln!(
+            html,
+            "<p>Processed {} pages ({} empty) in {:.1}s: {} founders, {} news, {} jobs, {} links, {} quality flags.</p>",
+            p.pages,
+            p.empty_pages,
+            p.elapsed_ms / 1000.0,
+            p.founders,
+            p.news,
+            p.jobs,
+            p.links,
+            p.quality_flags
+        );
+    }
+
+    let _ = writeln!(html, "<h3>New companies ({})</h3>", summary.new_companies.len());
+    if summary.new_companies.is_empty() {
+        html.push_str("<p>None.</p>\n");
+    } else {
+        let _ = writeln!(html, "<table style=\"{}\">", TABLE);
+        for (slug, name, batch) in &summary.new_companies {
+            let link = format!(
+                "<a href=\"https://www.ycombinator.com/companies/{}\">{}</a>",
+                escape(slug),
+                escape(name.as_deref().unwrap_or(slug))
+            );
+            row(&mut html, &[link, escape(batch.as_deref().unwrap_or("-"))]);
+        }
+        html.push_str("</table>\n");
+    }
+
+    let _ = writeln!(html, "<h3>Status changes ({})</h3>", summary.status_changes.len());
+    if summary.status_changes.is_empty() {
+        html.push_str("<p>None.</p>\n");
+    } else {
+        let _ = writeln!(html, "<table style=\"{}\">", TABLE);
+        for (slug, old, new) in &summary.status_changes {
+            row(&mut html, &[escape(slug), format!("{} → {}", escape(old), escape(new))]);
+        }
+        html.push_str("</table>\n");
+    }
+    html.push_str("</body></html>\n");
+    html
+}
+
+fn escape(s: &str) -> String {
+    s.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;").replace('"', "&quot;")
+}
+
+#[cfg(not(feature = "email"))]
+pub fn send(_config: &EmailConfig, _subject: &str, _html: String) -> Result<()> {
+    anyhow::bail!("report --email needs a build with the email feature")
+}
+
+/// Send `html` to every `[email] to` address over SMTP. Blocks; call it
+/// from `block_in_place` inside the runtime.
+#[cfg(feature = "email")]
+pub fn send(config: &EmailConfig, subject: &str, html: String) -> Result<()> {
+    use anyhow::Context;
+    use lettre::message::header::ContentType;
+    use lettre::transport::smtp::authentication::Credentials;
+    use lettre::{Message, SmtpTransport, Transport};
+
+    let host = config.smtp_host.as_deref().context("[email] smtp_host is not set")?;
+    let from = config.from.as_deref().context("[email] from is not set")?;
+    anyhow::ensure!(!config.to.is_empty(), "[email] to lists no recipients");
+    let mut message = Message::builder()
+        .from(from.parse().with_context(|| format!("[email] from: {}", from))?)
+        .subject(subject)
+        .header(ContentType::TEXT_HTML);
+    for to in &config.to {
+        message = message.to(to.parse().with_context(|| format!("[email] to: {}", to))?);
+    }
+    let message = message.body(html)?;
+
+    let port = config.smtp_port.unwrap_or(DEFAULT_SMTP_PORT);
+    let mut transport = if port == 465 { SmtpTransport::relay(host)? } else { SmtpTransport::starttls_relay(host)? };
+    transport = transport.port(port);
+    if let Some(user) = &config.username {
+        let password = std::env::var("SMTP_PASSWORD").context("SMTP_PASSWORD must be set when [email] username is")?;
+        transport = transport.credentials(Credentials::new(user.clone(), password));
+    }
+    transport.build().send(&message).with_context(|| format!("sending via {}:{}", host, port))?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn summary_renders_counts_new_companies_and_status_changes() {
+        let summary = RunSummary {
+            manifest: RunManifest {
+                run_id: "20240501T020000-1".into(),
+                command: "refresh".into(),
+                crate_version: "0.1.0".into(),
+                started_at: Some("2024-05-01 02:00:00".into()),
+                finished_at: Some("2024-05-01 02:10:00".into()),
+                ..Default::default()
+            },
+            counts: vec![("companies".into(), 10, 12), ("founders".into(), 20, 20)],
+            metrics: None,
+            new_companies: vec![("acme".into(), Some("Acme <Labs>".into()), Some("Winter 2024".into()))],
+            status_changes: vec![("beta".into(), "Active".into(), "Acquired".into())],
+        };
+        assert_eq!(subject(&summary), "YC scraper: refresh finished 2024-05-01 02:10:00 (+1 companies, 1 status changes)");
+        let html = render_html(&summary);
+        assert!(html.contains(">+2<"), "{}", html);
+        assert!(html.contains("https://www.ycombinator.com/companies/acme\">Acme &lt;Labs&gt;</a>"));
+        assert!(html.contains("Active → Acquired"));
+        assert!(html.contains("<h3>New companies (1)</h3>"));
+    }
+}
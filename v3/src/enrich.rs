@@ -0,0 +1,247 @@
+use anyhow::Result;
+use rusqlite::Connection;
+use serde::Deserialize;
+
+use crate::db::{self, SocialStats};
+use crate::parser::extract::links::profile_handle;
+
+/// Where `enrich` gets founder profile stats from.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Provider {
+    /// Enrich nothing.
+    #[default]
+    None,
+    /// The X API v2 user lookup (`twitter` feature, `X_BEARER_TOKEN`).
+    X,
+}
+
+/// `[enrich]` in `yc.toml`.
+#[derive(Debug, Default, Clone, Deserialize)]
+#[serde(deny_unknown_fields, default)]
+pub struct EnrichConfig {
+    pub provider: Provider,
+    /// Stats younger than this are not fetched again.
+    pub max_age_days: Option<u32>,
+}
+
+const DEFAULT_MAX_AGE_DAYS: u32 = 30;
+
+/// A source of founder social profile data. Implement it to plug in another
+/// data provider; the default lookup enriches nothing.
+pub trait Enricher {
+    /// Recorded in `founder_social_stats.provider`.
+    fn name(&self) -> &'static str;
+
+    /// Public stats for a lowercase Twitter/X handle; `None` when the
+    /// provider has no such profile.
+    fn twitter_profile(&self, _handle: &str) -> Result<Option<SocialStats>> {
+        Ok(None)
+    }
+}
+
+pub struct NoEnrichment;
+
+impl Enricher for NoEnrichment {
+    fn name(&self) -> &'static str {
+        "none"
+    }
+}
+
+pub fn open(provider: Provider) -> Result<Box<dyn Enricher>> {
+    match provider {
+        Provider::None => Ok(Box::new(NoEnrichment)),
+        Provider::X => x_provider(),
+    }
+}
+
+#[cfg(not(feature = "twitter"))]
+fn x_provider() -> Result<Box<dyn Enricher>> {
+    anyhow::bail!("the x enrichment provider needs a build with the twitter feature")
+}
+
+#[cfg(feature = "twitter")]
+fn x_provider() -> Result<Box<dyn Enricher>> {
+    Ok(Box::new(x::XApi::from_env()?))
+}
+
+/// Handle of a twitter.com / x.com profile URL.
+pub fn twitter_handle(url: &str) -> Option<String> {
+    let lower = url.to_lowercase();
+    let rest = lower.split("//").nth(1).unwrap_or(&lower);
+    let host = rest.split(['/', '?', '#']).next().unwrap_or("").trim_start_matches("www.");
+    matches!(host, "twitter.com" | "x.com")
+        .then(|| profile_handle(&lower))
+        .flatten()
+        .map(|h| h.trim_start_matches('@').to_string())
+}
+
+/// Look up every founder handle without stats from the last
+/// `max_age_days` (at most `limit`) and save what the provider returns.
+/// Returns (handles looked up, profiles saved). Rows are saved as they
+/// arrive, so a provider error keeps everything fetched before it.
+pub fn enrich_founders(
+    conn: &Connection,
+    enricher: &dyn Enricher,
+    max_age_days: Option<u32>,
+    limit: Option<usize>,
+) -> Result<(usize, usize)> {
+    let fresh = db::fetch_fresh_social_handles(conn, max_age_days.unwrap_or(DEFAULT_MAX_AGE_DAYS))?;
+    let mut handles: Vec<String> = db::fetch_founder_twitter(conn)?
+        .iter()
+        .filter_map(|url| twitter_handle(url))
+        .filter(|h| !fresh.contains(h))
+        .collect();
+    handles.sort();
+    handles.dedup();
+    handles.truncate(limit.unwrap_or(usize::MAX));
+
+    let mut saved = 0;
+    for handle in &handles {
+        if let Some(stats) = enricher.twitter_profile(handle)? {
+            db::save_social_stats(conn, handle, enricher.name(), &stats)?;
+            saved += 1;
+        }
+    }
+    Ok((handles.len(), saved))
+}
+
+#[cfg(feature = "twitter")]
+mod x {
+    use anyhow::{Context, Result};
+    use serde::Deserialize;
+
+    use super::Enricher;
+    use crate::db::SocialStats;
+
+    const USER_LOOKUP_URL: &str = "https://api.twitter.com/2/users/by/username";
+
+    /// X API v2 user lookup with an app bearer token. Requests are sync
+    /// from the caller's view: `enrich` runs inside `block_in_place`.
+    pub struct XApi {
+        client: reqwest::Client,
+        token: String,
+        runtime: tokio::runtime::Handle,
+    }
+
+    impl XApi {
+        pub fn from_env() -> Result<Self> {
+            let token = std::env::var("X_BEARER_TOKEN").context("X_BEARER_TOKEN must be set for the x provider")?;
+            Ok(XApi { client: reqwest::Client::new(), token, runtime: tokio::runtime::Handle::current() })
+        }
+    }
+
+    #[derive(Deserialize)]
+    struct Lookup {
+        data: Option<User>,
+    }
+
+    #[derive(Deserialize)]
+    struct User {
+        name: Option<String>,
+        description: Option<String>,
+        public_metrics: Option<Metrics>,
+    }
+
+    #[derive(Deserialize)]
+    struct Metrics {
+        followers_count: Option<i64>,
+        following_count: Option<i64>,
+        tweet_count: Option<i64>,
+    }
+
+    /// A lookup response; unknown and suspended users come back without `data`.
+    pub(super) fn parse_user(body: &str) -> Result<Option<SocialStats>> {
+        let lookup: Lookup = serde_json::from_str(body)?;
+        Ok(lookup.data.map(|u| {
+            let m = u.public_metrics;
+            SocialStats {
+                name: u.name,
+                bio: u.description.filter(|d| !d.is_empty()),
+                followers: m.as_ref().and_then(|m| m.followers_count),
+                following: m.as_ref().and_then(|m| m.following_count),
+                posts: m.as_ref().and_then(|m| m.tweet_count),
+            }
+        }))
+    }
+
+    impl Enricher for XApi {
+        fn name(&self) -> &'static str {
+            "x"
+        }
+
+        fn twitter_profile(&self, handle: &str) -> Result<Option<SocialStats>> {
+            let url = format!("{}/{}?user.fields=description,public_metrics", USER_LOOKUP_URL, handle);
+            self.runtime.block_on(async {
+                let response = self.client.get(&url).bearer_auth(&self.token).send().await?;
+                if response.status() == reqwest::StatusCode::TOO_MANY_REQUESTS {
+                    anyhow::bail!("X API rate limit reached; run enrich again later to continue");
+                }
+                let body = response.error_for_status()?.text().await?;
+                parse_user(&body).with_context(|| format!("parsing the X profile of @{}", handle))
+            })
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        #[test]
+        fn parses_user_lookup() {
+            let body = r#"{"data":{"id":"1","name":"Jane Doe","username":"janedoe","description":"Building Acme",
+                "public_metrics":{"followers_count":1200,"following_count":300,"tweet_count":4500,"listed_count":9}}}"#;
+            let stats = parse_user(body).unwrap().unwrap();
+            assert_eq!(stats.followers, Some(1200));
+            assert_eq!(stats.bio.as_deref(), Some("Building Acme"));
+            let missing = r#"{"errors":[{"detail":"Could not find user with username: [nobody]."}]}"#;
+            assert_eq!(parse_user(missing).unwrap(), None);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Fixed;
+
+    impl Enricher for Fixed {
+        fn name(&self) -> &'static str {
+            "fixed"
+        }
+
+        fn twitter_profile(&self, handle: &str) -> Result<Option<SocialStats>> {
+            Ok((handle != "ghost").then(|| SocialStats { followers: Some(handle.len() as i64), ..Default::default() }))
+        }
+    }
+
+    #[test]
+    fn enriches_each_handle_once() {
+        assert_eq!(twitter_handle("https://x.com/JaneDoe?s=21").as_deref(), Some("janedoe"));
+        assert_eq!(twitter_handle("https://twitter.com/@bob/").as_deref(), Some("bob"));
+        assert_eq!(twitter_handle("https://dropbox.com/jane"), None);
+
+        let conn = Connection::open_in_memory().unwrap();
+        db::init_schema(&conn).unwrap();
+        conn.execute_batch(
+            "INSERT INTO companies (slug, url) VALUES ('acme', 'u/acme');
+             INSERT INTO founders (company_slug, name, twitter) VALUES
+                 ('acme', 'Jane', 'https://x.com/janedoe'),
+                 ('acme', 'Jane D.', 'https://twitter.com/JaneDoe'),
+                 ('acme', 'Ghost', 'https://x.com/ghost'),
+                 ('acme', 'Bob', 'https://github.com/bob');",
+        )
+        .unwrap();
+        assert_eq!(enrich_founders(&conn, &NoEnrichment, None, None).unwrap(), (2, 0));
+        assert_eq!(enrich_founders(&conn, &Fixed, None, None).unwrap(), (2, 1));
+        let (provider, followers): (String, i64) = conn
+            .query_row("SELECT provider, followers FROM founder_social_stats WHERE handle = 'janedoe'", [], |r| {
+                Ok((r.get(0)?, r.get(1)?))
+            })
+            .unwrap();
+        assert_eq!((provider.as_str(), followers), ("fixed", 7));
+        // Fresh stats are skipped; the handle without a profile is asked again
+        assert_eq!(enrich_founders(&conn, &Fixed, None, None).unwrap(), (1, 0));
+    }
+}
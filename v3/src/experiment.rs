@@ -0,0 +1,248 @@
+use std::collections::BTreeMap;
+
+use anyhow::Result;
+use rayon::prelude::*;
+
+use crate::db::{FounderRow, ScrapedPage};
+use crate::output::out;
+use crate::parser::extract::{founders, ExtractedData};
+use crate::parser::sections::{self, Section};
+use crate::parser::{self, blocks, lang, ParserConfig};
+
+/// Compared values for one page: field name → rendered value.
+pub type Fields = BTreeMap<&'static str, String>;
+
+/// One implementation of an extractor under test.
+pub struct Variant {
+    pub name: &'static str,
+    pub run: fn(&ScrapedPage, &ParserConfig) -> Fields,
+}
+
+/// A baseline and a candidate run over the same pages, field by field.
+pub struct Experiment {
+    pub name: &'static str,
+    pub about: &'static str,
+    pub baseline: Variant,
+    pub candidate: Variant,
+}
+
+/// Register new strategies here; `experiment run <name>` picks one.
+pub const EXPERIMENTS: &[Experiment] = &[
+    Experiment {
+        name: "founder-sections",
+        about: "Founders from Person blocks in founders sections only (current) vs in any section",
+        baseline: Variant { name: "founders-section", run: founders_in_founder_sections },
+        candidate: Variant { name: "any-section", run: founders_in_any_section },
+    },
+    Experiment {
+        name: "person-name-words",
+        about: "Whole pipeline with max_person_name_words from yc.toml (current) vs 4",
+        baseline: Variant { name: "configured", run: pipeline },
+        candidate: Variant { name: "max-4-words", run: pipeline_four_word_names },
+    },
+];
+
+/// Agreement on one field across pages.
+pub struct FieldReport {
+    pub field: &'static str,
+    pub agree: usize,
+    pub differ: usize,
+    /// First pages where the variants differ: (slug, baseline value, candidate value).
+    pub examples: Vec<(String, String, String)>,
+}
+
+pub fn find(name: &str) -> Result<&'static Experiment> {
+    EXPERIMENTS.iter().find(|e| e.name == name).ok_or_else(|| {
+        let names: Vec<&str> = EXPERIMENTS.iter().map(|e| e.name).collect();
+        anyhow::anyhow!("unknown experiment '{}' (expected one of: {})", name, names.join(", "))
+    })
+}
+
+/// Run both variants over every page, in parallel per chunk. Fields only one
+/// variant produces count as differing. Returns (pages, reports by field).
+pub fn run(
+    experiment: &Experiment,
+    pages: impl Iterator<Item = Result<Vec<ScrapedPage>>>,
+    config: &ParserConfig,
+    max_examples: usize,
+) -> Result<(usize, Vec<FieldReport>)> {
+    let mut reports: BTreeMap<&'static str, FieldReport> = BTreeMap::new();
+    let mut total = 0;
+    for chunk in pages {
+        let chunk = chunk?;
+        let outputs: Vec<(Fields, Fields)> = chunk
+            .par_iter()
+            .map(|page| ((experiment.baseline.run)(page, config), (experiment.candidate.run)(page, config)))
+            .collect();
+        for (page, (a, b)) in chunk.iter().zip(outputs) {
+            let mut names: Vec<&'static str> = a.keys().chain(b.keys()).copied().collect();
+            names.sort_unstable();
+            names.dedup();
+            for field in names {
+                let r = reports.entry(field).or_insert_with(|| FieldReport {
+                    field,
+                    agree: 0,
+                    differ: 0,
+                    examples: Vec::new(),
+                });
+                let (va, vb) = (a.get(field), b.get(field));
+                if va == vb {
+                    r.agree += 1;
+                    continue;
+                }
+                r.differ += 1;
+                if r.examples.len() < max_examples {
+                    let show = |v: Option<&String>| v.cloned().unwrap_or_else(|| "(missing)".into());
+                    r.examples.push((page.slug.clone(), show(va), show(vb)));
+                }
+            }
+        }
+        total += chunk.len();
+    }
+    Ok((total, reports.into_values().collect()))
+}
+
+pub fn print_experiments() {
+    for e in EXPERIMENTS {
+        out!("{:<20} {} ({} vs {})", e.name, e.about, e.baseline.name, e.candidate.name);
+    }
+}
+
+pub fn print_report(experiment: &Experiment, pages: usize, reports: &[FieldReport]) {
+    out!(
+        "{}: {} (baseline) vs {} (candidate) over {} pages\n",
+        experiment.name, experiment.baseline.name, experiment.candidate.name, pages
+    );
+    out!("{:<20} {:>8} {:>8} {:>7}", "field", "agree", "differ", "agree%");
+    for r in reports {
+        out!(
+            "{:<20} {:>8} {:>8} {:>6.1}%",
+            r.field,
+            r.agree,
+            r.differ,
+            r.agree as f64 * 100.0 / (r.agree + r.differ).max(1) as f64
+        );
+    }
+    for r in reports.iter().filter(|r| !r.examples.is_empty()) {
+        out!("\n── {} ──", r.field);
+        for (slug, a, b) in &r.examples {
+            out!("  {}", slug);
+            out!("    - {}", a);
+            out!("    + {}", b);
+        }
+    }
+}
+
+// ── Variants ──
+
+fn lex(page: &ScrapedPage, config: &ParserConfig) -> blocks::Lexed {
+    let lexicon = lang::Lexicon::for_lang(lang::detect_page(&page.markdown));
+    blocks::lex_with(&page.markdown, &lexicon, config)
+}
+
+fn founder_fields(founders: &[FounderRow]) -> Fields {
+    let mut names: Vec<&str> = founders.iter().map(|f| f.name.as_str()).collect();
+    names.sort_unstable();
+    let mut titles: Vec<String> =
+        founders.iter().map(|f| format!("{}: {}", f.name, f.title.as_deref().unwrap_or("-"))).collect();
+    titles.sort_unstable();
+    Fields::from([
+        ("founders.count", founders.len().to_string()),
+        ("founders.names", names.join("; ")),
+        ("founders.titles", titles.join("; ")),
+    ])
+}
+
+fn founders_in_founder_sections(page: &ScrapedPage, config: &ParserConfig) -> Fields {
+    let lexed = lex(page, config);
+    founder_fields(&founders::extract(&page.slug, &sections::cluster_sections_with(&lexed, config)))
+}
+
+fn founders_in_any_section(page: &ScrapedPage, config: &ParserConfig) -> Fields {
+    let lexed = lex(page, config);
+    let sections: Vec<Section> = sections::cluster_sections_with(&lexed, config)
+        .into_iter()
+        .map(|mut s| {
+            s.kind = "founders".to_string();
+            s
+        })
+        .collect();
+    founder_fields(&founders::extract(&page.slug, &sections))
+}
+
+fn pipeline_fields(data: &ExtractedData) -> Fields {
+    let c = &data.company;
+    let opt = |v: &Option<String>| v.clone().unwrap_or_default();
+    let mut fields = Fields::from([
+        ("company.name", opt(&c.name)),
+        ("company.batch", opt(&c.batch)),
+        ("company.status", c.status.map(|s| s.to_string()).unwrap_or_default()),
+        ("company.team_size", c.team_size.map(|n| n.to_string()).unwrap_or_default()),
+        ("company.location", opt(&c.location)),
+        ("jobs.count", data.jobs.len().to_string()),
+        ("news.count", data.news.len().to_string()),
+        ("links.count", data.links.len().to_string()),
+        ("qa.count", data.qa.len().to_string()),
+    ]);
+    fields.extend(founder_fields(&data.founders));
+    fields
+}
+
+fn pipeline(page: &ScrapedPage, config: &ParserConfig) -> Fields {
+    pipeline_fields(&parser::process_page(page, config))
+}
+
+fn pipeline_four_word_names(page: &ScrapedPage, config: &ParserConfig) -> Fields {
+    let config = ParserConfig { max_person_name_words: 4, ..config.clone() };
+    pipeline_fields(&parser::process_page(page, &config))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fixture(slug: &str) -> ScrapedPage {
+        ScrapedPage {
+            page_data_id: 1,
+            slug: slug.to_string(),
+            url: format!("https://www.ycombinator.com/companies/{}", slug),
+            markdown: std::fs::read_to_string(format!("tests/fixtures/{}.md", slug)).unwrap(),
+            meta_description: None,
+            html: false,
+        }
+    }
+
+    #[test]
+    fn identical_variants_agree_everywhere() {
+        let same = Experiment {
+            name: "same",
+            about: "",
+            baseline: Variant { name: "a", run: pipeline },
+            candidate: Variant { name: "b", run: pipeline },
+        };
+        let pages = vec![Ok(vec![fixture("stripe"), fixture("doordash")])];
+        let (n, reports) = run(&same, pages.into_iter(), &ParserConfig::default(), 3).unwrap();
+        assert_eq!(n, 2);
+        assert!(reports.iter().all(|r| r.agree == 2 && r.differ == 0), "{}", reports.len());
+        assert!(reports.iter().any(|r| r.field == "founders.names"));
+    }
+
+    #[test]
+    fn differences_keep_examples() {
+        let fewer = Experiment {
+            name: "fewer",
+            about: "",
+            baseline: Variant { name: "all", run: founders_in_founder_sections },
+            candidate: Variant { name: "none", run: |_, _| founder_fields(&[]) },
+        };
+        let pages = vec![Ok(vec![fixture("stripe")]), Ok(vec![fixture("groupahead")])];
+        let (_, reports) = run(&fewer, pages.into_iter(), &ParserConfig::default(), 1).unwrap();
+        let count = reports.iter().find(|r| r.field == "founders.count").unwrap();
+        assert_eq!((count.agree, count.differ), (0, 2));
+        assert_eq!(count.examples.len(), 1);
+        assert_eq!(count.examples[0].0, "stripe");
+        assert_eq!(count.examples[0].2, "0");
+        assert!(find("founder-sections").is_ok());
+        assert!(find("nope").is_err());
+    }
+}
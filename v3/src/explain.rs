@@ -0,0 +1,131 @@
+use crate::parser::blocks::{self, Block};
+use crate::parser::{lang, sections, ParserConfig};
+
+const RESET: &str = "\x1b[0m";
+const DIM: &str = "\x1b[2m";
+
+/// ANSI colour for a section kind; unknown (extras) sections stay plain.
+fn section_color(kind: &str) -> &'static str {
+    match kind {
+        "header" => "\x1b[36m",
+        "description" => "\x1b[32m",
+        "founders" => "\x1b[35m",
+        "news" => "\x1b[33m",
+        "jobs" => "\x1b[34m",
+        "footer_meta" => "\x1b[96m",
+        "launches" => "\x1b[92m",
+        "qa" => "\x1b[95m",
+        _ => "",
+    }
+}
+
+fn block_label(block: &Block) -> String {
+    match block {
+        Block::Heading { level, .. } => format!("Heading{}", level),
+        Block::Link { .. } => "Link".into(),
+        Block::TagLink { .. } => "TagLink".into(),
+        Block::MetaField { key, .. } => format!("Meta({})", key),
+        Block::StatusLine(_) => "Status".into(),
+        Block::Person { .. } => "Person".into(),
+        Block::Text(_) => "Text".into(),
+        Block::Code { .. } => "Code".into(),
+        Block::Quote(_) => "Quote".into(),
+        Block::Empty => "Empty".into(),
+    }
+}
+
+/// Labels of the blocks starting on a line, repeats folded: "Link×3".
+fn fold_labels(labels: &[String]) -> String {
+    let mut out: Vec<String> = Vec::new();
+    let mut i = 0;
+    while i < labels.len() {
+        let run = labels[i..].iter().take_while(|l| **l == labels[i]).count();
+        out.push(if run > 1 { format!("{}×{}", labels[i], run) } else { labels[i].clone() });
+        i += run;
+    }
+    out.join(" ")
+}
+
+/// Every markdown line with the section it was clustered into and the
+/// blocks lexed from it, the way `process` parses the page. Lines a
+/// multi-line block continues over are marked `┆`; lines no block came
+/// from are marked `-`. With `color`, each line takes its section's colour.
+pub fn explain(markdown: &str, config: &ParserConfig, color: bool) -> String {
+    let lexicon = lang::Lexicon::for_lang(lang::detect_page(markdown));
+    let lexed = blocks::lex_with(markdown, &lexicon, config);
+    let sections = sections::cluster_sections_with(&lexed, config);
+
+    let lines: Vec<&str> = markdown.lines().collect();
+    // Per line: (section kind, labels of blocks starting here, continued)
+    let mut notes: Vec<(Option<&str>, Vec<String>, bool)> = vec![(None, Vec::new(), false); lines.len()];
+    for section in &sections {
+        for (block, span) in section.blocks.iter().zip(section.spans) {
+            for line in span.start_line..=span.end_line.min(lines.len()) {
+                let Some(note) = notes.get_mut(line.wrapping_sub(1)) else { continue };
+                note.0.get_or_insert(&section.kind);
+                if line == span.start_line {
+                    note.1.push(block_label(block));
+                } else {
+                    note.2 = true;
+                }
+            }
+        }
+    }
+
+    let kind_width = sections.iter().map(|s| s.kind.len()).max().unwrap_or(0).max(7);
+    let mut out = String::new();
+    let mut previous = None;
+    for (i, (line, (kind, labels, continued))) in lines.iter().zip(&notes).enumerate() {
+        let blocks = match (labels.is_empty(), continued) {
+            (false, _) => fold_labels(labels),
+            (true, true) => "┆".to_string(),
+            (true, false) => "-".to_string(),
+        };
+        let kind_text = match *kind {
+            Some(k) if previous != Some(k) => k,
+            Some(_) => "",
+            None => "-",
+        };
+        previous = *kind;
+        let row = format!("{:>5} {:<kind_width$} {:<18} │ {}", i + 1, kind_text, blocks, line);
+        match kind.map(section_color).filter(|c| color && !c.is_empty()) {
+            Some(c) => out.push_str(&format!("{}{}{}\n", c, row, RESET)),
+            None if color && kind.is_none() => out.push_str(&format!("{}{}{}\n", DIM, row, RESET)),
+            None => out.push_str(&format!("{}\n", row)),
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn lines_carry_section_and_blocks() {
+        let md = "[Companies](/companies)\n\
+                  ### Payroll for teams\n\
+                  \n\
+                  Founded:2021\n\
+                  Batch:W24\n\
+                  Status:Active\n\
+                  Location:Berlin\n\
+                  [\n\
+                  Jobs\n\
+                  ](https://www.ycombinator.com/companies/acme/jobs)[x](https://x.com/a)[y](https://y.com/b)\n";
+        let out = explain(md, &ParserConfig::default(), false);
+        let lines: Vec<&str> = out.lines().collect();
+        assert_eq!(lines.len(), 10);
+        assert!(lines[0].starts_with("    1 header"), "{}", out);
+        assert!(lines[0].contains(" Link "), "{}", out);
+        assert!(lines[1].contains("description") && lines[1].contains("Heading3"), "{}", out);
+        assert!(lines[3].contains("footer_meta") && lines[3].contains("Meta(Founded)"), "{}", out);
+        // Section names only where a section starts
+        assert!(lines[4].starts_with("    5             "), "{}", out);
+        assert!(lines[7].contains(" Link "), "{}", out);
+        assert!(lines[8].contains(" ┆ "), "{}", out);
+        assert!(lines[9].trim_end().ends_with("[y](https://y.com/b)"), "{}", out);
+        assert!(!out.contains('\x1b'));
+        assert!(explain(md, &ParserConfig::default(), true).contains(section_color("footer_meta")));
+    }
+}
@@ -0,0 +1,595 @@
+use std::path::Path;
+
+use anyhow::Result;
+use rusqlite::Connection;
+
+use crate::redact::RedactionPolicy;
+
+/// Rows copied per table, in load order.
+pub type ExportCounts = Vec<(&'static str, usize)>;
+
+#[derive(Debug, Clone, Copy, clap::ValueEnum)]
+pub enum ExportFormat {
+    /// Typed DuckDB replica of every published table (`duckdb` feature)
+    Duckdb,
+    /// Excel workbook: companies, founders, jobs and news sheets (`xlsx` feature)
+    Xlsx,
+}
+
+/// Workbook sheets as (sheet name, table), in tab order.
+#[cfg_attr(not(feature = "xlsx"), allow(dead_code))]
+const XLSX_SHEETS: &[(&str, &str)] = &[
+    ("companies", "companies"),
+    ("founders", "founders"),
+    ("jobs", "company_jobs"),
+    ("news", "news"),
+];
+
+/// A `*_at` column value: `datetime('now')` text, or a bare date (founder
+/// dates stored before they kept the time) taken as midnight.
+#[cfg_attr(not(any(feature = "xlsx", feature = "duckdb")), allow(dead_code))]
+fn parse_timestamp(s: &str) -> Option<chrono::NaiveDateTime> {
+    chrono::NaiveDateTime::parse_from_str(s, "%Y-%m-%d %H:%M:%S")
+        .ok()
+        .or_else(|| chrono::NaiveDate::parse_from_str(s, "%Y-%m-%d").ok()?.and_hms_opt(0, 0, 0))
+}
+
+/// Write the stakeholder tables to an Excel workbook at `out`.
+#[cfg(not(feature = "xlsx"))]
+pub fn write_xlsx(_conn: &Connection, _out: &Path, _policy: Option<&RedactionPolicy>) -> Result<ExportCounts> {
+    anyhow::bail!("Excel export is not compiled in; rebuild with `cargo build --features xlsx`")
+}
+
+#[cfg(feature = "xlsx")]
+pub use workbook::write_xlsx;
+
+#[cfg(feature = "xlsx")]
+mod workbook {
+    use std::fs;
+
+    use anyhow::Context;
+    use chrono::{Datelike, Timelike};
+    use rusqlite::types::ValueRef;
+    use rust_xlsxwriter::{ExcelDateTime, Format, Workbook, Worksheet};
+
+    use super::*;
+    use crate::db;
+    use crate::manifest;
+    use crate::publish::{INTERNAL_COLUMNS, TABLES};
+    use crate::redact::{self, Action};
+
+    /// Excel's per-cell text limit; longer values are cut.
+    const MAX_CELL_CHARS: usize = 32_767;
+    const MAX_COLUMN_WIDTH: u32 = 60;
+
+    /// Cell types, derived from the SQLite declarations as for DuckDB.
+    #[derive(Clone, Copy, Debug, PartialEq)]
+    enum Cell {
+        Number,
+        Boolean,
+        DateTime,
+        Text,
+    }
+
+    impl Cell {
+        fn of(col: &db::ColumnInfo) -> Cell {
+            let decl = col.decl_type.to_uppercase();
+            if decl.contains("BOOL") {
+                Cell::Boolean
+            } else if ["INT", "REAL", "FLOA", "DOUB"].iter().any(|t| decl.contains(t)) {
+                Cell::Number
+            } else if col.name.ends_with("_at") {
+                Cell::DateTime
+            } else {
+                Cell::Text
+            }
+        }
+    }
+
+    fn excel_datetime(s: &str) -> Option<ExcelDateTime> {
+        let ts = super::parse_timestamp(s)?;
+        ExcelDateTime::from_ymd(ts.year() as u16, ts.month() as u8, ts.day() as u8)
+            .and_then(|d| d.and_hms(ts.hour() as u16, ts.minute() as u8, ts.second()))
+            .ok()
+    }
+
+    fn write_cell(
+        sheet: &mut Worksheet,
+        row: u32,
+        col: u16,
+        cell: Cell,
+        value: ValueRef,
+        datetime: &Format,
+    ) -> Result<()> {
+        match (cell, value) {
+            (_, ValueRef::Null) => {}
+            (Cell::Boolean, ValueRef::Integer(i)) => {
+                sheet.write_boolean(row, col, i != 0)?;
+            }
+            (_, ValueRef::Integer(i)) => {
+                sheet.write_number(row, col, i as f64)?;
+            }
+            (_, ValueRef::Real(f)) => {
+                sheet.write_number(row, col, f)?;
+            }
+            (_, ValueRef::Text(t)) => {
+                let text = String::from_utf8_lossy(t);
+                match excel_datetime(&text).filter(|_| cell == Cell::DateTime) {
+                    Some(dt) => sheet.write_datetime_with_format(row, col, &dt, datetime)?,
+                    None => match text.char_indices().nth(MAX_CELL_CHARS) {
+                        Some((cut, _)) => sheet.write_string(row, col, &text[..cut])?,
+                        None => sheet.write_string(row, col, text.as_ref())?,
+                    },
+                };
+            }
+            (_, ValueRef::Blob(_)) => {}
+        }
+        Ok(())
+    }
+
+    /// One sheet per `XLSX_SHEETS` entry with a bold, frozen header row and
+    /// numbers, booleans and `*_at` timestamps as typed cells, then a
+    /// `manifest` sheet for the latest run. Saved next to `out` and renamed
+    /// into place.
+    pub fn write_xlsx(conn: &Connection, out: &Path, policy: Option<&RedactionPolicy>) -> Result<ExportCounts> {
+        let header = Format::new().set_bold();
+        let datetime = Format::new().set_num_format("yyyy-mm-dd hh:mm:ss");
+        let mut book = Workbook::new();
+        let mut counts = Vec::with_capacity(XLSX_SHEETS.len());
+
+        for &(name, table) in XLSX_SHEETS {
+            let slug_column = TABLES.iter().find(|t| t.table == table).and_then(|t| t.slug_column);
+            let columns: Vec<(db::ColumnInfo, Cell, Action)> = redact::table_columns(conn, table, policy)?
+                .into_iter()
+                .filter(|(c, _)| !INTERNAL_COLUMNS.contains(&(table, c.name.as_str())))
+                .map(|(c, action)| {
+                    let cell = Cell::of(&c);
+                    (c, cell, action)
+                })
+                .collect();
+            let names: Vec<&str> = columns.iter().map(|(c, ..)| c.name.as_str()).collect();
+
+            let sheet = book.add_worksheet();
+            sheet.set_name(name)?;
+            sheet.write_row_with_format(0, 0, names.iter().copied(), &header)?;
+            sheet.set_freeze_panes(1, 0)?;
+
+            let mut r = 0u32;
+            let rows = db::for_each_row(conn, table, &names, slug_column, |row| {
+                r += 1;
+                for (i, (_, cell, action)) in columns.iter().enumerate() {
+                    let value = row.get_ref(i)?;
+                    let redacted = policy.and_then(|p| p.apply(*action, value));
+                    let value = redacted.as_ref().map_or(value, ValueRef::from);
+                    write_cell(sheet, r, i as u16, *cell, value, &datetime)?;
+                }
+                Ok(())
+            })?;
+            sheet.set_autofit_max_width(MAX_COLUMN_WIDTH);
+            sheet.autofit();
+            counts.push((name, rows));
+        }
+
+        if let Some(m) = db::fetch_latest_run_manifest(conn)? {
+            let sheet = book.add_worksheet();
+            sheet.set_name("manifest")?;
+            sheet.write_row_with_format(0, 0, ["field", "value"], &header)?;
+            for (r, (field, value)) in manifest::fields(&m).into_iter().enumerate() {
+                sheet.write_string(r as u32 + 1, 0, field)?;
+                sheet.write_string(r as u32 + 1, 1, value)?;
+            }
+            sheet.set_autofit_max_width(MAX_COLUMN_WIDTH);
+            sheet.autofit();
+        }
+
+        let tmp = out.with_extension("xlsx.tmp");
+        book.save(&tmp).with_context(|| format!("writing {}", tmp.display()))?;
+        fs::rename(&tmp, out).with_context(|| format!("moving export to {}", out.display()))?;
+        Ok(counts)
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        #[test]
+        fn writes_one_sheet_per_table() {
+            let conn = Connection::open_in_memory().unwrap();
+            db::init_schema(&conn).unwrap();
+            conn.execute_batch(
+                "INSERT INTO companies (slug, url, name, team_size, status) VALUES ('acme', 'https://x/acme', 'Acme', 12, 'Active');
+                 INSERT INTO founders (company_slug, name, name_key, is_active) VALUES ('acme', 'Jane Doe', 'jane doe', 1);",
+            )
+            .unwrap();
+            let out = std::env::temp_dir().join(format!("yc-export-test-{}.xlsx", std::process::id()));
+            let counts = write_xlsx(&conn, &out, None).unwrap();
+            assert_eq!(counts, vec![("companies", 1), ("founders", 1), ("jobs", 0), ("news", 0)]);
+            assert!(fs::read(&out).unwrap().starts_with(b"PK"), "xlsx is a zip container");
+            fs::remove_file(&out).unwrap();
+
+            assert!(excel_datetime("2024-03-01 12:00:00").is_some());
+            assert!(excel_datetime("2024-03-01").is_some());
+            assert!(excel_datetime("yesterday").is_none());
+        }
+    }
+}
+
+/// Copy the published tables into a fresh DuckDB file at `out`.
+#[cfg(not(feature = "duckdb"))]
+pub fn write_duckdb(_conn: &Connection, _out: &Path, _policy: Option<&RedactionPolicy>) -> Result<ExportCounts> {
+    anyhow::bail!("DuckDB export is not compiled in; rebuild with `cargo build --features duckdb`")
+}
+
+#[cfg(feature = "duckdb")]
+pub use duck::write_duckdb;
+
+#[cfg(feature = "duckdb")]
+mod duck {
+    use std::fs;
+
+    use anyhow::Context;
+    use duckdb::types::{TimeUnit, Value};
+    use rusqlite::types::ValueRef;
+
+    use super::*;
+    use crate::db;
+    use crate::manifest;
+    use crate::publish::TABLES;
+    use crate::redact::{self, Action};
+
+    /// DuckDB column types, derived from the SQLite declarations.
+    #[derive(Clone, Copy, Debug, PartialEq)]
+    enum Ty {
+        BigInt,
+        Double,
+        Boolean,
+        Timestamp,
+        Blob,
+        Varchar,
+    }
+
+    impl Ty {
+        /// SQLite stores timestamps as `datetime('now')` TEXT; our `*_at`
+        /// columns hold that format (see `parse_timestamp`).
+        fn of(col: &db::ColumnInfo) -> Ty {
+            let decl = col.decl_type.to_uppercase();
+            if decl.contains("BOOL") {
+                Ty::Boolean
+            } else if decl.contains("INT") {
+                Ty::BigInt
+            } else if decl.contains("REAL") || decl.contains("FLOA") || decl.contains("DOUB") {
+                Ty::Double
+            } else if decl.contains("BLOB") {
+                Ty::Blob
+            } else if col.name.ends_with("_at") {
+                Ty::Timestamp
+            } else {
+                Ty::Varchar
+            }
+        }
+
+        fn sql(self) -> &'static str {
+            match self {
+                Ty::BigInt => "BIGINT",
+                Ty::Double => "DOUBLE",
+                Ty::Boolean => "BOOLEAN",
+                Ty::Timestamp => "TIMESTAMP",
+                Ty::Blob => "BLOB",
+                Ty::Varchar => "VARCHAR",
+            }
+        }
+
+        /// Convert one SQLite value; text that doesn't fit the type becomes NULL.
+        fn convert(self, v: ValueRef) -> Value {
+            match (self, v) {
+                (_, ValueRef::Null) => Value::Null,
+                (Ty::Boolean, ValueRef::Integer(i)) => Value::Boolean(i != 0),
+                (Ty::BigInt, ValueRef::Integer(i)) => Value::BigInt(i),
+                (Ty::BigInt, ValueRef::Real(f)) => Value::BigInt(f as i64),
+                (Ty::Double, ValueRef::Real(f)) => Value::Double(f),
+                (Ty::Double, ValueRef::Integer(i)) => Value::Double(i as f64),
+                (Ty::Timestamp, ValueRef::Text(t)) => std::str::from_utf8(t)
+                    .ok()
+                    .and_then(super::parse_timestamp)
+                    .map_or(Value::Null, |ts| {
+                        Value::Timestamp(TimeUnit::Microsecond, ts.and_utc().timestamp_micros())
+                    }),
+                (Ty::Blob, ValueRef::Blob(b)) => Value::Blob(b.to_vec()),
+                (Ty::Varchar, ValueRef::Text(t)) => Value::Text(String::from_utf8_lossy(t).into_owned()),
+                (Ty::Varchar, ValueRef::Integer(i)) => Value::Text(i.to_string()),
+                (Ty::Varchar, ValueRef::Real(f)) => Value::Text(f.to_string()),
+                _ => Value::Null,
+            }
+        }
+    }
+
+    /// `CREATE TABLE` with types, NOT NULL, primary key, and the foreign keys
+    /// whose parent table is part of the export.
+    fn create_table_sql(
+        table: &str,
+        columns: &[(db::ColumnInfo, Ty)],
+        foreign_keys: &[db::ForeignKey],
+    ) -> String {
+        let mut lines: Vec<String> = columns
+            .iter()
+            .map(|(c, ty)| {
+                let not_null = if c.not_null { " NOT NULL" } else { "" };
+                format!("    \"{}\" {}{}", c.name, ty.sql(), not_null)
+            })
+            .collect();
+        let pk: Vec<String> = columns
+            .iter()
+            .filter(|(c, _)| c.primary_key)
+            .map(|(c, _)| format!("\"{}\"", c.name))
+            .collect();
+        if !pk.is_empty() {
+            lines.push(format!("    PRIMARY KEY ({})", pk.join(", ")));
+        }
+        for fk in foreign_keys {
+            if TABLES.iter().any(|t| t.table == fk.parent) {
+                lines.push(format!(
+                    "    FOREIGN KEY (\"{}\") REFERENCES {}(\"{}\")",
+                    fk.column, fk.parent, fk.parent_column
+                ));
+            }
+        }
+        format!("CREATE TABLE {} (\n{}\n);", table, lines.join(",\n"))
+    }
+
+    /// Built next to `out` and renamed into place, so readers never open a
+    /// half-loaded replica. The latest run's manifest goes in `run_manifest`.
+    /// Orphaned rows would violate the DuckDB foreign keys, so the export
+    /// refuses to start until `doctor --repair` has run.
+    pub fn write_duckdb(conn: &Connection, out: &Path, policy: Option<&RedactionPolicy>) -> Result<ExportCounts> {
+        let orphans: usize = db::find_orphans(conn)?
+            .iter()
+            .filter(|o| TABLES.iter().any(|t| t.table == o.table))
+            .map(|o| o.count)
+            .sum();
+        if orphans > 0 {
+            anyhow::bail!("{} orphaned rows would break foreign keys; run `doctor --repair` first", orphans);
+        }
+
+        let tmp = out.with_extension("duckdb.tmp");
+        let _ = fs::remove_file(&tmp);
+        let duck = duckdb::Connection::open(&tmp)
+            .with_context(|| format!("creating {}", tmp.display()))?;
+
+        let mut counts = Vec::with_capacity(TABLES.len());
+        for t in TABLES {
+            let (columns, actions): (Vec<(db::ColumnInfo, Ty)>, Vec<Action>) =
+                redact::table_columns(conn, t.table, policy)?
+                    .into_iter()
+                    .map(|(c, action)| {
+                        let ty = Ty::of(&c);
+                        ((c, ty), action)
+                    })
+                    .unzip();
+            // A column dropped by the redaction policy takes its key along
+            let fks: Vec<db::ForeignKey> = db::foreign_keys(conn, t.table)?
+                .into_iter()
+                .filter(|fk| columns.iter().any(|(c, _)| c.name == fk.column))
+                .collect();
+            duck.execute_batch(&create_table_sql(t.table, &columns, &fks))?;
+
+            let names: Vec<&str> = columns.iter().map(|(c, _)| c.name.as_str()).collect();
+            let mut appender = duck.appender(t.table)?;
+            let rows = db::for_each_row(conn, t.table, &names, t.slug_column, |row| {
+                let mut values = Vec::with_capacity(columns.len());
+                for (i, (_, ty)) in columns.iter().enumerate() {
+                    let value = row.get_ref(i)?;
+                    let redacted = policy.and_then(|p| p.apply(actions[i], value));
+                    values.push(ty.convert(redacted.as_ref().map_or(value, ValueRef::from)));
+                }
+                appender.append_row(duckdb::appender_params_from_iter(values))?;
+                Ok(())
+            })?;
+            appender.flush()?;
+            drop(appender);
+            counts.push((t.table, rows));
+        }
+        if let Some(m) = db::fetch_latest_run_manifest(conn)? {
+            duck.execute_batch("CREATE TABLE run_manifest (field VARCHAR PRIMARY KEY, value VARCHAR);")?;
+            let mut appender = duck.appender("run_manifest")?;
+            for (field, value) in manifest::fields(&m) {
+                appender.append_row(duckdb::appender_params_from_iter([
+                    Value::Text(field.to_string()),
+                    Value::Text(value),
+                ]))?;
+            }
+            appender.flush()?;
+        }
+        duck.execute_batch("CHECKPOINT;")?;
+        drop(duck);
+
+        fs::rename(&tmp, out).with_context(|| format!("moving export to {}", out.display()))?;
+        Ok(counts)
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        fn col(name: &str, decl: &str, not_null: bool, primary_key: bool) -> db::ColumnInfo {
+            db::ColumnInfo { name: name.to_string(), decl_type: decl.to_string(), not_null, primary_key }
+        }
+
+        #[test]
+        fn maps_sqlite_declarations() {
+            assert_eq!(Ty::of(&col("team_size", "INTEGER", false, false)), Ty::BigInt);
+            assert_eq!(Ty::of(&col("is_active", "BOOLEAN", false, false)), Ty::Boolean);
+            assert_eq!(Ty::of(&col("created_at", "TEXT", true, false)), Ty::Timestamp);
+            assert_eq!(Ty::of(&col("name", "TEXT", false, false)), Ty::Varchar);
+            assert!(matches!(
+                Ty::Timestamp.convert(ValueRef::Text(b"2024-03-01 12:00:00")),
+                Value::Timestamp(TimeUnit::Microsecond, 1_709_294_400_000_000)
+            ));
+            assert!(matches!(Ty::Boolean.convert(ValueRef::Integer(1)), Value::Boolean(true)));
+        }
+
+        #[test]
+        fn exports_tables_with_keys() {
+            let conn = Connection::open_in_memory().unwrap();
+            db::init_schema(&conn).unwrap();
+            conn.execute_batch(
+                "INSERT INTO companies (slug, url, name, team_size, status) VALUES ('acme', 'https://x/acme', 'Acme', 12, 'Active');
+                 INSERT INTO founders (company_slug, name, name_key, first_seen_at, departed_at)
+                     VALUES ('acme', 'Jane Doe', 'jane doe', '2024-01-05 09:00:00', '2024-06-01');",
+            )
+            .unwrap();
+            let out = std::env::temp_dir().join(format!("yc-export-test-{}.duckdb", std::process::id()));
+            let counts = write_duckdb(&conn, &out, None).unwrap();
+            assert_eq!(counts[0], ("companies", 1));
+
+            let duck = duckdb::Connection::open(&out).unwrap();
+            let (team, active): (i64, bool) = duck
+                .query_row("SELECT team_size, is_active FROM companies", [], |r| Ok((r.get(0)?, r.get(1)?)))
+                .unwrap();
+            assert_eq!((team, active), (12, true));
+            let (seen, departed): (String, String) = duck
+                .query_row(
+                    "SELECT CAST(first_seen_at AS VARCHAR), CAST(departed_at AS VARCHAR) FROM founders",
+                    [],
+                    |r| Ok((r.get(0)?, r.get(1)?)),
+                )
+                .unwrap();
+            assert_eq!((seen.as_str(), departed.as_str()), ("2024-01-05 09:00:00", "2024-06-01 00:00:00"));
+            assert!(duck
+                .execute("INSERT INTO founders (id, company_slug, name) VALUES (99, 'nope', 'X')", [])
+                .is_err());
+            drop(duck);
+            fs::remove_file(&out).unwrap();
+        }
+    }
+}
+
+/// What each `export --per-company` file holds, as (key, table, slug
+/// column, one row per company). Companies come first: they decide which
+/// files exist.
+const BUNDLE_PARTS: &[(&str, &str, &str, bool)] = &[
+    ("company", "companies", "slug", true),
+    ("founders", "founders", "company_slug", false),
+    ("jobs", "company_jobs", "company_slug", false),
+    ("news", "news", "company_slug", false),
+    ("links", "company_links", "company_slug", false),
+    ("sections", "company_sections", "slug", true),
+];
+
+/// One JSON file per company in scope, `<out>/<slug>.json`: the company
+/// row and its founders, jobs, news, links and parsed sections, typed as
+/// in the other exports (booleans, numbers) and redacted per `policy`.
+/// Returns the rows per part; `company` is the number of files.
+pub fn write_per_company(conn: &Connection, out: &Path, policy: Option<&RedactionPolicy>) -> Result<ExportCounts> {
+    use std::collections::BTreeMap;
+
+    use anyhow::Context;
+    use rusqlite::types::ValueRef;
+    use serde_json::{Map, Value};
+
+    use crate::db;
+    use crate::publish::INTERNAL_COLUMNS;
+    use crate::redact;
+
+    let mut bundles: BTreeMap<String, Map<String, Value>> = BTreeMap::new();
+    let mut counts: ExportCounts = Vec::with_capacity(BUNDLE_PARTS.len() + 1);
+    for &(key, table, slug_column, single) in BUNDLE_PARTS {
+        let (columns, actions): (Vec<db::ColumnInfo>, Vec<redact::Action>) = redact::table_columns(conn, table, policy)?
+            .into_iter()
+            .filter(|(c, _)| !INTERNAL_COLUMNS.contains(&(table, c.name.as_str())))
+            .unzip();
+        // The slug is read unredacted up front to file each row under
+        let mut names = vec![slug_column];
+        names.extend(columns.iter().map(|c| c.name.as_str()));
+
+        let mut rows = 0;
+        db::for_each_row(conn, table, &names, Some(slug_column), |row| {
+            let slug: String = row.get(0)?;
+            let bundle = match bundles.get_mut(&slug) {
+                Some(bundle) => bundle,
+                None if table == "companies" => bundles.entry(slug).or_default(),
+                None => return Ok(()),
+            };
+            let mut object = Map::new();
+            for (i, col) in columns.iter().enumerate() {
+                let value = row.get_ref(i + 1)?;
+                let redacted = policy.and_then(|p| p.apply(actions[i], value));
+                object.insert(col.name.clone(), json_value(col, redacted.as_ref().map_or(value, ValueRef::from)));
+            }
+            if single {
+                bundle.insert(key.to_string(), Value::Object(object));
+            } else if let Value::Array(items) = bundle.entry(key).or_insert_with(|| Value::Array(Vec::new())) {
+                items.push(Value::Object(object));
+            }
+            rows += 1;
+            Ok(())
+        })?;
+        counts.push((key, rows));
+    }
+
+    std::fs::create_dir_all(out).with_context(|| format!("creating {}", out.display()))?;
+    for (slug, mut bundle) in bundles {
+        for &(key, _, _, single) in BUNDLE_PARTS {
+            bundle.entry(key).or_insert(if single { Value::Null } else { Value::Array(Vec::new()) });
+        }
+        let path = out.join(format!("{}.json", file_stem(&slug)));
+        let json = serde_json::to_string_pretty(&bundle)? + "\n";
+        std::fs::write(&path, json).with_context(|| format!("writing {}", path.display()))?;
+    }
+    Ok(counts)
+}
+
+/// A column value as JSON: BOOLEAN columns as true/false, blobs left out.
+fn json_value(col: &crate::db::ColumnInfo, v: rusqlite::types::ValueRef) -> serde_json::Value {
+    use rusqlite::types::ValueRef;
+    use serde_json::Value;
+
+    match v {
+        ValueRef::Null | ValueRef::Blob(_) => Value::Null,
+        ValueRef::Integer(i) if col.decl_type.to_uppercase().contains("BOOL") => Value::Bool(i != 0),
+        ValueRef::Integer(i) => i.into(),
+        ValueRef::Real(f) => serde_json::Number::from_f64(f).map_or(Value::Null, Value::Number),
+        ValueRef::Text(t) => Value::String(String::from_utf8_lossy(t).into_owned()),
+    }
+}
+
+/// `slug` as a file name: anything but letters, digits, '-' and '_' becomes '_'.
+fn file_stem(slug: &str) -> String {
+    slug.chars().map(|c| if c.is_alphanumeric() || matches!(c, '-' | '_') { c } else { '_' }).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn per_company_files_bundle_each_companys_rows() {
+        let conn = Connection::open_in_memory().unwrap();
+        crate::db::init_schema(&conn).unwrap();
+        conn.execute_batch(
+            "INSERT INTO companies (slug, url, name, team_size) VALUES ('acme', 'https://x/acme', 'Acme', 12), ('beta', 'https://x/beta', 'Beta', NULL);
+             INSERT INTO founders (company_slug, name, name_key, is_active) VALUES ('acme', 'Jane Doe', 'jane doe', 1), ('acme', 'Sam Lee', 'sam lee', 0);
+             INSERT INTO news (company_slug, title, url) VALUES ('acme', 'Acme raises $5M', 'https://n/1');",
+        )
+        .unwrap();
+        let out = std::env::temp_dir().join(format!("yc-per-company-test-{}", std::process::id()));
+        let counts = write_per_company(&conn, &out, None).unwrap();
+        assert_eq!(&counts[..3], [("company", 2), ("founders", 2), ("jobs", 0)]);
+
+        let acme: serde_json::Value = serde_json::from_str(&std::fs::read_to_string(out.join("acme.json")).unwrap()).unwrap();
+        assert_eq!(acme["company"]["team_size"], 12);
+        assert_eq!(acme["founders"][1]["is_active"], false);
+        assert!(acme["founders"][0].get("name_key").is_none(), "internal columns stay out");
+        assert_eq!(acme["news"][0]["title"], "Acme raises $5M");
+        let beta: serde_json::Value = serde_json::from_str(&std::fs::read_to_string(out.join("beta.json")).unwrap()).unwrap();
+        assert_eq!((beta["jobs"].as_array().map(Vec::len), &beta["sections"]), (Some(0), &serde_json::Value::Null));
+        std::fs::remove_dir_all(&out).unwrap();
+    }
+
+    #[test]
+    fn timestamps_parse_with_or_without_a_time() {
+        let ts = |s| parse_timestamp(s).map(|t| t.to_string());
+        assert_eq!(ts("2024-03-01 12:00:00").as_deref(), Some("2024-03-01 12:00:00"));
+        assert_eq!(ts("2024-03-01").as_deref(), Some("2024-03-01 00:00:00"), "founder dates stored before the time was kept");
+        assert_eq!(ts("yesterday"), None);
+    }
+}
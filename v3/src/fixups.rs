@@ -0,0 +1,198 @@
+use std::borrow::Cow;
+
+use anyhow::{Context, Result};
+use regex::Regex;
+use rusqlite::Connection;
+
+use crate::db::{self, Fixup};
+use crate::output::out;
+
+/// Free-text columns copied from page markdown, where the built-in rules apply.
+const TEXT_COLUMNS: &[(&str, &str)] = &[
+    ("companies", "tagline"),
+    ("company_sections", "description"),
+    ("founders", "bio"),
+    ("news", "title"),
+    ("company_jobs", "title"),
+];
+
+/// (pattern, replacement, note) stored for every `TEXT_COLUMNS` entry by `fixups seed`.
+const BUILTIN: &[(&str, &str, &str)] = &[
+    (r"\\([_*#`\[\]()])", "$1", "markdown escape left in text (\\_ -> _)"),
+    (r"&amp;", "&", "HTML entity left in text"),
+    (r"&#0?39;", "'", "HTML entity left in text"),
+    (r"[\x{200B}-\x{200D}\x{2060}\x{FEFF}]", "", "zero-width characters"),
+];
+
+/// Characters of unchanged text shown either side of a change.
+const DIFF_CONTEXT: usize = 30;
+
+pub struct Change {
+    pub fixup_id: i64,
+    pub table: String,
+    pub column: String,
+    pub rowid: i64,
+    pub before: String,
+    pub after: String,
+}
+
+/// Store a rule for `target` ("table.column") after checking the pattern compiles.
+pub fn add(conn: &Connection, target: &str, pattern: &str, replacement: &str, note: Option<&str>) -> Result<i64> {
+    let (table, column) = target
+        .split_once('.')
+        .with_context(|| format!("target {:?} should be table.column", target))?;
+    Regex::new(pattern).with_context(|| format!("invalid pattern {:?}", pattern))?;
+    db::add_fixup(conn, table, column, pattern, replacement, note)
+}
+
+/// Store the built-in rules; returns how many were new.
+pub fn seed(conn: &Connection) -> Result<usize> {
+    let before = db::fetch_fixups(conn)?.len();
+    for (table, column) in TEXT_COLUMNS {
+        for (pattern, replacement, note) in BUILTIN {
+            db::add_fixup(conn, table, column, pattern, replacement, Some(note))?;
+        }
+    }
+    Ok(db::fetch_fixups(conn)?.len() - before)
+}
+
+/// Run the stored rules (all, or the ids in `only`) in id order inside one
+/// transaction, each seeing the output of the ones before it. A dry run
+/// rolls the transaction back, so it reports exactly what a real run writes.
+pub fn apply(conn: &Connection, only: &[i64], dry_run: bool) -> Result<Vec<Change>> {
+    let rules: Vec<Fixup> = db::fetch_fixups(conn)?
+        .into_iter()
+        .filter(|f| only.is_empty() || only.contains(&f.id))
+        .collect();
+    if let Some(missing) = only.iter().find(|id| !rules.iter().any(|f| f.id == **id)) {
+        anyhow::bail!("no fixup with id {}", missing);
+    }
+
+    let tx = conn.unchecked_transaction()?;
+    let mut changes = Vec::new();
+    for rule in &rules {
+        let re = Regex::new(&rule.pattern).with_context(|| format!("fixup {}: invalid pattern", rule.id))?;
+        let rows = db::rewrite_text_column(&tx, &rule.table_name, &rule.column_name, |text| {
+            match re.replace_all(text, rule.replacement.as_str()) {
+                Cow::Owned(s) => Some(s),
+                Cow::Borrowed(_) => None,
+            }
+        })
+        .with_context(|| format!("fixup {}", rule.id))?;
+        if !dry_run {
+            db::record_fixup_run(&tx, rule.id, rows.len())?;
+        }
+        changes.extend(rows.into_iter().map(|(rowid, before, after)| Change {
+            fixup_id: rule.id,
+            table: rule.table_name.clone(),
+            column: rule.column_name.clone(),
+            rowid,
+            before,
+            after,
+        }));
+    }
+    if !dry_run {
+        tx.commit()?;
+    }
+    Ok(changes)
+}
+
+/// The part of `before` that differs from `after`, with `DIFF_CONTEXT`
+/// characters around it and newlines shown as `\n`: ("old", "new").
+pub fn diff_snippet(before: &str, after: &str) -> (String, String) {
+    let (a, b): (Vec<char>, Vec<char>) = (before.chars().collect(), after.chars().collect());
+    let prefix = a.iter().zip(&b).take_while(|(x, y)| x == y).count();
+    let max_suffix = a.len().min(b.len()) - prefix;
+    let suffix = a.iter().rev().zip(b.iter().rev()).take(max_suffix).take_while(|(x, y)| x == y).count();
+
+    let show = |chars: &[char]| {
+        let start = prefix.saturating_sub(DIFF_CONTEXT);
+        let end = (chars.len() - suffix + DIFF_CONTEXT).min(chars.len());
+        let body: String = chars[start..end].iter().collect();
+        format!(
+            "{}{}{}",
+            if start > 0 { "…" } else { "" },
+            body.replace('\n', "\\n"),
+            if end < chars.len() { "…" } else { "" }
+        )
+    };
+    (show(&a), show(&b))
+}
+
+pub fn print_rules(rules: &[Fixup]) {
+    if rules.is_empty() {
+        out!("No fixups stored. Add one with 'fixups add' or the built-ins with 'fixups seed'.");
+        return;
+    }
+    out!("{:>4}  {:<22} {:<36} {:<8} {:>7}  last applied", "id", "column", "pattern", "replace", "changed");
+    for f in rules {
+        out!(
+            "{:>4}  {:<22} {:<36} {:<8} {:>7}  {}",
+            f.id,
+            format!("{}.{}", f.table_name, f.column_name),
+            f.pattern,
+            format!("{:?}", f.replacement),
+            f.rows_changed,
+            f.applied_at.as_deref().unwrap_or("never"),
+        );
+        if let Some(note) = &f.note {
+            out!("{:>6}{}", "", note);
+        }
+    }
+}
+
+pub fn print_changes(changes: &[Change]) {
+    for c in changes {
+        let (old, new) = diff_snippet(&c.before, &c.after);
+        out!("#{} {}.{} rowid {}", c.fixup_id, c.table, c.column, c.rowid);
+        out!("  - {}", old);
+        out!("  + {}", new);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tagline(conn: &Connection) -> String {
+        conn.query_row("SELECT tagline FROM companies WHERE slug = 'acme'", [], |r| r.get(0)).unwrap()
+    }
+
+    #[test]
+    fn dry_run_reports_what_apply_writes() {
+        let conn = Connection::open_in_memory().unwrap();
+        db::init_schema(&conn).unwrap();
+        conn.execute(
+            "INSERT INTO companies (slug, url, tagline) VALUES ('acme', 'u/acme', ?1)",
+            ["Payroll \\_ tax for SMBs &amp; startups\u{200B}"],
+        )
+        .unwrap();
+        assert_eq!(seed(&conn).unwrap(), TEXT_COLUMNS.len() * BUILTIN.len());
+        assert_eq!(seed(&conn).unwrap(), 0, "seeding twice adds nothing");
+
+        let preview = apply(&conn, &[], true).unwrap();
+        assert_eq!(preview.len(), 3, "one change per matching rule");
+        assert_eq!(preview.last().unwrap().after, "Payroll _ tax for SMBs & startups");
+        assert_eq!(tagline(&conn), "Payroll \\_ tax for SMBs &amp; startups\u{200B}");
+
+        assert_eq!(apply(&conn, &[], false).unwrap().len(), 3);
+        assert_eq!(tagline(&conn), "Payroll _ tax for SMBs & startups");
+        assert!(apply(&conn, &[], false).unwrap().is_empty());
+        assert_eq!(db::fetch_fixups(&conn).unwrap().iter().map(|f| f.rows_changed).sum::<i64>(), 3);
+
+        assert!(add(&conn, "companies.team_size", "x", "", None).is_err());
+        assert!(add(&conn, "companies.tagline", "(", "", None).is_err());
+        assert!(apply(&conn, &[999], true).is_err());
+    }
+
+    #[test]
+    fn snippet_shows_change_in_context() {
+        let before = format!("{}&amp;{}", "a".repeat(40), "b".repeat(40));
+        let after = before.replace("&amp;", "&");
+        let (old, new) = diff_snippet(&before, &after);
+        // The shared "&" counts towards the leading context
+        assert_eq!(old, format!("…{}&amp;{}…", "a".repeat(29), "b".repeat(30)));
+        assert_eq!(new, format!("…{}&{}…", "a".repeat(29), "b".repeat(30)));
+        assert_eq!(diff_snippet("x\u{200B}\ny", "x\ny"), ("x\u{200B}\\ny".to_string(), "x\\ny".to_string()));
+    }
+}
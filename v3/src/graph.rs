@@ -0,0 +1,154 @@
+use std::collections::BTreeMap;
+use std::io::Write;
+
+use anyhow::Result;
+
+use crate::db::ReferenceEdge;
+use crate::report::csv_field;
+
+#[derive(Clone, Copy, Debug, PartialEq, clap::ValueEnum)]
+pub enum GraphFormat {
+    /// Edge list: from_slug,to_slug,section plus names and batches
+    Csv,
+    /// GraphML with name, batch and scraped attributes on nodes (Gephi, NetworkX)
+    Graphml,
+}
+
+/// A company in the reference graph.
+struct Node<'a> {
+    name: Option<&'a str>,
+    batch: Option<&'a str>,
+    /// Whether the company has a processed page; targets not scraped yet
+    /// only have a slug.
+    scraped: bool,
+}
+
+/// Write `edges` as `format`. Returns (nodes, edges).
+pub fn write(out: &mut impl Write, edges: &[ReferenceEdge], format: GraphFormat) -> Result<(usize, usize)> {
+    let nodes = nodes(edges);
+    match format {
+        GraphFormat::Csv => write_csv(out, edges)?,
+        GraphFormat::Graphml => write_graphml(out, &nodes, edges)?,
+    }
+    Ok((nodes.len(), edges.len()))
+}
+
+fn nodes(edges: &[ReferenceEdge]) -> BTreeMap<&str, Node<'_>> {
+    let mut nodes = BTreeMap::new();
+    for e in edges {
+        nodes.insert(
+            e.from_slug.as_str(),
+            Node { name: e.from_name.as_deref(), batch: e.from_batch.as_deref(), scraped: true },
+        );
+    }
+    for e in edges {
+        nodes.entry(e.to_slug.as_str()).or_insert(Node {
+            name: e.to_name.as_deref(),
+            batch: e.to_batch.as_deref(),
+            scraped: e.to_name.is_some(),
+        });
+    }
+    nodes
+}
+
+fn write_csv(out: &mut impl Write, edges: &[ReferenceEdge]) -> Result<()> {
+    writeln!(out, "from_slug,to_slug,section,from_name,from_batch,to_name,to_batch")?;
+    for e in edges {
+        let cells = [
+            Some(e.from_slug.as_str()),
+            Some(e.to_slug.as_str()),
+            Some(e.section.as_str()),
+            e.from_name.as_deref(),
+            e.from_batch.as_deref(),
+            e.to_name.as_deref(),
+            e.to_batch.as_deref(),
+        ];
+        let line: Vec<String> = cells.iter().map(|c| csv_field(c.unwrap_or(""))).collect();
+        writeln!(out, "{}", line.join(","))?;
+    }
+    Ok(())
+}
+
+fn write_graphml(out: &mut impl Write, nodes: &BTreeMap<&str, Node>, edges: &[ReferenceEdge]) -> Result<()> {
+    writeln!(out, r#"<?xml version="1.0" encoding="UTF-8"?>"#)?;
+    writeln!(out, r#"<graphml xmlns="http://graphml.graphdrawing.org/xmlns">"#)?;
+    writeln!(out, r#"  <key id="name" for="node" attr.name="name" attr.type="string"/>"#)?;
+    writeln!(out, r#"  <key id="batch" for="node" attr.name="batch" attr.type="string"/>"#)?;
+    writeln!(out, r#"  <key id="scraped" for="node" attr.name="scraped" attr.type="boolean"/>"#)?;
+    writeln!(out, r#"  <key id="section" for="edge" attr.name="section" attr.type="string"/>"#)?;
+    writeln!(out, r#"  <graph id="yc_references" edgedefault="directed">"#)?;
+    for (slug, node) in nodes {
+        writeln!(out, r#"    <node id="{}">"#, xml_escape(slug))?;
+        for (key, value) in [("name", node.name), ("batch", node.batch)] {
+            if let Some(v) = value {
+                writeln!(out, r#"      <data key="{}">{}</data>"#, key, xml_escape(v))?;
+            }
+        }
+        writeln!(out, r#"      <data key="scraped">{}</data>"#, node.scraped)?;
+        writeln!(out, "    </node>")?;
+    }
+    for e in edges {
+        writeln!(
+            out,
+            r#"    <edge source="{}" target="{}"><data key="section">{}</data></edge>"#,
+            xml_escape(&e.from_slug),
+            xml_escape(&e.to_slug),
+            xml_escape(&e.section)
+        )?;
+    }
+    writeln!(out, "  </graph>")?;
+    writeln!(out, "</graphml>")?;
+    Ok(())
+}
+
+fn xml_escape(s: &str) -> String {
+    s.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;").replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db;
+
+    fn edges() -> Vec<ReferenceEdge> {
+        let conn = rusqlite::Connection::open_in_memory().unwrap();
+        db::init_schema(&conn).unwrap();
+        conn.execute_batch(
+            "INSERT INTO companies (slug, name, batch, url) VALUES
+                 ('acme', 'Acme & Co', 'W24', 'u1'), ('globex', 'Globex', 'S23', 'u2'),
+                 ('old-initech', 'Initech', 'S20', 'u3'), ('initech', 'Initech', 'S20', 'u4');
+             UPDATE companies SET renamed_to = 'initech' WHERE slug = 'old-initech';
+             INSERT INTO company_references (from_slug, to_slug, section) VALUES
+                 ('acme', 'globex', 'description'), ('acme', 'hooli', 'description'),
+                 ('acme', 'old-initech', 'news'), ('initech', 'old-initech', 'description');",
+        )
+        .unwrap();
+        let all = db::fetch_reference_edges(&conn, false).unwrap();
+        assert_eq!(db::fetch_reference_edges(&conn, true).unwrap().len(), 2);
+        all
+    }
+
+    #[test]
+    fn csv_edge_list_follows_renames() {
+        let mut out = Vec::new();
+        assert_eq!(write(&mut out, &edges(), GraphFormat::Csv).unwrap(), (4, 3));
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "from_slug,to_slug,section,from_name,from_batch,to_name,to_batch\n\
+             acme,globex,description,Acme & Co,W24,Globex,S23\n\
+             acme,hooli,description,Acme & Co,W24,,\n\
+             acme,initech,news,Acme & Co,W24,Initech,S20\n"
+        );
+    }
+
+    #[test]
+    fn graphml_marks_unscraped_targets() {
+        let mut out = Vec::new();
+        write(&mut out, &edges(), GraphFormat::Graphml).unwrap();
+        let xml = String::from_utf8(out).unwrap();
+        assert!(xml.contains(r#"<data key="name">Acme &amp; Co</data>"#));
+        assert!(xml.contains("<node id=\"hooli\">\n      <data key=\"scraped\">false</data>"));
+        assert!(xml.contains(r#"<edge source="acme" target="initech"><data key="section">news</data></edge>"#));
+        assert_eq!(xml.matches("<node ").count(), 4);
+    }
+}
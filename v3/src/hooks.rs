@@ -0,0 +1,172 @@
+use std::process::Stdio;
+
+use anyhow::{bail, Context, Result};
+use serde::Deserialize;
+use tokio::process::Command;
+
+/// Shell commands run around pipeline phases, from the `[hooks]` table of `yc.toml`.
+///
+/// Each command runs through the shell with run metadata in `YC_*` env vars.
+/// Its stdout goes to our stderr so `--sink jsonl` output stays clean. A
+/// non-zero exit fails the command: before scraping that aborts the run,
+/// afterwards the data is already saved but the exit status reports it.
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Hooks {
+    /// Before `scrape` / `run` start fetching pages.
+    pub pre_scrape: Option<String>,
+    /// After `process` / `run` finish extracting.
+    pub post_process: Option<String>,
+    /// After a `run` completes.
+    pub post_run: Option<String>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Phase {
+    PreScrape,
+    PostProcess,
+    PostRun,
+}
+
+impl Phase {
+    pub fn name(self) -> &'static str {
+        match self {
+            Phase::PreScrape => "pre_scrape",
+            Phase::PostProcess => "post_process",
+            Phase::PostRun => "post_run",
+        }
+    }
+}
+
+/// Metadata handed to hooks; counts accumulate as the command progresses.
+pub struct RunInfo {
+    pub run_id: String,
+    pub command: &'static str,
+    pub db_path: String,
+    counts: Vec<(&'static str, usize)>,
+}
+
+impl RunInfo {
+    pub fn new(command: &'static str, db_path: &str) -> Self {
+        RunInfo {
+            run_id: format!(
+                "{}-{}",
+                chrono::Utc::now().format("%Y%m%dT%H%M%SZ"),
+                std::process::id()
+            ),
+            command,
+            db_path: db_path.to_string(),
+            counts: Vec::new(),
+        }
+    }
+
+    /// Set a count, exported as `YC_<KEY>` (e.g. "scraped" → `YC_SCRAPED`)
+    /// and reported in the `--json` result.
+    pub fn count(&mut self, key: &'static str, n: usize) {
+        crate::output::count(key, n);
+        match self.counts.iter_mut().find(|(k, _)| *k == key) {
+            Some((_, v)) => *v = n,
+            None => self.counts.push((key, n)),
+        }
+    }
+
+    fn env(&self, phase: Phase) -> Vec<(String, String)> {
+        let mut env = vec![
+            ("YC_HOOK".to_string(), phase.name().to_string()),
+            ("YC_RUN_ID".to_string(), self.run_id.clone()),
+            ("YC_COMMAND".to_string(), self.command.to_string()),
+            ("YC_DB_PATH".to_string(), self.db_path.clone()),
+        ];
+        env.extend(
+            self.counts
+                .iter()
+                .map(|(k, n)| (format!("YC_{}", k.to_uppercase()), n.to_string())),
+        );
+        env
+    }
+}
+
+impl Hooks {
+    fn command(&self, phase: Phase) -> Option<&str> {
+        match phase {
+            Phase::PreScrape => self.pre_scrape.as_deref(),
+            Phase::PostProcess => self.post_process.as_deref(),
+            Phase::PostRun => self.post_run.as_deref(),
+        }
+        .filter(|c| !c.trim().is_empty())
+    }
+
+    /// Run the hook for `phase`, if configured, and wait for it without
+    /// blocking the runtime.
+    pub async fn fire(&self, phase: Phase, info: &RunInfo) -> Result<()> {
+        let Some(cmd) = self.command(phase) else {
+            return Ok(());
+        };
+        tracing::info!(hook = phase.name(), run_id = %info.run_id, "running hook: {}", cmd);
+        let status = shell(cmd)
+            .envs(info.env(phase))
+            .stdin(Stdio::null())
+            .stdout(std::io::stderr())
+            .status()
+            .await
+            .with_context(|| format!("starting {} hook", phase.name()))?;
+        if !status.success() {
+            bail!("{} hook failed ({}): {}", phase.name(), status, cmd);
+        }
+        Ok(())
+    }
+}
+
+#[cfg(unix)]
+fn shell(cmd: &str) -> Command {
+    let mut c = Command::new("sh");
+    c.arg("-c").arg(cmd);
+    c
+}
+
+#[cfg(windows)]
+fn shell(cmd: &str) -> Command {
+    let mut c = Command::new("cmd");
+    c.arg("/C").arg(cmd);
+    c
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn env_carries_run_metadata_and_counts() {
+        let mut info = RunInfo::new("run", "data/yc.sqlite");
+        info.count("scraped", 3);
+        info.count("scraped", 5);
+        info.count("companies", 4);
+        let env = info.env(Phase::PostRun);
+        let get = |k: &str| env.iter().find(|(key, _)| key == k).map(|(_, v)| v.as_str());
+        assert_eq!(get("YC_HOOK"), Some("post_run"));
+        assert_eq!(get("YC_COMMAND"), Some("run"));
+        assert_eq!(get("YC_DB_PATH"), Some("data/yc.sqlite"));
+        assert_eq!(get("YC_SCRAPED"), Some("5"));
+        assert_eq!(get("YC_COMPANIES"), Some("4"));
+        assert_eq!(get("YC_RUN_ID"), Some(info.run_id.as_str()));
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn fire_runs_configured_hook_and_reports_failure() {
+        let out = std::env::temp_dir().join(format!("yc-hook-test-{}", std::process::id()));
+        let hooks = Hooks {
+            pre_scrape: Some(format!("printf '%s' \"$YC_HOOK:$YC_PAGES\" > '{}'", out.display())),
+            post_process: Some("exit 3".to_string()),
+            post_run: None,
+        };
+        let mut info = RunInfo::new("scrape", "data/yc.sqlite");
+        info.count("pages", 12);
+
+        hooks.fire(Phase::PreScrape, &info).await.unwrap();
+        assert_eq!(std::fs::read_to_string(&out).unwrap(), "pre_scrape:12");
+        assert!(hooks.fire(Phase::PostProcess, &info).await.is_err());
+        hooks.fire(Phase::PostRun, &info).await.unwrap();
+        std::fs::remove_file(&out).unwrap();
+    }
+}
@@ -0,0 +1,49 @@
+//! The scraper behind the `yc_scraper` binary. Most of it is internal to
+//! the CLI (`cli.rs`); the parser is public so other crates can run it
+//! with extractors of their own (`parser::extract::custom`).
+
+mod blobstore;
+// Replaying goes through the scraper, which needs `network`
+#[cfg_attr(not(feature = "network"), allow(dead_code))]
+mod cassette;
+mod claims;
+pub mod cli;
+mod config;
+mod db;
+mod demo;
+mod email;
+mod enrich;
+mod experiment;
+mod explain;
+mod export;
+mod fixups;
+mod graph;
+mod hooks;
+#[cfg(feature = "network")]
+mod linkcheck;
+mod manifest;
+mod model;
+#[cfg(not(feature = "network"))]
+mod offline;
+mod output;
+pub mod parser;
+mod publish;
+mod query;
+mod redact;
+mod report;
+#[cfg(feature = "network")]
+mod resolve;
+mod review;
+#[cfg(feature = "network")]
+mod scraper;
+mod sink;
+mod sitemap;
+mod slugs;
+mod telemetry;
+mod validate;
+mod writer;
+
+#[cfg(not(feature = "network"))]
+use offline::{linkcheck, resolve, scraper};
+
+pub use db::{CustomRow, ScrapedPage};
@@ -0,0 +1,75 @@
+use std::sync::Arc;
+use std::time::Instant;
+
+use anyhow::Result;
+use indicatif::ProgressStyle;
+use reqwest::StatusCode;
+use tokio::sync::Semaphore;
+use tokio::task::JoinSet;
+
+use crate::db::LinkCheckRow;
+use crate::output;
+use crate::resolve::redirect_client;
+
+const CONCURRENCY: usize = 24;
+
+/// Check `(url, domain)` pairs concurrently, recording final status and latency.
+pub async fn check_links(items: Vec<(String, String)>) -> Result<Vec<LinkCheckRow>> {
+    let client = redirect_client()?;
+    let semaphore = Arc::new(Semaphore::new(CONCURRENCY));
+
+    let pb = output::progress_bar(items.len() as u64);
+    pb.set_style(
+        ProgressStyle::default_bar()
+            .template("[{elapsed_precise}] {bar:40} {pos}/{len} ({per_sec}, eta {eta})")?
+            .progress_chars("=> "),
+    );
+
+    let mut tasks = JoinSet::new();
+    for (url, domain) in items {
+        let client = client.clone();
+        let sem = Arc::clone(&semaphore);
+        tasks.spawn(async move {
+            let _permit = sem.acquire().await.unwrap();
+            let start = Instant::now();
+            let result = check_one(&client, &url).await;
+            let latency_ms = start.elapsed().as_millis() as i64;
+            match result {
+                Ok(status) => LinkCheckRow {
+                    url,
+                    domain,
+                    status: Some(status.as_u16() as i32),
+                    error: None,
+                    latency_ms,
+                },
+                Err(e) => LinkCheckRow {
+                    url,
+                    domain,
+                    status: None,
+                    error: Some(e.to_string()),
+                    latency_ms,
+                },
+            }
+        });
+    }
+
+    let mut out = Vec::new();
+    while let Some(res) = tasks.join_next().await {
+        out.push(res?);
+        pb.inc(1);
+    }
+    pb.finish_and_clear();
+    Ok(out)
+}
+
+/// HEAD the URL (following redirects), retrying with GET when HEAD is refused.
+async fn check_one(client: &reqwest::Client, url: &str) -> Result<StatusCode> {
+    let status = client.head(url).send().await?.status();
+    if matches!(
+        status,
+        StatusCode::METHOD_NOT_ALLOWED | StatusCode::FORBIDDEN | StatusCode::NOT_IMPLEMENTED
+    ) {
+        return Ok(client.get(url).send().await?.status());
+    }
+    Ok(status)
+}
@@ -0,0 +1,107 @@
+use std::collections::BTreeMap;
+
+use anyhow::Result;
+use rusqlite::Connection;
+use serde_json::{json, Value};
+
+use crate::db::{self, RunManifest};
+use crate::hooks::RunInfo;
+use crate::parser::ParserConfig;
+use crate::publish::TABLES;
+
+/// Commit the binary was built from, set by build.rs.
+pub const GIT_HASH: Option<&str> = option_env!("YC_GIT_HASH");
+
+/// Rows per published table, as a JSON object.
+fn dataset_counts(conn: &Connection) -> Result<String> {
+    let tables: Vec<&str> = TABLES.iter().map(|t| t.table).collect();
+    let counts: BTreeMap<String, usize> = db::count_rows(conn, &tables)?.into_iter().collect();
+    Ok(serde_json::to_string(&counts)?)
+}
+
+/// Record what is about to produce `info`'s data: the build, the parser
+/// settings, the command line and the dataset size going in.
+pub fn begin(conn: &Connection, info: &RunInfo, config: &ParserConfig) -> Result<()> {
+    db::insert_run_manifest(
+        conn,
+        &RunManifest {
+            run_id: info.run_id.clone(),
+            command: info.command.to_string(),
+            crate_version: env!("CARGO_PKG_VERSION").to_string(),
+            git_hash: GIT_HASH.map(String::from),
+            parser_config: serde_json::to_string(config)?,
+            args: serde_json::to_string(&std::env::args().collect::<Vec<_>>())?,
+            counts_before: dataset_counts(conn)?,
+            ..Default::default()
+        },
+    )
+}
+
+/// Close the manifest with the dataset size coming out. A run that fails
+/// before this keeps `finished_at` NULL and is never embedded in exports.
+pub fn finish(conn: &Connection, info: &RunInfo) -> Result<()> {
+    db::finish_run_manifest(conn, &info.run_id, &dataset_counts(conn)?)
+}
+
+/// A manifest as one JSON object, its JSON columns inlined.
+pub fn to_json(m: &RunManifest) -> Value {
+    let parse = |s: &str| serde_json::from_str(s).unwrap_or(Value::Null);
+    json!({
+        "run_id": m.run_id,
+        "command": m.command,
+        "crate_version": m.crate_version,
+        "git_hash": m.git_hash,
+        "parser_config": parse(&m.parser_config),
+        "args": parse(&m.args),
+        "counts_before": parse(&m.counts_before),
+        "counts_after": m.counts_after.as_deref().map_or(Value::Null, parse),
+        "started_at": m.started_at,
+        "finished_at": m.finished_at,
+    })
+}
+
+/// (field, value) rows for a key/value sheet or table; JSON fields stay
+/// compact JSON text and missing values are empty.
+#[cfg_attr(not(any(feature = "xlsx", feature = "duckdb")), allow(dead_code))]
+pub fn fields(m: &RunManifest) -> Vec<(&'static str, String)> {
+    vec![
+        ("run_id", m.run_id.clone()),
+        ("command", m.command.clone()),
+        ("crate_version", m.crate_version.clone()),
+        ("git_hash", m.git_hash.clone().unwrap_or_default()),
+        ("parser_config", m.parser_config.clone()),
+        ("args", m.args.clone()),
+        ("counts_before", m.counts_before.clone()),
+        ("counts_after", m.counts_after.clone().unwrap_or_default()),
+        ("started_at", m.started_at.clone().unwrap_or_default()),
+        ("finished_at", m.finished_at.clone().unwrap_or_default()),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn manifest_records_counts_before_and_after() {
+        let conn = Connection::open_in_memory().unwrap();
+        db::init_schema(&conn).unwrap();
+        let info = RunInfo::new("process", ":memory:");
+        let config = ParserConfig { min_meta_cluster: 4, ..Default::default() };
+        begin(&conn, &info, &config).unwrap();
+        assert_eq!(db::fetch_latest_run_manifest(&conn).unwrap(), None);
+
+        conn.execute("INSERT INTO companies (slug, url) VALUES ('acme', 'x')", []).unwrap();
+        finish(&conn, &info).unwrap();
+        let m = db::fetch_latest_run_manifest(&conn).unwrap().unwrap();
+        assert_eq!(m.run_id, info.run_id);
+        let j = to_json(&m);
+        assert_eq!(j["crate_version"], env!("CARGO_PKG_VERSION"));
+        assert_eq!(j["parser_config"]["min_meta_cluster"], 4);
+        assert_eq!(j["counts_before"]["companies"], 0);
+        assert_eq!(j["counts_after"]["companies"], 1);
+        assert!(j["args"].is_array());
+        assert!(j["finished_at"].is_string());
+        assert_eq!(fields(&m).len(), 10);
+    }
+}
@@ -0,0 +1,330 @@
+use std::fmt;
+use std::str::FromStr;
+
+// ── Company status ──
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CompanyStatus {
+    Active,
+    Public,
+    Acquired,
+    Inactive,
+}
+
+impl CompanyStatus {
+    pub const ALL: [CompanyStatus; 4] = [
+        CompanyStatus::Active,
+        CompanyStatus::Public,
+        CompanyStatus::Acquired,
+        CompanyStatus::Inactive,
+    ];
+
+    /// The exact string stored in `companies.status`.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            CompanyStatus::Active => "Active",
+            CompanyStatus::Public => "Public",
+            CompanyStatus::Acquired => "Acquired",
+            CompanyStatus::Inactive => "Inactive",
+        }
+    }
+}
+
+impl fmt::Display for CompanyStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad(self.as_str())
+    }
+}
+
+impl FromStr for CompanyStatus {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        if let Some(status) = Self::ALL.iter().find(|v| v.as_str().eq_ignore_ascii_case(s)) {
+            return Ok(*status);
+        }
+        let names: Vec<&str> = Self::ALL.iter().map(|v| v.as_str()).collect();
+        Err(unknown_value("status", s, &names))
+    }
+}
+
+impl serde::Serialize for CompanyStatus {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+impl<'de> serde::Deserialize<'de> for CompanyStatus {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        s.parse().map_err(serde::de::Error::custom)
+    }
+}
+
+impl rusqlite::types::ToSql for CompanyStatus {
+    fn to_sql(&self) -> rusqlite::Result<rusqlite::types::ToSqlOutput<'_>> {
+        Ok(self.as_str().into())
+    }
+}
+
+/// Reading a value outside the enum (a DB written without the CHECK
+/// constraint, or edited by hand) is an error naming the value.
+impl rusqlite::types::FromSql for CompanyStatus {
+    fn column_result(value: rusqlite::types::ValueRef<'_>) -> rusqlite::types::FromSqlResult<Self> {
+        value.as_str()?.parse().map_err(|e: String| rusqlite::types::FromSqlError::Other(e.into()))
+    }
+}
+
+// ── Batch ──
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Season {
+    Winter,
+    Spring,
+    Summer,
+    Fall,
+}
+
+impl Season {
+    pub const ALL: [Season; 4] = [Season::Winter, Season::Spring, Season::Summer, Season::Fall];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Season::Winter => "Winter",
+            Season::Spring => "Spring",
+            Season::Summer => "Summer",
+            Season::Fall => "Fall",
+        }
+    }
+
+    /// YC shorthand letter: W24, X25 (Spring), S09, F24.
+    pub fn code(self) -> char {
+        match self {
+            Season::Winter => 'W',
+            Season::Spring => 'X',
+            Season::Summer => 'S',
+            Season::Fall => 'F',
+        }
+    }
+
+    fn from_code(c: char) -> Option<Season> {
+        Self::ALL
+            .into_iter()
+            .find(|s| s.code() == c.to_ascii_uppercase())
+    }
+}
+
+/// A YC batch such as "Winter 2024". Parses full names and shorthand ("W24").
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Batch {
+    pub season: Season,
+    pub year: i32,
+}
+
+impl Batch {
+    /// The batch running on `date` on YC's four-batch calendar (since 2024):
+    /// Winter from January, Spring from April, Summer from July, Fall from
+    /// October.
+    pub fn on(date: chrono::NaiveDate) -> Batch {
+        use chrono::Datelike;
+        let season = Season::ALL[(date.month0() / 3) as usize];
+        Batch { season, year: date.year() }
+    }
+
+    /// The batch after this one.
+    pub fn next(self) -> Batch {
+        let i = Season::ALL.iter().position(|s| *s == self.season).unwrap_or(0);
+        match Season::ALL.get(i + 1) {
+            Some(&season) => Batch { season, year: self.year },
+            None => Batch { season: Season::Winter, year: self.year + 1 },
+        }
+    }
+
+    /// Sort key in calendar order: year * 10 plus 1-4 for Winter..Fall
+    /// (`companies.batch_ordinal`).
+    pub fn ordinal(self) -> i32 {
+        self.year * 10 + self.season as i32 + 1
+    }
+}
+
+impl fmt::Display for Batch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}", self.season.as_str(), self.year)
+    }
+}
+
+impl FromStr for Batch {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        let split = s
+            .find(|c: char| c.is_ascii_digit())
+            .ok_or_else(|| format!("invalid batch '{}': expected e.g. 'Winter 2024' or 'W24'", s))?;
+        let (season_part, year_part) = s.split_at(split);
+        let season_part = season_part.trim();
+
+        let year: i32 = year_part
+            .trim()
+            .parse()
+            .map_err(|_| format!("invalid batch year in '{}'", s))?;
+        let year = match year {
+            0..=99 => 2000 + year,
+            2005..=2100 => year,
+            _ => return Err(format!("invalid batch year in '{}'", s)),
+        };
+
+        let mut chars = season_part.chars();
+        let season = match (chars.next(), chars.next()) {
+            (Some(c), None) => Season::from_code(c),
+            _ => Season::ALL
+                .into_iter()
+                .find(|v| v.as_str().eq_ignore_ascii_case(season_part)),
+        };
+        match season {
+            Some(season) => Ok(Batch { season, year }),
+            None => {
+                let names: Vec<&str> = Season::ALL.iter().map(|v| v.as_str()).collect();
+                Err(unknown_value("batch season", season_part, &names))
+            }
+        }
+    }
+}
+
+// ── Timestamp ──
+
+/// A UTC instant for `--since` style arguments. Parses "2024-05-01",
+/// "2024-05-01 12:00:00" (or with a `T`) and RFC 3339 with an offset.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Timestamp(pub chrono::NaiveDateTime);
+
+impl Timestamp {
+    /// Comparable with SQLite `datetime('now')` / `strftime('%Y-%m-%d %H:%M:%f')` text.
+    pub fn to_sql_string(self) -> String {
+        self.0.format("%Y-%m-%d %H:%M:%S%.3f").to_string()
+    }
+}
+
+impl fmt::Display for Timestamp {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0.format("%Y-%m-%d %H:%M:%S"))
+    }
+}
+
+impl FromStr for Timestamp {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        use chrono::{DateTime, NaiveDate, NaiveDateTime};
+
+        let s = s.trim();
+        if let Ok(t) = DateTime::parse_from_rfc3339(s) {
+            return Ok(Timestamp(t.naive_utc()));
+        }
+        for format in ["%Y-%m-%d %H:%M:%S%.f", "%Y-%m-%dT%H:%M:%S%.f", "%Y-%m-%d %H:%M"] {
+            if let Ok(t) = NaiveDateTime::parse_from_str(s, format) {
+                return Ok(Timestamp(t));
+            }
+        }
+        NaiveDate::parse_from_str(s, "%Y-%m-%d")
+            .map(|d| Timestamp(d.and_time(chrono::NaiveTime::MIN)))
+            .map_err(|_| format!("invalid timestamp '{}': expected e.g. '2024-05-01' or '2024-05-01 12:00:00' (UTC)", s))
+    }
+}
+
+// ── Suggestions ──
+
+/// "unknown status 'Acquird' (did you mean 'Acquired'?)", or the valid list.
+fn unknown_value(what: &str, got: &str, valid: &[&str]) -> String {
+    let best = valid
+        .iter()
+        .map(|v| (edit_distance(&got.to_lowercase(), &v.to_lowercase()), *v))
+        .min();
+    match best {
+        Some((d, v)) if d <= 3 => format!("unknown {} '{}' (did you mean '{}'?)", what, got, v),
+        _ => format!("unknown {} '{}' (expected one of: {})", what, got, valid.join(", ")),
+    }
+}
+
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut prev: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut cur = vec![i + 1; b.len() + 1];
+        for (j, cb) in b.iter().enumerate() {
+            let cost = usize::from(ca != *cb);
+            cur[j + 1] = (prev[j] + cost).min(prev[j + 1] + 1).min(cur[j] + 1);
+        }
+        prev = cur;
+    }
+    prev[b.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn status_parses_case_insensitively() {
+        assert_eq!("acquired".parse::<CompanyStatus>(), Ok(CompanyStatus::Acquired));
+        assert_eq!(" Public ".parse::<CompanyStatus>(), Ok(CompanyStatus::Public));
+    }
+
+    #[test]
+    fn status_typo_suggests() {
+        let err = "Acquird".parse::<CompanyStatus>().unwrap_err();
+        assert!(err.contains("did you mean 'Acquired'"), "{}", err);
+        let err = "zzzzzzzzzz".parse::<CompanyStatus>().unwrap_err();
+        assert!(err.contains("expected one of"), "{}", err);
+    }
+
+    #[test]
+    fn status_round_trips_through_json_and_sqlite() {
+        assert_eq!(serde_json::to_string(&CompanyStatus::Public).unwrap(), "\"Public\"");
+        assert_eq!(serde_json::from_str::<CompanyStatus>("\"inactive\"").unwrap(), CompanyStatus::Inactive);
+        let err = serde_json::from_str::<CompanyStatus>("\"Acquird\"").unwrap_err();
+        assert!(err.to_string().contains("did you mean 'Acquired'"), "{}", err);
+
+        let conn = rusqlite::Connection::open_in_memory().unwrap();
+        let read = |sql: &str| conn.query_row(sql, [], |r| r.get::<_, Option<CompanyStatus>>(0));
+        assert_eq!(read("SELECT 'Acquired'").unwrap(), Some(CompanyStatus::Acquired));
+        assert_eq!(read("SELECT NULL").unwrap(), None);
+        assert!(read("SELECT 'Stealth'").is_err());
+        let stored: String =
+            conn.query_row("SELECT ?1", [CompanyStatus::Active], |r| r.get(0)).unwrap();
+        assert_eq!(stored, "Active");
+        assert_eq!(format!("{:<8}|", CompanyStatus::Public), "Public  |");
+    }
+
+    #[test]
+    fn batch_formats() {
+        let w24 = Batch { season: Season::Winter, year: 2024 };
+        assert_eq!("Winter 2024".parse::<Batch>(), Ok(w24));
+        assert_eq!("W24".parse::<Batch>(), Ok(w24));
+        assert_eq!("winter2024".parse::<Batch>(), Ok(w24));
+        assert_eq!("X25".parse::<Batch>().unwrap().season, Season::Spring);
+        assert_eq!(w24.to_string(), "Winter 2024");
+
+        let may = chrono::NaiveDate::from_ymd_opt(2025, 5, 20).unwrap();
+        assert_eq!(Batch::on(may), "X25".parse().unwrap());
+        assert_eq!(Batch::on(may).next(), "S25".parse().unwrap());
+        assert_eq!("F25".parse::<Batch>().unwrap().next(), "W26".parse().unwrap());
+    }
+
+    #[test]
+    fn timestamp_formats() {
+        let noon: Timestamp = "2024-05-01 12:00:00".parse().unwrap();
+        assert_eq!("2024-05-01T12:00:00".parse::<Timestamp>(), Ok(noon));
+        assert_eq!("2024-05-01T14:00:00+02:00".parse::<Timestamp>(), Ok(noon));
+        assert_eq!("2024-05-01".parse::<Timestamp>().unwrap().to_sql_string(), "2024-05-01 00:00:00.000");
+        assert!("last tuesday".parse::<Timestamp>().is_err());
+    }
+
+    #[test]
+    fn batch_typo_suggests() {
+        let err = "Wintr 2024".parse::<Batch>().unwrap_err();
+        assert!(err.contains("did you mean 'Winter'"), "{}", err);
+        assert!("Winter".parse::<Batch>().is_err());
+    }
+}
@@ -0,0 +1,118 @@
+use anyhow::Result;
+
+fn disabled(what: &str) -> anyhow::Error {
+    anyhow::anyhow!("{} needs network access, which is not compiled in; rebuild with the `network` feature", what)
+}
+
+/// Stand-in for `scraper.rs` when built without `network`.
+pub mod scraper {
+    use super::*;
+    use crate::writer::DbWriter;
+
+    pub struct ScrapeStats {
+        pub total: usize,
+        pub ok: usize,
+        pub errors: usize,
+        pub invalid: usize,
+        pub renamed: usize,
+        pub error_categories: Vec<(&'static str, usize)>,
+    }
+
+    /// Accepted by the CLI; scraping fails before it matters.
+    #[derive(Clone, Copy, Debug, Default, PartialEq, Eq, clap::ValueEnum)]
+    pub enum ScrapeMode {
+        #[default]
+        Single,
+        Crawl,
+    }
+
+    /// Accepted by the CLI like `ScrapeMode`.
+    #[derive(Clone, Copy, Debug, PartialEq, Eq, clap::ValueEnum)]
+    pub enum RawFormat {
+        Plain,
+        Gzip,
+    }
+
+    #[derive(Clone, Copy, Debug, Default, PartialEq, Eq, clap::ValueEnum)]
+    pub enum PageFormat {
+        #[default]
+        Markdown,
+        Html,
+    }
+
+    /// Built by the CLI; there's nothing to configure offline.
+    #[allow(dead_code)]
+    #[derive(Clone, Copy, Default)]
+    pub struct ScrapeOptions {
+        pub retry_errors: bool,
+        pub failover: bool,
+        pub mode: ScrapeMode,
+        pub keep_raw: Option<RawFormat>,
+        pub return_format: PageFormat,
+    }
+
+    pub async fn scrape_pages_streaming(
+        _writer: &DbWriter,
+        _pages: Vec<(i64, String, String)>,
+        _options: ScrapeOptions,
+        _cassette: Option<std::sync::Arc<crate::cassette::Cassette>>,
+    ) -> Result<ScrapeStats> {
+        Err(disabled("Scraping"))
+    }
+
+    pub async fn scrape_single_page(_url: &str) -> Result<String> {
+        Err(disabled("Scraping"))
+    }
+
+    pub struct JobScrapeStats {
+        pub saved: usize,
+        pub skipped: usize,
+        pub failed: usize,
+        pub stacks: Vec<(&'static str, usize)>,
+    }
+
+    pub async fn scrape_job_pages(_writer: &DbWriter, _jobs: Vec<(i64, String)>) -> Result<JobScrapeStats> {
+        Err(disabled("Scraping"))
+    }
+}
+
+/// Stand-in for `resolve.rs` when built without `network`.
+pub mod resolve {
+    use super::*;
+
+    pub struct Resolved {
+        pub id: i64,
+        pub canonical_url: String,
+        pub redirected: bool,
+        pub failed: bool,
+    }
+
+    pub async fn resolve_urls(_items: Vec<(i64, String)>) -> Result<Vec<Resolved>> {
+        Err(disabled("Redirect resolution"))
+    }
+
+    /// Never reached: `resolve_urls` fails first.
+    pub fn ats_vendor(_url: &str) -> Option<&'static str> {
+        None
+    }
+}
+
+/// Stand-in for `linkcheck.rs` when built without `network`.
+pub mod linkcheck {
+    use super::*;
+    use crate::db::LinkCheckRow;
+
+    pub async fn check_links(_items: Vec<(String, String)>) -> Result<Vec<LinkCheckRow>> {
+        Err(disabled("Link checking"))
+    }
+}
+
+/// Stand-in for `sitemap::fetch_company_urls` when built without `network`.
+pub async fn fetch_company_urls() -> Result<Vec<crate::db::PageUrl>> {
+    Err(disabled("Fetching the sitemap"))
+}
+
+/// Stand-in for the `--sink https://...` writer when built without `network`.
+pub fn http_sink(_url: &str) -> Result<Box<dyn crate::sink::ExtractSink + 'static>> {
+    Err(disabled("The HTTP sink"))
+}
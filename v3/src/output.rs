@@ -0,0 +1,164 @@
+use std::collections::BTreeMap;
+use std::sync::{Mutex, OnceLock};
+use std::time::Duration;
+
+use indicatif::ProgressBar;
+use serde::Serialize;
+
+/// How a command reports: free-form text, nothing (`--quiet`), or one JSON
+/// result object on stdout when it ends (`--json`).
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Mode {
+    Human,
+    Quiet,
+    Json,
+}
+
+static MODE: OnceLock<Mode> = OnceLock::new();
+static RESULT: Mutex<Outcome> = Mutex::new(Outcome::new());
+
+/// What `--json` prints: counts, non-fatal errors and phase timings the
+/// command recorded, plus the error it failed with.
+#[derive(Serialize, Debug, Default)]
+pub struct Outcome {
+    pub command: String,
+    pub ok: bool,
+    pub counts: BTreeMap<&'static str, usize>,
+    pub errors: Vec<String>,
+    pub timings_ms: BTreeMap<&'static str, f64>,
+}
+
+impl Outcome {
+    const fn new() -> Self {
+        Outcome {
+            command: String::new(),
+            ok: false,
+            counts: BTreeMap::new(),
+            errors: Vec::new(),
+            timings_ms: BTreeMap::new(),
+        }
+    }
+
+    fn close(&mut self, command: &str, result: &anyhow::Result<()>, elapsed: Duration) {
+        self.command = command.to_string();
+        self.ok = result.is_ok();
+        if let Err(e) = result {
+            self.errors.push(format!("{:#}", e));
+        }
+        self.timings_ms.insert("total", millis(elapsed));
+    }
+}
+
+fn millis(d: Duration) -> f64 {
+    d.as_secs_f64() * 1000.0
+}
+
+pub fn init(mode: Mode) {
+    let _ = MODE.set(mode);
+}
+
+pub fn mode() -> Mode {
+    MODE.get().copied().unwrap_or(Mode::Human)
+}
+
+/// True unless human-readable output is wanted.
+pub fn quiet() -> bool {
+    mode() != Mode::Human
+}
+
+/// Set a count in the result object; a later call for the same key wins.
+pub fn count(key: &'static str, n: usize) {
+    RESULT.lock().unwrap().counts.insert(key, n);
+}
+
+/// A problem the command carried on past (a failed listing, a skipped file).
+pub fn error(message: impl Into<String>) {
+    RESULT.lock().unwrap().errors.push(message.into());
+}
+
+/// Time spent in one phase; "total" is added by `finish`.
+pub fn timing(key: &'static str, elapsed: Duration) {
+    RESULT.lock().unwrap().timings_ms.insert(key, millis(elapsed));
+}
+
+/// A progress bar, hidden unless output is human.
+pub fn progress_bar(len: u64) -> ProgressBar {
+    if quiet() {
+        ProgressBar::hidden()
+    } else {
+        ProgressBar::new(len)
+    }
+}
+
+/// Close the result for `command` and take it.
+pub fn finish(command: &str, result: &anyhow::Result<()>, elapsed: Duration) -> Outcome {
+    let mut outcome = std::mem::take(&mut *RESULT.lock().unwrap());
+    outcome.close(command, result, elapsed);
+    outcome
+}
+
+/// `println!` for human-readable text; silent under `--quiet` and `--json`.
+macro_rules! out {
+    () => {
+        $crate::output::out!("")
+    };
+    ($($arg:tt)*) => {
+        if !$crate::output::quiet() {
+            $crate::output::print_line(format_args!($($arg)*));
+        }
+    };
+}
+pub(crate) use out;
+
+#[cfg(test)]
+thread_local! {
+    static CAPTURED: std::cell::RefCell<Option<String>> = const { std::cell::RefCell::new(None) };
+}
+
+/// One `out!` line: to stdout, or to the buffer of an enclosing `capture`.
+pub fn print_line(line: std::fmt::Arguments) {
+    #[cfg(test)]
+    {
+        use std::fmt::Write;
+        let captured = CAPTURED.with_borrow_mut(|buf| buf.as_mut().map(|buf| writeln!(buf, "{}", line)));
+        if captured.is_some() {
+            return;
+        }
+    }
+    println!("{}", line);
+}
+
+/// What `f` prints with `out!` on this thread, instead of printing it.
+#[cfg(test)]
+pub fn capture(f: impl FnOnce()) -> String {
+    CAPTURED.set(Some(String::new()));
+    f();
+    CAPTURED.take().unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn outcome_serializes_counts_errors_and_timings() {
+        let mut outcome = Outcome::default();
+        outcome.counts.insert("pages", 4);
+        outcome.errors.push("listing W24 failed".into());
+        outcome.timings_ms.insert("scrape", 250.0);
+        let failed: anyhow::Result<()> = Err(anyhow::anyhow!("disk full").context("saving pages"));
+        outcome.close("scrape", &failed, Duration::from_secs(1));
+        let json = serde_json::to_value(&outcome).unwrap();
+        assert_eq!(json["command"], "scrape");
+        assert_eq!(json["ok"], false);
+        assert_eq!(json["counts"]["pages"], 4);
+        assert_eq!(json["errors"][0], "listing W24 failed");
+        assert_eq!(json["errors"][1], "saving pages: disk full");
+        assert_eq!(json["timings_ms"]["scrape"], 250.0);
+        assert_eq!(json["timings_ms"]["total"], 1000.0);
+
+        let mut ok = Outcome::default();
+        ok.close("stats", &Ok(()), Duration::ZERO);
+        assert_eq!(serde_json::to_value(&ok).unwrap()["errors"], serde_json::json!([]));
+    }
+}
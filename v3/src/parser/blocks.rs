@@ -1,16 +1,24 @@
-use std::collections::HashSet;
+use std::collections::{HashSet, VecDeque};
 use std::sync::LazyLock;
 
 use regex::Regex;
+use serde::Serialize;
+
+use super::dates;
+use super::lang::Lexicon;
+use super::ParserConfig;
 
 static HEADING_RE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"^(#{1,6})\s+(.+)$").unwrap());
 static SINGLE_LINK_RE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"^\[([^\]]*)\]\(([^)]+)\)$").unwrap());
 static INLINE_LINKS_RE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"\[([^\]]*)\]\(([^)]+)\)").unwrap());
 static CLOSE_LINK_RE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"^\]\(([^)]+)\)(.*)$").unwrap());
+static META_LINK_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^([A-Z][A-Za-z ]{1,22}):\s*\[([^\]]+)\]\(([^)]+)\)$").unwrap());
 static META_RE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"^([A-Z][A-Za-z ]{1,22}):(.*)$").unwrap());
 static TAG_RE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"/companies/(industry|location)/").unwrap());
 static URL_RE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"\((https?://[^)]+)\)").unwrap());
 static DOMAIN_RE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"https?://(?:www\.)?([^/]+)").unwrap());
+static FENCE_RE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"^(`{3,}|~{3,})\s*([^`]*)$").unwrap());
 
 #[derive(Debug, Clone)]
 pub enum Block {
@@ -26,179 +34,363 @@ pub enum Block {
         links: Vec<(String, String)>, // (domain, url)
     },
     Text(String),
+    /// Fenced code, verbatim; `lang` is the info string after the fence.
+    Code { lang: Option<String>, text: String },
+    /// Consecutive `>` lines with the marker stripped, joined by newlines.
+    Quote(String),
     Empty,
 }
 
 const STATUS_KEYWORDS: &[&str] = &["Active", "Public", "Acquired", "Inactive"];
-const TITLE_KEYWORDS: &[&str] = &["Founder", "CEO", "CTO", "COO", "Co-", "President", "Partner"];
+/// Lex with English title keywords and noise labels and default thresholds.
+#[cfg(test)]
+pub fn lex(markdown: &str) -> Lexed {
+    lex_with(markdown, &Lexicon::english(), &ParserConfig::default())
+}
+
+/// 1-based, inclusive range of markdown lines a block was lexed from.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub struct Span {
+    pub start_line: usize,
+    pub end_line: usize,
+}
 
-pub fn classify_lines(markdown: &str) -> Vec<Block> {
-    if markdown.trim().is_empty() {
-        return vec![Block::Empty];
+impl Span {
+    /// Smallest span covering both.
+    pub fn merge(self, other: Span) -> Span {
+        Span {
+            start_line: self.start_line.min(other.start_line),
+            end_line: self.end_line.max(other.end_line),
+        }
     }
+}
 
-    let lines: Vec<&str> = markdown.lines().collect();
-    let mut blocks = Vec::with_capacity(lines.len());
-    let mut seen_names: HashSet<String> = HashSet::new();
-    let mut i = 0;
+impl std::fmt::Display for Span {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.start_line == self.end_line {
+            write!(f, "{}", self.start_line)
+        } else {
+            write!(f, "{}-{}", self.start_line, self.end_line)
+        }
+    }
+}
 
-    while i < lines.len() {
-        let line = lines[i].trim();
+/// Parses the `Display` form back: "41-44" or "41".
+impl std::str::FromStr for Span {
+    type Err = std::num::ParseIntError;
 
-        if line.is_empty() {
-            blocks.push(Block::Empty);
-            i += 1;
-            continue;
-        }
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (start, end) = s.split_once('-').unwrap_or((s, s));
+        Ok(Span { start_line: start.trim().parse()?, end_line: end.trim().parse()? })
+    }
+}
 
-        // ── Multi-line link: line is "[" or starts a sequence [\ntext\n](url) ──
-        if line == "[" {
-            i = consume_multiline_link(&lines, i, &mut blocks);
-            continue;
-        }
+/// Lexer output: `spans[i]` is the line range `blocks[i]` came from.
+pub struct Lexed {
+    pub blocks: Vec<Block>,
+    pub spans: Vec<Span>,
+}
 
-        // ── Continuation link: ](url) possibly with trailing [ ──
-        if line.starts_with("](") {
-            if let Some(caps) = CLOSE_LINK_RE.captures(line) {
-                // This is a stray ](url) — emit as bare link
-                emit_link("", &caps[1], &mut blocks);
-                let rest = caps[2].trim();
-                if rest == "[" {
-                    i = consume_multiline_link(&lines, i + 1, &mut blocks);
-                    continue;
-                }
+/// Classify every line using `lex` for title keywords and noise labels and
+/// `config` for the person-name limits, recording the line range behind each block.
+pub fn lex_with(markdown: &str, lex: &Lexicon, config: &ParserConfig) -> Lexed {
+    let (blocks, spans) = lex_iter(markdown, lex, config).unzip();
+    Lexed { blocks, spans }
+}
+
+/// The lexer as an iterator of `(block, line span)`. Lines are read from
+/// the markdown and classified as blocks are pulled, so a caller that scans
+/// blocks without keeping them never holds more than one step's worth of
+/// lines or blocks, however long the page.
+pub fn lex_iter<'a>(markdown: &'a str, lex: &'a Lexicon, config: &'a ParserConfig) -> LexIter<'a> {
+    let blank = markdown.trim().is_empty();
+    LexIter {
+        lines: Lines { source: if blank { "" } else { markdown }.lines(), window: VecDeque::new(), offset: 0 },
+        next_line: 0,
+        step: Vec::new(),
+        pending: if blank { VecDeque::from([Block::Empty]) } else { VecDeque::new() },
+        span: Span { start_line: 1, end_line: 1 },
+        seen_names: HashSet::new(),
+        lex,
+        config,
+    }
+}
+
+pub struct LexIter<'a> {
+    lines: Lines<'a>,
+    next_line: usize,
+    /// Scratch buffer `classify_at` pushes into.
+    step: Vec<Block>,
+    /// Blocks of the current step not yet yielded; they share `span`.
+    pending: VecDeque<Block>,
+    span: Span,
+    seen_names: HashSet<String>,
+    lex: &'a Lexicon,
+    config: &'a ParserConfig,
+}
+
+impl Iterator for LexIter<'_> {
+    type Item = (Block, Span);
+
+    fn next(&mut self) -> Option<(Block, Span)> {
+        loop {
+            if let Some(block) = self.pending.pop_front() {
+                return Some((block, self.span));
             }
-            i += 1;
-            continue;
+            let i = self.next_line;
+            self.lines.get(i)?;
+            let next = classify_at(&mut self.lines, i, &mut self.step, &mut self.seen_names, self.lex, self.config)
+                .max(i + 1);
+            // A step that ran off the end of the page ends on its last line
+            let end_line = if self.lines.get(next - 1).is_some() { next } else { self.lines.read() };
+            // Every block emitted for this step (e.g. several links on one
+            // line) shares the step's line range.
+            self.span = Span { start_line: i + 1, end_line };
+            self.pending.extend(self.step.drain(..));
+            self.next_line = next;
+            self.lines.release(next);
         }
+    }
+}
 
-        // ── Heading: ### text ──
-        if let Some(caps) = HEADING_RE.captures(line) {
-            blocks.push(Block::Heading {
-                level: caps[1].len() as u8,
-                text: caps[2].to_string(),
-            });
-            i += 1;
-            continue;
-        }
+/// The markdown's lines, read on demand. Only the lines from the current
+/// step onwards are kept; the lexer never looks back past the step start.
+struct Lines<'a> {
+    source: std::str::Lines<'a>,
+    window: VecDeque<&'a str>,
+    /// Index of `window[0]` in the whole page.
+    offset: usize,
+}
 
-        // ── Single link on the line: [text](url) ──
-        if SINGLE_LINK_RE.is_match(line) {
-            let caps = SINGLE_LINK_RE.captures(line).unwrap();
-            emit_link(&caps[1], &caps[2], &mut blocks);
-            i += 1;
-            continue;
+impl<'a> Lines<'a> {
+    /// Line `i` (0-based), reading ahead as far as needed.
+    fn get(&mut self, i: usize) -> Option<&'a str> {
+        let at = i.checked_sub(self.offset)?;
+        while self.window.len() <= at {
+            self.window.push_back(self.source.next()?);
         }
+        Some(self.window[at])
+    }
 
-        // ── Line with multiple inline links: [](url1)[](url2) or ending with [ ──
-        if line.contains("](") && line.contains('[') {
-            // Extract all links on this line
-            for caps in INLINE_LINKS_RE.captures_iter(line) {
-                emit_link(&caps[1], &caps[2], &mut blocks);
-            }
-            // If line ends with [, next link is multi-line
-            if line.ends_with('[') {
-                i = consume_multiline_link(&lines, i + 1, &mut blocks);
-                continue;
+    /// Number of lines read so far.
+    fn read(&self) -> usize {
+        self.offset + self.window.len()
+    }
+
+    /// Forget the lines before `i`.
+    fn release(&mut self, i: usize) {
+        let n = i.saturating_sub(self.offset).min(self.window.len());
+        self.window.drain(..n);
+        self.offset += n;
+    }
+}
+
+/// Classify the line at `i`, pushing zero or more blocks. Returns the index
+/// of the next unconsumed line.
+fn classify_at(
+    lines: &mut Lines<'_>,
+    i: usize,
+    blocks: &mut Vec<Block>,
+    seen_names: &mut HashSet<String>,
+    lex: &Lexicon,
+    config: &ParserConfig,
+) -> usize {
+    let Some(line) = lines.get(i).map(|l| l.trim()) else {
+        return i + 1;
+    };
+
+    if line.is_empty() {
+        blocks.push(Block::Empty);
+        return i + 1;
+    }
+
+    // ── Fenced code and blockquotes: their lines are content, not fields or links ──
+    if let Some(caps) = FENCE_RE.captures(line) {
+        return consume_code(lines, i, &caps[1], caps[2].trim(), blocks);
+    }
+    if line.starts_with('>') {
+        return consume_quote(lines, i, blocks);
+    }
+
+    // ── Multi-line link: line is "[" or starts a sequence [\ntext\n](url) ──
+    if line == "[" {
+        return consume_multiline_link(lines, i, blocks);
+    }
+
+    // ── Continuation link: ](url) possibly with trailing [ ──
+    if line.starts_with("](") {
+        if let Some(caps) = CLOSE_LINK_RE.captures(line) {
+            // This is a stray ](url) — emit as bare link
+            emit_link("", &caps[1], blocks);
+            let rest = caps[2].trim();
+            if rest == "[" {
+                return consume_multiline_link(lines, i + 1, blocks);
             }
-            i += 1;
-            continue;
         }
+        return i + 1;
+    }
 
-        // ── Status line ──
-        if STATUS_KEYWORDS.contains(&line) {
-            blocks.push(Block::StatusLine(line.to_string()));
-            i += 1;
-            continue;
-        }
+    // ── Heading: ### text ──
+    if let Some(caps) = HEADING_RE.captures(line) {
+        blocks.push(Block::Heading {
+            level: caps[1].len() as u8,
+            text: caps[2].to_string(),
+        });
+        return i + 1;
+    }
 
-        // ── Meta field: Key:Value or Key: (empty value) ──
-        if let Some(caps) = META_RE.captures(line) {
-            blocks.push(Block::MetaField {
-                key: caps[1].trim().to_string(),
-                value: caps[2].trim().to_string(),
-            });
-            i += 1;
-            continue;
+    // ── Meta field whose value is a link: Primary Partner:[Name](url) ──
+    if let Some(caps) = META_LINK_RE.captures(line) {
+        blocks.push(Block::MetaField {
+            key: caps[1].trim().to_string(),
+            value: caps[2].trim().to_string(),
+        });
+        emit_link(&caps[2], &caps[3], blocks);
+        return i + 1;
+    }
+
+    // ── Single link on the line: [text](url) ──
+    if SINGLE_LINK_RE.is_match(line) {
+        let caps = SINGLE_LINK_RE.captures(line).unwrap();
+        emit_link(&caps[1], &caps[2], blocks);
+        return i + 1;
+    }
+
+    // ── Line with multiple inline links: [](url1)[](url2) or ending with [ ──
+    if line.contains("](") && line.contains('[') {
+        // Extract all links on this line
+        for caps in INLINE_LINKS_RE.captures_iter(line) {
+            emit_link(&caps[1], &caps[2], blocks);
+        }
+        // If line ends with [, next link is multi-line
+        if line.ends_with('[') {
+            return consume_multiline_link(lines, i + 1, blocks);
         }
+        return i + 1;
+    }
+
+    // ── Status line ──
+    if STATUS_KEYWORDS.contains(&line) {
+        blocks.push(Block::StatusLine(line.to_string()));
+        return i + 1;
+    }
 
-        // ── Person detection ──
-        if line.len() < 60
-            && !line.contains("](")
-            && !line.contains(':')
-            && !line.contains('›')
-            && !line.starts_with("[>")
-            && !is_date_like(line)
-            && !is_noise_line(line)
-            && line.split_whitespace().count() <= 6
+    // ── Meta field: Key:Value or Key: (empty value) ──
+    if let Some(caps) = META_RE.captures(line) {
+        blocks.push(Block::MetaField {
+            key: caps[1].trim().to_string(),
+            value: caps[2].trim().to_string(),
+        });
+        return i + 1;
+    }
+
+    // ── Person detection ──
+    if line.len() < config.max_person_name_len
+        && !line.contains("](")
+        && !line.contains(':')
+        && !line.contains('›')
+        && !line.starts_with("[>")
+        && !is_date_like(line, &config.date_formats)
+        && !is_noise_line(line, lex)
+        && line.split_whitespace().count() <= config.max_person_name_words
+    {
+        if let Some((person, consumed)) =
+            try_parse_person(lines, i, seen_names, lex, config)
         {
-            if let Some((person, consumed)) =
-                try_parse_person(&lines, i, &mut seen_names)
-            {
-                blocks.push(person);
-                i += consumed;
-                continue;
-            }
+            blocks.push(person);
+            return i + consumed;
         }
-
-        // ── Plain text ──
-        blocks.push(Block::Text(line.to_string()));
-        i += 1;
     }
 
-    blocks
+    // ── Plain text ──
+    blocks.push(Block::Text(line.to_string()));
+    i + 1
 }
 
 /// Consume a multi-line link starting at `start` (which should be a "[" line or
-/// the line after a trailing "["). Reads text lines until ](url).
+/// the line after a trailing "["). Reads text lines until ](url); a "](url)["
+/// close starts the next link, so chains are followed in a loop rather than
+/// by recursion (remote pages can chain thousands).
 /// Returns the next line index to process.
 fn consume_multiline_link(
-    lines: &[&str],
+    lines: &mut Lines<'_>,
     start: usize,
     blocks: &mut Vec<Block>,
 ) -> usize {
-    let mut text_parts = Vec::new();
     let mut j = start;
+    'link: loop {
+        let mut text_parts = Vec::new();
 
-    // If current line is "[", skip it
-    if j < lines.len() && lines[j].trim() == "[" {
-        j += 1;
-    }
+        // If current line is "[", skip it
+        if lines.get(j).is_some_and(|l| l.trim() == "[") {
+            j += 1;
+        }
 
-    // Collect text until ](url)
-    while j < lines.len() {
-        let l = lines[j].trim();
-        if let Some(url_part) = l.strip_prefix("](") {
-            let (url, has_trailing_open) = if let Some(end) = url_part.find(')') {
-                let u = &url_part[..end];
-                let rest = url_part[end + 1..].trim();
-                (u, rest == "[" || rest.ends_with('['))
-            } else {
-                (url_part.trim_end_matches(')'), false)
-            };
+        // Collect text until ](url)
+        while let Some(l) = lines.get(j).map(|l| l.trim()) {
+            j += 1;
+            if let Some(url_part) = l.strip_prefix("](") {
+                let (url, has_trailing_open) = match url_part.split_once(')') {
+                    Some((u, rest)) => (u, rest.trim_end().ends_with('[')),
+                    None => (url_part.trim_end_matches(')'), false),
+                };
 
-            let text = text_parts.join(" ");
-            emit_link(&text, url, blocks);
+                let text = text_parts.join(" ");
+                emit_link(&text, url, blocks);
 
-            if has_trailing_open {
-                return consume_multiline_link(lines, j + 1, blocks);
+                if has_trailing_open {
+                    continue 'link;
+                }
+                return j;
             }
-            return j + 1;
+            text_parts.push(l);
+        }
+
+        // Never found closing — push as text
+        for part in text_parts {
+            blocks.push(Block::Text(part.to_string()));
         }
-        text_parts.push(l);
+        return j;
+    }
+}
+
+/// Consume a fenced code block opened at `start` by `fence`, through the
+/// closing fence (same character, at least as long) or the end of the page.
+fn consume_code(lines: &mut Lines<'_>, start: usize, fence: &str, info: &str, blocks: &mut Vec<Block>) -> usize {
+    let marker = fence.chars().next().unwrap_or('`');
+    let mut j = start + 1;
+    let mut body = Vec::new();
+    while let Some(l) = lines.get(j) {
         j += 1;
+        let t = l.trim();
+        if t.len() >= fence.len() && t.chars().all(|c| c == marker) {
+            break;
+        }
+        body.push(l);
     }
+    blocks.push(Block::Code {
+        lang: (!info.is_empty()).then(|| info.to_string()),
+        text: body.join("\n"),
+    });
+    j
+}
 
-    // Never found closing — push as text
-    for part in text_parts {
-        blocks.push(Block::Text(part.to_string()));
+/// Consume consecutive `>` lines starting at `start` into one quote, one
+/// level of marker (and the space after it) stripped.
+fn consume_quote(lines: &mut Lines<'_>, start: usize, blocks: &mut Vec<Block>) -> usize {
+    let mut j = start;
+    let mut body = Vec::new();
+    while let Some(rest) = lines.get(j).and_then(|l| l.trim().strip_prefix('>')) {
+        body.push(rest.strip_prefix(' ').unwrap_or(rest));
+        j += 1;
     }
+    blocks.push(Block::Quote(body.join("\n").trim().to_string()));
     j
 }
 
 fn emit_link(text: &str, url: &str, blocks: &mut Vec<Block>) {
     if TAG_RE.is_match(url) {
-        let tag = url.rsplit('/').next().unwrap_or("").replace("%20", " ");
+        let tag = clean_tag(url.rsplit('/').next().unwrap_or(""));
         blocks.push(Block::TagLink {
             tag,
             url: url.to_string(),
@@ -211,15 +403,43 @@ fn emit_link(text: &str, url: &str, blocks: &mut Vec<Block>) {
     }
 }
 
+/// A tag as it appears in a `/companies/industry/...` URL segment or link
+/// text: percent-escapes decoded (`B2B%20SaaS`, `AI%2FML`), whitespace runs
+/// collapsed, trimmed.
+pub fn clean_tag(raw: &str) -> String {
+    let bytes = raw.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        let escaped = bytes
+            .get(i + 1..i + 3)
+            .filter(|h| h.iter().all(u8::is_ascii_hexdigit))
+            .and_then(|h| u8::from_str_radix(std::str::from_utf8(h).ok()?, 16).ok());
+        match (bytes[i], escaped) {
+            (b'%', Some(b)) => {
+                out.push(b);
+                i += 3;
+            }
+            (b, _) => {
+                out.push(b);
+                i += 1;
+            }
+        }
+    }
+    String::from_utf8_lossy(&out).split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
 fn try_parse_person(
-    lines: &[&str],
+    lines: &mut Lines<'_>,
     start: usize,
     seen: &mut HashSet<String>,
+    lex: &Lexicon,
+    config: &ParserConfig,
 ) -> Option<(Block, usize)> {
-    let name = lines[start].trim().to_string();
+    let name = lines.get(start)?.trim().to_string();
 
     if seen.contains(&name) {
-        let consumed = skip_person_block(lines, start);
+        let consumed = skip_person_block(lines, start, lex);
         return Some((Block::Empty, consumed));
     }
 
@@ -227,8 +447,7 @@ fn try_parse_person(
     let mut person_links = Vec::new();
 
     // Collect social links: only BARE links [](url) or ](url), not [Title](url)
-    while j < lines.len() {
-        let l = lines[j].trim();
+    while let Some(l) = lines.get(j).map(|l| l.trim()) {
         if l.is_empty() {
             j += 1;
             continue;
@@ -255,33 +474,21 @@ fn try_parse_person(
     }
 
     // Accept person if they have social links OR a recognized title on the next line
-    if person_links.is_empty() {
-        let next_is_title = j < lines.len()
-            && TITLE_KEYWORDS.iter().any(|kw| lines[j].trim().contains(kw));
-        if !next_is_title {
-            return None;
-        }
+    let title = lines.get(j).map(|l| l.trim()).filter(|t| lex.is_title(t));
+    if person_links.is_empty() && title.is_none() {
+        return None;
     }
-
-    let title = if j < lines.len() {
-        let t = lines[j].trim();
-        if TITLE_KEYWORDS.iter().any(|kw| t.contains(kw)) {
-            j += 1;
-            Some(t.to_string())
-        } else {
-            None
-        }
-    } else {
-        None
-    };
+    if title.is_some() {
+        j += 1;
+    }
+    let title = title.map(str::to_string);
 
     let mut bio_parts = Vec::new();
-    while j < lines.len() {
-        let l = lines[j].trim();
+    while let Some(l) = lines.get(j).map(|l| l.trim()) {
         if l.is_empty() || l.starts_with('[') || l.starts_with('#') {
             break;
         }
-        if l.len() < 60 && !l.contains("](") && seen.contains(l) {
+        if l.len() < config.max_person_name_len && !l.contains("](") && seen.contains(l) {
             break;
         }
         bio_parts.push(l.to_string());
@@ -306,8 +513,11 @@ fn try_parse_person(
     ))
 }
 
-fn is_date_like(s: &str) -> bool {
-    // "May 07, 2023", "Nov 20, 2022", "Dec 01, 2025"
+fn is_date_like(s: &str, date_formats: &[String]) -> bool {
+    // "May 07, 2023", "Nov 20, 2022", "Dec 01, 2025"; "7 May 2023"
+    if dates::parse(s, date_formats).is_some() {
+        return true;
+    }
     const MONTHS: &[&str] = &[
         "Jan", "Feb", "Mar", "Apr", "May", "Jun",
         "Jul", "Aug", "Sep", "Oct", "Nov", "Dec",
@@ -317,8 +527,11 @@ fn is_date_like(s: &str) -> bool {
         && trimmed.as_bytes().last().is_some_and(|c| c.is_ascii_digit())
 }
 
-fn is_noise_line(s: &str) -> bool {
+fn is_noise_line(s: &str, lex: &Lexicon) -> bool {
     let lower = s.to_lowercase();
+    if lex.noise_labels.contains(&lower.as_str()) {
+        return true;
+    }
     // Section headers, metrics, navigation fragments, media placeholders
     lower == "latest news"
         || lower.starts_with("jobs at ")
@@ -335,10 +548,9 @@ fn is_noise_line(s: &str) -> bool {
         || s.chars().all(|c| c.is_ascii_digit() || c == ',' || c == ' ')
 }
 
-fn skip_person_block(lines: &[&str], start: usize) -> usize {
+fn skip_person_block(lines: &mut Lines<'_>, start: usize, lex: &Lexicon) -> usize {
     let mut j = start + 1;
-    while j < lines.len() {
-        let l = lines[j].trim();
+    while let Some(l) = lines.get(j).map(|l| l.trim()) {
         if l.is_empty() {
             j += 1;
             continue;
@@ -349,11 +561,11 @@ fn skip_person_block(lines: &[&str], start: usize) -> usize {
         }
         break;
     }
-    if j < lines.len() && TITLE_KEYWORDS.iter().any(|kw| lines[j].trim().contains(kw)) {
+    if lines.get(j).is_some_and(|l| lex.is_title(l.trim())) {
         j += 1;
     }
-    while j < lines.len() && !lines[j].trim().is_empty() {
-        if lines[j].trim().starts_with('[') || lines[j].trim().starts_with('#') {
+    while let Some(l) = lines.get(j).map(|l| l.trim()).filter(|l| !l.is_empty()) {
+        if l.starts_with('[') || l.starts_with('#') {
             break;
         }
         j += 1;
@@ -365,6 +577,60 @@ fn skip_person_block(lines: &[&str], start: usize) -> usize {
 mod tests {
     use super::*;
 
+    fn classify_lines(markdown: &str) -> Vec<Block> {
+        lex(markdown).blocks
+    }
+
+    #[test]
+    fn iterator_matches_collected_lex() {
+        let md = "### Acme\n\n[\nJobs\n](https://acme.com/jobs)\n[a](https://a.com)[b](https://b.com)\nFounded:2021\n";
+        let (lex_, config) = (Lexicon::english(), ParserConfig::default());
+        let lexed = lex(md);
+        let streamed: Vec<(Block, Span)> = lex_iter(md, &lex_, &config).collect();
+        assert_eq!(streamed.len(), lexed.blocks.len());
+        assert!(streamed.iter().map(|(_, s)| *s).eq(lexed.spans.iter().copied()));
+        assert_eq!(streamed[2].1, Span { start_line: 3, end_line: 5 });
+        // Both links on line 6 share its span
+        assert_eq!((streamed[3].1, streamed[4].1), (Span { start_line: 6, end_line: 6 }, streamed[3].1));
+        assert!(matches!(&streamed[4].0, Block::Link { url, .. } if url == "https://b.com"));
+        // An unclosed fence runs to the last line
+        let open: Vec<_> = lex_iter("intro\n```\ncode\n", &lex_, &config).collect();
+        assert_eq!(open.last().unwrap().1, Span { start_line: 2, end_line: 3 });
+        let blank: Vec<_> = lex_iter(" \n", &lex_, &config).collect();
+        assert!(matches!(blank[..], [(Block::Empty, Span { start_line: 1, end_line: 1 })]));
+    }
+
+    #[test]
+    fn truncated_and_multibyte_input_lexes() {
+        // Inputs that ended lookahead past the last line or sliced inside a
+        // multibyte character
+        let inputs = [
+            "[",
+            "](",
+            "](é",
+            "x [\n",
+            "](https://a.com)é[",
+            "[\nÜber uns\n",
+            "```",
+            "> ",
+            "Jane Doe",
+            "Jane Doe\n[](",
+            "José Ñúñez\n\n",
+            "日本語\n[](https://例え.jp/ü)",
+            "Zoë Lee\n[](https://x.com/zoe)\nCEO\nZoë Lee",
+            "Primary Partner:[Zoë](",
+        ];
+        for md in inputs {
+            let lexed = lex(md);
+            assert_eq!(lexed.blocks.len(), lexed.spans.len(), "{:?}", md);
+        }
+        let blocks = classify_lines("[\nÜber uns\n](https://ü.example/ä)ß[\n日本\n](https://例え.jp)");
+        assert!(matches!(&blocks[0], Block::Link { text, url } if text == "Über uns" && url == "https://ü.example/ä"));
+        assert!(matches!(&blocks[1], Block::Link { text, url } if text == "日本" && url == "https://例え.jp"));
+        let blocks = classify_lines("日本語\n[](https://例え.jp/ü)");
+        assert!(matches!(&blocks[0], Block::Person { name, links, .. } if name == "日本語" && links[0].0 == "例え.jp"));
+    }
+
     #[test]
     fn heading() {
         let blocks = classify_lines("### Some heading text");
@@ -381,6 +647,9 @@ mod tests {
     fn tag_link() {
         let blocks = classify_lines("[Fintech](https://www.ycombinator.com/companies/industry/Fintech)");
         assert!(matches!(&blocks[0], Block::TagLink { tag, .. } if tag == "Fintech"));
+        let blocks = classify_lines("[AI/ML](https://www.ycombinator.com/companies/industry/AI%2FML%20%20Tools)");
+        assert!(matches!(&blocks[0], Block::TagLink { tag, .. } if tag == "AI/ML Tools"));
+        assert_eq!(clean_tag("100%25 Remote%zz"), "100% Remote%zz");
     }
 
     #[test]
@@ -389,6 +658,13 @@ mod tests {
         assert!(matches!(&blocks[0], Block::MetaField { key, value } if key == "Founded" && value == "2009"));
     }
 
+    #[test]
+    fn meta_field_with_link_value() {
+        let blocks = classify_lines("Primary Partner:[Tom Blomfield](https://www.ycombinator.com/people/tom-blomfield)");
+        assert!(matches!(&blocks[0], Block::MetaField { key, value } if key == "Primary Partner" && value == "Tom Blomfield"));
+        assert!(matches!(&blocks[1], Block::Link { url, .. } if url.ends_with("/people/tom-blomfield")));
+    }
+
     #[test]
     fn meta_field_empty_value() {
         let blocks = classify_lines("Status:");
@@ -427,6 +703,14 @@ mod tests {
         }
     }
 
+    #[test]
+    fn long_multiline_link_chain_does_not_overflow() {
+        let md = format!("[\n{}", "a\n](https://x.com)[\n".repeat(200_000));
+        let blocks = classify_lines(&md);
+        let links = blocks.iter().filter(|b| matches!(b, Block::Link { .. })).count();
+        assert_eq!(links, 200_000);
+    }
+
     #[test]
     fn person_detection() {
         let md = "Patrick Collison\n[](https://twitter.com/patrickc)\n[](https://www.linkedin.com/in/patrickcollison/)\nFounder/CEO";
@@ -439,6 +723,23 @@ mod tests {
         }
     }
 
+    #[test]
+    fn localized_title_needs_lexicon() {
+        let md = "María López\nCofundadora";
+        let is_person = |blocks: Vec<Block>| blocks.iter().any(|b| matches!(b, Block::Person { .. }));
+        assert!(!is_person(classify_lines(md)));
+        assert!(is_person(lex_with(md, &Lexicon::for_lang(Some("spa")), &ParserConfig::default()).blocks));
+    }
+
+    #[test]
+    fn person_name_limits_are_configurable() {
+        let md = "Maria de los Angeles Garcia y Lopez\nFounder/CEO";
+        let is_person = |blocks: Vec<Block>| blocks.iter().any(|b| matches!(b, Block::Person { .. }));
+        assert!(!is_person(classify_lines(md)), "7 words is over the default limit");
+        let config = ParserConfig { max_person_name_words: 8, ..ParserConfig::default() };
+        assert!(is_person(lex_with(md, &Lexicon::english(), &config).blocks));
+    }
+
     #[test]
     fn person_dedup() {
         let md = "Patrick Collison\n[](https://twitter.com/patrickc)\nFounder/CEO\n\nPatrick Collison\n[](https://twitter.com/patrickc)\nFounder/CEO";
@@ -447,6 +748,24 @@ mod tests {
         assert_eq!(persons.len(), 1);
     }
 
+    #[test]
+    fn code_and_quotes_are_not_classified_line_by_line() {
+        let md = "Intro\n\n```yaml\nRole: Engineer\n[docs](https://example.com)\n```\n> Team Size: 40\n>\n> Jane Doe\n\n~~~~\nStatus: open\n~~~\nstill code";
+        let lexed = lex(md);
+        let blocks: Vec<&Block> = lexed.blocks.iter().filter(|b| !matches!(b, Block::Empty)).collect();
+        assert!(matches!(blocks[0], Block::Text(t) if t == "Intro"));
+        assert!(matches!(blocks[1], Block::Code { lang: Some(l), text }
+            if l == "yaml" && text == "Role: Engineer\n[docs](https://example.com)"));
+        assert!(matches!(blocks[2], Block::Quote(q) if q == "Team Size: 40\n\nJane Doe"));
+        // A shorter fence doesn't close a longer one; unclosed runs to the end
+        assert!(matches!(blocks[3], Block::Code { lang: None, text } if text == "Status: open\n~~~\nstill code"));
+        assert_eq!(blocks.len(), 4);
+        assert!(!lexed.blocks.iter().any(|b| matches!(b, Block::MetaField { .. } | Block::Link { .. })));
+
+        let code = lexed.blocks.iter().position(|b| matches!(b, Block::Code { .. })).unwrap();
+        assert_eq!(lexed.spans[code], Span { start_line: 3, end_line: 6 });
+    }
+
     #[test]
     fn stripe_fixture() {
         let md = std::fs::read_to_string("tests/fixtures/stripe.md").unwrap();
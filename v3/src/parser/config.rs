@@ -0,0 +1,40 @@
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+/// Section and person detection thresholds (`[parser]` in yc.toml). The
+/// defaults fit YC company pages; loosen them for corpora that trip the
+/// heuristics instead of editing the lexer.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ParserConfig {
+    /// Consecutive MetaField blocks (Empty, StatusLine and bare links don't
+    /// break the run) that start the footer_meta section.
+    pub min_meta_cluster: usize,
+    /// Lines at least this many bytes long are never person names.
+    pub max_person_name_len: usize,
+    /// Lines with more words than this are never person names.
+    pub max_person_name_words: usize,
+    /// USD per unit of a salary currency (`EUR = 1.08`), overriding or
+    /// extending the built-in rates.
+    pub usd_rates: BTreeMap<String, f64>,
+    /// Publisher per news domain (`"sifted.eu" = "Sifted"`), overriding or
+    /// extending the built-in press list.
+    pub press_domains: BTreeMap<String, String>,
+    /// chrono formats for news dates (`"%d.%m.%Y"`), tried after the
+    /// built-in ones in `dates::FORMATS`.
+    pub date_formats: Vec<String>,
+}
+
+impl Default for ParserConfig {
+    fn default() -> Self {
+        ParserConfig {
+            min_meta_cluster: 3,
+            max_person_name_len: 60,
+            max_person_name_words: 6,
+            usd_rates: BTreeMap::new(),
+            press_domains: BTreeMap::new(),
+            date_formats: Vec::new(),
+        }
+    }
+}
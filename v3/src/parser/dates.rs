@@ -0,0 +1,46 @@
+use chrono::NaiveDate;
+
+/// chrono formats tried in order on a whole line: "May 07, 2023" and
+/// "September 7, 2023" (news), "7 May 2023", ISO "2023-05-07" (launches).
+/// `%B` also reads abbreviated month names.
+pub const FORMATS: &[&str] = &["%B %d, %Y", "%d %B %Y", "%Y-%m-%d"];
+
+/// The date a line consists of, trying `FORMATS`, then `extra` formats
+/// (`[parser] date_formats`), then RFC 3339 timestamps.
+pub fn parse(text: &str, extra: &[String]) -> Option<NaiveDate> {
+    let text = text.trim();
+    FORMATS
+        .iter()
+        .copied()
+        .chain(extra.iter().map(String::as_str))
+        .find_map(|f| NaiveDate::parse_from_str(text, f).ok())
+        .or_else(|| chrono::DateTime::parse_from_rfc3339(text).ok().map(|t| t.date_naive()))
+}
+
+/// `parse`, as "YYYY-MM-DD".
+pub fn iso(text: &str, extra: &[String]) -> Option<String> {
+    parse(text, extra).map(|d| d.format("%Y-%m-%d").to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn page_date_styles() {
+        for text in ["May 07, 2023", "May 7, 2023", "may 07, 2023", " 7 May 2023 ", "07 May 2023", "2023-05-07", "2023-05-07T09:30:00Z"] {
+            assert_eq!(iso(text, &[]).as_deref(), Some("2023-05-07"), "{}", text);
+        }
+        assert_eq!(iso("September 21, 2022", &[]).as_deref(), Some("2022-09-21"));
+        assert_eq!(iso("21 Sep 2022", &[]).as_deref(), Some("2022-09-21"));
+    }
+
+    #[test]
+    fn extra_formats_and_non_dates() {
+        assert_eq!(iso("07.05.2023", &[]), None);
+        assert_eq!(iso("07.05.2023", &["%d.%m.%Y".to_string()]).as_deref(), Some("2023-05-07"));
+        for text in ["", "May 2023", "Founded 2023", "Feb 30, 2023", "May 07, 2023 by Jane"] {
+            assert_eq!(parse(text, &[]), None, "{}", text);
+        }
+    }
+}
@@ -1,18 +1,32 @@
+use std::sync::LazyLock;
+
+use chrono::Datelike;
 use regex::Regex;
 
-use crate::db::CompanyRow;
+use crate::db::{CompanyRow, LocationRow};
+use crate::model::CompanyStatus;
 use crate::parser::blocks::Block;
+use crate::parser::lang;
 use crate::parser::sections::Section;
 
+use super::quality;
+
+/// Link text giving the number of open jobs: "View all 12 jobs", "3 open
+/// roles", or the jobs tab, "Jobs 3".
+static JOBS_LISTED_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?i)^(?:view all\s+)?(\d{1,4})\s+(?:open\s+)?(?:jobs|roles|positions)$|^jobs\s*\(?(\d{1,4})\)?$").unwrap()
+});
+
 pub fn extract(slug: &str, url: &str, sections: &[Section]) -> CompanyRow {
     let header = find_section(sections, "header");
     let footer = find_section(sections, "footer_meta");
     let jobs = find_section(sections, "jobs");
+    let description = find_section(sections, "description");
 
     // Name + tagline: skip page title ("… | Y Combinator") and breadcrumbs ("…›…")
     let header_texts: Vec<&String> = header
         .iter()
-        .flat_map(|s| &s.blocks)
+        .flat_map(|s| s.blocks)
         .filter_map(|b| match b {
             Block::Text(t)
                 if !t.is_empty()
@@ -30,7 +44,7 @@ pub fn extract(slug: &str, url: &str, sections: &[Section]) -> CompanyRow {
     // Tags from TagLink blocks (anywhere)
     let all_tags: Vec<String> = sections
         .iter()
-        .flat_map(|s| &s.blocks)
+        .flat_map(|s| s.blocks)
         .filter_map(|b| match b {
             Block::TagLink { tag, .. } => Some(tag.clone()),
             _ => None,
@@ -46,7 +60,7 @@ pub fn extract(slug: &str, url: &str, sections: &[Section]) -> CompanyRow {
     let batch_re = Regex::new(r"\?batch=([^)]+)").unwrap();
     let batch_raw = header
         .iter()
-        .flat_map(|s| &s.blocks)
+        .flat_map(|s| s.blocks)
         .find_map(|b| match b {
             Block::Link { url, .. } => batch_re.captures(url).map(|c| c[1].replace("%20", " ")),
             _ => None,
@@ -59,16 +73,22 @@ pub fn extract(slug: &str, url: &str, sections: &[Section]) -> CompanyRow {
     // Status from StatusLine (anywhere in header or footer)
     let status = sections
         .iter()
-        .flat_map(|s| &s.blocks)
+        .flat_map(|s| s.blocks)
         .find_map(|b| match b {
-            Block::StatusLine(s) => Some(s.clone()),
+            Block::StatusLine(s) => match s.parse::<CompanyStatus>() {
+                Ok(status) => Some(status),
+                Err(e) => {
+                    tracing::warn!("{}: {}", slug, e);
+                    None
+                }
+            },
             _ => None,
         });
 
     // Homepage: first external Link in header
     let homepage = header
         .iter()
-        .flat_map(|s| &s.blocks)
+        .flat_map(|s| s.blocks)
         .find_map(|b| match b {
             Block::Link { url, .. }
                 if url.starts_with("http") && !url.contains("ycombinator.com") =>
@@ -83,15 +103,21 @@ pub fn extract(slug: &str, url: &str, sections: &[Section]) -> CompanyRow {
     let team_size =
         get_meta(footer, "Team Size").and_then(|s| s.replace(",", "").parse::<i32>().ok());
     let location = get_meta(footer, "Location");
+    // Age only for founding years that pass the batch cross-check
+    let company_age_years = founded_year
+        .filter(|&y| quality::founded_year_bound(y, batch_year).is_none())
+        .map(|y| (chrono::Utc::now().year() - y).max(0));
     let batch_footer = get_meta(footer, "Batch");
 
-    // Primary Partner
-    let primary_partner = get_meta(footer, "Primary Partner");
+    // Partners: name, plus slug when the footer links the profile. Footers
+    // show a "Primary Partner", a "Group Partner" or both.
+    let (primary_partner, primary_partner_slug) = get_partner(footer, "Primary Partner");
+    let (group_partner, group_partner_slug) = get_partner(footer, "Group Partner");
 
     // Social links from footer bare Link blocks
     let social_links: Vec<&String> = footer
         .iter()
-        .flat_map(|s| &s.blocks)
+        .flat_map(|s| s.blocks)
         .filter_map(|b| match b {
             Block::Link { url, text } if text.is_empty() && url.starts_with("http") => Some(url),
             _ => None,
@@ -120,7 +146,7 @@ pub fn extract(slug: &str, url: &str, sections: &[Section]) -> CompanyRow {
         .map(|u| u.to_string());
 
     // Job count from jobs section
-    let job_count = jobs
+    let job_count_extracted = jobs
         .map(|s| {
             s.blocks
                 .iter()
@@ -130,6 +156,18 @@ pub fn extract(slug: &str, url: &str, sections: &[Section]) -> CompanyRow {
                 .count() as i32
         })
         .unwrap_or(0);
+    let job_count_listed = job_count_listed(sections, footer);
+
+    // Language of the description prose (headings, links and code excluded)
+    let description_text: Vec<&str> = description
+        .iter()
+        .flat_map(|s| s.blocks)
+        .filter_map(|b| match b {
+            Block::Text(t) | Block::Quote(t) => Some(t.as_str()),
+            _ => None,
+        })
+        .collect();
+    let description_lang = lang::detect(&description_text.join("\n")).map(str::to_string);
 
     CompanyRow {
         slug: slug.to_string(),
@@ -142,23 +180,86 @@ pub fn extract(slug: &str, url: &str, sections: &[Section]) -> CompanyRow {
         status,
         homepage,
         founded_year,
+        company_age_years,
         team_size,
         location,
         primary_partner,
+        primary_partner_slug,
+        group_partner,
+        group_partner_slug,
         tags,
-        job_count,
+        job_count_extracted,
+        job_count_listed,
         linkedin,
         twitter,
         facebook,
         crunchbase,
         github,
+        description_lang,
     }
 }
 
-fn find_section<'a>(sections: &'a [Section], kind: &str) -> Option<&'a Section> {
+/// Longest meta description kept whole; longer ones are cut to their first sentence.
+const META_TAGLINE_MAX: usize = 200;
+
+/// Tagline from the page's meta description, for pages whose header has
+/// none. YC's site-wide default description is not a tagline.
+pub fn meta_tagline(description: &str) -> Option<String> {
+    let text = description.split_whitespace().collect::<Vec<_>>().join(" ");
+    if text.is_empty() || text.starts_with("Y Combinator") {
+        return None;
+    }
+    if text.chars().count() <= META_TAGLINE_MAX {
+        return Some(text);
+    }
+    let first = text.split_inclusive(". ").next().unwrap_or(&text).trim_end();
+    Some(first.chars().take(META_TAGLINE_MAX).collect())
+}
+
+/// The number of open jobs the page states, from a "Jobs" footer field or
+/// the text of a link to the company's jobs page.
+fn job_count_listed(sections: &[Section], footer: Option<&Section>) -> Option<i32> {
+    if let Some(n) = ["Jobs", "Open Jobs"]
+        .iter()
+        .find_map(|k| get_meta(footer, k))
+        .and_then(|v| v.trim().parse().ok())
+    {
+        return Some(n);
+    }
+    sections.iter().flat_map(|s| s.blocks).find_map(|b| match b {
+        Block::Link { text, url } if url.trim_end_matches('/').ends_with("/jobs") => {
+            let text = text.split_whitespace().collect::<Vec<_>>().join(" ");
+            let caps = JOBS_LISTED_RE.captures(&text)?;
+            caps.get(1).or(caps.get(2))?.as_str().parse().ok()
+        }
+        _ => None,
+    })
+}
+
+fn find_section<'a, 's>(sections: &'a [Section<'s>], kind: &str) -> Option<&'a Section<'s>> {
     sections.iter().find(|s| s.kind == kind)
 }
 
+/// Footer locations after the first, which stays on `companies.location`
+/// (e.g. an HQ followed by a remote hub). Repeats are dropped.
+pub fn secondary_locations(slug: &str, sections: &[Section]) -> Vec<LocationRow> {
+    let mut seen: Vec<String> = Vec::new();
+    for location in get_meta_all(find_section(sections, "footer_meta"), "Location") {
+        if !location.is_empty() && !seen.contains(&location) {
+            seen.push(location);
+        }
+    }
+    seen.into_iter()
+        .enumerate()
+        .skip(1)
+        .map(|(position, location)| LocationRow {
+            company_slug: slug.to_string(),
+            location,
+            position: position as i32,
+        })
+        .collect()
+}
+
 fn get_meta(section: Option<&Section>, key: &str) -> Option<String> {
     section.and_then(|s| {
         s.blocks.iter().find_map(|b| match b {
@@ -168,6 +269,52 @@ fn get_meta(section: Option<&Section>, key: &str) -> Option<String> {
     })
 }
 
+/// Every value of a repeated footer field, in page order.
+fn get_meta_all(section: Option<&Section>, key: &str) -> Vec<String> {
+    section
+        .iter()
+        .flat_map(|s| s.blocks)
+        .filter_map(|b| match b {
+            Block::MetaField { key: k, value } if k == key => Some(value.clone()),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Partner name and `/people/<slug>` for a footer field, given either as
+/// "Key:Name", "Key:[Name](url)" or "Key:" followed by the profile link.
+fn get_partner(section: Option<&Section>, key: &str) -> (Option<String>, Option<String>) {
+    section.map_or((None, None), |s| partner_in(s.blocks, key))
+}
+
+/// `get_partner` over bare blocks, e.g. a footer lexed again from
+/// `company_sections.footer`.
+pub fn partner_in(blocks: &[Block], key: &str) -> (Option<String>, Option<String>) {
+    let Some(idx) = blocks
+        .iter()
+        .position(|b| matches!(b, Block::MetaField { key: k, .. } if k == key))
+    else {
+        return (None, None);
+    };
+    let value = match &blocks[idx] {
+        Block::MetaField { value, .. } => Some(value.clone()).filter(|v| !v.is_empty()),
+        _ => None,
+    };
+    let link = blocks[idx + 1..]
+        .iter()
+        .find(|b| !matches!(b, Block::Empty))
+        .and_then(|b| match b {
+            Block::Link { text, url } => {
+                super::partners::people_slug(url).map(|slug| (text.clone(), slug))
+            }
+            _ => None,
+        });
+    match link {
+        Some((text, slug)) => (value.or(Some(text).filter(|t| !t.is_empty())), Some(slug)),
+        None => (value, None),
+    }
+}
+
 fn parse_batch(batch: &str) -> (Option<String>, Option<i32>) {
     let parts: Vec<&str> = batch.split_whitespace().collect();
     let season = parts.first().map(|s| s.to_string());
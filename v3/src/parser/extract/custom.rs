@@ -0,0 +1,178 @@
+use std::sync::OnceLock;
+
+use serde_json::{Map, Value};
+
+use crate::db::CustomRow;
+use crate::parser::blocks::Block;
+use crate::parser::dates;
+use crate::parser::sections::Section;
+
+/// One row from an extractor outside the built-in set: free-form fields,
+/// stored as a JSON object in `custom_rows`.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct GenericRow {
+    pub fields: Map<String, Value>,
+    /// Markdown lines the row came from, e.g. "12-14".
+    pub source_lines: Option<String>,
+}
+
+impl GenericRow {
+    pub fn with(mut self, key: &str, value: impl Into<Value>) -> Self {
+        self.fields.insert(key.to_string(), value.into());
+        self
+    }
+}
+
+/// A process-time extractor run on every page after the built-in ones.
+/// Register it on top of `Registry::builtin` (`install`, or
+/// `process_page_with`) to get rows into `custom_rows` without touching
+/// `extract_all`.
+pub trait Extractor: Send + Sync {
+    /// Stored in `custom_rows.extractor`; unique within a registry.
+    fn name(&self) -> &'static str;
+    fn extract(&self, slug: &str, sections: &[Section]) -> Vec<GenericRow>;
+}
+
+/// The extractors `extract_all` runs, in registration order.
+#[derive(Default)]
+pub struct Registry {
+    extractors: Vec<Box<dyn Extractor>>,
+}
+
+impl Registry {
+    /// The extractors shipped with the scraper.
+    pub fn builtin() -> Self {
+        let mut registry = Registry::default();
+        registry.register(Launches);
+        registry
+    }
+
+    /// Add `extractor`; one with the same name is replaced.
+    pub fn register(&mut self, extractor: impl Extractor + 'static) -> &mut Self {
+        self.extractors.retain(|e| e.name() != extractor.name());
+        self.extractors.push(Box::new(extractor));
+        self
+    }
+
+    /// Every extractor's rows for one page, tagged with extractor and slug.
+    pub fn run(&self, slug: &str, sections: &[Section]) -> Vec<CustomRow> {
+        self.extractors
+            .iter()
+            .flat_map(|e| {
+                let name = e.name();
+                e.extract(slug, sections).into_iter().enumerate().map(move |(i, row)| CustomRow {
+                    extractor: name,
+                    company_slug: slug.to_string(),
+                    position: i,
+                    data: Value::Object(row.fields),
+                    source_lines: row.source_lines,
+                })
+            })
+            .collect()
+    }
+}
+
+static REGISTRY: OnceLock<Registry> = OnceLock::new();
+
+/// Make `registry` the one `process` uses, e.g. `Registry::builtin()` with
+/// extractors of your own, before running the CLI. Once a page has been
+/// processed the registry is fixed, and `registry` is handed back.
+pub fn install(registry: Registry) -> Result<(), Registry> {
+    REGISTRY.set(registry)
+}
+
+/// The registry `process` uses: the installed one, else the builtin set.
+pub fn registry() -> &'static Registry {
+    REGISTRY.get_or_init(Registry::builtin)
+}
+
+/// Launch YC posts linked from the "Company Launches" section: title, URL
+/// and the date line under the link when there is one.
+struct Launches;
+
+impl Extractor for Launches {
+    fn name(&self) -> &'static str {
+        "launches"
+    }
+
+    fn extract(&self, _slug: &str, sections: &[Section]) -> Vec<GenericRow> {
+        let mut rows: Vec<GenericRow> = Vec::new();
+        for section in sections.iter().filter(|s| s.kind == "launches") {
+            for (i, block) in section.blocks.iter().enumerate() {
+                let Block::Link { text, url } = block else { continue };
+                let title = text.trim().trim_start_matches('#').trim();
+                if !url.contains("/launches/") || title.is_empty() || rows.iter().any(|r| r.fields["url"] == *url) {
+                    continue;
+                }
+                let mut row = GenericRow {
+                    source_lines: section.block_span(i).map(|s| s.to_string()),
+                    ..GenericRow::default()
+                }
+                .with("title", title)
+                .with("url", url.as_str());
+                let date = section.blocks[i + 1..].iter().find(|b| !matches!(b, Block::Empty));
+                if let Some(Block::Text(t)) = date {
+                    if let Some(date) = dates::iso(t, &[]) {
+                        row = row.with("date", date);
+                    }
+                }
+                rows.push(row);
+            }
+        }
+        rows
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::blocks::lex;
+    use crate::parser::sections::cluster_sections;
+
+    struct Headings;
+
+    impl Extractor for Headings {
+        fn name(&self) -> &'static str {
+            "headings"
+        }
+
+        fn extract(&self, _slug: &str, sections: &[Section]) -> Vec<GenericRow> {
+            sections
+                .iter()
+                .flat_map(|s| s.blocks)
+                .filter_map(|b| match b {
+                    Block::Heading { text, .. } => Some(GenericRow::default().with("text", text.as_str())),
+                    _ => None,
+                })
+                .collect()
+        }
+    }
+
+    #[test]
+    fn registered_extractors_run_in_order() {
+        let md = "[Companies](/companies)\n\
+                  ### Payroll for teams\n\
+                  \n\
+                  Company Launches\n\
+                  [### Acme - payroll in one click](https://www.ycombinator.com/launches/Abc-acme)\n\
+                  2024-02-13\n\
+                  [Acme](https://www.ycombinator.com/companies/acme)\n";
+        let lexed = lex(md);
+        let sections = cluster_sections(&lexed);
+
+        let mut registry = Registry::builtin();
+        registry.register(Headings).register(Headings);
+        let names: Vec<_> = registry.extractors.iter().map(|e| e.name()).collect();
+        assert_eq!(names, ["launches", "headings"]);
+
+        let rows = registry.run("acme", &sections);
+        let launch = rows.iter().find(|r| r.extractor == "launches").expect("launch row");
+        assert_eq!(launch.data["title"], "Acme - payroll in one click");
+        assert_eq!(launch.data["url"], "https://www.ycombinator.com/launches/Abc-acme");
+        assert_eq!(launch.data["date"], "2024-02-13");
+        assert_eq!(launch.source_lines.as_deref(), Some("5"));
+        let headings: Vec<_> = rows.iter().filter(|r| r.extractor == "headings").collect();
+        assert_eq!(headings[0].data["text"], "Payroll for teams");
+        assert!(headings.iter().enumerate().all(|(i, r)| r.position == i && r.company_slug == "acme"));
+    }
+}
@@ -0,0 +1,119 @@
+use regex::Regex;
+use std::sync::LazyLock;
+
+use crate::db::{ExperienceRow, FounderRow};
+
+pub const EDUCATION: &str = "education";
+pub const EMPLOYER: &str = "employer";
+
+/// Phrases followed by where a founder worked before: "previously at X",
+/// "ex-X", "formerly led growth at X", "spent 5 years at X".
+static EMPLOYER_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(
+        r"(?i)(?:\b(?:previously|formerly|prior to)\b[^.;!?\n]{0,80}?\s(?:at|@|with)|\bex-|\b(?:worked|spent[^.;!?\n]{0,30}?)\s+at|\bformer\b[^.;!?\n]{0,40}?\s(?:at|@))\s*",
+    )
+    .unwrap()
+});
+
+/// Phrases followed by a school: "studied CS at X", "graduated from X",
+/// "dropped out of X", "B.S. in Physics from X", "alumnus of X".
+static EDUCATION_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(
+        r"(?i)(?:\b(?:studied|graduated|dropped out|degree|B\.?S\.?c?|B\.?A\.?|M\.?S\.?c?|Ph\.?D\.?|MBA|bachelor'?s|master'?s)(?:\s|\b)[^;!?\n]{0,60}?\b(?:at|from|of)|\balum(?:nus|na|ni)?\s+of)\s+",
+    )
+    .unwrap()
+});
+
+/// A proper name at the start of the text: capitalized words, optionally
+/// joined by "of" / "for" ("University of Waterloo"). Dots only inside a
+/// word ("Booking.com"), so a name never runs into the next sentence.
+static ORG_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"^(?:the\s+)?([A-Z0-9][\w&-]*(?:\.\w+)*(?:\s+(?:(?:of|for|de)\s+)?[A-Z0-9][\w&-]*(?:\.\w+)*){0,5})")
+        .unwrap()
+});
+
+/// Separator between names in "Ex-Facebook, YouTube and Google".
+static LIST_SEP_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^(?:,\s*(?:and\s+|&\s+)?|\s+(?:and|&|&amp;)\s+)").unwrap());
+
+/// Schools and previous employers mentioned in founder bios, one row per
+/// (founder, kind, org).
+pub fn extract(founders: &[FounderRow]) -> Vec<ExperienceRow> {
+    let mut rows: Vec<ExperienceRow> = Vec::new();
+    for f in founders {
+        let Some(bio) = f.bio.as_deref() else { continue };
+        for (kind, re) in [(EMPLOYER, &*EMPLOYER_RE), (EDUCATION, &*EDUCATION_RE)] {
+            for m in re.find_iter(bio) {
+                for org in org_list(&bio[m.end()..]) {
+                    let seen = rows
+                        .iter()
+                        .any(|r| r.founder_key == f.name_key && r.kind == kind && r.org == org);
+                    if !seen {
+                        rows.push(ExperienceRow {
+                            company_slug: f.company_slug.clone(),
+                            founder_key: f.name_key.clone(),
+                            kind,
+                            org,
+                        });
+                    }
+                }
+            }
+        }
+    }
+    rows
+}
+
+/// Names at the start of `text`, following list separators.
+fn org_list(mut text: &str) -> Vec<String> {
+    let mut orgs = Vec::new();
+    while let Some(c) = ORG_RE.captures(text) {
+        orgs.push(c[1].to_string());
+        text = &text[c.get(0).unwrap().end()..];
+        match LIST_SEP_RE.find(text) {
+            Some(sep) => text = &text[sep.end()..],
+            None => break,
+        }
+    }
+    orgs
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn founder(bio: &str) -> FounderRow {
+        FounderRow {
+            company_slug: "acme".into(),
+            name: "Jane Doe".into(),
+            name_key: "jane doe".into(),
+            title: None,
+            bio: Some(bio.into()),
+            is_active: true,
+            linkedin: None,
+            twitter: None,
+            source_lines: None,
+        }
+    }
+
+    fn orgs(bio: &str, kind: &str) -> Vec<String> {
+        extract(&[founder(bio)]).into_iter().filter(|r| r.kind == kind).map(|r| r.org).collect()
+    }
+
+    #[test]
+    fn employers_and_schools_from_bio_sentences() {
+        assert_eq!(
+            orgs("Product @ Shopify. Ex-Facebook, GroupAhead, YouTube and Google. Dad &amp; biker.", EMPLOYER),
+            ["Facebook", "GroupAhead", "YouTube", "Google"]
+        );
+        assert_eq!(orgs("Previously, she led growth at Uber and Lyft.", EMPLOYER), ["Uber", "Lyft"]);
+        assert_eq!(orgs("Spent 6 years at Goldman Sachs as a trader.", EMPLOYER), ["Goldman Sachs"]);
+        assert_eq!(orgs("Ex-Google engineer.", EMPLOYER), ["Google"]);
+        assert_eq!(
+            orgs("Studied CS at MIT. B.S. in Physics from the University of Waterloo.", EDUCATION),
+            ["MIT", "University of Waterloo"]
+        );
+        assert_eq!(orgs("Dropped out of Stanford's PhD program.", EDUCATION), ["Stanford"]);
+        assert!(orgs("Previously an ex-founder, and I love hiking.", EMPLOYER).is_empty());
+        assert!(orgs("Building the future of payments.", EDUCATION).is_empty());
+    }
+}
@@ -1,5 +1,7 @@
 use crate::db::FounderRow;
 use crate::parser::blocks::Block;
+use crate::parser::lang::Lexicon;
+use crate::parser::names;
 use crate::parser::sections::Section;
 
 pub fn extract(slug: &str, sections: &[Section]) -> Vec<FounderRow> {
@@ -7,7 +9,7 @@ pub fn extract(slug: &str, sections: &[Section]) -> Vec<FounderRow> {
     let mut is_active = true;
 
     for section in sections.iter().filter(|s| s.kind == "founders") {
-        for block in &section.blocks {
+        for (i, block) in section.blocks.iter().enumerate() {
             match block {
                 Block::Text(t) if t.contains("Former") || t.contains("Inactive") => {
                     is_active = false;
@@ -23,13 +25,15 @@ pub fn extract(slug: &str, sections: &[Section]) -> Vec<FounderRow> {
                 } => {
                     founders.push(FounderRow {
                         company_slug: slug.to_string(),
-                        name: name.clone(),
+                        name: names::normalize(name),
+                        name_key: names::name_key(name),
                         title: title.clone(),
                         bio: bio.clone(),
                         is_active,
                         linkedin: find_link(links, "linkedin.com"),
                         twitter: find_link(links, "twitter.com")
                             .or_else(|| find_link(links, "x.com")),
+                        source_lines: section.block_span(i).map(|s| s.to_string()),
                     });
                 }
                 _ => {}
@@ -40,9 +44,107 @@ pub fn extract(slug: &str, sections: &[Section]) -> Vec<FounderRow> {
     founders
 }
 
+/// Non-empty lines after a name that may hold its title.
+const TITLE_WINDOW: usize = 3;
+/// Longer title-keyword lines are bio sentences; the founder is kept without a title.
+const MAX_TITLE_LEN: usize = 60;
+
+/// Looser second pass over a founders section's stored text (`founders_raw`)
+/// for pages where `extract` found nobody: a name-like line with a title
+/// keyword on any of the next three lines is a founder. Links aren't
+/// attributed, and there are no source lines to point back to.
+pub fn recover(slug: &str, founders_raw: &str, lex: &Lexicon) -> Vec<FounderRow> {
+    let lines: Vec<&str> = founders_raw
+        .lines()
+        .map(|l| l.trim().trim_start_matches('#').trim())
+        .filter(|l| !l.is_empty())
+        .collect();
+    let mut founders: Vec<FounderRow> = Vec::new();
+    let mut is_active = true;
+    let mut i = 0;
+    while i < lines.len() {
+        let line = lines[i];
+        if line.contains("Former") || line.contains("Inactive") {
+            is_active = false;
+        } else if line.contains("Active Founders") || line == "Founders" {
+            is_active = true;
+        }
+        if !name_like(line, lex) {
+            i += 1;
+            continue;
+        }
+        let title_at = lines
+            .iter()
+            .enumerate()
+            .skip(i + 1)
+            .take(TITLE_WINDOW)
+            .take_while(|(_, l)| !name_like(l, lex))
+            .find(|(_, l)| lex.is_title(l))
+            .map(|(j, _)| j);
+        let Some(j) = title_at else {
+            i += 1;
+            continue;
+        };
+        let key = names::name_key(line);
+        if !founders.iter().any(|f| f.name_key == key) {
+            founders.push(FounderRow {
+                company_slug: slug.to_string(),
+                name: names::normalize(line),
+                name_key: key,
+                title: Some(lines[j].to_string()).filter(|t| t.len() <= MAX_TITLE_LEN),
+                bio: None,
+                is_active,
+                linkedin: None,
+                twitter: None,
+                source_lines: None,
+            });
+        }
+        i = j + 1;
+    }
+    founders
+}
+
+/// Two to four name words (`names::looks_like_name`: capitalized, with
+/// particles and suffixes allowed), and not a title or section label itself
+/// ("Active Founders" contains a title keyword).
+fn name_like(line: &str, lex: &Lexicon) -> bool {
+    names::looks_like_name(line, 2, 4)
+        && !lex.is_title(line)
+        && !lex.noise_labels.contains(&line.to_lowercase().as_str())
+}
+
 fn find_link(links: &[(String, String)], domain_pattern: &str) -> Option<String> {
     links
         .iter()
         .find(|(domain, _)| domain.contains(domain_pattern))
         .map(|(_, url)| url.clone())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn recovery_pairs_names_with_nearby_titles() {
+        let raw = "Active Founders\n\nJane Doe\n\n[](https://linkedin.com/in/jane)\nStanford CS, ex-Stripe.\nFounder/CEO\n\n\
+                   ### Ravi Kumar\nCTO\n\nSam Lee\nLoves climbing.\nRuns growth.\nBuilt three companies.\nCOO\n\n\
+                   Juan de la Cruz\nCo-founder\n\nFormer Founders\n\nMax Roe\nCo-founder and President of the board, previously leading a fintech\n\n\
+                   Acme Labs\nOur Team\nJane Doe\nCEO";
+        let founders = recover("acme", raw, &Lexicon::english());
+        let got: Vec<(&str, Option<&str>, bool)> =
+            founders.iter().map(|f| (f.name.as_str(), f.title.as_deref(), f.is_active)).collect();
+        assert_eq!(
+            got,
+            [
+                ("Jane Doe", Some("Founder/CEO"), true),
+                ("Ravi Kumar", Some("CTO"), true),
+                ("Juan de la Cruz", Some("Co-founder"), true),
+                ("Max Roe", None, false),
+            ]
+        );
+        assert!(recover("acme", "Founders\n\nWe are hiring engineers.", &Lexicon::english()).is_empty());
+        let caps = recover("acme", "Former Founders\n\nMAX ROE\nCo-founder", &Lexicon::english());
+        let got: Vec<_> = caps.iter().map(|f| (f.name.as_str(), f.title.as_deref(), f.is_active)).collect();
+        assert_eq!(got, [("Max Roe", Some("Co-founder"), false)]);
+    }
+}
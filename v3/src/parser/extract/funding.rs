@@ -0,0 +1,111 @@
+use std::sync::LazyLock;
+
+use regex::Regex;
+
+use super::salary;
+
+/// "Series A", "seed round", "pre-seed".
+static ROUND_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"(?i)\b(?:(pre-?seed)|(seed)|series ([a-h]))\b").unwrap());
+
+/// Words that make a title about raising money rather than anything else
+/// carrying an amount or the word "seed".
+static RAISE_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?i)\b(?:rais(?:es|ed|ing)|secur(?:es|ed)|clos(?:es|ed)|lands|bags|nabs|funding|financing|round|backed)\b")
+        .unwrap()
+});
+
+/// "$20M", "$4.5 million", "€1bn", "₹50 crore", "USD 10M", "$1,200,000".
+static AMOUNT_RE: LazyLock<Regex> = LazyLock::new(|| {
+    let cur = r"US\$|CA\$|C\$|A\$|S\$|\$|€|£|₹|USD|EUR|GBP|CAD|AUD|SGD|CHF|INR";
+    Regex::new(&format!(
+        r"({cur})\s?(\d[\d,]*(?:\.\d+)?)\s?(?i:(billion|million|thousand|crore|bln|bn|mn|cr|[bmk])\b)?"
+    ))
+    .unwrap()
+});
+
+/// A funding announcement read from a news title.
+#[derive(Debug, PartialEq)]
+pub struct Funding {
+    /// "Pre-Seed", "Seed", "Series A", ...; `None` when the title only
+    /// says how much was raised.
+    pub round: Option<String>,
+    pub amount: Option<f64>,
+    pub currency: Option<&'static str>,
+}
+
+/// The funding round a news title announces, if it announces one: a named
+/// Series round, a seed round next to a raise word, or an amount next to
+/// either ("Acme raises $5M").
+pub fn classify(title: &str) -> Option<Funding> {
+    let raise = RAISE_RE.is_match(title);
+    let round = ROUND_RE.captures(title).and_then(|caps| {
+        if let Some(series) = caps.get(3) {
+            Some(format!("Series {}", series.as_str().to_uppercase()))
+        } else if !raise {
+            None
+        } else if caps.get(1).is_some() {
+            Some("Pre-Seed".to_string())
+        } else {
+            Some("Seed".to_string())
+        }
+    });
+    let amount = (raise || round.is_some()).then(|| amount(title)).flatten();
+    if round.is_none() && amount.is_none() {
+        return None;
+    }
+    Some(Funding {
+        round,
+        currency: amount.map(|(c, _)| c),
+        amount: amount.map(|(_, n)| n),
+    })
+}
+
+/// The first money amount in `text`. Bare numbers need to be written out
+/// in full ("$1,200,000"): "$5" alone is more likely a price.
+fn amount(text: &str) -> Option<(&'static str, f64)> {
+    AMOUNT_RE.captures_iter(text).find_map(|caps| {
+        let n: f64 = caps[2].replace(',', "").parse().ok()?;
+        let scale = match caps.get(3).map(|u| u.as_str().to_lowercase()).as_deref() {
+            Some("billion" | "bln" | "bn" | "b") => 1e9,
+            Some("million" | "mn" | "m") => 1e6,
+            Some("thousand" | "k") => 1e3,
+            Some("crore" | "cr") => 1e7,
+            _ if n >= 10_000.0 => 1.0,
+            _ => return None,
+        };
+        Some((salary::currency(&caps[1]), n * scale))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn funding(round: Option<&str>, amount: Option<f64>, currency: Option<&'static str>) -> Option<Funding> {
+        Some(Funding { round: round.map(str::to_string), amount, currency })
+    }
+
+    #[test]
+    fn rounds_and_amounts_from_titles() {
+        assert_eq!(
+            classify("Acme raises $20M Series B led by Sequoia"),
+            funding(Some("Series B"), Some(20e6), Some("USD"))
+        );
+        assert_eq!(classify("Acme closes €4.5 million seed round"), funding(Some("Seed"), Some(4.5e6), Some("EUR")));
+        assert_eq!(classify("Acme lands pre-seed funding"), funding(Some("Pre-Seed"), None, None));
+        assert_eq!(classify("Inside Acme's series a"), funding(Some("Series A"), None, None));
+        assert_eq!(classify("Acme's $12M Series A, explained"), funding(Some("Series A"), Some(12e6), Some("USD")));
+        assert_eq!(classify("Acme raised ₹50 crore"), funding(None, Some(50e7), Some("INR")));
+        assert_eq!(classify("Acme secures $1,200,000 to grow"), funding(None, Some(1.2e6), Some("USD")));
+        assert_eq!(classify("Acme bags $1bn in new funding"), funding(None, Some(1e9), Some("USD")));
+    }
+
+    #[test]
+    fn other_news_is_not_funding() {
+        assert_eq!(classify("Acme launches a $5 plan"), None);
+        assert_eq!(classify("Acme raises prices for teams"), None);
+        assert_eq!(classify("How Acme helps farmers seed crops"), None);
+        assert_eq!(classify("Acme hits $10M ARR"), None);
+    }
+}
@@ -0,0 +1,201 @@
+use crate::db::JobDetailRow;
+use crate::parser::keywords;
+
+/// Longest line treated as a section heading; longer lines are body text
+/// even when they start like one ("Requirements for this role include ...").
+const MAX_HEADING_CHARS: usize = 60;
+
+/// A job detail page split into the v2 body sections.
+#[derive(Default, Debug)]
+pub struct JobBody {
+    pub responsibilities: Vec<String>,
+    pub requirements: Vec<String>,
+    pub nice_to_have: Vec<String>,
+    pub benefits: Vec<String>,
+    pub summary: Option<String>,
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum Part {
+    Responsibilities,
+    Requirements,
+    Nice,
+    Benefits,
+}
+
+/// Split job page markdown by its headings. Port of v2 `body.rs`; unlike v2
+/// (which ran on nav-stripped text) nothing is collected before the first
+/// heading, and "Other jobs at ..." ends the body.
+pub fn split(markdown: &str) -> JobBody {
+    let mut body = JobBody::default();
+    let mut current: Option<Part> = None;
+
+    for raw in markdown.lines() {
+        let line = raw
+            .trim()
+            .trim_start_matches('#')
+            .trim_start_matches(['-', '*', '•'])
+            .trim()
+            .trim_matches('*')
+            .trim();
+        if line.is_empty() {
+            continue;
+        }
+        let lower = line.to_lowercase();
+        if is_end(&lower) {
+            break;
+        }
+        if line.chars().count() <= MAX_HEADING_CHARS {
+            if let Some(part) = heading(&lower) {
+                current = Some(part);
+                continue;
+            }
+        }
+        let target = match current {
+            Some(Part::Responsibilities) => &mut body.responsibilities,
+            Some(Part::Requirements) => &mut body.requirements,
+            Some(Part::Nice) => &mut body.nice_to_have,
+            Some(Part::Benefits) => &mut body.benefits,
+            None => continue,
+        };
+        target.push(line.to_string());
+    }
+
+    // Summary: first 3 responsibilities, else first 2 requirements (as v2)
+    let summary = if body.responsibilities.is_empty() {
+        body.requirements.iter().take(2).cloned().collect::<Vec<_>>()
+    } else {
+        body.responsibilities.iter().take(3).cloned().collect()
+    };
+    if !summary.is_empty() {
+        body.summary = Some(summary.join("\n"));
+    }
+    body
+}
+
+fn heading(lower: &str) -> Option<Part> {
+    let starts = |prefixes: &[&str]| prefixes.iter().any(|p| lower.starts_with(p));
+    if starts(&[
+        "about the role", "about the job", "what you will do", "what you'll do",
+        "responsibilities", "role description",
+    ]) {
+        Some(Part::Responsibilities)
+    } else if starts(&[
+        "requirements", "qualifications", "what we're looking for", "who you are",
+        "must have", "must-haves",
+    ]) {
+        Some(Part::Requirements)
+    } else if starts(&["nice to have", "nice-to-haves", "preferred", "bonus"]) {
+        Some(Part::Nice)
+    } else if starts(&["benefits", "perks"]) {
+        Some(Part::Benefits)
+    } else {
+        None
+    }
+}
+
+fn is_end(lower: &str) -> bool {
+    ["other jobs at", "more jobs at", "similar jobs"].iter().any(|p| lower.starts_with(p))
+}
+
+/// Body sections plus keyword chips for one scraped job page. The stack
+/// comes from requirement and nice-to-have bullets, or the whole body when
+/// the page has neither heading. Blank and 404 pages (see
+/// `validate::job_content_kind`) are stored with their kind, unparsed.
+pub fn extract(job_id: i64, markdown: String) -> JobDetailRow {
+    let content_kind = crate::validate::job_content_kind(&markdown);
+    if content_kind != crate::validate::JOB {
+        return JobDetailRow {
+            job_id,
+            markdown,
+            content_kind,
+            responsibilities: None,
+            requirements: None,
+            nice_to_have: None,
+            benefits_text: None,
+            summary: None,
+            tech_stack: Vec::new(),
+            benefits: Vec::new(),
+        };
+    }
+    let body = split(&markdown);
+    let mut skills: Vec<String> = body.requirements.iter().chain(&body.nice_to_have).cloned().collect();
+    if skills.is_empty() {
+        skills = markdown.lines().map(str::to_string).collect();
+    }
+    let join = |lines: &[String]| Some(lines.join("\n")).filter(|s| !s.is_empty());
+    JobDetailRow {
+        job_id,
+        content_kind,
+        responsibilities: join(&body.responsibilities),
+        requirements: join(&body.requirements),
+        nice_to_have: join(&body.nice_to_have),
+        benefits_text: join(&body.benefits),
+        summary: body.summary,
+        tech_stack: keywords::chips(&skills, keywords::TECH_STACK),
+        benefits: keywords::chips(&body.benefits, keywords::BENEFITS),
+        markdown,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PAGE: &str = "\
+[Companies](/companies) › Acme
+
+# Senior Backend Engineer
+
+### About the role
+
+- Own our payments API
+- Ship features weekly
+
+### Requirements
+
+- 4+ years of Python or Go
+- Comfortable with PostgreSQL and Kubernetes
+
+**Nice to have**
+
+- Rust
+
+### Benefits
+
+* Equity and a 401(k)
+* Unlimited PTO
+
+### Other jobs at Acme
+
+- Python Engineer
+";
+
+    #[test]
+    fn splits_sections_and_extracts_chips() {
+        let row = extract(7, PAGE.to_string());
+        assert_eq!(row.responsibilities.as_deref(), Some("Own our payments API\nShip features weekly"));
+        assert_eq!(
+            row.requirements.as_deref(),
+            Some("4+ years of Python or Go\nComfortable with PostgreSQL and Kubernetes")
+        );
+        assert_eq!(row.nice_to_have.as_deref(), Some("Rust"));
+        assert_eq!(row.summary, row.responsibilities);
+        assert_eq!(row.tech_stack, vec!["python", "rust", "go", "postgres", "kubernetes"]);
+        assert_eq!(row.benefits, vec!["401k", "equity", "unlimited pto", "pto"]);
+    }
+
+    #[test]
+    fn not_found_pages_are_not_parsed() {
+        let row = extract(8, format!("# 404: This page could not be found\n\n{}", PAGE));
+        assert_eq!(row.content_kind, "not_found");
+        assert!(row.responsibilities.is_none() && row.tech_stack.is_empty());
+    }
+
+    #[test]
+    fn long_lines_are_not_headings() {
+        let body = split("### Responsibilities\n\nRequirements for this role include owning the billing system end to end\n");
+        assert_eq!(body.responsibilities.len(), 1);
+        assert!(body.requirements.is_empty());
+    }
+}
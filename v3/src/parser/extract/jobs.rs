@@ -1,17 +1,17 @@
 use regex::Regex;
 
+use super::salary;
 use crate::db::JobRow;
 use crate::parser::blocks::Block;
 use crate::parser::sections::Section;
 
 pub fn extract(slug: &str, sections: &[Section]) -> Vec<JobRow> {
-    let salary_re = Regex::new(r"^\$[\d,]+K?\s*-\s*\$[\d,]+K?").unwrap();
     let exp_re = Regex::new(r"^\d+\+?\s*years?$").unwrap();
     let apply_re = Regex::new(r"\[Apply Now[^\]]*\]\(([^)]+)\)").unwrap();
     let mut items = Vec::new();
 
     for section in sections.iter().filter(|s| s.kind == "jobs") {
-        let blocks = &section.blocks;
+        let blocks = section.blocks;
         let mut i = 0;
 
         while i < blocks.len() {
@@ -36,8 +36,8 @@ pub fn extract(slug: &str, sections: &[Section]) -> Vec<JobRow> {
                                     apply_url = Some(caps[1].to_string());
                                     j += 1;
                                     break;
-                                } else if salary_re.is_match(t) {
-                                    salary = Some(t.to_string());
+                                } else if let Some(range) = salary::parse(t) {
+                                    salary = Some((t.to_string(), range));
                                 } else if exp_re.is_match(t) {
                                     experience = Some(t.to_string());
                                 } else {
@@ -62,9 +62,20 @@ pub fn extract(slug: &str, sections: &[Section]) -> Vec<JobRow> {
                         title: text.clone(),
                         url: url.clone(),
                         location,
-                        salary,
+                        salary_currency: salary.as_ref().map(|(_, r)| r.currency.to_string()),
+                        salary_min: salary.as_ref().map(|(_, r)| r.min),
+                        salary_max: salary.as_ref().map(|(_, r)| r.max),
+                        // Filled in from `[parser.usd_rates]` by `process_page`
+                        salary_min_usd: None,
+                        salary_max_usd: None,
+                        salary: salary.map(|(text, _)| text),
                         experience,
                         apply_url,
+                        // Listing line through its last metadata line
+                        source_lines: section
+                            .block_span(i)
+                            .zip(section.block_span(j - 1))
+                            .map(|(a, b)| a.merge(b).to_string()),
                     });
 
                     i = j;
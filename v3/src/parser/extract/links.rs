@@ -1,15 +1,19 @@
-use std::collections::HashSet;
+use std::collections::{BTreeMap, HashSet};
 
-use crate::db::LinkRow;
+use crate::db::{FounderRow, LinkRow};
 use crate::parser::blocks::Block;
+use crate::parser::names;
 use crate::parser::sections::Section;
 
-pub fn extract(slug: &str, sections: &[Section]) -> Vec<LinkRow> {
+/// Links on the page, each tied to one of `founders` (by name key) when it is
+/// listed on their card or is a personal profile whose handle names them.
+pub fn extract(slug: &str, sections: &[Section], founders: &[FounderRow]) -> Vec<LinkRow> {
     let mut seen = HashSet::new();
     let mut links = Vec::new();
 
     for section in sections {
-        for block in &section.blocks {
+        for (i, block) in section.blocks.iter().enumerate() {
+            let source_lines = section.block_span(i).map(|s| s.to_string());
             if let Block::Link { url, .. } = block {
                 if url.contains("ycombinator.com") || seen.contains(url) {
                     continue;
@@ -22,10 +26,15 @@ pub fn extract(slug: &str, sections: &[Section]) -> Vec<LinkRow> {
                     url: url.clone(),
                     domain,
                     link_type,
+                    publisher: None,
+                    founder_key: match_founder(url, founders),
+                    source_lines: source_lines.clone(),
                 });
             }
             // Also extract links from Person blocks
-            if let Block::Person { links: plinks, .. } = block {
+            if let Block::Person { name, links: plinks, .. } = block {
+                let key = names::name_key(name);
+                let founder_key = founders.iter().any(|f| f.name_key == key).then_some(key);
                 for (_, url) in plinks {
                     if url.contains("ycombinator.com") || seen.contains(url) {
                         continue;
@@ -38,6 +47,9 @@ pub fn extract(slug: &str, sections: &[Section]) -> Vec<LinkRow> {
                         url: url.clone(),
                         domain,
                         link_type,
+                        publisher: None,
+                        founder_key: founder_key.clone(),
+                        source_lines: source_lines.clone(),
                     });
                 }
             }
@@ -47,6 +59,73 @@ pub fn extract(slug: &str, sections: &[Section]) -> Vec<LinkRow> {
     links
 }
 
+fn match_founder(url: &str, founders: &[FounderRow]) -> Option<String> {
+    let keys: Vec<&str> = founders.iter().map(|f| f.name_key.as_str()).collect();
+    handle_owner(url, &keys).map(str::to_string)
+}
+
+/// The one name key among `name_keys` whose name appears in the handle of a
+/// personal profile link (LinkedIn /in/, Twitter/X, GitHub, ...). Full name
+/// joined with "", "-" or "_", or a last name of 4+ letters; no match when
+/// two founders fit (e.g. siblings sharing a surname).
+pub fn handle_owner<'a>(url: &str, name_keys: &[&'a str]) -> Option<&'a str> {
+    let handle = profile_handle(url)?;
+    let mut matched = name_keys.iter().filter(|key| {
+        let parts: Vec<&str> = key.split(' ').collect();
+        let last = parts.last().filter(|l| parts.len() > 1 && l.len() >= 4);
+        last.is_some_and(|l| handle.contains(l))
+            || ["", "-", "_"]
+                .iter()
+                .map(|sep| parts.join(sep))
+                .any(|v| v.len() >= 4 && handle.contains(&v))
+    });
+    let first = matched.next()?;
+    matched.next().is_none().then_some(*first)
+}
+
+/// Lowercased account part of a personal profile URL; `None` for company
+/// pages and other sites.
+pub fn profile_handle(url: &str) -> Option<String> {
+    let lower = url.to_lowercase();
+    let rest = lower.split("//").nth(1).unwrap_or(&lower);
+    let rest = rest.split(['?', '#']).next().unwrap_or("").trim_start_matches("www.");
+    let (host, path) = rest.split_once('/').unwrap_or((rest, ""));
+    let mut segments = path.split('/').filter(|s| !s.is_empty());
+    let first = segments.next();
+    let handle = match (host, first) {
+        (h, Some("in" | "pub")) if h.ends_with("linkedin.com") => segments.next(),
+        ("twitter.com" | "x.com" | "github.com" | "instagram.com" | "calendly.com" | "cal.com", Some(f))
+            if !matches!(f, "intent" | "share" | "orgs" | "company") =>
+        {
+            Some(f)
+        }
+        ("medium.com", Some(f)) => f.strip_prefix('@'),
+        (h, _) if h.ends_with(".substack.com") => h.strip_suffix(".substack.com"),
+        _ => None,
+    }?;
+    Some(handle.to_string())
+}
+
+/// Approximate registrable domain: "foo.herokuapp.com" → "herokuapp.com",
+/// "shop.example.co.uk" → "example.co.uk". Used to group hosting providers.
+pub fn base_domain(domain: &str) -> String {
+    let host = domain
+        .split(':')
+        .next()
+        .unwrap_or(domain)
+        .trim_end_matches('.')
+        .to_lowercase();
+    let labels: Vec<&str> = host.split('.').collect();
+    let n = labels.len();
+    if n <= 2 {
+        return host;
+    }
+    let two_part_suffix = labels[n - 1].len() == 2
+        && matches!(labels[n - 2], "co" | "com" | "org" | "net" | "ac" | "gov" | "edu");
+    let keep = if two_part_suffix { 3 } else { 2 };
+    labels[n - keep..].join(".")
+}
+
 fn extract_domain(url: &str) -> String {
     url.split("//")
         .nth(1)
@@ -58,6 +137,45 @@ fn extract_domain(url: &str) -> String {
         .to_string()
 }
 
+/// News outlets, by registrable domain. `[parser.press_domains]` entries
+/// override these and add outlets.
+const PRESS_DOMAINS: &[(&str, &str)] = &[
+    ("techcrunch.com", "TechCrunch"),
+    ("forbes.com", "Forbes"),
+    ("businessinsider.com", "Business Insider"),
+    ("axios.com", "Axios"),
+    ("bloomberg.com", "Bloomberg"),
+    ("yourstory.com", "YourStory"),
+    ("inc42.com", "Inc42"),
+    ("techinasia.com", "Tech in Asia"),
+    ("venturebeat.com", "VentureBeat"),
+    ("theinformation.com", "The Information"),
+    ("wsj.com", "The Wall Street Journal"),
+    ("ft.com", "Financial Times"),
+    ("reuters.com", "Reuters"),
+];
+
+/// Publisher of a press domain, from `overrides` or the built-in list.
+/// Subdomains count: "finance.yahoo.com" matches "yahoo.com".
+pub fn press_publisher(domain: &str, overrides: &BTreeMap<String, String>) -> Option<String> {
+    let base = base_domain(domain);
+    overrides
+        .get(&base)
+        .cloned()
+        .or_else(|| PRESS_DOMAINS.iter().find(|(d, _)| *d == base).map(|(_, p)| p.to_string()))
+}
+
+/// Mark links to news outlets `press` with their publisher. Links already
+/// typed (a social profile) keep their type.
+pub fn classify_press(links: &mut [LinkRow], overrides: &BTreeMap<String, String>) {
+    for link in links.iter_mut().filter(|l| l.link_type.is_none()) {
+        if let Some(publisher) = press_publisher(&link.domain, overrides) {
+            link.link_type = Some("press".into());
+            link.publisher = Some(publisher);
+        }
+    }
+}
+
 fn classify_domain(domain: &str) -> Option<String> {
     match domain {
         d if d.contains("linkedin.com") => Some("linkedin".into()),
@@ -71,3 +189,81 @@ fn classify_domain(domain: &str) -> Option<String> {
         _ => None,
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn press_links_get_publisher() {
+        let link = |url: &str| LinkRow {
+            company_slug: "acme".into(),
+            url: url.into(),
+            domain: extract_domain(url),
+            link_type: classify_domain(&extract_domain(url)),
+            publisher: None,
+            founder_key: None,
+            source_lines: None,
+        };
+        let mut links = vec![
+            link("https://techcrunch.com/2024/01/05/acme-raises"),
+            link("https://www.ft.com/content/acme"),
+            link("https://sifted.eu/articles/acme"),
+            link("https://acme.com"),
+            link("https://twitter.com/acme"),
+        ];
+        let overrides = BTreeMap::from([("sifted.eu".to_string(), "Sifted".to_string())]);
+        classify_press(&mut links, &overrides);
+        let typed: Vec<(Option<&str>, Option<&str>)> =
+            links.iter().map(|l| (l.link_type.as_deref(), l.publisher.as_deref())).collect();
+        assert_eq!(
+            typed,
+            [
+                (Some("press"), Some("TechCrunch")),
+                (Some("press"), Some("Financial Times")),
+                (Some("press"), Some("Sifted")),
+                (None, None),
+                (Some("twitter"), None),
+            ]
+        );
+        assert_eq!(press_publisher("sifted.eu", &BTreeMap::new()), None);
+    }
+
+    #[test]
+    fn base_domain_groups_subdomains() {
+        assert_eq!(base_domain("foo.herokuapp.com"), "herokuapp.com");
+        assert_eq!(base_domain("stripe.com"), "stripe.com");
+        assert_eq!(base_domain("shop.example.co.uk"), "example.co.uk");
+        assert_eq!(base_domain("app.example.io:8080"), "example.io");
+    }
+
+    fn founder(name: &str) -> FounderRow {
+        FounderRow {
+            company_slug: "acme".into(),
+            name: name.into(),
+            name_key: names::name_key(name),
+            title: None,
+            bio: None,
+            is_active: true,
+            linkedin: None,
+            twitter: None,
+            source_lines: None,
+        }
+    }
+
+    #[test]
+    fn personal_links_match_founders_by_name() {
+        let founders = [founder("Jane Doe"), founder("Bob Li"), founder("Sam Li")];
+        let m = |url| match_founder(url, &founders);
+        assert_eq!(m("https://www.linkedin.com/in/jane-doe-12ab/").as_deref(), Some("jane doe"));
+        assert_eq!(m("https://twitter.com/JaneDoe?lang=en").as_deref(), Some("jane doe"));
+        assert_eq!(m("https://github.com/janedoe-dev").as_deref(), Some("jane doe"));
+        assert_eq!(m("https://jane.substack.com/p/hello").as_deref(), None);
+        assert_eq!(m("https://x.com/bobli").as_deref(), Some("bob li"));
+        // Company pages and other sites are never personal
+        assert_eq!(m("https://www.linkedin.com/company/doe-industries"), None);
+        assert_eq!(m("https://janedoe.com"), None);
+        // "li" is too short to tell Bob from Sam on its own
+        assert_eq!(m("https://github.com/the-lis"), None);
+    }
+}
@@ -30,7 +30,7 @@ pub fn extract(slug: &str, sections: &[Section]) -> Vec<MeetingLinkRow> {
     let mut rows = Vec::new();
 
     for section in sections {
-        for block in &section.blocks {
+        for (i, block) in section.blocks.iter().enumerate() {
             let urls: Vec<&str> = match block {
                 Block::Link { url, .. } => vec![url.as_str()],
                 Block::Person { links, .. } => links.iter().map(|(_, u)| u.as_str()).collect(),
@@ -49,6 +49,7 @@ pub fn extract(slug: &str, sections: &[Section]) -> Vec<MeetingLinkRow> {
                         url: url.to_string(),
                         domain,
                         link_type: link_type.to_string(),
+                        source_lines: section.block_span(i).map(|s| s.to_string()),
                     });
                 }
             }
@@ -58,7 +59,7 @@ pub fn extract(slug: &str, sections: &[Section]) -> Vec<MeetingLinkRow> {
     rows
 }
 
-fn classify_meeting_url(url: &str) -> Option<&'static str> {
+pub fn classify_meeting_url(url: &str) -> Option<&'static str> {
     MEETING_DOMAINS
         .iter()
         .find(|(domain, _)| url.contains(domain))
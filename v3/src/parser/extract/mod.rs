@@ -1,56 +1,105 @@
 pub mod company;
+pub mod custom;
+pub mod experience;
+pub mod funding;
 pub mod founders;
+pub mod job_body;
 pub mod jobs;
 pub mod links;
 pub mod meetings;
 pub mod news;
 pub mod partners;
+pub mod qa;
+pub mod quality;
+pub mod references;
+pub mod salary;
+pub mod tags;
 
+use super::blocks::Span;
+use super::lang::Lexicon;
 use super::sections::Section;
 use crate::db::*;
 
+#[derive(serde::Serialize)]
 pub struct ExtractedData {
     pub sections: SectionRow,
     pub company: CompanyRow,
     pub founders: Vec<FounderRow>,
+    pub experience: Vec<ExperienceRow>,
     pub news: Vec<NewsRow>,
     pub jobs: Vec<JobRow>,
     pub links: Vec<LinkRow>,
     pub meeting_links: Vec<MeetingLinkRow>,
+    pub qa: Vec<QaRow>,
+    pub locations: Vec<LocationRow>,
+    /// Links to other YC company pages.
+    pub references: Vec<ReferenceRow>,
+    pub quality: Vec<QualityRow>,
+    /// Rows from the extractors in the registry passed to `extract_all`.
+    pub custom: Vec<CustomRow>,
 }
 
+#[allow(clippy::too_many_arguments)]
 pub fn extract_all(
     slug: &str,
     url: &str,
     page_data_id: i64,
     sections: &[Section],
+    meta_description: Option<&str>,
+    lexicon: &Lexicon,
+    date_formats: &[String],
+    registry: &custom::Registry,
 ) -> ExtractedData {
-    let company = company::extract(slug, url, sections);
-    let founder_rows = founders::extract(slug, sections);
-    let news_rows = news::extract(slug, sections);
+    let mut company = company::extract(slug, url, sections);
+    if company.tagline.is_none() {
+        company.tagline = meta_description.and_then(company::meta_tagline);
+    }
+    let section_row = build_section_row(slug, url, page_data_id, sections);
+    let mut founder_rows = founders::extract(slug, sections);
+    if founder_rows.is_empty() {
+        if let Some(raw) = &section_row.founders_raw {
+            founder_rows = founders::recover(slug, raw, lexicon);
+        }
+    }
+    let experience_rows = experience::extract(&founder_rows);
+    let news_rows = news::extract(slug, sections, date_formats);
     let job_rows = jobs::extract(slug, sections);
-    let link_rows = links::extract(slug, sections);
+    let link_rows = links::extract(slug, sections, &founder_rows);
     let meeting_rows = meetings::extract(slug, sections);
-    let section_row = build_section_row(slug, url, page_data_id, sections);
+    let qa_rows = qa::extract(slug, sections);
+    let location_rows = company::secondary_locations(slug, sections);
+    let reference_rows = references::extract(slug, sections);
+    let quality_rows = quality::check(slug, sections, &company, &founder_rows);
+    let custom_rows = registry.run(slug, sections);
 
     ExtractedData {
         sections: section_row,
         company,
         founders: founder_rows,
+        experience: experience_rows,
         news: news_rows,
         jobs: job_rows,
         links: link_rows,
         meeting_links: meeting_rows,
+        qa: qa_rows,
+        locations: location_rows,
+        references: reference_rows,
+        quality: quality_rows,
+        custom: custom_rows,
     }
 }
 
 fn build_section_row(slug: &str, url: &str, page_data_id: i64, sections: &[Section]) -> SectionRow {
+    // Interleaved markers can split one kind into several sections
+    // (news, jobs, news); every one of them goes into the column.
     let get_raw = |kind: &str| -> Option<String> {
-        sections
+        let texts: Vec<String> = sections
             .iter()
-            .find(|s| s.kind == kind)
+            .filter(|s| s.kind == kind)
             .map(section_to_text)
             .filter(|t| !t.is_empty())
+            .collect();
+        (!texts.is_empty()).then(|| texts.join("\n\n"))
     };
 
     // Collect unknown sections as JSON extras
@@ -66,10 +115,30 @@ fn build_section_row(slug: &str, url: &str, page_data_id: i64, sections: &[Secti
                     | "launches"
                     | "footer_meta"
                     | "founders"
+                    | "qa"
             )
         })
-        .map(|s| serde_json::json!({ "kind": s.kind, "text": section_to_text(s) }))
+        .map(|s| {
+            serde_json::json!({
+                "kind": s.kind,
+                "shape": section_shape(s),
+                "text": section_to_text(s),
+            })
+        })
         .collect();
+    // Repeated kinds (e.g. two unknown sections) share one covering span
+    let mut spans: std::collections::BTreeMap<&str, Span> = std::collections::BTreeMap::new();
+    for s in sections {
+        if let Some(span) = s.span() {
+            let merged = spans.get(s.kind.as_str()).map_or(span, |prev| prev.merge(span));
+            spans.insert(&s.kind, merged);
+        }
+    }
+    let spans: serde_json::Map<String, serde_json::Value> = spans
+        .into_iter()
+        .map(|(kind, span)| (kind.to_string(), span.to_string().into()))
+        .collect();
+
     let extras = if unknowns.is_empty() {
         None
     } else {
@@ -89,9 +158,42 @@ fn build_section_row(slug: &str, url: &str, page_data_id: i64, sections: &[Secti
         founders_raw: get_raw("founders"),
         launches: get_raw("launches"),
         extras,
+        spans: (!spans.is_empty()).then(|| serde_json::Value::Object(spans).to_string()),
+        parse_ms: 0.0,
+        block_count: 0,
+        section_count: sections.len(),
     }
 }
 
+/// Block composition with runs collapsed, e.g. "text link*3 text".
+/// Used by `extras-report` to group unknown sections by structure.
+pub fn section_shape(section: &Section) -> String {
+    use super::blocks::Block;
+    let mut runs: Vec<(&str, usize)> = Vec::new();
+    for b in section.blocks {
+        let name = match b {
+            Block::Empty => continue,
+            Block::Heading { .. } => "heading",
+            Block::Link { .. } => "link",
+            Block::TagLink { .. } => "tag",
+            Block::MetaField { .. } => "meta",
+            Block::StatusLine(_) => "status",
+            Block::Person { .. } => "person",
+            Block::Text(_) => "text",
+            Block::Code { .. } => "code",
+            Block::Quote(_) => "quote",
+        };
+        match runs.last_mut() {
+            Some((last, n)) if *last == name => *n += 1,
+            _ => runs.push((name, 1)),
+        }
+    }
+    runs.iter()
+        .map(|(name, n)| if *n > 1 { format!("{}*{}", name, n) } else { name.to_string() })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
 fn section_to_text(section: &Section) -> String {
     use super::blocks::Block;
     section
@@ -100,6 +202,12 @@ fn section_to_text(section: &Section) -> String {
         .map(|b| match b {
             Block::Empty => String::new(),
             Block::Text(t) => t.clone(),
+            Block::Code { lang, text } => format!("```{}\n{}\n```", lang.as_deref().unwrap_or(""), text),
+            Block::Quote(q) => q
+                .lines()
+                .map(|l| if l.is_empty() { ">".to_string() } else { format!("> {}", l) })
+                .collect::<Vec<_>>()
+                .join("\n"),
             Block::Heading { text, level } => format!("{} {}", "#".repeat(*level as usize), text),
             Block::Link { text, url } => {
                 if text.is_empty() {
@@ -127,21 +235,48 @@ fn section_to_text(section: &Section) -> String {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::parser::blocks::classify_lines;
+    use crate::parser::blocks::{lex, Lexed};
     use crate::parser::sections::cluster_sections;
 
-    fn parse(fixture: &str) -> Vec<Section> {
-        let md = std::fs::read_to_string(format!("tests/fixtures/{}.md", fixture)).unwrap();
-        let blocks = classify_lines(&md);
-        cluster_sections(&blocks)
+    fn lex_fixture(fixture: &str) -> Lexed {
+        lex(&std::fs::read_to_string(format!("tests/fixtures/{}.md", fixture)).unwrap())
+    }
+
+    #[test]
+    fn shape_collapses_runs() {
+        let lexed = lex("Hear from the team\n\n[a](https://a.com)\n[b](https://b.com)\nQuote");
+        let section = Section {
+            kind: "other".to_string(),
+            blocks: &lexed.blocks,
+            spans: &lexed.spans,
+        };
+        assert_eq!(section_shape(&section), "text link*2 text");
+    }
+
+    #[test]
+    fn repeated_kinds_fill_one_column() {
+        let md = "Latest News\n\n[Acme raises seed](https://techcrunch.com/acme)\n\nJan 5, 2024\n\n\
+                  Jobs at Acme\n\n[Engineer](https://www.ycombinator.com/companies/acme/jobs/1-engineer)\n\n\
+                  Latest News\n\n[Acme launches](https://theverge.com/acme)\n\nMar 2, 2024\n";
+        let lexed = lex(md);
+        let sections = cluster_sections(&lexed);
+        let kinds: Vec<&str> = sections.iter().map(|s| s.kind.as_str()).collect();
+        assert_eq!(kinds, ["news", "jobs", "news"]);
+        let row = build_section_row("acme", "https://www.ycombinator.com/companies/acme", 1, &sections);
+        let news = row.news.unwrap();
+        assert!(news.contains("Acme raises seed") && news.contains("Acme launches"), "{}", news);
+        assert!(!news.contains("Engineer"));
+        assert!(row.jobs.unwrap().contains("Engineer"));
+        assert_eq!(row.description, None);
     }
 
     #[test]
     fn stripe_company() {
-        let sections = parse("stripe");
+        let lexed = lex_fixture("stripe");
+        let sections = cluster_sections(&lexed);
         let c = company::extract("stripe", "https://www.ycombinator.com/companies/stripe", &sections);
         assert_eq!(c.name.as_deref(), Some("Stripe"));
-        assert_eq!(c.status.as_deref(), Some("Active"));
+        assert_eq!(c.status, Some(crate::model::CompanyStatus::Active));
         assert_eq!(c.team_size, Some(7000));
         assert_eq!(c.founded_year, Some(2009));
         assert!(c.linkedin.is_some());
@@ -150,7 +285,8 @@ mod tests {
 
     #[test]
     fn stripe_founders() {
-        let sections = parse("stripe");
+        let lexed = lex_fixture("stripe");
+        let sections = cluster_sections(&lexed);
         let f = founders::extract("stripe", &sections);
         assert_eq!(f.len(), 2);
         let names: Vec<&str> = f.iter().map(|x| x.name.as_str()).collect();
@@ -158,17 +294,89 @@ mod tests {
         assert!(names.contains(&"John Collison"));
     }
 
+    #[test]
+    fn meta_description_fills_missing_tagline() {
+        let lexed = lex("Founded:2020\n\nBatch:Winter 2021\n");
+        let sections = cluster_sections(&lexed);
+        let page = "https://www.ycombinator.com/companies/acme";
+        let extract = |meta| extract_all("acme", page, 1, &sections, meta, &Lexicon::english(), &[], custom::registry());
+        let data = extract(Some("  Payroll for\nremote teams. "));
+        assert_eq!(data.company.tagline.as_deref(), Some("Payroll for remote teams."));
+        let data = extract(Some("Y Combinator created a new model for funding startups."));
+        assert_eq!(data.company.tagline, None);
+
+        let long = format!("Acme builds payroll. {}", "More detail. ".repeat(20));
+        assert_eq!(company::meta_tagline(&long).as_deref(), Some("Acme builds payroll."));
+    }
+
+    #[test]
+    fn footer_partner_link_captures_slug() {
+        let md = "Founded:2020\n\nBatch:Winter 2021\n\nPrimary Partner:[Tom Blomfield](https://www.ycombinator.com/people/tom-blomfield)\n";
+        let lexed = lex(md);
+        let sections = cluster_sections(&lexed);
+        let c = company::extract("acme", "https://www.ycombinator.com/companies/acme", &sections);
+        assert_eq!(c.primary_partner.as_deref(), Some("Tom Blomfield"));
+        assert_eq!(c.primary_partner_slug.as_deref(), Some("tom-blomfield"));
+        assert_eq!(c.group_partner, None);
+
+        let md = "Founded:2020\n\nBatch:Winter 2021\n\nGroup Partner:[Dalton Caldwell](https://www.ycombinator.com/people/dalton-caldwell)\n";
+        let lexed = lex(md);
+        let sections = cluster_sections(&lexed);
+        let c = company::extract("acme", "https://www.ycombinator.com/companies/acme", &sections);
+        assert_eq!(c.primary_partner, None);
+        assert_eq!(c.group_partner.as_deref(), Some("Dalton Caldwell"));
+        assert_eq!(c.group_partner_slug.as_deref(), Some("dalton-caldwell"));
+    }
+
+    #[test]
+    fn repeated_location_keeps_primary_and_stores_rest() {
+        let md = "Founded:2020\n\nLocation:San Francisco, CA\n\nLocation:London, UK\n\nLocation:San Francisco, CA\n\nLocation:Remote\n";
+        let lexed = lex(md);
+        let sections = cluster_sections(&lexed);
+        let c = company::extract("acme", "https://www.ycombinator.com/companies/acme", &sections);
+        assert_eq!(c.location.as_deref(), Some("San Francisco, CA"));
+        let rest: Vec<(String, i32)> = company::secondary_locations("acme", &sections)
+            .into_iter()
+            .map(|l| (l.location, l.position))
+            .collect();
+        assert_eq!(rest, vec![("London, UK".to_string(), 1), ("Remote".to_string(), 2)]);
+    }
+
     #[test]
     fn doordash_news() {
-        let sections = parse("doordash");
-        let n = news::extract("doordash", &sections);
+        let lexed = lex_fixture("doordash");
+        let sections = cluster_sections(&lexed);
+        let n = news::extract("doordash", &sections, &[]);
         assert!(n.len() >= 3);
         assert!(n.iter().all(|x| !x.url.contains("ycombinator.com")));
     }
 
+    #[test]
+    fn news_dates_in_other_styles() {
+        let md = "### Payroll for teams\n\
+                  Latest News\n\
+                  [Acme raises seed](https://techcrunch.com/acme-seed)\n\
+                  7 May 2023\n\
+                  [Acme goes global](https://sifted.eu/acme)\n\
+                  2024-01-15\n\
+                  [Acme in Germany](https://handelsblatt.com/acme)\n\
+                  15.02.2024\n";
+        let lexed = lex(md);
+        let sections = cluster_sections(&lexed);
+        let published = |formats: &[String]| -> Vec<Option<String>> {
+            news::extract("acme", &sections, formats).into_iter().map(|n| n.published).collect()
+        };
+        assert_eq!(
+            published(&[]),
+            [Some("2023-05-07".to_string()), Some("2024-01-15".to_string()), None]
+        );
+        assert_eq!(published(&["%d.%m.%Y".to_string()])[2].as_deref(), Some("2024-02-15"));
+    }
+
     #[test]
     fn doordash_jobs() {
-        let sections = parse("doordash");
+        let lexed = lex_fixture("doordash");
+        let sections = cluster_sections(&lexed);
         let j = jobs::extract("doordash", &sections);
         assert!(j.len() >= 4);
         assert!(j.iter().any(|x| x.salary.is_some()));
@@ -176,14 +384,16 @@ mod tests {
 
     #[test]
     fn groupahead_no_news_or_jobs() {
-        let sections = parse("groupahead");
-        assert!(news::extract("groupahead", &sections).is_empty());
+        let lexed = lex_fixture("groupahead");
+        let sections = cluster_sections(&lexed);
+        assert!(news::extract("groupahead", &sections, &[]).is_empty());
         assert!(jobs::extract("groupahead", &sections).is_empty());
     }
 
     #[test]
     fn groupahead_founders_clean() {
-        let sections = parse("groupahead");
+        let lexed = lex_fixture("groupahead");
+        let sections = cluster_sections(&lexed);
         let f = founders::extract("groupahead", &sections);
         // No "Batch:Winter 2015" contamination
         assert!(f.iter().all(|x| !x.name.contains("Batch")));
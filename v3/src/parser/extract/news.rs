@@ -1,15 +1,15 @@
-use regex::Regex;
-
 use crate::db::NewsRow;
 use crate::parser::blocks::Block;
+use crate::parser::dates;
 use crate::parser::sections::Section;
 
-pub fn extract(slug: &str, sections: &[Section]) -> Vec<NewsRow> {
-    let date_re = Regex::new(r"^[A-Z][a-z]{2} \d{2}, \d{4}$").unwrap();
+/// External links in news sections, with the date line under each stored
+/// as "YYYY-MM-DD" (`dates::FORMATS` plus `date_formats`).
+pub fn extract(slug: &str, sections: &[Section], date_formats: &[String]) -> Vec<NewsRow> {
     let mut items = Vec::new();
 
     for section in sections.iter().filter(|s| s.kind == "news") {
-        let blocks = &section.blocks;
+        let blocks = section.blocks;
         let mut i = 0;
         while i < blocks.len() {
             if let Block::Link { text, url, .. } = &blocks[i] {
@@ -19,9 +19,7 @@ pub fn extract(slug: &str, sections: &[Section]) -> Vec<NewsRow> {
                         .iter()
                         .find(|b| !matches!(b, Block::Empty))
                         .and_then(|b| match b {
-                            Block::Text(t) if date_re.is_match(t.trim()) => {
-                                Some(t.trim().to_string())
-                            }
+                            Block::Text(t) => dates::iso(t, date_formats),
                             _ => None,
                         });
                     items.push(NewsRow {
@@ -29,6 +27,7 @@ pub fn extract(slug: &str, sections: &[Section]) -> Vec<NewsRow> {
                         title: text.clone(),
                         url: url.clone(),
                         published,
+                        source_lines: section.block_span(i).map(|s| s.to_string()),
                     });
                 }
             }
@@ -109,6 +109,11 @@ pub fn parse_partners_page(markdown: &str) -> Vec<PartnerRow> {
     partners
 }
 
+/// Partner slug from a YC profile URL: ".../people/tom-blomfield" → "tom-blomfield".
+pub fn people_slug(url: &str) -> Option<String> {
+    PEOPLE_URL_RE.captures(url).map(|c| c[1].to_string())
+}
+
 /// Search a company's raw markdown for /people/{slug} references.
 /// Returns the deduplicated slugs of all partners found.
 pub fn find_partner_urls_in_markdown(markdown: &str) -> Vec<String> {
@@ -0,0 +1,106 @@
+use crate::db::QaRow;
+use crate::parser::blocks::{Block, Span};
+use crate::parser::sections::{is_qa_label, Section};
+
+/// Question/answer pairs from "Selected answers" / FAQ sections. A question
+/// is a line ending in '?'; its answer is the text up to the next question.
+pub fn extract(slug: &str, sections: &[Section]) -> Vec<QaRow> {
+    let mut rows: Vec<QaRow> = Vec::new();
+
+    for section in sections.iter().filter(|s| s.kind == "qa") {
+        let mut answer: Vec<&str> = Vec::new();
+        let mut question: Option<String> = None;
+        // Question line through the last answer line so far
+        let mut span: Option<Span> = None;
+
+        for (i, block) in section.blocks.iter().enumerate() {
+            let line = match block {
+                Block::Text(t) => t.as_str(),
+                Block::Heading { text, .. } => text.as_str(),
+                _ => continue,
+            };
+            if is_qa_label(line) {
+                continue;
+            }
+            match question_text(line) {
+                Some(q) => {
+                    push_pair(&mut rows, slug, question.take(), &answer, span);
+                    answer.clear();
+                    question = Some(q);
+                    span = section.block_span(i);
+                }
+                None if question.is_some() => {
+                    answer.push(line);
+                    span = match (span, section.block_span(i)) {
+                        (Some(a), Some(b)) => Some(a.merge(b)),
+                        (a, b) => a.or(b),
+                    };
+                }
+                None => {}
+            }
+        }
+        push_pair(&mut rows, slug, question, &answer, span);
+    }
+
+    rows
+}
+
+fn push_pair(
+    rows: &mut Vec<QaRow>,
+    slug: &str,
+    question: Option<String>,
+    answer: &[&str],
+    span: Option<Span>,
+) {
+    let Some(question) = question else { return };
+    if answer.is_empty() || rows.iter().any(|r| r.question == question) {
+        return;
+    }
+    rows.push(QaRow {
+        company_slug: slug.to_string(),
+        question,
+        answer: answer.join("\n"),
+        source_lines: span.map(|s| s.to_string()),
+    });
+}
+
+/// "**Q: Why now?**" → "Why now?"; None for lines that aren't questions.
+fn question_text(line: &str) -> Option<String> {
+    let q = line.trim().trim_matches('*').trim();
+    let q = q.strip_prefix("Q:").unwrap_or(q).trim();
+    (q.ends_with('?') && q.len() > 3).then(|| q.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::blocks::lex;
+    use crate::parser::sections::cluster_sections;
+
+    #[test]
+    fn selected_answers_become_pairs() {
+        let md = "### Payments for everyone.\nWe build payments.\n\
+                  Selected answers from Acme's original YC application\n\
+                  **What is your company going to make?**\n\
+                  A payments API.\nIt is simple.\n\
+                  **Why did you pick this idea?**\n\
+                  We needed it ourselves.\n";
+        let lexed = lex(md);
+        let sections = cluster_sections(&lexed);
+        let rows = extract("acme", &sections);
+        assert_eq!(rows.len(), 2);
+        assert_eq!(rows[0].question, "What is your company going to make?");
+        assert_eq!(rows[0].answer, "A payments API.\nIt is simple.");
+        assert_eq!(rows[1].answer, "We needed it ourselves.");
+        assert_eq!(rows[0].source_lines.as_deref(), Some("4-6"));
+        assert_eq!(rows[1].source_lines.as_deref(), Some("7-8"));
+    }
+
+    #[test]
+    fn question_without_answer_is_dropped() {
+        let md = "FAQ\nIs it free?\n";
+        let lexed = lex(md);
+        let sections = cluster_sections(&lexed);
+        assert!(extract("acme", &sections).is_empty());
+    }
+}
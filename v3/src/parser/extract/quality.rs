@@ -0,0 +1,394 @@
+use std::collections::BTreeSet;
+
+use crate::db::{CompanyRow, FounderRow, LinkRow, NewsRow, QualityRow};
+use crate::parser::blocks::Block;
+use crate::parser::sections::Section;
+
+/// Cross-checks between independent signals on the page. Only mismatches
+/// are returned; a clean page yields an empty Vec.
+pub fn check(
+    slug: &str,
+    sections: &[Section],
+    company: &CompanyRow,
+    founders: &[FounderRow],
+) -> Vec<QualityRow> {
+    [
+        founder_count(slug, sections, founders),
+        thin_company(slug, company),
+        founded_year(slug, company),
+        job_count(slug, company),
+        suspicious_founder_names(slug, founders),
+    ]
+    .into_iter()
+    .flatten()
+    .collect()
+}
+
+/// Links to news outlets on a page that yielded no news rows: the news
+/// section most likely failed to parse. Runs after press links are typed.
+pub fn press_without_news(slug: &str, links: &[LinkRow], news: &[NewsRow]) -> Option<QualityRow> {
+    let press = links.iter().filter(|l| l.link_type.as_deref() == Some("press")).count();
+    if press == 0 || !news.is_empty() {
+        return None;
+    }
+    Some(QualityRow {
+        company_slug: slug.to_string(),
+        check_name: "press_without_news".to_string(),
+        expected: press as i64,
+        actual: 0,
+        detail: Some(format!("{} press links but no news rows", press)),
+    })
+}
+
+/// Company pages that yielded fewer than half of the core fields, usually a
+/// stub page, a layout change, or a scrape that returned an error page.
+fn thin_company(slug: &str, c: &CompanyRow) -> Option<QualityRow> {
+    let fields = [
+        ("name", c.name.is_some()),
+        ("tagline", c.tagline.is_some()),
+        ("batch", c.batch.is_some()),
+        ("status", c.status.is_some()),
+        ("homepage", c.homepage.is_some()),
+        ("location", c.location.is_some()),
+        ("team_size", c.team_size.is_some()),
+        ("founded_year", c.founded_year.is_some()),
+    ];
+    let present = fields.iter().filter(|(_, ok)| *ok).count();
+    if present * 2 >= fields.len() {
+        return None;
+    }
+    let missing: Vec<&str> = fields.iter().filter(|(_, ok)| !ok).map(|(f, _)| *f).collect();
+    Some(QualityRow {
+        company_slug: slug.to_string(),
+        check_name: "thin_company".to_string(),
+        expected: fields.len() as i64,
+        actual: present as i64,
+        detail: Some(format!("missing: {}", missing.join(", "))),
+    })
+}
+
+/// Earliest plausible `founded_year`; older values are misparsed footers.
+pub const MIN_FOUNDED_YEAR: i32 = 1990;
+
+/// The bound `founded` breaks: the year after the batch (companies are
+/// founded before or just after YC, not years later), or `MIN_FOUNDED_YEAR`.
+pub fn founded_year_bound(founded: i32, batch_year: Option<i32>) -> Option<i32> {
+    if founded < MIN_FOUNDED_YEAR {
+        Some(MIN_FOUNDED_YEAR)
+    } else {
+        batch_year.map(|b| b + 1).filter(|&latest| founded > latest)
+    }
+}
+
+fn founded_year(slug: &str, c: &CompanyRow) -> Option<QualityRow> {
+    let founded = c.founded_year?;
+    let bound = founded_year_bound(founded, c.batch_year)?;
+    let detail = if founded < MIN_FOUNDED_YEAR {
+        format!("founded {} before {}", founded, MIN_FOUNDED_YEAR)
+    } else {
+        format!("founded {} more than a year after batch {}", founded, c.batch.as_deref().unwrap_or("?"))
+    };
+    Some(QualityRow {
+        company_slug: slug.to_string(),
+        check_name: "founded_year_inconsistent".to_string(),
+        expected: bound as i64,
+        actual: founded as i64,
+        detail: Some(detail),
+    })
+}
+
+/// Fewer (or more) job links than the page says it has; usually entries the
+/// jobs section parse missed.
+fn job_count(slug: &str, c: &CompanyRow) -> Option<QualityRow> {
+    let listed = c.job_count_listed.filter(|&n| n != c.job_count_extracted)?;
+    Some(QualityRow {
+        company_slug: slug.to_string(),
+        check_name: "job_count".to_string(),
+        expected: listed as i64,
+        actual: c.job_count_extracted as i64,
+        detail: Some(format!("listed {}, extracted {}", listed, c.job_count_extracted)),
+    })
+}
+
+/// Words that show up in "names" when a title or heading was misread as a person.
+const NON_NAME_WORDS: &[&str] = &[
+    "founder", "cofounder", "co-founder", "ceo", "cto", "coo", "inc", "llc", "team", "hiring",
+    "jobs", "company", "the",
+];
+
+/// Why a founder name looks wrong, if it does.
+fn name_problem(name: &str) -> Option<&'static str> {
+    let words: Vec<&str> = name.split_whitespace().collect();
+    let is_title_word = |w: &&str| {
+        let w = w.trim_matches(|c: char| !c.is_alphanumeric() && c != '-').to_lowercase();
+        NON_NAME_WORDS.contains(&w.as_str())
+    };
+    if name.chars().any(|c| c.is_ascii_digit()) {
+        Some("digits")
+    } else if name.contains('@') || name.contains("http") || name.contains('/') {
+        Some("url or handle")
+    } else if words.iter().any(is_title_word) {
+        Some("title word")
+    } else if words.len() > 4 {
+        Some("too many words")
+    } else if words.len() == 1 {
+        Some("single word")
+    } else {
+        None
+    }
+}
+
+fn suspicious_founder_names(slug: &str, founders: &[FounderRow]) -> Option<QualityRow> {
+    let flagged: Vec<String> = founders
+        .iter()
+        .filter_map(|f| name_problem(&f.name).map(|why| format!("{} ({})", f.name, why)))
+        .collect();
+    if flagged.is_empty() {
+        return None;
+    }
+    Some(QualityRow {
+        company_slug: slug.to_string(),
+        check_name: "suspicious_founder_name".to_string(),
+        expected: 0,
+        actual: flagged.len() as i64,
+        detail: Some(flagged.join("; ")),
+    })
+}
+
+/// Compare extracted founders against what the page shows: a declared
+/// `Founders:N` meta field when present, otherwise the Person blocks plus
+/// any profile-link cards the person detector skipped.
+fn founder_count(slug: &str, sections: &[Section], founders: &[FounderRow]) -> Option<QualityRow> {
+    let declared = sections.iter().flat_map(|s| s.blocks).find_map(|b| match b {
+        Block::MetaField { key, value } if key == "Founders" => value.trim().parse::<usize>().ok(),
+        _ => None,
+    });
+
+    let missed = orphan_profile_cards(sections);
+    let (expected, source) = match declared {
+        Some(n) => (n, "declared"),
+        None => (founders.len() + missed.len(), "profile_links"),
+    };
+    if expected == founders.len() {
+        return None;
+    }
+
+    let mut detail = format!("{}: expected {}, extracted {}", source, expected, founders.len());
+    if !missed.is_empty() {
+        let urls: Vec<&str> = missed.iter().map(|card| card[0].as_str()).collect();
+        detail.push_str(&format!("; unmatched profiles: {}", urls.join(", ")));
+    }
+    Some(QualityRow {
+        company_slug: slug.to_string(),
+        check_name: "founder_count".to_string(),
+        expected: expected as i64,
+        actual: founders.len() as i64,
+        detail: Some(detail),
+    })
+}
+
+/// Runs of bare profile links in founders sections that no Person block
+/// claimed, deduplicated (pages render each card twice).
+fn orphan_profile_cards(sections: &[Section]) -> BTreeSet<Vec<String>> {
+    let mut cards = BTreeSet::new();
+    for section in sections.iter().filter(|s| s.kind == "founders") {
+        let mut run: Vec<String> = Vec::new();
+        let mut after_text = false;
+        let mut prev_text = false;
+        for block in section.blocks {
+            match block {
+                Block::Empty => continue,
+                Block::Link { text, url } if text.is_empty() => {
+                    if run.is_empty() {
+                        after_text = prev_text;
+                    }
+                    run.push(url.clone());
+                    continue;
+                }
+                _ => {}
+            }
+            flush_card(&mut run, after_text, &mut cards);
+            prev_text = matches!(block, Block::Text(_));
+        }
+        flush_card(&mut run, after_text, &mut cards);
+    }
+    cards
+}
+
+fn flush_card(run: &mut Vec<String>, after_text: bool, cards: &mut BTreeSet<Vec<String>>) {
+    let personal = run.iter().any(|u| u.contains("linkedin.com/in/"))
+        || (after_text && run.iter().any(|u| u.contains("twitter.com/") || u.contains("x.com/")));
+    if personal {
+        let mut card = std::mem::take(run);
+        card.sort();
+        cards.insert(card);
+    }
+    run.clear();
+}
+
+// ── Quality score ──
+
+/// What `score` weighs for one stored company.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct ScoreInputs {
+    pub name: bool,
+    pub batch: bool,
+    pub status: bool,
+    pub founders: usize,
+    /// LinkedIn / Twitter / Facebook / Crunchbase / GitHub links.
+    pub socials: usize,
+    pub description_chars: usize,
+}
+
+/// Points per input; they add up to 100.
+const NAME_POINTS: i32 = 15;
+const BATCH_POINTS: i32 = 15;
+const STATUS_POINTS: i32 = 10;
+const FOUNDER_POINTS: i32 = 25;
+const SOCIAL_POINTS: i32 = 15;
+const DESCRIPTION_POINTS: i32 = 20;
+/// Socials earning full points; each one below earns a third.
+const FULL_SOCIALS: usize = 3;
+/// Description length earning full points; shorter ones earn a share.
+const FULL_DESCRIPTION_CHARS: usize = 300;
+
+/// 0–100: how complete a company row is, so consumers can drop junk rows
+/// with one comparison. Name, batch, status and any founder earn their
+/// full points; socials and description length earn a share.
+pub fn score(i: &ScoreInputs) -> i32 {
+    let share = |n: usize, full: usize, points: i32| n.min(full) as i32 * points / full as i32;
+    NAME_POINTS * i.name as i32
+        + BATCH_POINTS * i.batch as i32
+        + STATUS_POINTS * i.status as i32
+        + FOUNDER_POINTS * (i.founders > 0) as i32
+        + share(i.socials, FULL_SOCIALS, SOCIAL_POINTS)
+        + share(i.description_chars, FULL_DESCRIPTION_CHARS, DESCRIPTION_POINTS)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::blocks::lex;
+    use crate::parser::extract::{company, founders};
+    use crate::parser::sections::cluster_sections;
+
+    fn flags(md: &str) -> Vec<QualityRow> {
+        let lexed = lex(md);
+        let sections = cluster_sections(&lexed);
+        let c = company::extract("acme", "https://www.ycombinator.com/companies/acme", &sections);
+        check("acme", &sections, &c, &founders::extract("acme", &sections))
+    }
+
+    fn flag<'a>(flags: &'a [QualityRow], check_name: &str) -> Option<&'a QualityRow> {
+        flags.iter().find(|f| f.check_name == check_name)
+    }
+
+    #[test]
+    fn score_weighs_presence_and_shares() {
+        assert_eq!(score(&ScoreInputs::default()), 0);
+        let full = ScoreInputs { name: true, batch: true, status: true, founders: 2, socials: 5, description_chars: 900 };
+        assert_eq!(score(&full), 100);
+        let stub = ScoreInputs { name: true, socials: 1, description_chars: 150, ..Default::default() };
+        assert_eq!(score(&stub), 15 + 5 + 10);
+    }
+
+    #[test]
+    fn fixtures_are_consistent() {
+        for name in ["stripe", "doordash", "groupahead"] {
+            let md = std::fs::read_to_string(format!("tests/fixtures/{}.md", name)).unwrap();
+            assert!(flags(&md).is_empty(), "{}: {:?}", name, flags(&md).first().map(|f| &f.detail));
+        }
+    }
+
+    #[test]
+    fn missed_person_is_flagged() {
+        // Second card's title isn't recognized and the line has a colon, so
+        // person detection skips it but the LinkedIn card remains.
+        let md = "Founders\n\nJane Doe\n[](https://www.linkedin.com/in/janedoe)\nFounder/CEO\n\n\
+                  Dr: John Roe\n[](https://www.linkedin.com/in/johnroe)\nEngineer\n";
+        let f = flags(md);
+        let f = flag(&f, "founder_count").unwrap();
+        assert_eq!((f.expected, f.actual), (2, 1));
+        assert!(f.detail.as_deref().unwrap().contains("linkedin.com/in/johnroe"));
+    }
+
+    #[test]
+    fn listed_job_count_is_checked() {
+        let jobs = "Jobs at Acme\n[View all 3 jobs](https://www.ycombinator.com/companies/acme/jobs)\n\
+                    [Engineer](https://www.ycombinator.com/companies/acme/jobs/a1-engineer)\n\
+                    [Designer](https://www.ycombinator.com/companies/acme/jobs/b2-designer)\n";
+        let f = flags(jobs);
+        let f = flag(&f, "job_count").unwrap();
+        assert_eq!((f.expected, f.actual), (3, 2));
+
+        let lexed = lex(&jobs.replace("View all 3 jobs", "View all jobs"));
+        let sections = cluster_sections(&lexed);
+        let c = company::extract("acme", "https://www.ycombinator.com/companies/acme", &sections);
+        assert_eq!((c.job_count_extracted, c.job_count_listed), (2, None));
+        let lexed = lex("[\nJobs\n2\n](https://www.ycombinator.com/companies/acme/jobs)\n");
+        let sections = cluster_sections(&lexed);
+        let c = company::extract("acme", "https://www.ycombinator.com/companies/acme", &sections);
+        assert_eq!(c.job_count_listed, Some(2));
+    }
+
+    #[test]
+    fn declared_count_wins() {
+        let md = "Founders:3\n\nJane Doe\n[](https://www.linkedin.com/in/janedoe)\nFounder/CEO\n";
+        let f = flags(md);
+        let f = flag(&f, "founder_count").unwrap();
+        assert_eq!((f.expected, f.actual), (3, 1));
+    }
+
+    #[test]
+    fn press_links_without_news_are_flagged() {
+        use crate::parser::extract::{links, news};
+        use std::collections::BTreeMap;
+
+        // A press link whose date line didn't come through: no news section
+        let lexed = lex("### Payroll for teams\n\n[Acme raises seed](https://techcrunch.com/acme)\n\nFounded:2020\n");
+        let sections = cluster_sections(&lexed);
+        let mut found = links::extract("acme", &sections, &[]);
+        links::classify_press(&mut found, &BTreeMap::new());
+        let news = news::extract("acme", &sections, &[]);
+        assert!(news.is_empty());
+        let f = press_without_news("acme", &found, &news).unwrap();
+        assert_eq!((f.expected, f.actual), (1, 0));
+
+        let dated = lex("Latest News\n\n[Acme raises seed](https://techcrunch.com/acme)\n\nJan 5, 2024\n");
+        let sections = cluster_sections(&dated);
+        let mut found = links::extract("acme", &sections, &[]);
+        links::classify_press(&mut found, &BTreeMap::new());
+        assert!(press_without_news("acme", &found, &news::extract("acme", &sections, &[])).is_none());
+    }
+
+    #[test]
+    fn thin_page_is_flagged() {
+        let f = flags("Founders:3\n\nJane Doe\n[](https://www.linkedin.com/in/janedoe)\nFounder/CEO\n");
+        let thin = flag(&f, "thin_company").unwrap();
+        assert_eq!(thin.expected, 8);
+        assert!(thin.detail.as_deref().unwrap().contains("homepage"));
+    }
+
+    #[test]
+    fn founded_year_checked_against_batch() {
+        let f = flags(
+            "[Winter 2021](https://www.ycombinator.com/companies?batch=Winter%202021)\n\n\
+             Founded:2024\n\nTeam Size:4\n\nLocation:Berlin\n",
+        );
+        let bad = flag(&f, "founded_year_inconsistent").unwrap();
+        assert_eq!((bad.expected, bad.actual), (2022, 2024));
+        assert_eq!(founded_year_bound(2022, Some(2021)), None);
+        assert_eq!(founded_year_bound(1890, None), Some(MIN_FOUNDED_YEAR));
+        assert_eq!(founded_year_bound(2030, None), None);
+    }
+
+    #[test]
+    fn odd_founder_names() {
+        assert_eq!(name_problem("Patrick Collison"), None);
+        assert_eq!(name_problem("José-María O'Neil"), None);
+        assert_eq!(name_problem("Founder CEO"), Some("title word"));
+        assert_eq!(name_problem("Jane Doe 2"), Some("digits"));
+        assert_eq!(name_problem("Madonna"), Some("single word"));
+        assert_eq!(name_problem("linkedin.com/in/jane"), Some("url or handle"));
+    }
+}
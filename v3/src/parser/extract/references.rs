@@ -0,0 +1,72 @@
+use std::collections::HashSet;
+
+use crate::db::ReferenceRow;
+use crate::parser::blocks::Block;
+use crate::parser::sections::Section;
+use crate::sitemap;
+
+/// Other YC companies the page links to (`/companies/<slug>`), once per
+/// target, from link blocks and from links inline in description text.
+/// Links back to the page itself and to directory pages (industry,
+/// location, jobs) are not references.
+pub fn extract(slug: &str, sections: &[Section]) -> Vec<ReferenceRow> {
+    let own = sitemap::canonical_slug(slug);
+    let mut seen = HashSet::new();
+    let mut rows = Vec::new();
+
+    for section in sections {
+        for (i, block) in section.blocks.iter().enumerate() {
+            let targets: Vec<String> = match block {
+                Block::Link { url, .. } => sitemap::slug_of_link(url).into_iter().collect(),
+                Block::Text(text) | Block::Quote(text) => sitemap::linked_slugs(text).collect(),
+                _ => continue,
+            };
+            for to_slug in targets {
+                if to_slug == own || !seen.insert(to_slug.clone()) {
+                    continue;
+                }
+                rows.push(ReferenceRow {
+                    from_slug: slug.to_string(),
+                    to_slug,
+                    section: section.kind.clone(),
+                    source_lines: section.block_span(i).map(|s| s.to_string()),
+                });
+            }
+        }
+    }
+
+    rows
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::blocks::lex;
+    use crate::parser::sections::cluster_sections;
+
+    fn references(md: &str) -> Vec<String> {
+        let lexed = lex(md);
+        let sections = cluster_sections(&lexed);
+        extract("acme", &sections).into_iter().map(|r| r.to_slug).collect()
+    }
+
+    #[test]
+    fn links_to_other_companies() {
+        let md = "### Rockets for everyone.\nAcme builds rockets.\n\n\
+                  Spun out of [Globex](https://www.ycombinator.com/companies/globex) and \
+                  partnered with [Initech](/companies/Initech?utm=x).\n\n\
+                  [Globex again](https://www.ycombinator.com/companies/globex)\n\n\
+                  [Hooli](https://ycombinator.com/companies/hooli/)\n";
+        assert_eq!(references(md), ["globex", "initech", "hooli"]);
+    }
+
+    #[test]
+    fn self_and_directory_links_are_not_references() {
+        let md = "### Rockets for everyone.\nAcme builds rockets.\n\n\
+                  [Acme](https://www.ycombinator.com/companies/acme)\n\n\
+                  [Fintech](https://www.ycombinator.com/companies/industry/fintech)\n\n\
+                  [View all jobs](https://www.ycombinator.com/companies/acme/jobs)\n\n\
+                  [Site](https://acme.com/companies/globex)\n";
+        assert!(references(md).is_empty());
+    }
+}
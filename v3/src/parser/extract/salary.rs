@@ -0,0 +1,128 @@
+use std::collections::BTreeMap;
+use std::sync::LazyLock;
+
+use regex::{Captures, Regex};
+
+use crate::db::JobRow;
+
+/// Approximate USD per unit. `[parser.usd_rates]` entries override these
+/// and add currencies; a currency with no rate keeps its USD columns NULL.
+const USD_RATES: &[(&str, f64)] = &[
+    ("USD", 1.0),
+    ("EUR", 1.08),
+    ("GBP", 1.27),
+    ("CAD", 0.73),
+    ("AUD", 0.66),
+    ("SGD", 0.74),
+    ("CHF", 1.12),
+    ("INR", 0.012),
+];
+
+/// Symbol or ISO code → currency. Longer prefixes of `$` come first.
+const CURRENCIES: &[(&str, &str)] = &[
+    ("US$", "USD"),
+    ("CA$", "CAD"),
+    ("C$", "CAD"),
+    ("A$", "AUD"),
+    ("S$", "SGD"),
+    ("$", "USD"),
+    ("€", "EUR"),
+    ("£", "GBP"),
+    ("₹", "INR"),
+    ("USD", "USD"),
+    ("EUR", "EUR"),
+    ("GBP", "GBP"),
+    ("CAD", "CAD"),
+    ("AUD", "AUD"),
+    ("SGD", "SGD"),
+    ("CHF", "CHF"),
+    ("INR", "INR"),
+];
+
+/// "$120K - $150K", "€50K – €70K", "₹25L - ₹40L", "CA$90,000 - 110,000".
+/// The second currency may be left out; trailing text (equity) is ignored.
+static RANGE_RE: LazyLock<Regex> = LazyLock::new(|| {
+    let cur = r"US\$|CA\$|C\$|A\$|S\$|\$|€|£|₹|USD|EUR|GBP|CAD|AUD|SGD|CHF|INR";
+    let amount = r"(\d[\d,]*(?:\.\d+)?)\s?(K|k|M|L|Cr)?\b";
+    Regex::new(&format!(r"^({cur})\s?{amount}\s*[-–—]\s*({cur})?\s?{amount}")).unwrap()
+});
+
+/// A listing's salary range in its own currency.
+#[derive(Debug, PartialEq)]
+pub struct Salary {
+    pub currency: &'static str,
+    pub min: f64,
+    pub max: f64,
+}
+
+pub fn currency(symbol: &str) -> &'static str {
+    CURRENCIES.iter().find(|(s, _)| *s == symbol).map_or("USD", |(_, c)| c)
+}
+
+fn amount(caps: &Captures, number: usize, unit: Option<&str>) -> Option<f64> {
+    let n: f64 = caps[number].replace(',', "").parse().ok()?;
+    let scale = match unit {
+        Some("K" | "k") => 1e3,
+        Some("M") => 1e6,
+        Some("L") => 1e5,
+        Some("Cr") => 1e7,
+        _ => 1.0,
+    };
+    Some(n * scale)
+}
+
+/// The salary range at the start of a job listing line, if there is one.
+pub fn parse(text: &str) -> Option<Salary> {
+    let caps = RANGE_RE.captures(text.trim())?;
+    let cur = currency(&caps[1]);
+    if caps.get(4).is_some_and(|c| currency(c.as_str()) != cur) {
+        return None;
+    }
+    // "$120 - 150K": the unit on the top of the range covers both ends
+    let (unit_min, unit_max) = (caps.get(3).map(|m| m.as_str()), caps.get(6).map(|m| m.as_str()));
+    let min = amount(&caps, 2, unit_min.or(unit_max))?;
+    let max = amount(&caps, 5, unit_max)?;
+    (min <= max).then_some(Salary { currency: cur, min, max })
+}
+
+/// USD per unit of `currency`, from `overrides` or the built-in table.
+pub fn usd_rate(currency: &str, overrides: &BTreeMap<String, f64>) -> Option<f64> {
+    overrides
+        .get(currency)
+        .copied()
+        .or_else(|| USD_RATES.iter().find(|(c, _)| *c == currency).map(|(_, r)| *r))
+}
+
+/// Fill `salary_min_usd` / `salary_max_usd` from each job's native range.
+pub fn convert(jobs: &mut [JobRow], overrides: &BTreeMap<String, f64>) {
+    for job in jobs {
+        let rate = job.salary_currency.as_deref().and_then(|c| usd_rate(c, overrides));
+        job.salary_min_usd = job.salary_min.zip(rate).map(|(v, r)| (v * r).round() as i64);
+        job.salary_max_usd = job.salary_max.zip(rate).map(|(v, r)| (v * r).round() as i64);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ranges_in_several_currencies() {
+        let s = |currency, min, max| Some(Salary { currency, min, max });
+        assert_eq!(parse("$120K - $150K"), s("USD", 120e3, 150e3));
+        assert_eq!(parse("$202K - $303K / 0.50% - 1.00%"), s("USD", 202e3, 303e3));
+        assert_eq!(parse("€50K – €70K"), s("EUR", 50e3, 70e3));
+        assert_eq!(parse("£60,000 - £80,000"), s("GBP", 60e3, 80e3));
+        assert_eq!(parse("₹25L - ₹40L"), s("INR", 25e5, 40e5));
+        assert_eq!(parse("CA$90K - 110K"), s("CAD", 90e3, 110e3));
+        assert_eq!(parse("$120 - 150K"), s("USD", 120e3, 150e3));
+        assert_eq!(parse("€50K - £70K"), None);
+        assert_eq!(parse("San Francisco, CA, US"), None);
+        assert_eq!(parse("3+ years"), None);
+
+        let overrides = BTreeMap::from([("EUR".to_string(), 1.1)]);
+        assert_eq!(usd_rate("EUR", &overrides), Some(1.1));
+        assert_eq!(usd_rate("INR", &overrides), Some(0.012));
+        assert_eq!(usd_rate("XYZ", &overrides), None);
+    }
+}
@@ -0,0 +1,110 @@
+use regex::Regex;
+use std::collections::{HashMap, HashSet};
+use std::sync::LazyLock;
+
+use crate::db::TagCatalogRow;
+use crate::parser::blocks::clean_tag;
+
+pub const INDUSTRY_URL: &str = "https://www.ycombinator.com/companies/industry/";
+
+static INDUSTRY_LINK_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"/companies/industry/([^)\s/?#\]]+)").unwrap());
+
+static H1_RE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"^#\s+(.+)$").unwrap());
+
+/// "Fintech Startups funded by Y Combinator (YC) 2025" → "Fintech".
+static TITLE_SUFFIX_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"(?i)\s+(?:startups|companies)\b.*$").unwrap());
+
+/// Catalog key: tags that differ only in case or spacing share one.
+pub fn tag_key(tag: &str) -> String {
+    clean_tag(tag).to_lowercase()
+}
+
+/// Landing page URL for an industry tag.
+pub fn tag_url(tag: &str) -> String {
+    format!("{}{}", INDUSTRY_URL, clean_tag(tag).replace('%', "%25").replace(' ', "%20").replace('/', "%2F"))
+}
+
+/// Industry tags linked from a page (company pages, other tag pages),
+/// cleaned, one per key in order of first appearance.
+pub fn industry_links(md: &str) -> Vec<String> {
+    let mut seen = HashSet::new();
+    INDUSTRY_LINK_RE
+        .captures_iter(md)
+        .map(|c| clean_tag(&c[1]))
+        .filter(|tag| !tag.is_empty() && seen.insert(tag_key(tag)))
+        .collect()
+}
+
+/// Canonical name and description from a tag landing page. The name is the
+/// H1 minus its "Startups funded by..." tail, falling back to `linked` (the
+/// tag as it appeared in the URL we followed); the description is the first
+/// paragraph of plain text after the H1.
+pub fn parse_tag_page(linked: &str, md: &str) -> TagCatalogRow {
+    let lines: Vec<&str> = md.lines().map(str::trim).collect();
+    let h1 = lines.iter().position(|l| H1_RE.is_match(l));
+    let tag = h1
+        .map(|i| clean_tag(&TITLE_SUFFIX_RE.replace(&H1_RE.captures(lines[i]).unwrap()[1], "")))
+        .filter(|name| tag_key(name) == tag_key(linked))
+        .unwrap_or_else(|| clean_tag(linked));
+    let description = lines[h1.map_or(0, |i| i + 1)..]
+        .iter()
+        .take_while(|l| !l.starts_with('#'))
+        .find(|l| l.len() >= 40 && !l.starts_with(['[', '!', '*', '-', '|', '>']) && !l.contains("]("))
+        .map(|l| l.replace("&amp;", "&"));
+    TagCatalogRow { url: tag_url(&tag), tag, description }
+}
+
+/// Rewrite a comma-joined tag list with catalog spellings (`catalog` maps
+/// `tag_key` to the canonical tag). Tags the catalog doesn't know, such as
+/// locations, are kept as cleaned; repeats are dropped.
+pub fn normalize_list(tags: &str, catalog: &HashMap<String, String>) -> String {
+    let mut seen = HashSet::new();
+    tags.split(',')
+        .map(|t| {
+            let key = tag_key(t);
+            let tag = catalog.get(&key).cloned().unwrap_or_else(|| clean_tag(t));
+            (key, tag)
+        })
+        .filter(|(key, _)| !key.is_empty() && seen.insert(key.clone()))
+        .map(|(_, tag)| tag)
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tag_pages_and_normalization() {
+        let md = "[Companies](https://www.ycombinator.com/companies)\n\
+                  # Fintech Startups funded by Y Combinator (YC) 2025\n\
+                  \n\
+                  [Apply](https://www.ycombinator.com/apply)\n\
+                  Fintech startups build software for payments, lending, banking &amp; insurance.\n\
+                  [Banking and Exchange](https://www.ycombinator.com/companies/industry/Banking%20and%20Exchange)\n\
+                  [Payments](/companies/industry/Payments)[fintech](/companies/industry/fintech)\n";
+        let row = parse_tag_page("fintech", md);
+        assert_eq!(row.tag, "Fintech");
+        assert_eq!(row.url, "https://www.ycombinator.com/companies/industry/Fintech");
+        assert_eq!(
+            row.description.as_deref(),
+            Some("Fintech startups build software for payments, lending, banking & insurance.")
+        );
+        assert_eq!(industry_links(md), ["Banking and Exchange", "Payments", "fintech"]);
+
+        // A title that isn't about the tag we followed is ignored
+        let row = parse_tag_page("B2B%20SaaS", "# Page not found\n");
+        assert_eq!((row.tag.as_str(), row.description), ("B2B SaaS", None));
+        assert_eq!(row.url, "https://www.ycombinator.com/companies/industry/B2B%20SaaS");
+
+        let catalog: HashMap<String, String> =
+            ["Fintech", "B2B SaaS", "E-commerce"].iter().map(|t| (tag_key(t), t.to_string())).collect();
+        assert_eq!(
+            normalize_list("fintech, b2b  saas, E-Commerce, san-francisco-bay-area, FINTECH", &catalog),
+            "Fintech, B2B SaaS, E-commerce, san-francisco-bay-area"
+        );
+    }
+}
@@ -0,0 +1,190 @@
+use scraper::{ElementRef, Html, Node};
+
+/// Elements whose content is never page text.
+const SKIPPED: &[&str] = &[
+    "head", "script", "style", "noscript", "template", "svg", "img", "picture", "video", "audio",
+    "canvas", "iframe", "select",
+];
+
+/// Elements that start and end a line.
+const BLOCKS: &[&str] = &[
+    "address", "article", "aside", "blockquote", "body", "br", "dd", "details", "dialog", "div",
+    "dl", "dt", "fieldset", "figcaption", "figure", "footer", "form", "h1", "h2", "h3", "h4", "h5",
+    "h6", "header", "hr", "li", "main", "nav", "ol", "p", "section", "summary", "table", "td", "th",
+    "tr", "ul",
+];
+
+/// Render spider.cloud's raw HTML (`--return-format html`) as the text
+/// `blocks::lex_with` reads: one line per block element, `#` headings,
+/// and every `<a href>` as a one-line `[text](url)` whatever markup its
+/// text is spread over, so none of the markdown converter's broken
+/// multi-line links reach the lexer. Root-relative links are resolved
+/// against `base_url`.
+pub fn to_text(html: &str, base_url: &str) -> String {
+    enum Step<N> {
+        Open(N),
+        Close(N),
+    }
+
+    let doc = Html::parse_document(html);
+    let mut out = Lines::default();
+    // Walked with an explicit stack: remote pages can nest arbitrarily deep
+    let mut stack = vec![Step::Open(doc.tree.root())];
+    while let Some(step) = stack.pop() {
+        match step {
+            Step::Open(node) => match node.value() {
+                Node::Text(t) => out.push_text(t),
+                Node::Element(el) => {
+                    let name = el.name();
+                    if SKIPPED.contains(&name) {
+                        continue;
+                    }
+                    let text = || ElementRef::wrap(node).map(text_of).unwrap_or_default();
+                    if let ("a", Some(href)) = (name, el.attr("href")) {
+                        out.push_link(&collapse(&text()), &resolve(href, base_url));
+                        continue;
+                    }
+                    if name == "pre" {
+                        out.push_code(&text());
+                        continue;
+                    }
+                    if BLOCKS.contains(&name) {
+                        out.break_line();
+                    }
+                    if let Some(level) = name.strip_prefix('h').and_then(|n| n.parse::<usize>().ok()) {
+                        out.line.push_str(&"#".repeat(level.clamp(1, 6)));
+                        out.line.push(' ');
+                    }
+                    stack.push(Step::Close(node));
+                    stack.extend(node.children().rev().map(Step::Open));
+                }
+                Node::Document | Node::Fragment => stack.extend(node.children().rev().map(Step::Open)),
+                _ => {}
+            },
+            Step::Close(node) => {
+                if node.value().as_element().is_some_and(|el| BLOCKS.contains(&el.name())) {
+                    out.break_line();
+                }
+            }
+        }
+    }
+    out.break_line();
+    out.lines.join("\n")
+}
+
+#[derive(Default)]
+struct Lines {
+    lines: Vec<String>,
+    line: String,
+}
+
+impl Lines {
+    /// Append text with whitespace runs collapsed, as a browser shows it.
+    fn push_text(&mut self, text: &str) {
+        for c in text.chars() {
+            if c.is_whitespace() {
+                if !self.line.is_empty() && !self.line.ends_with(' ') {
+                    self.line.push(' ');
+                }
+            } else {
+                self.line.push(c);
+            }
+        }
+    }
+
+    fn push_link(&mut self, text: &str, url: &str) {
+        self.line.push('[');
+        self.line.push_str(text);
+        self.line.push_str("](");
+        self.line.push_str(url);
+        self.line.push(')');
+    }
+
+    /// A fenced block with the text verbatim.
+    fn push_code(&mut self, text: &str) {
+        self.break_line();
+        self.lines.push("```".to_string());
+        self.lines.extend(text.trim_matches('\n').lines().map(str::to_string));
+        self.lines.push("```".to_string());
+    }
+
+    /// End the current line; blank lines and empty headings are dropped.
+    fn break_line(&mut self) {
+        let line = self.line.trim();
+        if !line.is_empty() && !line.chars().all(|c| c == '#') {
+            self.lines.push(line.to_string());
+        }
+        self.line.clear();
+    }
+}
+
+/// Text under `el`, skipped elements left out, whitespace as written.
+fn text_of(el: ElementRef<'_>) -> String {
+    let mut text = String::new();
+    let mut stack = vec![*el];
+    while let Some(n) = stack.pop() {
+        match n.value() {
+            Node::Text(t) => text.push_str(t),
+            Node::Element(el) if SKIPPED.contains(&el.name()) => {}
+            Node::Element(el) if el.name() == "br" => text.push('\n'),
+            _ => stack.extend(n.children().rev()),
+        }
+    }
+    text
+}
+
+fn collapse(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// `href` as an absolute URL the lexer's link patterns can hold: "//" and
+/// "/" links get `base_url`'s scheme and host, and spaces and parentheses
+/// are percent-escaped.
+fn resolve(href: &str, base_url: &str) -> String {
+    let href = href.trim();
+    let (scheme, rest) = base_url.split_once("://").unwrap_or(("https", base_url));
+    let origin = format!("{}://{}", scheme, rest.split('/').next().unwrap_or(""));
+    let url = if let Some(path) = href.strip_prefix("//") {
+        format!("{}://{}", scheme, path)
+    } else if href.starts_with('/') {
+        format!("{}{}", origin, href)
+    } else {
+        href.to_string()
+    };
+    url.replace(' ', "%20").replace('(', "%28").replace(')', "%29")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::blocks::{lex, Block};
+
+    const BASE: &str = "https://www.ycombinator.com/companies/acme";
+
+    #[test]
+    fn links_stay_on_one_line_whatever_their_markup() {
+        let html = r#"<html><head><title>Acme</title><script>var x = 1;</script></head><body>
+            <h1>Acme</h1>
+            <a href="/jobs">
+              <div>Jobs</div>
+              <span>at
+                Acme</span>
+            </a>
+            <div><span>Founded:</span><span>2021</span></div>
+            <a href="//acme.com/a (b)"><img src="logo.png">Home</a>
+        </body></html>"#;
+        assert_eq!(
+            to_text(html, BASE),
+            "# Acme\n[Jobs at Acme](https://www.ycombinator.com/jobs)\nFounded:2021\n[Home](https://acme.com/a%20%28b%29)"
+        );
+        let blocks = lex(&to_text(html, BASE)).blocks;
+        assert!(matches!(&blocks[1], Block::Link { text, url } if text == "Jobs at Acme" && url == "https://www.ycombinator.com/jobs"));
+        assert!(matches!(&blocks[2], Block::MetaField { key, value } if key == "Founded" && value == "2021"));
+    }
+
+    #[test]
+    fn pre_becomes_a_fence_and_blank_blocks_are_dropped() {
+        let html = "<p>  </p><h3></h3><p>Intro\n text</p><pre>let a = 1;\n  a\n</pre><ul><li>One</li><li>Two<br>Three</li></ul>";
+        assert_eq!(to_text(html, BASE), "Intro text\n```\nlet a = 1;\n  a\n```\nOne\nTwo\nThree");
+    }
+}
@@ -0,0 +1,120 @@
+/// (chip, needles) for technologies named in job requirements. Needles match
+/// whole words, case-insensitively; a needle with capitals matches only that
+/// exact case ("Go", not "go above and beyond").
+pub const TECH_STACK: &[(&str, &[&str])] = &[
+    ("python", &["python"]),
+    ("typescript", &["typescript"]),
+    ("javascript", &["javascript"]),
+    ("node.js", &["node", "node.js", "nodejs"]),
+    ("react", &["react", "react.js", "reactjs"]),
+    ("react native", &["react native"]),
+    ("next.js", &["next.js", "nextjs"]),
+    ("vue", &["vue", "vue.js", "vuejs"]),
+    ("angular", &["angular"]),
+    ("rust", &["rust"]),
+    ("go", &["golang", "Go"]),
+    ("java", &["java"]),
+    ("kotlin", &["kotlin"]),
+    ("swift", &["swift", "swiftui"]),
+    ("scala", &["scala"]),
+    ("ruby", &["ruby"]),
+    ("rails", &["rails", "ruby on rails"]),
+    ("php", &["php"]),
+    ("c++", &["c++", "cpp"]),
+    ("c#", &["c#"]),
+    (".net", &[".net", "dotnet"]),
+    ("elixir", &["elixir"]),
+    ("haskell", &["haskell"]),
+    ("django", &["django"]),
+    ("fastapi", &["fastapi"]),
+    ("sql", &["sql"]),
+    ("postgres", &["postgres", "postgresql"]),
+    ("mysql", &["mysql"]),
+    ("mongodb", &["mongodb", "mongo"]),
+    ("redis", &["redis"]),
+    ("kafka", &["kafka"]),
+    ("spark", &["spark", "pyspark"]),
+    ("airflow", &["airflow"]),
+    ("dbt", &["dbt"]),
+    ("snowflake", &["snowflake"]),
+    ("graphql", &["graphql"]),
+    ("docker", &["docker"]),
+    ("kubernetes", &["kubernetes", "k8s"]),
+    ("terraform", &["terraform"]),
+    ("aws", &["aws", "amazon web services"]),
+    ("gcp", &["gcp", "google cloud"]),
+    ("azure", &["azure"]),
+    ("pytorch", &["pytorch"]),
+    ("tensorflow", &["tensorflow"]),
+    ("llm", &["llm", "llms", "large language models"]),
+];
+
+/// (chip, needles) for perks listed under a job's benefits heading.
+pub const BENEFITS: &[(&str, &[&str])] = &[
+    ("health", &["health", "medical", "healthcare"]),
+    ("dental", &["dental"]),
+    ("vision", &["vision insurance", "vision coverage", "dental and vision", "dental vision"]),
+    ("401k", &["401k", "401 k", "retirement"]),
+    ("equity", &["equity", "stock options", "options"]),
+    ("unlimited pto", &["unlimited pto", "unlimited vacation", "unlimited time off"]),
+    ("pto", &["pto", "paid time off", "vacation"]),
+    ("parental leave", &["parental leave", "maternity", "paternity"]),
+    ("remote", &["remote", "work from home", "wfh"]),
+    ("learning budget", &["learning budget", "learning stipend", "education stipend", "conferences"]),
+    ("meals", &["lunch", "lunches", "meals", "dinner", "snacks"]),
+    ("wellness", &["gym", "fitness", "wellness"]),
+    ("home office", &["home office", "equipment stipend", "wfh stipend"]),
+    ("visa sponsorship", &["visa", "visa sponsorship"]),
+    ("relocation", &["relocation"]),
+    ("commuter", &["commuter", "transit"]),
+];
+
+/// Chips from `dict` mentioned in `lines`, in dictionary order.
+pub fn chips(lines: &[String], dict: &[(&'static str, &[&str])]) -> Vec<&'static str> {
+    let text = lines.join("\n");
+    let exact = padded_words(&text, false);
+    let folded = padded_words(&text, true);
+    dict.iter()
+        .filter(|(_, needles)| {
+            needles.iter().any(|n| {
+                let haystack = if n.chars().any(char::is_uppercase) { &exact } else { &folded };
+                haystack.contains(&format!(" {} ", n))
+            })
+        })
+        .map(|(chip, _)| *chip)
+        .collect()
+}
+
+/// " word word " with punctuation other than `+ # .` as separators and
+/// sentence-final dots dropped, so "C++", "C#" and "Node.js," survive.
+fn padded_words(s: &str, lowercase: bool) -> String {
+    let words: Vec<String> = s
+        .split(|c: char| !(c.is_alphanumeric() || matches!(c, '+' | '#' | '.')))
+        .map(|w| w.trim_end_matches('.'))
+        .filter(|w| !w.is_empty())
+        .map(|w| if lowercase { w.to_lowercase() } else { w.to_string() })
+        .collect();
+    format!(" {} ", words.join(" "))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lines(s: &str) -> Vec<String> {
+        s.lines().map(str::to_string).collect()
+    }
+
+    #[test]
+    fn stack_chips_match_whole_words() {
+        let req = lines("5+ years with Go, Rust or C++.\nExperience with Node.js, PostgreSQL and AWS\nTrustworthy and ready to go above and beyond");
+        assert_eq!(chips(&req, TECH_STACK), vec!["node.js", "rust", "go", "c++", "postgres", "aws"]);
+        assert!(chips(&lines("We go fast"), TECH_STACK).is_empty());
+    }
+
+    #[test]
+    fn benefit_chips() {
+        let perks = lines("Competitive salary and equity\nMedical, dental and vision\n401(k) matching\nUnlimited PTO");
+        assert_eq!(chips(&perks, BENEFITS), vec!["health", "dental", "vision", "401k", "equity", "unlimited pto", "pto"]);
+    }
+}
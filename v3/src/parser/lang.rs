@@ -0,0 +1,110 @@
+use whatlang::Lang;
+
+/// Language-dependent keywords used by the lexer. Page chrome is always
+/// English, so every lexicon keeps the English lists and adds to them.
+pub struct Lexicon {
+    /// Substrings marking a founder title line ("Founder/CEO", "Cofundadora").
+    pub title_keywords: Vec<&'static str>,
+    /// Lowercased section labels that must never be read as person names.
+    pub noise_labels: Vec<&'static str>,
+}
+
+const ENGLISH_TITLES: &[&str] = &["Founder", "CEO", "CTO", "COO", "Co-", "President", "Partner"];
+
+/// Per-language additions, keyed by ISO 639-3 code as returned by whatlang.
+const LOCALIZED: &[(&str, &[&str], &[&str])] = &[
+    (
+        "spa",
+        &["Fundador", "Fundadora", "Cofundador", "Cofundadora", "Director", "Directora"],
+        &["fundadores", "equipo", "nuestro equipo"],
+    ),
+    (
+        "por",
+        &["Fundador", "Fundadora", "Cofundador", "Cofundadora", "Sócio", "Diretor"],
+        &["fundadores", "equipe", "nossa equipe"],
+    ),
+    (
+        "fra",
+        &["Fondateur", "Fondatrice", "Cofondateur", "Cofondatrice", "PDG", "Directeur", "Président"],
+        &["fondateurs", "équipe", "notre équipe"],
+    ),
+    (
+        "deu",
+        &["Gründer", "Gründerin", "Mitgründer", "Mitgründerin", "Geschäftsführer", "Vorstand"],
+        &["gründer", "team", "unser team"],
+    ),
+];
+
+impl Lexicon {
+    pub fn english() -> Self {
+        Lexicon {
+            title_keywords: ENGLISH_TITLES.to_vec(),
+            noise_labels: Vec::new(),
+        }
+    }
+
+    /// English plus the additions for `lang` (ISO 639-3), if any.
+    pub fn for_lang(lang: Option<&str>) -> Self {
+        let mut lex = Self::english();
+        if let Some((_, titles, noise)) = LOCALIZED.iter().find(|(code, ..)| Some(*code) == lang) {
+            lex.title_keywords.extend_from_slice(titles);
+            lex.noise_labels.extend_from_slice(noise);
+        }
+        lex
+    }
+
+    pub fn is_title(&self, line: &str) -> bool {
+        self.title_keywords.iter().any(|kw| line.contains(kw))
+    }
+}
+
+/// whatlang's own `is_reliable` rejects most single-paragraph descriptions.
+const MIN_CONFIDENCE: f64 = 0.5;
+
+/// Detect the language of free text, as an ISO 639-3 code ("eng", "spa").
+/// Returns None for short or ambiguous text.
+pub fn detect(text: &str) -> Option<&'static str> {
+    if text.split_whitespace().count() < 5 {
+        return None;
+    }
+    whatlang::detect(text)
+        .filter(|info| info.confidence() >= MIN_CONFIDENCE)
+        .map(|info| info.lang().code())
+}
+
+/// Guess a page's content language from its prose lines (long, link-free),
+/// ignoring the English site chrome around them.
+pub fn detect_page(markdown: &str) -> Option<&'static str> {
+    let prose: Vec<&str> = markdown
+        .lines()
+        .map(str::trim)
+        .filter(|l| !l.contains("](") && l.split_whitespace().count() >= 8)
+        .collect();
+    detect(&prose.join("\n")).filter(|code| *code != Lang::Eng.code())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn detects_description_language() {
+        assert_eq!(
+            detect("Economic infrastructure for the internet. Businesses of every size use our software to accept payments."),
+            Some("eng")
+        );
+        assert_eq!(
+            detect("Somos una plataforma que ayuda a las pequeñas empresas de América Latina a recibir pagos en línea."),
+            Some("spa")
+        );
+        assert_eq!(detect("Stripe"), None);
+    }
+
+    #[test]
+    fn localized_lexicon_extends_english() {
+        let lex = Lexicon::for_lang(Some("spa"));
+        assert!(lex.is_title("Cofundadora y CEO"));
+        assert!(lex.is_title("Founder/CEO"));
+        assert!(!Lexicon::english().is_title("Cofundadora"));
+    }
+}
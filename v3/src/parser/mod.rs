@@ -1,13 +1,59 @@
+// blocks, config, dates, lang and sections use nothing outside parser:
+// fuzz/ compiles them on their own, without the database and network code.
 pub mod blocks;
+mod config;
+pub mod dates;
 pub mod extract;
+pub mod html;
+pub mod keywords;
+pub mod lang;
+pub mod names;
 pub mod sections;
 
+use std::time::Instant;
+
 use crate::db::ScrapedPage;
+pub use config::ParserConfig;
+use extract::custom::Registry;
 use extract::ExtractedData;
 
-/// Three-pass pipeline: markdown → blocks → sections → extracted data.
-pub fn process_page(page: &ScrapedPage) -> ExtractedData {
-    let blocks = blocks::classify_lines(&page.markdown);
-    let sections = sections::cluster_sections(&blocks);
-    extract::extract_all(&page.slug, &page.url, page.page_data_id, &sections)
+/// Three-pass pipeline: markdown → blocks → sections → extracted data,
+/// with the extractors in `custom::registry()`.
+pub fn process_page(page: &ScrapedPage, config: &ParserConfig) -> ExtractedData {
+    process_page_with(page, config, extract::custom::registry())
+}
+
+/// `process_page` with a registry of the caller's choosing.
+#[tracing::instrument(name = "parse", skip_all, fields(slug = %page.slug))]
+pub fn process_page_with(page: &ScrapedPage, config: &ParserConfig, registry: &Registry) -> ExtractedData {
+    let start = Instant::now();
+    let rendered;
+    let text = if page.html {
+        rendered = tracing::info_span!("html").in_scope(|| html::to_text(&page.markdown, &page.url));
+        &rendered
+    } else {
+        &page.markdown
+    };
+    let lexicon = lang::Lexicon::for_lang(lang::detect_page(text));
+    let lexed = tracing::info_span!("lex").in_scope(|| blocks::lex_with(text, &lexicon, config));
+    let sections =
+        tracing::info_span!("cluster").in_scope(|| sections::cluster_sections_with(&lexed, config));
+    let mut data = tracing::info_span!("extract").in_scope(|| {
+        extract::extract_all(
+            &page.slug,
+            &page.url,
+            page.page_data_id,
+            &sections,
+            page.meta_description.as_deref(),
+            &lexicon,
+            &config.date_formats,
+            registry,
+        )
+    });
+    extract::salary::convert(&mut data.jobs, &config.usd_rates);
+    extract::links::classify_press(&mut data.links, &config.press_domains);
+    data.quality.extend(extract::quality::press_without_news(&page.slug, &data.links, &data.news));
+    data.sections.parse_ms = start.elapsed().as_secs_f64() * 1000.0;
+    data.sections.block_count = lexed.blocks.len();
+    data
 }
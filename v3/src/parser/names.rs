@@ -0,0 +1,202 @@
+use unicode_normalization::char::is_combining_mark;
+use unicode_normalization::UnicodeNormalization;
+
+/// Lowercase words that join a surname to the given name ("van der Berg",
+/// "de la Cruz", "bin Salman"). Lowercase in names unless written otherwise.
+const PARTICLES: &[&str] = &[
+    "al", "bin", "da", "das", "de", "degli", "del", "della", "der", "di", "do", "dos", "du", "el", "ibn",
+    "la", "le", "st", "ten", "ter", "van", "von", "y", "zu",
+];
+
+/// Generational suffixes, in their display form.
+const SUFFIXES: &[&str] = &["Jr.", "Sr.", "II", "III", "IV"];
+
+/// Degrees and credentials after a name: shown, but not part of who it is.
+const CREDENTIALS: &[&str] = &["PhD", "MD", "MBA", "JD", "CPA"];
+
+/// `word` lowercased, without a trailing "," or ".".
+fn bare(word: &str) -> String {
+    word.trim_end_matches([',', '.']).to_lowercase()
+}
+
+fn suffix(word: &str) -> Option<&'static str> {
+    let w = bare(word);
+    SUFFIXES.iter().chain(CREDENTIALS).copied().find(|s| bare(s) == w)
+}
+
+/// Display form of a person name: trimmed, internal whitespace collapsed.
+pub fn clean_name(name: &str) -> String {
+    name.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Display form of a founder name: `clean_name`, then casing repaired
+/// when the whole name is written in one case. All-caps and all-lowercase
+/// names are title-cased around hyphens and apostrophes ("JEAN-LUC O'NEIL"
+/// → "Jean-Luc O'Neil"), particles after the first word are lowercase,
+/// and only dotted initials keep their capitals ("JOHN LI" → "John Li",
+/// "j.r. smith" → "J.R. Smith"): a short all-caps word can't be told from
+/// a short surname. Suffixes always take their usual form ("jr" → "Jr.",
+/// "phd" → "PhD"). A mixed-case name is kept as written: "McDonald",
+/// "DeAndre", "Van Buren" and "DJ Patil" spell themselves.
+pub fn normalize(name: &str) -> String {
+    let cleaned = clean_name(name);
+    let words: Vec<&str> = cleaned.split(' ').collect();
+    let is_suffix = |i: usize, word: &str| i > 0 && suffix(word).is_some();
+    let letters = || words.iter().enumerate().filter(|(i, w)| !is_suffix(*i, w)).flat_map(|(_, w)| w.chars());
+    let single_case = !letters().any(char::is_lowercase) || !letters().any(char::is_uppercase);
+    words
+        .iter()
+        .enumerate()
+        .map(|(i, word)| {
+            if let Some(s) = suffix(word).filter(|_| is_suffix(i, word)) {
+                let comma = if word.ends_with(',') { "," } else { "" };
+                return format!("{}{}", s, comma);
+            }
+            let particle = PARTICLES.contains(&word.to_lowercase().as_str());
+            if !single_case {
+                word.to_string()
+            } else if is_initials(word) {
+                word.to_uppercase()
+            } else if particle && i > 0 && i + 1 < words.len() {
+                word.to_lowercase()
+            } else {
+                title_case(word)
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Single letters each followed by a period: "J.", "J.R.".
+fn is_initials(word: &str) -> bool {
+    let mut chars = word.chars();
+    let mut any = false;
+    while let Some(c) = chars.next() {
+        if !c.is_alphabetic() || chars.next() != Some('.') {
+            return false;
+        }
+        any = true;
+    }
+    any
+}
+
+/// Uppercase the first letter after the start, a hyphen or an apostrophe;
+/// lowercase the rest.
+fn title_case(word: &str) -> String {
+    let mut out = String::with_capacity(word.len());
+    let mut upper = true;
+    for c in word.chars() {
+        if upper {
+            out.extend(c.to_uppercase());
+        } else {
+            out.extend(c.to_lowercase());
+        }
+        upper = matches!(c, '-' | '\'' | '’');
+    }
+    out
+}
+
+/// Whether `line` reads as a person's name of `min..=max` words: each word
+/// capitalized or all caps, letters (any script with case, diacritics
+/// included) plus hyphens, apostrophes and periods. Lowercase particles
+/// may sit between the first and last word, and suffixes may end it.
+pub fn looks_like_name(line: &str, min: usize, max: usize) -> bool {
+    let words: Vec<&str> = line.split_whitespace().collect();
+    let last = words.len().saturating_sub(1);
+    (min..=max).contains(&words.len())
+        && words.iter().enumerate().all(|(i, w)| {
+            let letters = w.trim_end_matches(',');
+            (i > 0 && suffix(w).is_some())
+                || (i > 0 && i < last && PARTICLES.contains(&letters))
+                || (letters.chars().next().is_some_and(char::is_uppercase)
+                    && letters.chars().all(|c| c.is_alphabetic() || matches!(c, '-' | '\'' | '’' | '.')))
+        })
+}
+
+/// Identity key for matching the same person across spellings:
+/// diacritics stripped, lowercased, punctuation dropped, whitespace collapsed,
+/// credentials after the name left out. "Patrick  Collison", "patrick
+/// collison", "Pátrick Collison" and "Patrick Collison, PhD" share a key.
+pub fn name_key(name: &str) -> String {
+    let mut words: Vec<&str> = name.split_whitespace().collect();
+    while words.len() > 1 && words.last().is_some_and(|w| CREDENTIALS.iter().any(|c| bare(c) == bare(w))) {
+        words.pop();
+    }
+    let folded: String = words
+        .join(" ")
+        .nfkd()
+        .filter(|c| !is_combining_mark(*c))
+        .flat_map(char::to_lowercase)
+        .map(|c| if c.is_alphanumeric() { c } else { ' ' })
+        .collect();
+    clean_name(&folded)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn clean_collapses_whitespace() {
+        assert_eq!(clean_name("  Patrick \t Collison "), "Patrick Collison");
+    }
+
+    #[test]
+    fn key_folds_case_whitespace_and_diacritics() {
+        let k = name_key("Patrick Collison");
+        assert_eq!(k, "patrick collison");
+        assert_eq!(name_key("Patrick  Collison"), k);
+        assert_eq!(name_key("PÁTRICK collison"), k);
+        assert_eq!(name_key("José-María O'Neil"), "jose maria o neil");
+        assert_eq!(name_key("Patrick Collison, PhD"), k);
+        assert_eq!(name_key("Patrick Collison Jr."), "patrick collison jr");
+    }
+
+    #[test]
+    fn normalize_repairs_casing_around_particles_and_suffixes() {
+        assert_eq!(normalize("JAN VAN DER BERG"), "Jan van der Berg");
+        assert_eq!(normalize("juan de la cruz"), "Juan de la Cruz");
+        assert_eq!(normalize("JEAN-LUC O'NEIL"), "Jean-Luc O'Neil");
+        assert_eq!(normalize("ÉLODIE  DURAND"), "Élodie Durand");
+        assert_eq!(normalize("martin luther king jr"), "Martin Luther King Jr.");
+        assert_eq!(normalize("Jane Doe, phd"), "Jane Doe, PhD");
+        assert_eq!(normalize("HENRY FORD iii"), "Henry Ford III");
+        // Mixed case is how the person writes it
+        assert_eq!(normalize("Ronald McDonald"), "Ronald McDonald");
+        assert_eq!(normalize("Martin Van Buren"), "Martin Van Buren");
+        assert_eq!(normalize("Jan VAN DER BERG"), "Jan VAN DER BERG");
+        // A particle at either end is a name of its own
+        assert_eq!(normalize("DE SOUZA"), "De Souza");
+        assert_eq!(normalize("ANNA DEL"), "Anna Del");
+    }
+
+    #[test]
+    fn normalize_keeps_initials() {
+        assert_eq!(normalize("DJ Patil"), "DJ Patil");
+        assert_eq!(normalize("JJ Abrams"), "JJ Abrams");
+        assert_eq!(normalize("J.R. Smith"), "J.R. Smith");
+        assert_eq!(normalize("DJ PATIL"), "Dj Patil", "all caps: no telling initials from a name");
+        assert_eq!(normalize("J.R. SMITH"), "J.R. Smith");
+        assert_eq!(normalize("j.r. smith"), "J.R. Smith");
+        assert_eq!(normalize("MARY K. ASH"), "Mary K. Ash");
+        assert_eq!(normalize("JOHN LI"), "John Li");
+        assert_eq!(normalize("AMY WU"), "Amy Wu");
+        assert_eq!(normalize("KEN NG"), "Ken Ng");
+        assert_eq!(normalize("YO-YO MA"), "Yo-Yo Ma");
+        assert_eq!(normalize("Ken NG"), "Ken NG", "mixed case is kept");
+    }
+
+    #[test]
+    fn names_with_particles_caps_and_suffixes_look_like_names() {
+        assert!(looks_like_name("Jan van der Berg", 2, 4));
+        assert!(looks_like_name("Juan de la Cruz", 2, 4));
+        assert!(looks_like_name("Jane DOE", 2, 4));
+        assert!(looks_like_name("Zoë Ångström-Lee", 2, 4));
+        assert!(looks_like_name("Jane Doe, PhD", 2, 4));
+        assert!(looks_like_name("Sam Lee Jr.", 2, 4));
+        assert!(!looks_like_name("de la Cruz", 2, 4));
+        assert!(!looks_like_name("Jane van", 2, 4));
+        assert!(!looks_like_name("We are hiring", 2, 4));
+        assert!(!looks_like_name("Jane Doe 2", 2, 4));
+    }
+}
@@ -2,39 +2,77 @@ use std::sync::LazyLock;
 
 use regex::Regex;
 
-use super::blocks::Block;
+use super::blocks::{Block, Lexed, Span};
+use super::dates;
+use super::ParserConfig;
 
-static DATE_RE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"^[A-Z][a-z]{2} \d{2}, \d{4}$").unwrap());
+static QA_LABEL_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?i)^(selected (answers|questions)( from [^.!?:]{1,80})?|faqs?|frequently asked questions)\s*[:.]?$")
+        .unwrap()
+});
 
+/// "Selected answers from …", "Selected questions", "FAQ" section labels:
+/// the whole line, so a description that starts with the words isn't one.
+pub fn is_qa_label(text: &str) -> bool {
+    QA_LABEL_RE.is_match(text.trim().trim_matches('*').trim())
+}
+
+/// A run of consecutive lexed blocks, borrowed from the page's `Lexed`
+/// rather than copied out of it.
 #[derive(Debug, Clone)]
-pub struct Section {
+pub struct Section<'a> {
     pub kind: String,
-    pub blocks: Vec<Block>,
+    pub blocks: &'a [Block],
+    /// Markdown line span of each block, parallel to `blocks`.
+    pub spans: &'a [Span],
 }
 
-/// Cluster a flat Vec<Block> into named sections by structural transitions.
-pub fn cluster_sections(blocks: &[Block]) -> Vec<Section> {
+impl Section<'_> {
+    /// Line span of `blocks[i]`.
+    pub fn block_span(&self, i: usize) -> Option<Span> {
+        self.spans.get(i).copied()
+    }
+
+    /// Lines from the first to the last non-empty block.
+    pub fn span(&self) -> Option<Span> {
+        self.blocks
+            .iter()
+            .zip(self.spans)
+            .filter(|(b, _)| !matches!(b, Block::Empty))
+            .map(|(_, s)| *s)
+            .reduce(Span::merge)
+    }
+}
+
+/// Cluster lexed blocks into named sections with default thresholds.
+#[cfg(test)]
+pub fn cluster_sections(lexed: &Lexed) -> Vec<Section<'_>> {
+    cluster_sections_with(lexed, &ParserConfig::default())
+}
+
+/// Cluster lexed blocks into named sections by structural transitions.
+pub fn cluster_sections_with<'a>(lexed: &'a Lexed, config: &ParserConfig) -> Vec<Section<'a>> {
+    let blocks = &lexed.blocks;
+    let section = |kind, start, end| Section {
+        kind,
+        blocks: &blocks[start..end],
+        spans: &lexed.spans[start..end],
+    };
     let mut sections: Vec<Section> = Vec::new();
-    let mut current_blocks: Vec<Block> = Vec::new();
+    let mut start = 0;
     let mut current_kind = "header".to_string();
     for (i, block) in blocks.iter().enumerate() {
-        if let Some(new_kind) = detect_transition(block, blocks, i, &current_kind) {
-            if !current_blocks.is_empty() {
-                sections.push(Section {
-                    kind: current_kind,
-                    blocks: std::mem::take(&mut current_blocks),
-                });
+        if let Some(new_kind) = detect_transition(block, blocks, i, &current_kind, config) {
+            if i > start {
+                sections.push(section(current_kind, start, i));
+                start = i;
             }
             current_kind = new_kind;
         }
-        current_blocks.push(block.clone());
     }
 
-    if !current_blocks.is_empty() {
-        sections.push(Section {
-            kind: current_kind,
-            blocks: current_blocks,
-        });
+    if start < blocks.len() {
+        sections.push(section(current_kind, start, blocks.len()));
     }
 
     sections
@@ -45,15 +83,21 @@ fn detect_transition(
     all: &[Block],
     idx: usize,
     current_kind: &str,
+    config: &ParserConfig,
 ) -> Option<String> {
     match block {
+        // Q&A label (text or heading) → "qa"
+        Block::Text(t) | Block::Heading { text: t, .. } if is_qa_label(t) && current_kind != "qa" => {
+            Some("qa".to_string())
+        }
+
         // ### heading → description
         Block::Heading { level: 3, .. } => Some("description".to_string()),
 
-        // Cluster of MetaField blocks (3+ consecutive, allowing gaps of Empty/StatusLine/bare Link)
+        // Cluster of MetaField blocks (min_meta_cluster+ consecutive, allowing gaps of Empty/StatusLine/bare Link)
         Block::MetaField { .. } if current_kind != "footer_meta" => {
             let meta_count = count_meta_cluster(all, idx);
-            if meta_count >= 3 {
+            if meta_count >= config.min_meta_cluster {
                 Some("footer_meta".to_string())
             } else {
                 None
@@ -84,7 +128,7 @@ fn detect_transition(
             let has_date = all[idx + 1..]
                 .iter()
                 .find(|b| !matches!(b, Block::Empty))
-                .map(|b| matches!(b, Block::Text(t) if DATE_RE.is_match(t.trim())))
+                .map(|b| matches!(b, Block::Text(t) if dates::parse(t, &config.date_formats).is_some()))
                 .unwrap_or(false);
             if has_date {
                 Some("news".to_string())
@@ -142,11 +186,11 @@ fn count_meta_cluster(blocks: &[Block], start: usize) -> usize {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::parser::blocks::classify_lines;
+    use crate::parser::blocks::lex;
 
     fn section_kinds(md: &str) -> Vec<String> {
-        let blocks = classify_lines(md);
-        let sections = cluster_sections(&blocks);
+        let lexed = lex(md);
+        let sections = cluster_sections(&lexed);
         sections.iter().map(|s| s.kind.clone()).collect()
     }
 
@@ -173,8 +217,8 @@ mod tests {
     #[test]
     fn doordash_has_jobs() {
         let md = std::fs::read_to_string("tests/fixtures/doordash.md").unwrap();
-        let blocks = classify_lines(&md);
-        let sections = cluster_sections(&blocks);
+        let lexed = lex(&md);
+        let sections = cluster_sections(&lexed);
         let jobs = sections.iter().find(|s| s.kind == "jobs");
         assert!(jobs.is_some());
         // Should have multiple job Link blocks
@@ -187,10 +231,49 @@ mod tests {
         assert!(job_links.len() >= 4);
     }
 
+    #[test]
+    fn qa_label_starts_section() {
+        let kinds = section_kinds("### Tagline\nAbout us.\nSelected answers from the YC application\nWhy now?\nBecause.");
+        assert_eq!(kinds, vec!["description", "qa"]);
+    }
+
+    #[test]
+    fn qa_label_is_a_whole_line() {
+        assert!(is_qa_label("**FAQ:**"));
+        assert!(is_qa_label("Frequently asked questions"));
+        assert!(is_qa_label("Selected answers from Acme's original YC application"));
+        assert!(!is_qa_label("FAQ bots that answer support tickets for you."));
+        assert!(!is_qa_label("Selected questions are routed to an expert. We built the marketplace."));
+        assert!(!is_qa_label("Frequently asked questions get answered by our AI agent"));
+    }
+
+    #[test]
+    fn meta_cluster_threshold_is_configurable() {
+        let lexed = lex("Some intro\n\nFounded:2020\n\nBatch:Winter 2021\n");
+        let kinds = |sections: Vec<Section>| sections.into_iter().map(|s| s.kind).collect::<Vec<_>>();
+        assert_eq!(kinds(cluster_sections(&lexed)), vec!["header"]);
+        let config = ParserConfig { min_meta_cluster: 2, ..ParserConfig::default() };
+        assert_eq!(kinds(cluster_sections_with(&lexed, &config)), vec!["header", "footer_meta"]);
+    }
+
     #[test]
     fn unknown_sections_not_lost() {
-        let blocks = classify_lines("Random paragraph\nthat matches nothing");
-        let sections = cluster_sections(&blocks);
+        let lexed = lex("Random paragraph\nthat matches nothing");
+        let sections = cluster_sections(&lexed);
         assert!(!sections.is_empty());
     }
+
+    #[test]
+    fn sections_keep_line_spans() {
+        let md = "[Home](/)\n\n### Payments.\nWe build payments for every business on the internet.\n\n\
+                  Founders\nJane Doe\n[](https://linkedin.com/in/jane)\nFounder/CEO\nBuilt things.\n";
+        let lexed = lex(md);
+        let sections = cluster_sections(&lexed);
+        let desc = sections.iter().find(|s| s.kind == "description").unwrap();
+        assert_eq!(desc.span(), Some(Span { start_line: 3, end_line: 4 }));
+        let founders = sections.iter().find(|s| s.kind == "founders").unwrap();
+        let person = founders.blocks.iter().position(|b| matches!(b, Block::Person { .. })).unwrap();
+        assert_eq!(founders.block_span(person), Some(Span { start_line: 7, end_line: 10 }));
+        assert_eq!(founders.block_span(person).unwrap().to_string(), "7-10");
+    }
 }
@@ -0,0 +1,373 @@
+use std::fmt::Write as _;
+use std::fs::{self, File};
+use std::io::{BufWriter, Write};
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use rusqlite::types::ValueRef;
+use rusqlite::Connection;
+
+use crate::db;
+use crate::manifest;
+use crate::redact::{self, RedactionPolicy};
+use crate::report::csv_field;
+
+/// A table shipped in a public snapshot.
+pub struct Published {
+    pub table: &'static str,
+    /// Column holding the company slug, for `--slugs-file` scoping.
+    /// `news_global` has none and is scoped through `news.article_id`.
+    pub slug_column: Option<&'static str>,
+    pub about: &'static str,
+}
+
+/// Parents before children, so foreign keys resolve when loaded in order.
+pub const TABLES: &[Published] = &[
+    Published {
+        table: "companies",
+        slug_column: Some("slug"),
+        about: "One row per YC company page: name, batch, status, team size, location, socials. \
+                A renamed company is listed under its current slug only, here and in every table.",
+    },
+    Published {
+        table: "founders",
+        slug_column: Some("company_slug"),
+        about: "Founders listed on each company page, with title, bio and social profiles.",
+    },
+    Published {
+        table: "founder_experience",
+        slug_column: Some("company_slug"),
+        about: "Schools (kind = education) and previous employers (kind = employer) named in founder bios.",
+    },
+    Published {
+        table: "news",
+        slug_column: Some("company_slug"),
+        about: "News articles linked from company pages.",
+    },
+    Published {
+        table: "news_global",
+        slug_column: None,
+        about: "One row per news article across companies (news.article_id), with how many companies cite it.",
+    },
+    Published {
+        table: "funding_events",
+        slug_column: Some("company_slug"),
+        about: "Funding rounds (round, amount, currency, date) read from news titles.",
+    },
+    Published {
+        table: "company_jobs",
+        slug_column: Some("company_slug"),
+        about: "Open job listings with location, salary and experience.",
+    },
+    Published {
+        table: "company_links",
+        slug_column: Some("company_slug"),
+        about: "External links on company pages, classified by domain.",
+    },
+    Published {
+        table: "meeting_links",
+        slug_column: Some("company_slug"),
+        about: "Scheduling links (Calendly, Cal.com, ...) found on company pages.",
+    },
+    Published {
+        table: "company_qa",
+        slug_column: Some("company_slug"),
+        about: "Question/answer pairs from \"Selected answers\" and FAQ sections.",
+    },
+    Published {
+        table: "company_locations",
+        slug_column: Some("company_slug"),
+        about: "Additional footer locations beyond companies.location (e.g. a second office).",
+    },
+    Published {
+        table: "company_references",
+        slug_column: Some("from_slug"),
+        about: "Links from a company page to other YC company pages (from_slug -> to_slug).",
+    },
+    Published {
+        table: "partners",
+        slug_column: None,
+        about: "YC group partners.",
+    },
+    Published {
+        table: "company_partners",
+        slug_column: Some("company_slug"),
+        about: "Company to partner matches: the primary partner, else the group partner.",
+    },
+    Published {
+        table: "tag_catalog",
+        slug_column: None,
+        about: "Canonical YC industry tags with the description from each tag's landing page.",
+    },
+];
+
+/// Pipeline-internal columns left out of the CSVs.
+pub const INTERNAL_COLUMNS: &[(&str, &str)] = &[("founders", "name_key"), ("tag_catalog", "tag_key")];
+
+const EXAMPLE_WIDTH: usize = 40;
+
+pub struct TableSummary {
+    pub table: &'static str,
+    pub file: String,
+    pub rows: usize,
+    pub bytes: u64,
+}
+
+struct ColumnSummary {
+    info: db::ColumnInfo,
+    non_null: usize,
+    example: Option<String>,
+}
+
+/// Write CSVs, DATA_DICTIONARY.md, LICENSE and manifest.json (with the
+/// latest run manifest) into `out`.
+/// With a redaction `policy`, the dictionary lists the columns as shipped.
+pub fn write_bundle(
+    conn: &Connection,
+    out: &Path,
+    license: &str,
+    policy: Option<&RedactionPolicy>,
+) -> Result<Vec<TableSummary>> {
+    fs::create_dir_all(out).with_context(|| format!("creating {}", out.display()))?;
+    let generated_at = chrono::Utc::now().format("%Y-%m-%dT%H:%M:%SZ").to_string();
+
+    let mut summaries = Vec::with_capacity(TABLES.len());
+    let mut dictionary = String::new();
+    for t in TABLES {
+        let (summary, columns) = export_table(conn, out, t, policy)?;
+        write_dictionary_entry(&mut dictionary, t, &summary, &columns);
+        summaries.push(summary);
+    }
+
+    fs::write(
+        out.join("DATA_DICTIONARY.md"),
+        format!(
+            "# Data dictionary\n\nGenerated {} from the SQLite schema. Empty CSV cells are NULL.\n{}",
+            generated_at, dictionary
+        ),
+    )?;
+    fs::write(out.join("LICENSE"), license_stub(license))?;
+
+    // The run that last changed the data, so a snapshot can be traced back to a build
+    let run = db::fetch_latest_run_manifest(conn)?.as_ref().map(manifest::to_json);
+    let manifest = serde_json::json!({
+        "generated_at": generated_at,
+        "license": license,
+        "run": run,
+        "tables": summaries.iter().map(|s| serde_json::json!({
+            "table": s.table,
+            "file": s.file,
+            "rows": s.rows,
+            "bytes": s.bytes,
+        })).collect::<Vec<_>>(),
+    });
+    fs::write(out.join("manifest.json"), serde_json::to_string_pretty(&manifest)? + "\n")?;
+    Ok(summaries)
+}
+
+fn export_table(
+    conn: &Connection,
+    out: &Path,
+    t: &Published,
+    policy: Option<&RedactionPolicy>,
+) -> Result<(TableSummary, Vec<ColumnSummary>)> {
+    let (mut columns, actions): (Vec<ColumnSummary>, Vec<redact::Action>) =
+        redact::table_columns(conn, t.table, policy)?
+            .into_iter()
+            .filter(|(c, _)| !INTERNAL_COLUMNS.contains(&(t.table, c.name.as_str())))
+            .map(|(info, action)| (ColumnSummary { info, non_null: 0, example: None }, action))
+            .unzip();
+    // Owned so the row callback can update `columns` while the names are borrowed.
+    let names: Vec<String> = columns.iter().map(|c| c.info.name.clone()).collect();
+    let names: Vec<&str> = names.iter().map(String::as_str).collect();
+
+    let file = format!("{}.csv", t.table);
+    let path = out.join(&file);
+    let mut w = BufWriter::new(
+        File::create(&path).with_context(|| format!("creating {}", path.display()))?,
+    );
+    writeln!(w, "{}", names.iter().map(|n| csv_field(n)).collect::<Vec<_>>().join(","))?;
+
+    let mut line = Vec::with_capacity(names.len());
+    let rows = db::for_each_row(conn, t.table, &names, t.slug_column, |row| {
+        line.clear();
+        for (i, col) in columns.iter_mut().enumerate() {
+            let value = row.get_ref(i)?;
+            let redacted = policy.and_then(|p| p.apply(actions[i], value));
+            let cell = render(redacted.as_ref().map_or(value, ValueRef::from));
+            if let Some(v) = &cell {
+                col.non_null += 1;
+                if col.example.is_none() && !v.trim().is_empty() {
+                    col.example = Some(v.clone());
+                }
+            }
+            line.push(csv_field(cell.as_deref().unwrap_or("")));
+        }
+        writeln!(w, "{}", line.join(","))?;
+        Ok(())
+    })?;
+    w.flush()?;
+    drop(w);
+
+    let bytes = fs::metadata(&path)?.len();
+    Ok((TableSummary { table: t.table, file, rows, bytes }, columns))
+}
+
+/// CSV text for one value; `None` for NULL.
+fn render(v: ValueRef) -> Option<String> {
+    match v {
+        ValueRef::Null => None,
+        ValueRef::Integer(i) => Some(i.to_string()),
+        ValueRef::Real(f) => Some(f.to_string()),
+        ValueRef::Text(t) => Some(String::from_utf8_lossy(t).into_owned()),
+        ValueRef::Blob(b) => Some(b.iter().map(|x| format!("{:02x}", x)).collect()),
+    }
+}
+
+fn write_dictionary_entry(
+    out: &mut String,
+    t: &Published,
+    summary: &TableSummary,
+    columns: &[ColumnSummary],
+) {
+    let _ = write!(
+        out,
+        "\n## {}\n\n`{}` · {} rows. {}\n\n| Column | Type | Nullable | Filled | Example |\n|---|---|---|---|---|\n",
+        t.table, summary.file, summary.rows, t.about
+    );
+    for c in columns {
+        let key = if c.info.primary_key { " (key)" } else { "" };
+        let filled = if summary.rows == 0 {
+            "—".to_string()
+        } else {
+            format!("{:.0}%", c.non_null as f64 * 100.0 / summary.rows as f64)
+        };
+        let example = c
+            .example
+            .as_deref()
+            .map(|e| format!("`{}`", md_cell(e)))
+            .unwrap_or_default();
+        let _ = writeln!(
+            out,
+            "| `{}`{} | {} | {} | {} | {} |",
+            c.info.name,
+            key,
+            if c.info.decl_type.is_empty() { "ANY" } else { &c.info.decl_type },
+            if c.info.not_null || c.info.primary_key { "no" } else { "yes" },
+            filled,
+            example
+        );
+    }
+}
+
+/// First line of `s`, shortened and made safe for a markdown table cell.
+fn md_cell(s: &str) -> String {
+    let line = s.lines().next().unwrap_or("").trim();
+    let mut cell: String = line.chars().take(EXAMPLE_WIDTH).collect();
+    if line.chars().count() > EXAMPLE_WIDTH {
+        cell.push('…');
+    }
+    cell.replace('|', "\\|").replace('`', "'")
+}
+
+fn license_stub(license: &str) -> String {
+    format!(
+        "SPDX-License-Identifier: {}\n\n\
+         TODO: replace this stub with the full license text before publishing.\n\n\
+         The data is extracted from public company pages on ycombinator.com.\n\
+         Company names, descriptions and founder profiles remain the property of\n\
+         their respective owners; the license above covers the compilation.\n",
+        license
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bundle_has_csvs_dictionary_and_manifest() {
+        let conn = Connection::open_in_memory().unwrap();
+        db::init_schema(&conn).unwrap();
+        conn.execute_batch(
+            "INSERT INTO companies (slug, url, name, tagline, status)
+                 VALUES ('acme', 'https://x/acme', 'Acme, Inc.', 'Line one
+line two', 'Active');
+             INSERT INTO founders (company_slug, name, name_key) VALUES ('acme', 'Jane Doe', 'jane doe');",
+        )
+        .unwrap();
+        let out = std::env::temp_dir().join(format!("yc-publish-test-{}", std::process::id()));
+        let summaries = write_bundle(&conn, &out, "CC-BY-4.0", None).unwrap();
+
+        let companies = summaries.iter().find(|s| s.table == "companies").unwrap();
+        assert_eq!(companies.rows, 1);
+        let csv = fs::read_to_string(out.join("companies.csv")).unwrap();
+        assert!(csv.starts_with("slug,url,name,tagline,"), "{}", csv);
+        assert!(csv.contains("\"Acme, Inc.\",\"Line one\nline two\""), "{}", csv);
+        assert!(csv.contains(",1,"), "generated is_active column exported: {}", csv);
+
+        let founders = fs::read_to_string(out.join("founders.csv")).unwrap();
+        assert!(!founders.contains("name_key"), "{}", founders);
+
+        let dict = fs::read_to_string(out.join("DATA_DICTIONARY.md")).unwrap();
+        assert!(dict.contains("| `slug` (key) | TEXT | no | 100% | `acme` |"), "{}", dict);
+        assert!(dict.contains("| `tagline` | TEXT | yes | 100% | `Line one` |"), "{}", dict);
+
+        let manifest: serde_json::Value =
+            serde_json::from_str(&fs::read_to_string(out.join("manifest.json")).unwrap()).unwrap();
+        assert_eq!(manifest["tables"].as_array().unwrap().len(), TABLES.len());
+        assert_eq!(manifest["tables"][1]["rows"], 1);
+        assert!(manifest["run"].is_null());
+        assert!(fs::read_to_string(out.join("LICENSE")).unwrap().contains("CC-BY-4.0"));
+        fs::remove_dir_all(&out).unwrap();
+    }
+
+    #[test]
+    fn scoped_bundle_keeps_articles_of_scoped_companies() {
+        let conn = Connection::open_in_memory().unwrap();
+        db::init_schema(&conn).unwrap();
+        conn.execute_batch(
+            "INSERT INTO companies (slug, url) VALUES ('acme', 'u/acme'), ('beta', 'u/beta');
+             INSERT INTO news (company_slug, title, url, article_id)
+                 VALUES ('acme', 'A', 'https://t.co/a', 'a'), ('beta', 'A', 'https://t.co/a', 'a'),
+                        ('beta', 'B', 'https://t.co/b', 'b');",
+        )
+        .unwrap();
+        db::rebuild_news_global(&conn).unwrap();
+        db::set_slug_scope(&conn, &["acme".to_string()]).unwrap();
+        let out = std::env::temp_dir().join(format!("yc-publish-scope-test-{}", std::process::id()));
+        let summaries = write_bundle(&conn, &out, "CC-BY-4.0", None).unwrap();
+        let articles = fs::read_to_string(out.join("news_global.csv")).unwrap();
+        fs::remove_dir_all(&out).unwrap();
+
+        assert_eq!(summaries.iter().find(|s| s.table == "news_global").unwrap().rows, 1);
+        assert!(articles.contains("https://t.co/a") && !articles.contains("https://t.co/b"), "{}", articles);
+    }
+
+    #[test]
+    fn redacted_bundle_hashes_and_drops_contact_details() {
+        let conn = Connection::open_in_memory().unwrap();
+        db::init_schema(&conn).unwrap();
+        conn.execute_batch(
+            "INSERT INTO companies (slug, url, name) VALUES ('acme', 'https://x/acme', 'Acme');
+             INSERT INTO founders (company_slug, name, name_key, bio, twitter)
+                 VALUES ('acme', 'Jane Doe', 'jane doe', 'Email jane@acme.io to chat.', 'https://x.com/jane');
+             INSERT INTO meeting_links (company_slug, url, domain, link_type)
+                 VALUES ('acme', 'https://calendly.com/jane', 'calendly.com', 'calendly');",
+        )
+        .unwrap();
+        let policy: RedactionPolicy = toml::from_str("[columns]\n\"founders.twitter\" = \"drop\"\n").unwrap();
+        let out = std::env::temp_dir().join(format!("yc-publish-redact-test-{}", std::process::id()));
+        write_bundle(&conn, &out, "CC-BY-4.0", Some(&policy)).unwrap();
+
+        let founders = fs::read_to_string(out.join("founders.csv")).unwrap();
+        assert!(!founders.contains("twitter") && !founders.contains("jane@"), "{}", founders);
+        assert!(founders.contains("Email [email "), "{}", founders);
+        let meetings = fs::read_to_string(out.join("meeting_links.csv")).unwrap();
+        assert!(!meetings.contains("calendly.com/jane"), "{}", meetings);
+        let dict = fs::read_to_string(out.join("DATA_DICTIONARY.md")).unwrap();
+        let founders_entry = dict.split("\n## founders\n").nth(1).unwrap().split("\n## ").next().unwrap();
+        assert!(!founders_entry.contains("`twitter`"), "{}", founders_entry);
+        fs::remove_dir_all(&out).unwrap();
+    }
+}
@@ -0,0 +1,130 @@
+use anyhow::Result;
+use rusqlite::types::Value;
+use rusqlite::{Connection, Row};
+
+/// A SELECT put together from fixed SQL fragments. Values that come from
+/// flags or callers (filter values, LIMIT) are bound as parameters, never
+/// formatted into the SQL, so filters compose without quoting concerns.
+///
+/// Fragments use anonymous `?` placeholders, bound in the order the
+/// fragments were added.
+pub struct Select {
+    head: String,
+    conditions: Vec<String>,
+    group_by: Option<&'static str>,
+    order_by: Option<&'static str>,
+    limit: Option<i64>,
+    params: Vec<Value>,
+}
+
+impl Select {
+    /// `head` is the query up to its WHERE clause: `SELECT id, url FROM pages`.
+    pub fn new(head: &str) -> Self {
+        Select {
+            head: head.to_string(),
+            conditions: Vec::new(),
+            group_by: None,
+            order_by: None,
+            limit: None,
+            params: Vec::new(),
+        }
+    }
+
+    /// AND a condition without parameters.
+    pub fn filter(mut self, condition: impl Into<String>) -> Self {
+        self.conditions.push(condition.into());
+        self
+    }
+
+    /// AND a condition whose single `?` is `value`.
+    pub fn filter_with(mut self, condition: &str, value: impl Into<Value>) -> Self {
+        self.conditions.push(condition.to_string());
+        self.params.push(value.into());
+        self
+    }
+
+    /// AND `column IN (?, ...)`. An empty list adds no condition.
+    pub fn any_of<V: Into<Value>>(mut self, column: &str, values: impl IntoIterator<Item = V>) -> Self {
+        let before = self.params.len();
+        self.params.extend(values.into_iter().map(Into::into));
+        let n = self.params.len() - before;
+        if n > 0 {
+            self.conditions.push(format!("{} IN ({})", column, vec!["?"; n].join(", ")));
+        }
+        self
+    }
+
+    pub fn group_by(mut self, columns: &'static str) -> Self {
+        self.group_by = Some(columns);
+        self
+    }
+
+    pub fn order_by(mut self, order: &'static str) -> Self {
+        self.order_by = Some(order);
+        self
+    }
+
+    /// No LIMIT for `None`.
+    pub fn limit(mut self, limit: Option<usize>) -> Self {
+        self.limit = limit.map(|n| n as i64);
+        self
+    }
+
+    pub fn sql(&self) -> String {
+        let mut sql = self.head.clone();
+        if !self.conditions.is_empty() {
+            sql.push_str(" WHERE ");
+            sql.push_str(&self.conditions.join(" AND "));
+        }
+        if let Some(group) = self.group_by {
+            sql.push_str(" GROUP BY ");
+            sql.push_str(group);
+        }
+        if let Some(order) = self.order_by {
+            sql.push_str(" ORDER BY ");
+            sql.push_str(order);
+        }
+        if self.limit.is_some() {
+            sql.push_str(" LIMIT ?");
+        }
+        sql
+    }
+
+    /// Run the query and map every row.
+    pub fn query<T>(&self, conn: &Connection, f: impl FnMut(&Row) -> rusqlite::Result<T>) -> Result<Vec<T>> {
+        let mut stmt = conn.prepare(&self.sql())?;
+        let limit = self.limit.map(Value::Integer);
+        let params = self.params.iter().chain(limit.as_ref());
+        let rows = stmt
+            .query_map(rusqlite::params_from_iter(params), f)?
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(rows)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn conditions_and_limit_are_bound() {
+        let conn = Connection::open_in_memory().unwrap();
+        conn.execute_batch(
+            "CREATE TABLE t (id INTEGER PRIMARY KEY, name TEXT, batch TEXT);
+             INSERT INTO t (name, batch) VALUES ('a', 'W24'), ('b', 'S24'), ('c', 'W24'), ('d''; DROP TABLE t; --', 'W24');",
+        )
+        .unwrap();
+        let select = Select::new("SELECT name FROM t")
+            .any_of("batch", ["W24", "X' OR 1=1 --"].map(String::from))
+            .filter_with("id > ?", 1)
+            .any_of("name", Vec::<String>::new())
+            .order_by("id")
+            .limit(Some(5));
+        assert_eq!(select.sql(), "SELECT name FROM t WHERE batch IN (?, ?) AND id > ? ORDER BY id LIMIT ?");
+        let names = select.query(&conn, |r| r.get::<_, String>(0)).unwrap();
+        assert_eq!(names, ["c", "d'; DROP TABLE t; --"]);
+
+        let all = Select::new("SELECT COUNT(*) FROM t").filter("1").group_by("batch").order_by("1").limit(None);
+        assert_eq!(all.query(&conn, |r| r.get::<_, i64>(0)).unwrap(), [1, 3]);
+    }
+}
@@ -0,0 +1,157 @@
+use std::collections::BTreeMap;
+use std::sync::LazyLock;
+
+use anyhow::Result;
+use regex::{Captures, Regex};
+use rusqlite::types::{Value, ValueRef};
+use rusqlite::Connection;
+use serde::Deserialize;
+use sha2::{Digest, Sha256};
+
+use crate::db::{self, ColumnInfo};
+use crate::parser::extract::meetings::classify_meeting_url;
+
+/// What happens to one exported column under `--redact`.
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Action {
+    Keep,
+    /// Left out of the export entirely.
+    Drop,
+    /// Replaced by a salted hash, so equal values still join.
+    Hash,
+    /// Emails, phone numbers and booking links inside the text replaced by
+    /// hashed placeholders; the rest kept.
+    Scrub,
+}
+
+/// Built-in rules; `[redact.columns]` entries override them per column.
+const DEFAULT_COLUMNS: &[(&str, Action)] = &[
+    // Every row is a booking link, most of them a founder's personal one
+    ("meeting_links.url", Action::Hash),
+];
+
+/// `[redact]` in `yc.toml`: how `export --redact` and `publish-prep
+/// --redact` treat contact details picked up from company pages.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields, default)]
+pub struct RedactionPolicy {
+    /// `"table.column" = "keep" | "drop" | "hash" | "scrub"`.
+    pub columns: BTreeMap<String, Action>,
+    /// Scrub every text column without a rule of its own.
+    pub scrub_text: bool,
+    /// Mixed into every hash so a published hash can't be confirmed by
+    /// hashing a guessed address. Set it before a public release.
+    pub salt: String,
+}
+
+impl Default for RedactionPolicy {
+    fn default() -> Self {
+        Self { columns: BTreeMap::new(), scrub_text: true, salt: String::new() }
+    }
+}
+
+static EMAIL_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"(?i)(?:mailto:)?[a-z0-9._%+-]+@[a-z0-9-]+(?:\.[a-z0-9-]+)*\.[a-z]{2,}").unwrap());
+
+/// "+1 415 555 0100", "(415) 555-0100", "020 7946 0958". A four-digit
+/// last group keeps dates and money ranges out.
+static PHONE_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?:(?:tel:)?\+\d{1,3}[\s.-]?)?(?:\(\d{2,4}\)\s?|\b\d{2,4}[\s.-])\d{3,4}[\s.-]\d{4}\b").unwrap()
+});
+
+static URL_RE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r#"https?://[^\s)\]>"']*[^\s)\]>"'.,;:!?]"#).unwrap());
+
+impl RedactionPolicy {
+    pub fn action(&self, table: &str, column: &str) -> Action {
+        let key = format!("{}.{}", table, column);
+        self.columns
+            .get(&key)
+            .copied()
+            .or_else(|| DEFAULT_COLUMNS.iter().find(|(k, _)| *k == key).map(|(_, a)| *a))
+            .unwrap_or(if self.scrub_text { Action::Scrub } else { Action::Keep })
+    }
+
+    /// The redacted value, or `None` when `v` goes out unchanged.
+    pub fn apply(&self, action: Action, v: ValueRef) -> Option<Value> {
+        let text = match v {
+            ValueRef::Text(t) => String::from_utf8_lossy(t),
+            ValueRef::Integer(i) if action == Action::Hash => i.to_string().into(),
+            _ => return None,
+        };
+        match action {
+            Action::Hash => Some(Value::Text(self.hash(&text))),
+            Action::Scrub => {
+                let scrubbed = self.scrub(&text);
+                (scrubbed != text).then_some(Value::Text(scrubbed))
+            }
+            Action::Keep | Action::Drop => None,
+        }
+    }
+
+    fn scrub(&self, text: &str) -> String {
+        let placeholder = |kind: &str, found: &str| format!("[{} {}]", kind, self.hash(found));
+        let text = URL_RE.replace_all(text, |c: &Captures| match classify_meeting_url(&c[0]) {
+            Some(_) => placeholder("booking link", &c[0]),
+            None => c[0].to_string(),
+        });
+        let text = EMAIL_RE.replace_all(&text, |c: &Captures| placeholder("email", &c[0]));
+        PHONE_RE.replace_all(&text, |c: &Captures| placeholder("phone", &c[0])).into_owned()
+    }
+
+    /// First 16 hex digits of SHA-256(salt, value).
+    fn hash(&self, value: &str) -> String {
+        let digest = Sha256::new().chain_update(&self.salt).chain_update([0u8]).chain_update(value).finalize();
+        digest[..8].iter().map(|b| format!("{:02x}", b)).collect()
+    }
+}
+
+/// Columns of `table` an export writes, each with what the policy does to
+/// it; dropped columns are left out. Without a policy every column is kept.
+pub fn table_columns(
+    conn: &Connection,
+    table: &str,
+    policy: Option<&RedactionPolicy>,
+) -> Result<Vec<(ColumnInfo, Action)>> {
+    Ok(db::table_columns(conn, table)?
+        .into_iter()
+        .map(|c| {
+            let action = policy.map_or(Action::Keep, |p| p.action(table, &c.name));
+            (c, action)
+        })
+        .filter(|(_, action)| *action != Action::Drop)
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hashes_drops_and_scrubs_contact_details() {
+        let mut policy: RedactionPolicy =
+            toml::from_str("salt = \"s\"\n[columns]\n\"founders.bio\" = \"drop\"\n").unwrap();
+        assert_eq!(policy.action("founders", "bio"), Action::Drop);
+        assert_eq!(policy.action("meeting_links", "url"), Action::Hash);
+        assert_eq!(policy.action("companies", "tagline"), Action::Scrub);
+
+        let url = ValueRef::Text(b"https://calendly.com/jane-doe/30min");
+        let Some(Value::Text(hashed)) = policy.apply(Action::Hash, url) else { panic!() };
+        assert_eq!(hashed.len(), 16);
+        assert_eq!(policy.apply(Action::Hash, url), Some(Value::Text(hashed.clone())));
+
+        let bio = ValueRef::Text(
+            b"Reach me at jane@acme.io or +1 (415) 555-0100, or book https://calendly.com/jane-doe/30min. \
+              Raised $1-2M in 2021-2022 across 150 000 users.",
+        );
+        let Some(Value::Text(scrubbed)) = policy.apply(Action::Scrub, bio) else { panic!() };
+        assert!(!scrubbed.contains("jane"), "{}", scrubbed);
+        assert!(!scrubbed.contains("555"), "{}", scrubbed);
+        assert!(scrubbed.contains(&format!("book [booking link {}].", hashed)), "{}", scrubbed);
+        assert!(scrubbed.ends_with("Raised $1-2M in 2021-2022 across 150 000 users."), "{}", scrubbed);
+        assert_eq!(policy.apply(Action::Scrub, ValueRef::Text(b"Payroll for teams")), None);
+
+        policy.salt = "other".into();
+        assert_ne!(policy.apply(Action::Hash, url), Some(Value::Text(hashed)));
+    }
+}
@@ -0,0 +1,1644 @@
+use std::cmp::Reverse;
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+
+use anyhow::Result;
+use rusqlite::Connection;
+
+use crate::db::{self, CohortRow, MeetingLinkStatus, OverviewRow, RepeatFounderRow, StaleRow};
+use crate::model::{Batch, CompanyStatus, Season};
+use crate::output::{self, out};
+use crate::parser::extract::quality::ScoreInputs;
+
+// ── Repeat founders ──
+
+/// A person matched to two or more YC companies.
+pub struct RepeatFounder {
+    pub name: String,
+    /// (company slug, batch), sorted by slug.
+    pub companies: Vec<(String, Option<String>)>,
+    pub match_methods: Vec<String>,
+}
+
+/// Repeat founders by the evidence behind them.
+pub struct RepeatFounders {
+    /// Matched on a LinkedIn or Twitter/X profile.
+    pub matched: Vec<RepeatFounder>,
+    /// Matched on name alone: likely the same person, but two founders can
+    /// share a name.
+    pub name_only: Vec<RepeatFounder>,
+}
+
+pub fn repeat_founders(conn: &Connection) -> Result<RepeatFounders> {
+    let edges = db::fetch_repeat_founders(conn)?;
+    Ok(split_repeat_founders(&edges))
+}
+
+/// Cluster profile matches into people, and name matches between founders
+/// no profile ties together into possible ones. A name match doesn't join
+/// a profile-matched person, so it can't pull in a namesake's company.
+fn split_repeat_founders(edges: &[RepeatFounderRow]) -> RepeatFounders {
+    let (profile, name): (Vec<RepeatFounderRow>, Vec<RepeatFounderRow>) =
+        edges.iter().cloned().partition(|e| e.match_method != "name");
+    let linked: HashSet<(i64, i64)> = profile.iter().map(|e| (e.founder_id, e.other_founder_id)).collect();
+    let name: Vec<RepeatFounderRow> =
+        name.into_iter().filter(|e| !linked.contains(&(e.founder_id, e.other_founder_id))).collect();
+    RepeatFounders { matched: cluster_repeat_founders(&profile), name_only: cluster_repeat_founders(&name) }
+}
+
+/// Union the pairwise matches from the `repeat_founders` view into people,
+/// so A↔B and B↔C report as one founder with three companies.
+fn cluster_repeat_founders(edges: &[RepeatFounderRow]) -> Vec<RepeatFounder> {
+    let mut parent: HashMap<i64, i64> = HashMap::new();
+
+    fn find(parent: &mut HashMap<i64, i64>, id: i64) -> i64 {
+        let p = *parent.entry(id).or_insert(id);
+        if p == id {
+            return id;
+        }
+        let root = find(parent, p);
+        parent.insert(id, root);
+        root
+    }
+
+    for e in edges {
+        let a = find(&mut parent, e.founder_id);
+        let b = find(&mut parent, e.other_founder_id);
+        if a != b {
+            parent.insert(a.max(b), a.min(b));
+        }
+    }
+
+    #[derive(Default)]
+    struct Cluster {
+        names: BTreeMap<String, usize>,
+        companies: BTreeMap<String, Option<String>>,
+        methods: BTreeSet<String>,
+    }
+
+    let mut clusters: BTreeMap<i64, Cluster> = BTreeMap::new();
+    for e in edges {
+        let root = find(&mut parent, e.founder_id);
+        let c = clusters.entry(root).or_default();
+        *c.names.entry(e.name.clone()).or_default() += 1;
+        c.companies.insert(e.company_slug.clone(), e.batch.clone());
+        c.companies
+            .insert(e.other_company_slug.clone(), e.other_batch.clone());
+        c.methods.insert(e.match_method.clone());
+    }
+
+    let mut founders: Vec<RepeatFounder> = clusters
+        .into_values()
+        .filter(|c| c.companies.len() >= 2)
+        .map(|c| RepeatFounder {
+            // Most frequent spelling wins; BTreeMap order breaks ties.
+            name: c
+                .names
+                .iter()
+                .max_by(|a, b| a.1.cmp(b.1).then(b.0.cmp(a.0)))
+                .map(|(n, _)| n.clone())
+                .unwrap_or_default(),
+            companies: c.companies.into_iter().collect(),
+            match_methods: c.methods.into_iter().collect(),
+        })
+        .collect();
+
+    founders.sort_by(|a, b| {
+        b.companies
+            .len()
+            .cmp(&a.companies.len())
+            .then_with(|| a.name.cmp(&b.name))
+    });
+    founders
+}
+
+pub fn print_repeat_founders(founders: &RepeatFounders) {
+    out!("── Repeat founders (2+ YC companies) ──");
+    if founders.matched.is_empty() {
+        out!("  None found.");
+    } else {
+        print_founder_companies(&founders.matched);
+        out!("\n{} repeat founders across {} companies", founders.matched.len(), company_count(&founders.matched));
+    }
+    if !founders.name_only.is_empty() {
+        out!("\n── Possible repeat founders (same name, no shared profile) ──");
+        print_founder_companies(&founders.name_only);
+        out!(
+            "\n{} possible repeat founders across {} companies",
+            founders.name_only.len(),
+            company_count(&founders.name_only)
+        );
+    }
+}
+
+fn company_count(founders: &[RepeatFounder]) -> usize {
+    founders.iter().flat_map(|f| f.companies.iter().map(|(s, _)| s.as_str())).collect::<BTreeSet<_>>().len()
+}
+
+fn print_founder_companies(founders: &[RepeatFounder]) {
+    for f in founders {
+        let companies: Vec<String> = f
+            .companies
+            .iter()
+            .map(|(slug, batch)| match batch {
+                Some(b) => format!("{} ({})", slug, b),
+                None => slug.clone(),
+            })
+            .collect();
+        out!(
+            "  {:<28} {} companies: {}  [{}]",
+            f.name,
+            f.companies.len(),
+            companies.join(", "),
+            f.match_methods.join(",")
+        );
+    }
+}
+
+// ── Domain health ──
+
+/// Domains linked by fewer companies are too noisy to rank.
+const MIN_DOMAIN_COMPANIES: usize = 3;
+
+pub fn print_domain_health(conn: &Connection) -> Result<()> {
+    out!("── Domains most associated with inactive companies ──");
+    let rows = db::fetch_domain_death_report(conn, MIN_DOMAIN_COMPANIES, 25)?;
+    if rows.is_empty() {
+        out!(
+            "  No checked domains shared by {}+ companies. Run 'check-links' first.",
+            MIN_DOMAIN_COMPANIES
+        );
+        return Ok(());
+    }
+    out!(
+        "  {:<28} | {:>9} | {:>8} | {:>6} | {:>7} | {:>8}",
+        "Domain", "Companies", "Inactive", "Dead%", "Link OK", "Mean ms"
+    );
+    out!("  {}", "-".repeat(82));
+    for r in &rows {
+        let latency = r
+            .mean_latency_ms
+            .map(|l| format!("{:.0}", l))
+            .unwrap_or_else(|| "-".into());
+        out!(
+            "  {:<28} | {:>9} | {:>8} | {:>5.1}% | {:>6.1}% | {:>8}",
+            r.base_domain,
+            r.companies,
+            r.inactive,
+            r.inactive as f64 * 100.0 / r.companies as f64,
+            r.success_rate * 100.0,
+            latency
+        );
+    }
+    let checks: i64 = rows.iter().map(|r| r.checks).sum();
+    out!("\n{} domains shown ({} link checks)", rows.len(), checks);
+    Ok(())
+}
+
+// ── Meeting links ──
+
+/// Meeting links aggregated by platform or batch.
+pub struct MeetingGroup {
+    pub label: String,
+    pub links: usize,
+    pub companies: usize,
+    /// Links with a liveness check; `dead` counts the failed ones.
+    pub checked: usize,
+    pub dead: usize,
+}
+
+/// Group meeting links under `key` (sort key, label), ordered by sort key.
+fn group_meeting_links<K: Ord>(
+    rows: &[MeetingLinkStatus],
+    key: impl Fn(&MeetingLinkStatus) -> (K, String),
+) -> Vec<MeetingGroup> {
+    let mut groups: BTreeMap<K, (String, usize, BTreeSet<&str>, usize, usize)> = BTreeMap::new();
+    for r in rows {
+        let (k, label) = key(r);
+        let g = groups.entry(k).or_insert_with(|| (label, 0, BTreeSet::new(), 0, 0));
+        g.1 += 1;
+        g.2.insert(&r.company_slug);
+        if let Some(live) = r.live {
+            g.3 += 1;
+            if !live {
+                g.4 += 1;
+            }
+        }
+    }
+    groups
+        .into_values()
+        .map(|(label, links, companies, checked, dead)| MeetingGroup {
+            label,
+            links,
+            companies: companies.len(),
+            checked,
+            dead,
+        })
+        .collect()
+}
+
+/// Platforms by link count, most used first.
+pub fn meeting_platforms(rows: &[MeetingLinkStatus]) -> Vec<MeetingGroup> {
+    let mut groups = group_meeting_links(rows, |r| (r.link_type.clone(), r.link_type.clone()));
+    groups.sort_by(|a, b| b.links.cmp(&a.links).then_with(|| a.label.cmp(&b.label)));
+    groups
+}
+
+/// Batches newest first, unknown batches last.
+pub fn meeting_batches(rows: &[MeetingLinkStatus]) -> Vec<MeetingGroup> {
+    group_meeting_links(rows, |r| batch_order(r.batch.as_deref()))
+}
+
+fn dead_share(g: &MeetingGroup) -> String {
+    if g.checked == 0 {
+        "-".to_string()
+    } else {
+        format!("{:.1}%", percent(g.dead, g.checked))
+    }
+}
+
+/// Platform and batch breakdown of `meeting_links`. `batch_sizes` are all
+/// companies per batch, for the share that publishes a booking link.
+pub fn print_meetings(rows: &[MeetingLinkStatus], batch_sizes: &[(Option<String>, usize)]) {
+    out!("── Meeting links by platform ──");
+    out!(
+        "  {:<12} | {:>6} | {:>9} | {:>7} | {:>5} | {:>6}",
+        "Platform", "Links", "Companies", "Checked", "Dead", "Dead%"
+    );
+    out!("  {}", "-".repeat(62));
+    for g in meeting_platforms(rows) {
+        out!(
+            "  {:<12} | {:>6} | {:>9} | {:>7} | {:>5} | {:>6}",
+            g.label,
+            g.links,
+            g.companies,
+            g.checked,
+            g.dead,
+            dead_share(&g)
+        );
+    }
+
+    let mut sizes: HashMap<String, usize> = HashMap::new();
+    for (batch, n) in batch_sizes {
+        *sizes.entry(batch_order(batch.as_deref()).1).or_default() += n;
+    }
+    out!("\n── Meeting links by batch ──");
+    out!(
+        "  {:<14} | {:>9} | {:>6} | {:>6} | {:>7} | {:>5} | {:>6}",
+        "Batch", "Companies", "Share", "Links", "Checked", "Dead", "Dead%"
+    );
+    out!("  {}", "-".repeat(74));
+    for g in meeting_batches(rows) {
+        let share = sizes.get(&g.label).map(|&n| format!("{:.1}%", percent(g.companies, n)));
+        out!(
+            "  {:<14} | {:>9} | {:>6} | {:>6} | {:>7} | {:>5} | {:>6}",
+            g.label,
+            g.companies,
+            share.unwrap_or_else(|| "-".into()),
+            g.links,
+            g.checked,
+            g.dead,
+            dead_share(&g)
+        );
+    }
+
+    let checked = rows.iter().filter(|r| r.live.is_some()).count();
+    let dead = rows.iter().filter(|r| r.live == Some(false)).count();
+    out!(
+        "\n{} meeting links at {} companies; {} checked, {} dead.",
+        rows.len(),
+        rows.iter().map(|r| r.company_slug.as_str()).collect::<BTreeSet<_>>().len(),
+        checked,
+        dead
+    );
+    if checked < rows.len() {
+        out!("Run 'meetings report --check' to check the remaining {}.", rows.len() - checked);
+    }
+}
+
+// ── Staleness ──
+
+/// Slugs listed per batch before eliding the rest.
+const STALE_LIST_PER_BATCH: usize = 8;
+
+/// (batch label, rows in that batch)
+type StaleGroup<'a> = (String, Vec<&'a StaleRow>);
+
+/// Group stale rows by batch, newest batch first; unknown batches last.
+fn group_stale_by_batch(rows: &[StaleRow]) -> Vec<StaleGroup<'_>> {
+    let mut groups: BTreeMap<BatchOrder, StaleGroup> = BTreeMap::new();
+    for r in rows {
+        let (key, label) = batch_order(r.batch.as_deref());
+        groups.entry(key).or_insert_with(|| (label, Vec::new())).1.push(r);
+    }
+    groups.into_values().collect()
+}
+
+/// Sorts newest batch first, then batch strings that don't parse (each
+/// its own group, by text), then companies without a batch.
+#[derive(PartialEq, Eq, PartialOrd, Ord)]
+enum BatchOrder {
+    Known(Reverse<i32>),
+    Unparsed(String),
+    Missing,
+}
+
+/// Sort key and display label for a stored batch string.
+fn batch_order(batch: Option<&str>) -> (BatchOrder, String) {
+    match (batch, batch.and_then(|b| b.parse::<Batch>().ok())) {
+        (_, Some(b)) => (BatchOrder::Known(Reverse(b.ordinal())), b.to_string()),
+        (Some(raw), None) => (BatchOrder::Unparsed(raw.to_string()), raw.to_string()),
+        (None, None) => (BatchOrder::Missing, "Unknown batch".to_string()),
+    }
+}
+
+pub fn print_stale(rows: &[StaleRow], days: f64) {
+    out!("── Companies last scraped more than {} days ago ──", days);
+    if rows.is_empty() {
+        out!("  None.");
+        return;
+    }
+    for (batch, group) in group_stale_by_batch(rows) {
+        let oldest = group.iter().map(|r| r.age_days).fold(0.0, f64::max);
+        out!("\n  {} — {} companies (oldest {:.0}d)", batch, group.len(), oldest);
+        for r in group.iter().take(STALE_LIST_PER_BATCH) {
+            out!("    {:<32} {:>5.0}d  {}", r.slug, r.age_days, r.last_scraped);
+        }
+        if group.len() > STALE_LIST_PER_BATCH {
+            out!("    … {} more", group.len() - STALE_LIST_PER_BATCH);
+        }
+    }
+    out!("\n{} stale companies", rows.len());
+}
+
+// ── Extraction quality ──
+
+pub fn print_quality(conn: &Connection) -> Result<()> {
+    out!("── Extraction quality flags ──");
+    let rows = db::fetch_quality_flags(conn, 50)?;
+    if rows.is_empty() {
+        out!("  No flagged companies.");
+        return Ok(());
+    }
+    out!(
+        "  {:<16} | {:<28} | {:>8} | {:>6} | Detail",
+        "Check", "Company", "Expected", "Actual"
+    );
+    out!("  {}", "-".repeat(90));
+    for r in &rows {
+        out!(
+            "  {:<16} | {:<28} | {:>8} | {:>6} | {}",
+            r.check_name,
+            r.company_slug,
+            r.expected,
+            r.actual,
+            r.detail.as_deref().unwrap_or("")
+        );
+    }
+    Ok(())
+}
+
+/// Rows listed by `print_missing_founders`.
+const MISSING_FOUNDERS_SHOWN: usize = 50;
+
+pub fn print_missing_founders(conn: &Connection) -> Result<()> {
+    out!("── Companies without founders ──");
+    let rows = db::fetch_founderless_companies(conn)?;
+    let with_section = rows.iter().filter(|r| r.section_chars > 0).count();
+    output::count("founderless", rows.len());
+    output::count("founderless_with_section", with_section);
+    if rows.is_empty() {
+        out!("  Every processed company has founders.");
+        return Ok(());
+    }
+    out!(
+        "  {} companies, {} of them with a founders section nobody was found in.",
+        rows.len(),
+        with_section
+    );
+    out!("  {:<28} | {:<12} | {:<10} | {:>13}", "Company", "Batch", "Status", "Section chars");
+    out!("  {}", "-".repeat(72));
+    for r in rows.iter().take(MISSING_FOUNDERS_SHOWN) {
+        out!(
+            "  {:<28} | {:<12} | {:<10} | {:>13}",
+            r.slug,
+            r.batch.as_deref().unwrap_or("-"),
+            r.status.as_deref().unwrap_or("-"),
+            r.section_chars
+        );
+    }
+    if rows.len() > MISSING_FOUNDERS_SHOWN {
+        out!("  ... and {} more", rows.len() - MISSING_FOUNDERS_SHOWN);
+    }
+    Ok(())
+}
+
+// ── Coverage ──
+
+/// Lower bounds of the quality_score bands `report coverage` counts.
+const SCORE_BANDS: [i32; 4] = [0, 25, 50, 75];
+
+/// Companies per `SCORE_BANDS` band, and how many are not scored yet.
+pub fn score_bands(rows: &[db::ScoredCompany]) -> ([usize; 4], usize) {
+    let mut bands = [0; 4];
+    let mut unscored = 0;
+    for r in rows {
+        match r.quality_score {
+            Some(q) => bands[SCORE_BANDS.iter().rposition(|&lo| q >= lo).unwrap_or(0)] += 1,
+            None => unscored += 1,
+        }
+    }
+    (bands, unscored)
+}
+
+/// Lowest-scored companies listed under the score bands.
+const LOWEST_SCORED_SHOWN: usize = 10;
+
+pub fn print_coverage(rows: &[db::ScoredCompany]) {
+    let pct = |n: usize| n as f64 * 100.0 / rows.len() as f64;
+    let with = |has: fn(&ScoreInputs) -> bool| rows.iter().filter(|r| has(&r.inputs)).count();
+    let inputs = [
+        ("name", with(|i| i.name)),
+        ("batch", with(|i| i.batch)),
+        ("status", with(|i| i.status)),
+        ("founders", with(|i| i.founders > 0)),
+        ("socials", with(|i| i.socials > 0)),
+        ("description", with(|i| i.description_chars > 0)),
+    ];
+    out!("── Coverage ({} companies) ──", rows.len());
+    for (label, n) in inputs {
+        output::count(label, n);
+        out!("  {:<12} {:>7}  {:>5.1}%", label, n, pct(n));
+    }
+
+    let (bands, unscored) = score_bands(rows);
+    let scores: Vec<i32> = rows.iter().filter_map(|r| r.quality_score).collect();
+    out!("\n── Quality score ──");
+    for (i, n) in bands.iter().enumerate() {
+        let hi = SCORE_BANDS.get(i + 1).map_or(100, |next| next - 1);
+        out!("  {:>3}-{:<3}      {:>7}  {:>5.1}%", SCORE_BANDS[i], hi, n, pct(*n));
+    }
+    if !scores.is_empty() {
+        out!("  Average {:.1}", scores.iter().sum::<i32>() as f64 / scores.len() as f64);
+    }
+    output::count("unscored", unscored);
+    if unscored > 0 {
+        out!("  {} companies not scored yet; run 'backfill'.", unscored);
+    }
+
+    let mut lowest: Vec<&db::ScoredCompany> = rows.iter().filter(|r| r.quality_score.is_some()).collect();
+    lowest.sort_by_key(|r| (r.quality_score, &r.slug));
+    if !lowest.is_empty() {
+        out!("\n── Lowest scored ──");
+        for r in lowest.iter().take(LOWEST_SCORED_SHOWN) {
+            out!(
+                "  {:>3}  {:<28} {}",
+                r.quality_score.unwrap_or(0),
+                r.slug,
+                r.batch.as_deref().unwrap_or("-")
+            );
+        }
+    }
+}
+
+// ── Jobs ──
+
+/// Buckets reported in the emoji breakdown, as in the v2 job stats.
+const EMOJI_BUCKETS: &[&str] = &["Sales", "Marketing", "Operations"];
+
+/// (label, needles) matched as whole words in engineering job titles.
+const STACK_KEYWORDS: &[(&str, &[&str])] = &[
+    ("python", &["python"]),
+    ("js", &["javascript", "typescript", "node", "react"]),
+    ("rust", &["rust"]),
+    ("assembly", &["assembly", "asm"]),
+    ("go", &["golang", "go"]),
+    ("scala", &["scala"]),
+    ("redis", &["redis"]),
+    ("postgres", &["postgres", "postgresql"]),
+    ("pytorch", &["pytorch"]),
+];
+
+const MEME_KEYWORDS: &[(&str, &[&str])] = &[
+    ("prompt engineer", &["prompt engineer", "prompt engineering"]),
+    ("openai", &["openai", "open ai"]),
+    ("gpt/llm", &["gpt", "llm", "llms"]),
+    ("vibe coding", &["vibe coding", "vibe coder", "vibe engineer"]),
+];
+
+/// Seniority words dropped before bucketing, so "Senior Sales Lead" reads as "sales lead".
+const SENIORITY: &[&str] = &[
+    "senior", "sr", "staff", "principal", "lead", "junior", "jr", "founding", "head of", "vp of",
+];
+
+/// v2 `stats.rs` analyses over v3 job listings. Keyword stats run on job
+/// titles, which every listing has; full descriptions only exist for jobs
+/// fetched with `scrape-jobs` (see `job_details`).
+pub struct JobStats {
+    pub total: usize,
+    pub emoji_jobs: usize,
+    pub by_bucket: Vec<(&'static str, usize)>,
+    /// (bucket, emoji jobs in bucket, % of all emoji jobs)
+    pub emoji_by_bucket: Vec<(&'static str, usize, f64)>,
+    pub engineering: usize,
+    pub stack: Vec<(&'static str, usize)>,
+    pub memes: Vec<(&'static str, usize)>,
+}
+
+pub fn job_stats(titles: &[String]) -> JobStats {
+    let jobs: Vec<(&str, bool, String)> = titles
+        .iter()
+        .map(|t| (role_bucket(t), t.chars().any(is_emoji), padded_words(t)))
+        .collect();
+
+    let mut by_bucket: BTreeMap<&str, usize> = BTreeMap::new();
+    for (bucket, ..) in &jobs {
+        *by_bucket.entry(bucket).or_default() += 1;
+    }
+    let mut by_bucket: Vec<(&str, usize)> = by_bucket.into_iter().collect();
+    by_bucket.sort_by_key(|&(b, n)| (Reverse(n), b));
+
+    let emoji_jobs = jobs.iter().filter(|(_, emoji, _)| *emoji).count();
+    let emoji_by_bucket = EMOJI_BUCKETS
+        .iter()
+        .map(|&b| {
+            let n = jobs.iter().filter(|(bucket, emoji, _)| *emoji && *bucket == b).count();
+            (b, n, percent(n, emoji_jobs))
+        })
+        .collect();
+
+    let engineering: Vec<&String> = jobs
+        .iter()
+        .filter(|(bucket, ..)| *bucket == "Engineering")
+        .map(|(_, _, words)| words)
+        .collect();
+    let mentions = |keywords: &[(&'static str, &[&str])]| -> Vec<(&'static str, usize)> {
+        keywords
+            .iter()
+            .map(|&(label, needles)| {
+                let n = engineering
+                    .iter()
+                    .filter(|words| needles.iter().any(|n| words.contains(&format!(" {} ", n))))
+                    .count();
+                (label, n)
+            })
+            .collect()
+    };
+
+    JobStats {
+        total: jobs.len(),
+        emoji_jobs,
+        by_bucket,
+        emoji_by_bucket,
+        engineering: engineering.len(),
+        stack: mentions(STACK_KEYWORDS),
+        memes: mentions(MEME_KEYWORDS),
+    }
+}
+
+/// v2's role buckets, applied to a job title with seniority words removed.
+fn role_bucket(title: &str) -> &'static str {
+    let mut lower = padded_words(title);
+    for word in SENIORITY {
+        lower = lower.replace(&format!(" {} ", word), " ");
+    }
+    let lower = lower.trim();
+    let starts = |prefixes: &[&str]| prefixes.iter().any(|p| lower.starts_with(p));
+
+    if starts(&[
+        "engineer", "software", "developer", "devops", "data eng", "ml engineer",
+        "machine learning", "ai engineer", "full stack", "fullstack", "backend", "frontend",
+    ]) {
+        "Engineering"
+    } else if starts(&["sales", "account executive", "ae ", "business development"])
+        || lower.contains("sales")
+    {
+        "Sales"
+    } else if starts(&["marketing", "growth"]) {
+        "Marketing"
+    } else if starts(&["operations", "ops"]) {
+        "Operations"
+    } else if starts(&["product"]) {
+        "Product"
+    } else if starts(&["design", "ux", "ui "]) {
+        "Design"
+    } else if starts(&["support", "customer"]) {
+        "Support"
+    } else if starts(&["finance"]) {
+        "Finance"
+    } else if starts(&["recruit", "talent", "people", "hr "]) {
+        "Recruiting & HR"
+    } else if starts(&["science", "research", "data scientist"]) {
+        "Science"
+    } else if lower.contains("engineer") || lower.contains("developer") {
+        "Engineering"
+    } else {
+        "Other"
+    }
+}
+
+/// Lowercase words joined by single spaces, padded so " word " matches whole words.
+fn padded_words(s: &str) -> String {
+    let words: Vec<String> = s
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|w| !w.is_empty())
+        .map(str::to_lowercase)
+        .collect();
+    format!(" {} ", words.join(" "))
+}
+
+fn is_emoji(c: char) -> bool {
+    matches!(
+        c as u32,
+        0x1F300..=0x1F5FF
+            | 0x1F600..=0x1F64F
+            | 0x1F680..=0x1F6FF
+            | 0x1F700..=0x1F77F
+            | 0x1F780..=0x1F7FF
+            | 0x1F800..=0x1F8FF
+            | 0x1F900..=0x1F9FF
+            | 0x1FA70..=0x1FAFF
+            | 0x2600..=0x26FF
+            | 0x2700..=0x27BF
+    )
+}
+
+fn percent(part: usize, total: usize) -> f64 {
+    if total == 0 {
+        0.0
+    } else {
+        part as f64 * 100.0 / total as f64
+    }
+}
+
+pub fn render_job_stats(s: &JobStats) -> String {
+    let mut out = String::new();
+    out.push_str("## Job Stats\n");
+    out.push_str(&format!(
+        "- Total job listings: {}\n- Listings with emojis: {} ({:.1}%)\n- Engineering listings: {}\n",
+        s.total,
+        s.emoji_jobs,
+        percent(s.emoji_jobs, s.total),
+        s.engineering
+    ));
+
+    out.push_str("\n### Listings by role bucket\n");
+    for (bucket, n) in &s.by_bucket {
+        out.push_str(&format!("- {}: {} ({:.1}%)\n", bucket, n, percent(*n, s.total)));
+    }
+
+    out.push_str("\n### Emojis by bucket (emoji jobs only)\n");
+    for (bucket, n, pct) in &s.emoji_by_bucket {
+        out.push_str(&format!("- {}: {} ({:.1}%)\n", bucket, n, pct));
+    }
+
+    out.push_str("\n### Engineering stack mentions\n");
+    for (label, n) in &s.stack {
+        out.push_str(&format!("- {}: {}\n", label, n));
+    }
+
+    out.push_str("\n### Engineering meme keywords\n");
+    for (label, n) in &s.memes {
+        out.push_str(&format!("- {}: {}\n", label, n));
+    }
+    out
+}
+
+/// `job_stats` rows, keyed like v2 (`emoji_bucket:Sales:count`, `stack:rust`, ...).
+pub fn job_stat_metrics(s: &JobStats, markdown: &str) -> Vec<(String, String)> {
+    let mut m = vec![
+        ("total_jobs".to_string(), s.total.to_string()),
+        ("emoji_jobs".to_string(), s.emoji_jobs.to_string()),
+        ("emoji_pct".to_string(), format!("{:.2}", percent(s.emoji_jobs, s.total))),
+        ("engineering_jobs".to_string(), s.engineering.to_string()),
+    ];
+    for (bucket, n) in &s.by_bucket {
+        m.push((format!("bucket:{}", bucket), n.to_string()));
+    }
+    for (bucket, n, pct) in &s.emoji_by_bucket {
+        m.push((format!("emoji_bucket:{}:count", bucket), n.to_string()));
+        m.push((format!("emoji_bucket:{}:pct", bucket), format!("{:.2}", pct)));
+    }
+    for (label, n) in &s.stack {
+        m.push((format!("stack:{}", label), n.to_string()));
+    }
+    for (label, n) in &s.memes {
+        m.push((format!("meme:{}", label), n.to_string()));
+    }
+    m.push(("markdown".to_string(), markdown.to_string()));
+    m
+}
+
+// ── Extras taxonomy ──
+
+/// Unknown sections sharing a first-line pattern and block shape.
+pub struct ExtrasCluster {
+    pub pattern: String,
+    pub shape: String,
+    pub kinds: Vec<String>,
+    /// Distinct companies, sorted.
+    pub companies: Vec<String>,
+    pub sample: String,
+}
+
+pub fn extras_clusters(conn: &Connection) -> Result<Vec<ExtrasCluster>> {
+    Ok(cluster_extras(&db::fetch_extras(conn)?))
+}
+
+/// Group `company_sections.extras` entries by (first-line pattern, shape),
+/// most widespread first. Entries written before shapes were recorded
+/// cluster under shape "?" until the page is re-processed.
+fn cluster_extras(rows: &[(String, String)]) -> Vec<ExtrasCluster> {
+    #[derive(Default)]
+    struct Acc {
+        kinds: BTreeSet<String>,
+        companies: BTreeSet<String>,
+        sample: String,
+    }
+
+    let mut clusters: BTreeMap<(String, String), Acc> = BTreeMap::new();
+    for (slug, json) in rows {
+        let Ok(serde_json::Value::Array(items)) = serde_json::from_str(json) else {
+            continue;
+        };
+        for item in &items {
+            let text = item["text"].as_str().unwrap_or("");
+            let first = text.lines().map(str::trim).find(|l| !l.is_empty()).unwrap_or("");
+            let key = (
+                first_line_pattern(first, slug),
+                item["shape"].as_str().unwrap_or("?").to_string(),
+            );
+            let acc = clusters.entry(key).or_default();
+            if let Some(kind) = item["kind"].as_str() {
+                acc.kinds.insert(kind.to_string());
+            }
+            if acc.companies.insert(slug.clone()) && acc.sample.is_empty() {
+                acc.sample = first.to_string();
+            }
+        }
+    }
+
+    let mut out: Vec<ExtrasCluster> = clusters
+        .into_iter()
+        .map(|((pattern, shape), acc)| ExtrasCluster {
+            pattern,
+            shape,
+            kinds: acc.kinds.into_iter().collect(),
+            companies: acc.companies.into_iter().collect(),
+            sample: acc.sample,
+        })
+        .collect();
+    out.sort_by(|a, b| {
+        b.companies
+            .len()
+            .cmp(&a.companies.len())
+            .then_with(|| a.pattern.cmp(&b.pattern))
+    });
+    out
+}
+
+/// "Hear from the Stripe team (12)" → "hear from the {company} team (#)".
+/// Link markup is reduced to its text; long lines keep their first 8 words.
+fn first_line_pattern(line: &str, slug: &str) -> String {
+    let mut text = line.trim().to_string();
+    if let Some(inner) = text.strip_prefix('[') {
+        if let Some((label, _)) = inner.split_once("](") {
+            text = if label.is_empty() { "{link}".to_string() } else { label.to_string() };
+        }
+    }
+    let mut text = text.to_lowercase();
+    let company = slug.replace('-', " ");
+    if company.len() >= 3 {
+        text = text.replace(&company, "{company}");
+    }
+    let text: String = text
+        .chars()
+        .map(|c| if c.is_ascii_digit() { '#' } else { c })
+        .collect();
+    let mut words: Vec<&str> = text.split_whitespace().collect();
+    if words.len() > 8 {
+        words.truncate(8);
+        words.push("…");
+    }
+    words.join(" ")
+}
+
+pub fn print_extras(clusters: &[ExtrasCluster], min_companies: usize, limit: usize) {
+    out!("── Unknown section patterns ──");
+    let shown: Vec<&ExtrasCluster> = clusters
+        .iter()
+        .filter(|c| c.companies.len() >= min_companies)
+        .take(limit)
+        .collect();
+    if shown.is_empty() {
+        out!("  No patterns shared by {}+ companies.", min_companies);
+        return;
+    }
+    for c in &shown {
+        let examples: Vec<&str> = c.companies.iter().take(3).map(String::as_str).collect();
+        out!("  {:>5}  {}", c.companies.len(), c.pattern);
+        out!("         shape: {}  [{}]", c.shape, c.kinds.join(","));
+        out!("         e.g. {} — {:?}", examples.join(", "), truncate_sample(&c.sample));
+    }
+    let entries: usize = clusters.iter().map(|c| c.companies.len()).sum();
+    out!(
+        "\n{} of {} patterns shown ({} section entries)",
+        shown.len(),
+        clusters.len(),
+        entries
+    );
+}
+
+fn truncate_sample(s: &str) -> String {
+    if s.chars().count() > 60 {
+        format!("{}…", s.chars().take(60).collect::<String>())
+    } else {
+        s.to_string()
+    }
+}
+
+// ── Batch comparison ──
+
+/// How many tags / locations to list in a comparison.
+const COMPARE_TOP_TAGS: usize = 15;
+const COMPARE_TOP_LOCATIONS: usize = 10;
+
+#[derive(Debug, Clone, Copy, clap::ValueEnum)]
+pub enum CompareFormat {
+    Markdown,
+    Csv,
+}
+
+/// Side-by-side metrics for one batch.
+#[derive(Default)]
+pub struct BatchSummary {
+    pub companies: usize,
+    pub avg_team_size: Option<f64>,
+    /// Companies with at least one open job.
+    pub hiring: usize,
+    pub acquired: usize,
+    pub inactive: usize,
+    pub tags: HashMap<String, usize>,
+    pub locations: HashMap<String, usize>,
+}
+
+/// Companies with a flagged founding year count only with `include_flagged`.
+pub fn compare_batches(conn: &Connection, batches: &[Batch], include_flagged: bool) -> Result<Vec<BatchSummary>> {
+    let mut rows = db::fetch_overview(conn, &[], batches, None, false, i64::MAX as usize)?;
+    rows.retain(|r| include_flagged || !r.founded_year_flagged);
+    Ok(batches
+        .iter()
+        .map(|b| {
+            let label = b.to_string();
+            summarize_batch(rows.iter().filter(|r| r.batch == label))
+        })
+        .collect())
+}
+
+fn summarize_batch<'a>(rows: impl Iterator<Item = &'a OverviewRow>) -> BatchSummary {
+    let mut s = BatchSummary::default();
+    let mut team_sizes = Vec::new();
+    for r in rows {
+        s.companies += 1;
+        team_sizes.extend(r.team_size);
+        if r.job_count > 0 {
+            s.hiring += 1;
+        }
+        match r.status {
+            Some(CompanyStatus::Acquired) => s.acquired += 1,
+            Some(CompanyStatus::Inactive) => s.inactive += 1,
+            _ => {}
+        }
+        for tag in r.tags.split(", ").filter(|t| !t.is_empty()) {
+            *s.tags.entry(tag.to_string()).or_default() += 1;
+        }
+        if !r.location.is_empty() {
+            *s.locations.entry(r.location.clone()).or_default() += 1;
+        }
+    }
+    if !team_sizes.is_empty() {
+        s.avg_team_size =
+            Some(team_sizes.iter().map(|&t| t as f64).sum::<f64>() / team_sizes.len() as f64);
+    }
+    s
+}
+
+/// One output row: (section, item, one cell per batch, change last − first).
+type CompareRow = (&'static str, String, Vec<String>, String);
+
+fn compare_rows(summaries: &[BatchSummary]) -> Vec<CompareRow> {
+    let pct = |n: usize, total: usize| {
+        if total == 0 {
+            0.0
+        } else {
+            n as f64 * 100.0 / total as f64
+        }
+    };
+    let delta = |vals: &[f64], unit: &str, decimals: usize| match (vals.first(), vals.last()) {
+        (Some(a), Some(b)) if vals.len() > 1 => format!("{:+.*}{}", decimals, b - a, unit),
+        _ => String::new(),
+    };
+
+    let mut rows: Vec<CompareRow> = Vec::new();
+    // A change between two rates is in percentage points, not percent.
+    let mut metric = |item: &str, vals: Vec<f64>, unit: &str, decimals: usize| {
+        let cells = vals.iter().map(|v| format!("{:.*}{}", decimals, v, unit)).collect();
+        let change_unit = if unit == "%" { "pp" } else { unit };
+        rows.push(("metrics", item.to_string(), cells, delta(&vals, change_unit, decimals)));
+    };
+    let counts = |f: fn(&BatchSummary) -> usize| -> Vec<f64> {
+        summaries.iter().map(|s| f(s) as f64).collect()
+    };
+    metric("Companies", counts(|s| s.companies), "", 0);
+    metric(
+        "Avg team size",
+        summaries.iter().map(|s| s.avg_team_size.unwrap_or(0.0)).collect(),
+        "",
+        1,
+    );
+    let shares = |f: fn(&BatchSummary) -> usize| -> Vec<f64> {
+        summaries.iter().map(|s| pct(f(s), s.companies)).collect()
+    };
+    metric("Hiring rate", shares(|s| s.hiring), "%", 1);
+    metric("Acquired", counts(|s| s.acquired), "", 0);
+    metric("Acquired rate", shares(|s| s.acquired), "%", 1);
+    metric("Inactive rate", shares(|s| s.inactive), "%", 1);
+
+    for (section, limit, get) in [
+        ("tags", COMPARE_TOP_TAGS, (|s| &s.tags) as fn(&BatchSummary) -> &HashMap<String, usize>),
+        ("locations", COMPARE_TOP_LOCATIONS, |s| &s.locations),
+    ] {
+        let mut totals: HashMap<&str, usize> = HashMap::new();
+        for s in summaries {
+            for (k, n) in get(s) {
+                *totals.entry(k).or_default() += n;
+            }
+        }
+        let mut keys: Vec<(&str, usize)> = totals.into_iter().collect();
+        keys.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(b.0)));
+        for (key, _) in keys.into_iter().take(limit) {
+            let vals: Vec<f64> = summaries
+                .iter()
+                .map(|s| pct(get(s).get(key).copied().unwrap_or(0), s.companies))
+                .collect();
+            let cells = vals.iter().map(|v| format!("{:.1}%", v)).collect();
+            rows.push((section, key.to_string(), cells, delta(&vals, "pp", 1)));
+        }
+    }
+    rows
+}
+
+pub fn print_compare(batches: &[Batch], summaries: &[BatchSummary], format: CompareFormat) {
+    let labels: Vec<String> = batches.iter().map(Batch::to_string).collect();
+    let rows = compare_rows(summaries);
+    match format {
+        CompareFormat::Csv => {
+            let header: Vec<String> = labels.iter().map(|l| csv_field(l)).collect();
+            println!("section,item,{},change", header.join(","));
+            for (section, item, cells, change) in &rows {
+                println!("{},{},{},{}", section, csv_field(item), cells.join(","), change);
+            }
+        }
+        CompareFormat::Markdown => {
+            out!("## Batch comparison: {}", labels.join(" vs "));
+            let mut current = "";
+            for (section, item, cells, change) in &rows {
+                if *section != current {
+                    current = section;
+                    let title = match *section {
+                        "metrics" => "Metric",
+                        "tags" => "Tag (share of companies)",
+                        _ => "Location (share of companies)",
+                    };
+                    out!("\n| {} | {} | Change |", title, labels.join(" | "));
+                    out!("|---|{}---:|", "---:|".repeat(labels.len()));
+                }
+                out!("| {} | {} | {} |", item.replace('|', "\\|"), cells.join(" | "), change);
+            }
+        }
+    }
+}
+
+pub fn csv_field(s: &str) -> String {
+    if s.contains([',', '"', '\n']) {
+        format!("\"{}\"", s.replace('"', "\"\""))
+    } else {
+        s.to_string()
+    }
+}
+
+// ── Overview groups ──
+
+#[derive(Debug, Clone, Copy, clap::ValueEnum)]
+pub enum OverviewGroup {
+    Batch,
+    Status,
+    /// Last part of the footer location; bare city names are US, bar a few well-known ones
+    LocationCountry,
+    /// Every tag counts the company once, so groups overlap
+    Tag,
+}
+
+/// Bare footer locations outside the US. YC lists most non-US companies as
+/// "City, Country" and US ones as just the city.
+const BARE_NON_US_CITIES: &[(&str, &str, &str)] = &[
+    ("London", "United Kingdom", "GB"),
+    ("Toronto", "Canada", "CA"),
+    ("Vancouver", "Canada", "CA"),
+    ("Montreal", "Canada", "CA"),
+    ("Paris", "France", "FR"),
+    ("Berlin", "Germany", "DE"),
+    ("Singapore", "Singapore", "SG"),
+    ("Hong Kong", "Hong Kong", "HK"),
+    ("Dubai", "United Arab Emirates", "AE"),
+    ("Tel Aviv", "Israel", "IL"),
+    ("Mexico City", "Mexico", "MX"),
+];
+
+/// Bare footer locations in the US; other single-part locations have no
+/// known country.
+const BARE_US_CITIES: &[&str] = &[
+    "San Francisco",
+    "SF Bay Area",
+    "San Francisco Bay Area",
+    "New York",
+    "New York City",
+    "NYC",
+    "Los Angeles",
+    "Seattle",
+    "Boston",
+    "Austin",
+    "Chicago",
+    "Palo Alto",
+    "Mountain View",
+    "San Jose",
+    "Oakland",
+    "Berkeley",
+    "Miami",
+    "Denver",
+];
+
+/// Two-letter codes of US states, DC and territories, as in "Austin, TX".
+const US_STATE_CODES: &[&str] = &[
+    "AL", "AK", "AZ", "AR", "CA", "CO", "CT", "DE", "FL", "GA", "HI", "ID", "IL", "IN", "IA", "KS", "KY", "LA",
+    "ME", "MD", "MA", "MI", "MN", "MS", "MO", "MT", "NE", "NV", "NH", "NJ", "NM", "NY", "NC", "ND", "OH", "OK",
+    "OR", "PA", "RI", "SC", "SD", "TN", "TX", "UT", "VT", "VA", "WA", "WV", "WI", "WY", "DC", "PR", "GU", "VI",
+];
+
+#[derive(Debug, Clone, Copy, clap::ValueEnum)]
+pub enum OverviewSort {
+    /// Newest batch first
+    Batch,
+    /// Highest quality_score first, unscored last
+    Quality,
+}
+
+const UNKNOWN_GROUP: &str = "(none)";
+
+pub struct OverviewGroupRow {
+    pub key: String,
+    pub companies: usize,
+    /// Companies with at least one open job.
+    pub hiring: usize,
+    pub avg_team_size: Option<f64>,
+}
+
+/// Country for a footer location: "Bengaluru, India" → "India",
+/// "Austin, TX" and "San Francisco" → "USA". A single part that isn't a
+/// known city ("Remote") has none.
+pub fn location_country(location: &str) -> Option<String> {
+    let parts: Vec<&str> = location.split(',').map(str::trim).filter(|p| !p.is_empty()).collect();
+    let last = *parts.last()?;
+    if matches!(last, "USA" | "US" | "United States" | "United States of America") {
+        return Some("USA".to_string());
+    }
+    let non_us = BARE_NON_US_CITIES.iter().find(|(city, ..)| city.eq_ignore_ascii_case(parts[0]));
+    if parts.len() == 1 {
+        if let Some((_, country, _)) = non_us {
+            return Some(country.to_string());
+        }
+        return BARE_US_CITIES.iter().any(|city| city.eq_ignore_ascii_case(last)).then(|| "USA".to_string());
+    }
+    match non_us {
+        // "Berlin, DE" is Germany, not Delaware; "Paris, TX" stays Texas
+        Some((_, country, code)) if last == *code => Some(country.to_string()),
+        _ if US_STATE_CODES.contains(&last) => Some("USA".to_string()),
+        _ => Some(last.to_string()),
+    }
+}
+
+/// Companies, hiring count and mean team size per group, largest first.
+pub fn group_overview(rows: &[OverviewRow], by: OverviewGroup) -> Vec<OverviewGroupRow> {
+    let mut groups: HashMap<String, (usize, usize, Vec<i32>)> = HashMap::new();
+    for r in rows {
+        let keys: Vec<String> = match by {
+            OverviewGroup::Batch => vec![r.batch.clone()],
+            OverviewGroup::Status => vec![r.status.map(|s| s.to_string()).unwrap_or_default()],
+            OverviewGroup::LocationCountry => vec![location_country(&r.location).unwrap_or_default()],
+            OverviewGroup::Tag => r.tags.split(", ").filter(|t| !t.is_empty()).map(str::to_string).collect(),
+        };
+        let keys = if keys.iter().all(String::is_empty) { vec![UNKNOWN_GROUP.to_string()] } else { keys };
+        for key in keys {
+            let g = groups.entry(key).or_default();
+            g.0 += 1;
+            if r.job_count > 0 {
+                g.1 += 1;
+            }
+            g.2.extend(r.team_size);
+        }
+    }
+    let mut out: Vec<OverviewGroupRow> = groups
+        .into_iter()
+        .map(|(key, (companies, hiring, sizes))| OverviewGroupRow {
+            key,
+            companies,
+            hiring,
+            avg_team_size: (!sizes.is_empty())
+                .then(|| sizes.iter().map(|&t| t as f64).sum::<f64>() / sizes.len() as f64),
+        })
+        .collect();
+    out.sort_by(|a, b| b.companies.cmp(&a.companies).then_with(|| a.key.cmp(&b.key)));
+    out
+}
+
+pub fn print_overview_groups(groups: &[OverviewGroupRow], total: usize, limit: usize) {
+    let width = groups.iter().take(limit).map(|g| g.key.chars().count()).max().unwrap_or(0).clamp(5, 40);
+    out!("{:<width$}  {:>9} {:>7} {:>7} {:>9}", "group", "companies", "share", "hiring", "avg team");
+    for g in groups.iter().take(limit) {
+        let key = if g.key.chars().count() > width {
+            format!("{}…", g.key.chars().take(width - 1).collect::<String>())
+        } else {
+            g.key.clone()
+        };
+        out!(
+            "{:<width$}  {:>9} {:>6.1}% {:>7} {:>9}",
+            key,
+            g.companies,
+            percent(g.companies, total),
+            g.hiring,
+            g.avg_team_size.map(|a| format!("{:.1}", a)).unwrap_or_else(|| "-".into()),
+        );
+    }
+    if groups.len() > limit {
+        out!("... {} more groups (raise -n to see them)", groups.len() - limit);
+    }
+    out!("\n{} groups over {} companies", groups.len(), total);
+}
+
+// ── Cohorts ──
+
+#[derive(Debug, Clone, Copy, clap::ValueEnum)]
+pub enum CohortBy {
+    /// One row per batch: age, survival, exits, median team size
+    Batch,
+    /// Both seasons of a batch year pooled: acquisition rate by year
+    Year,
+    /// Company age from founded year: median team size and survival
+    Age,
+}
+
+/// Status mix and team sizes for one cohort. "Active" counts Active and
+/// Public companies, like `companies.is_active`.
+#[derive(Default)]
+struct Cohort {
+    companies: usize,
+    active: usize,
+    public: usize,
+    acquired: usize,
+    inactive: usize,
+    team_sizes: Vec<i64>,
+}
+
+impl Cohort {
+    fn add(&mut self, row: &CohortRow) {
+        self.companies += 1;
+        match row.status {
+            Some(CompanyStatus::Active) => self.active += 1,
+            Some(CompanyStatus::Public) => {
+                self.active += 1;
+                self.public += 1;
+            }
+            Some(CompanyStatus::Acquired) => self.acquired += 1,
+            Some(CompanyStatus::Inactive) => self.inactive += 1,
+            None => {}
+        }
+        self.team_sizes.extend(row.team_size.map(i64::from));
+    }
+
+    fn pct(&self, n: usize) -> String {
+        format!("{:.1}", n as f64 * 100.0 / self.companies as f64)
+    }
+
+    /// Nearest-rank median, like the latency percentiles in `stats`.
+    fn median_team_size(&self) -> String {
+        if self.team_sizes.is_empty() {
+            return String::new();
+        }
+        let mut sizes = self.team_sizes.clone();
+        sizes.sort_unstable();
+        db::percentile(&sizes, 50.0).to_string()
+    }
+}
+
+/// Cohort metrics as CSV lines (header first), ages measured to `as_of_year`.
+/// Companies without the grouping field (batch, founded year) are left out.
+pub fn cohort_csv(rows: &[CohortRow], by: CohortBy, as_of_year: i32) -> Vec<String> {
+    let mut lines = Vec::new();
+    match by {
+        CohortBy::Batch => {
+            let mut cohorts: BTreeMap<(i32, Season), Cohort> = BTreeMap::new();
+            for r in rows {
+                if let Some(b) = r.batch.as_deref().and_then(|b| b.parse::<Batch>().ok()) {
+                    cohorts.entry((b.year, b.season)).or_default().add(r);
+                }
+            }
+            lines.push(
+                "batch,batch_year,years_since_batch,companies,active_pct,public_pct,acquired_pct,inactive_pct,median_team_size"
+                    .to_string(),
+            );
+            for ((year, season), c) in cohorts {
+                lines.push(format!(
+                    "{},{},{},{},{},{},{},{},{}",
+                    Batch { season, year },
+                    year,
+                    as_of_year - year,
+                    c.companies,
+                    c.pct(c.active),
+                    c.pct(c.public),
+                    c.pct(c.acquired),
+                    c.pct(c.inactive),
+                    c.median_team_size()
+                ));
+            }
+        }
+        CohortBy::Year => {
+            let mut cohorts: BTreeMap<i32, Cohort> = BTreeMap::new();
+            for r in rows {
+                if let Some(b) = r.batch.as_deref().and_then(|b| b.parse::<Batch>().ok()) {
+                    cohorts.entry(b.year).or_default().add(r);
+                }
+            }
+            lines.push(
+                "batch_year,years_since_batch,companies,acquired,acquired_pct,active_pct,inactive_pct,median_team_size"
+                    .to_string(),
+            );
+            for (year, c) in cohorts {
+                lines.push(format!(
+                    "{},{},{},{},{},{},{},{}",
+                    year,
+                    as_of_year - year,
+                    c.companies,
+                    c.acquired,
+                    c.pct(c.acquired),
+                    c.pct(c.active),
+                    c.pct(c.inactive),
+                    c.median_team_size()
+                ));
+            }
+        }
+        CohortBy::Age => {
+            let mut cohorts: BTreeMap<i32, Cohort> = BTreeMap::new();
+            for r in rows {
+                match r.founded_year {
+                    Some(y) if y <= as_of_year => cohorts.entry(as_of_year - y).or_default().add(r),
+                    _ => {}
+                }
+            }
+            lines.push("company_age_years,founded_year,companies,active_pct,median_team_size".to_string());
+            for (age, c) in cohorts {
+                lines.push(format!(
+                    "{},{},{},{},{}",
+                    age,
+                    as_of_year - age,
+                    c.companies,
+                    c.pct(c.active),
+                    c.median_team_size()
+                ));
+            }
+        }
+    }
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn score_bands_split_at_quarters() {
+        let scored = |q: Option<i32>| db::ScoredCompany {
+            slug: "acme".into(),
+            batch: None,
+            quality_score: q,
+            inputs: Default::default(),
+        };
+        let rows: Vec<_> = [Some(0), Some(24), Some(25), Some(74), Some(75), Some(100), None].map(scored).into();
+        assert_eq!(score_bands(&rows), ([2, 1, 1, 2], 1));
+    }
+
+    fn edge(a: i64, a_slug: &str, b: i64, b_slug: &str, method: &str) -> RepeatFounderRow {
+        RepeatFounderRow {
+            founder_id: a,
+            name: "Jane Doe".to_string(),
+            company_slug: a_slug.to_string(),
+            batch: None,
+            other_founder_id: b,
+            other_company_slug: b_slug.to_string(),
+            other_batch: None,
+            match_method: method.to_string(),
+        }
+    }
+
+    #[test]
+    fn transitive_matches_form_one_founder() {
+        let edges = vec![
+            edge(1, "acme", 2, "beta", "linkedin"),
+            edge(2, "beta", 1, "acme", "linkedin"),
+            edge(2, "beta", 3, "gamma", "twitter"),
+            edge(3, "gamma", 2, "beta", "twitter"),
+        ];
+        let founders = cluster_repeat_founders(&edges);
+        assert_eq!(founders.len(), 1);
+        assert_eq!(founders[0].companies.len(), 3);
+        assert_eq!(founders[0].match_methods, vec!["linkedin", "twitter"]);
+    }
+
+    #[test]
+    fn name_only_matches_are_reported_apart() {
+        let edges = vec![
+            edge(1, "acme", 2, "beta", "linkedin"),
+            edge(1, "acme", 2, "beta", "name"),
+            edge(2, "beta", 3, "gamma", "name"),
+            edge(4, "delta", 5, "eps", "name"),
+        ];
+        let founders = split_repeat_founders(&edges);
+        assert_eq!(founders.matched.len(), 1);
+        assert_eq!(founders.matched[0].companies.len(), 2, "a namesake's company isn't pulled in");
+        assert_eq!(founders.matched[0].match_methods, vec!["linkedin"]);
+        let possible: Vec<Vec<&str>> = founders
+            .name_only
+            .iter()
+            .map(|f| f.companies.iter().map(|(s, _)| s.as_str()).collect())
+            .collect();
+        assert_eq!(possible, vec![vec!["beta", "gamma"], vec!["delta", "eps"]]);
+    }
+
+    #[test]
+    fn separate_people_stay_separate() {
+        let edges = vec![
+            edge(1, "acme", 2, "beta", "twitter"),
+            edge(3, "gamma", 4, "delta", "name"),
+        ];
+        assert_eq!(cluster_repeat_founders(&edges).len(), 2);
+    }
+
+    #[test]
+    fn first_line_patterns_generalize() {
+        assert_eq!(
+            first_line_pattern("Hear from the Acme Labs team (12)", "acme-labs"),
+            "hear from the {company} team (##)"
+        );
+        assert_eq!(
+            first_line_pattern("[Selected questions](https://x.com/q)", "acme"),
+            "selected questions"
+        );
+        assert_eq!(first_line_pattern("[](https://x.com)", "acme"), "{link}");
+    }
+
+    #[test]
+    fn extras_cluster_across_companies() {
+        let rows = vec![
+            (
+                "acme".to_string(),
+                r#"[{"kind":"other","shape":"text link*2","text":"Hear from the Acme team\n[a](u)"}]"#
+                    .to_string(),
+            ),
+            (
+                "beta".to_string(),
+                r#"[{"kind":"other","shape":"text link*2","text":"Hear from the Beta team"},
+                    {"kind":"other","text":"Selected questions"}]"#
+                    .to_string(),
+            ),
+        ];
+        let clusters = cluster_extras(&rows);
+        assert_eq!(clusters.len(), 2);
+        assert_eq!(clusters[0].pattern, "hear from the {company} team");
+        assert_eq!(clusters[0].companies, vec!["acme", "beta"]);
+        assert_eq!(clusters[1].shape, "?");
+    }
+
+    fn company(batch: &str, status: &str, team: Option<i32>, tags: &str, jobs: i32) -> OverviewRow {
+        OverviewRow {
+            slug: String::new(),
+            name: String::new(),
+            batch: batch.to_string(),
+            status: status.parse().ok(),
+            team_size: team,
+            location: "San Francisco".to_string(),
+            primary_partner: String::new(),
+            tags: tags.to_string(),
+            job_count: jobs,
+            quality_score: None,
+            founded_year_flagged: false,
+        }
+    }
+
+    #[test]
+    fn batch_summary_metrics() {
+        let rows = [
+            company("Winter 2024", "Active", Some(4), "AI, Fintech", 2),
+            company("Winter 2024", "Acquired", Some(10), "AI", 0),
+            company("Winter 2024", "Active", None, "", 0),
+        ];
+        let s = summarize_batch(rows.iter());
+        assert_eq!(s.companies, 3);
+        assert_eq!(s.avg_team_size, Some(7.0));
+        assert_eq!(s.hiring, 1);
+        assert_eq!(s.acquired, 1);
+        assert_eq!(s.tags["AI"], 2);
+        assert_eq!(s.locations["San Francisco"], 3);
+    }
+
+    #[test]
+    fn overview_groups_by_country_and_tag() {
+        assert_eq!(location_country("Bengaluru, India").as_deref(), Some("India"));
+        assert_eq!(location_country("Austin, TX").as_deref(), Some("USA"));
+        assert_eq!(location_country("San Francisco").as_deref(), Some("USA"));
+        assert_eq!(location_country("london").as_deref(), Some("United Kingdom"));
+        assert_eq!(location_country(""), None);
+        assert_eq!(location_country("London, UK").as_deref(), Some("UK"));
+        assert_eq!(location_country("Berlin, DE").as_deref(), Some("Germany"));
+        assert_eq!(location_country("Wilmington, DE, USA").as_deref(), Some("USA"));
+        assert_eq!(location_country("Washington, DC").as_deref(), Some("USA"));
+        assert_eq!(location_country("Remote"), None);
+        assert_eq!(location_country("Springfield"), None);
+        assert_eq!(location_country("Paris, TX").as_deref(), Some("USA"));
+
+        let mut rows = [
+            company("Winter 2024", "Active", Some(4), "AI, Fintech", 2),
+            company("Winter 2024", "Acquired", Some(10), "AI", 0),
+            company("Summer 2023", "Active", None, "", 0),
+        ];
+        rows[1].location = "Toronto".to_string();
+        let countries = group_overview(&rows, OverviewGroup::LocationCountry);
+        let keys: Vec<(&str, usize)> = countries.iter().map(|g| (g.key.as_str(), g.companies)).collect();
+        assert_eq!(keys, vec![("USA", 2), ("Canada", 1)]);
+
+        let tags = group_overview(&rows, OverviewGroup::Tag);
+        let keys: Vec<(&str, usize)> = tags.iter().map(|g| (g.key.as_str(), g.companies)).collect();
+        assert_eq!(keys, vec![("AI", 2), ("(none)", 1), ("Fintech", 1)]);
+        assert_eq!(tags[0].avg_team_size, Some(7.0));
+        assert_eq!(tags[0].hiring, 1);
+        assert_eq!(tags[1].avg_team_size, None);
+    }
+
+    #[test]
+    fn compare_rows_report_share_shift() {
+        let a = [company("Winter 2023", "Active", Some(2), "Fintech", 0)];
+        let b = [
+            company("Winter 2024", "Active", Some(2), "AI", 1),
+            company("Winter 2024", "Active", Some(2), "Fintech", 0),
+        ];
+        let rows = compare_rows(&[summarize_batch(a.iter()), summarize_batch(b.iter())]);
+        let fintech = rows.iter().find(|r| r.0 == "tags" && r.1 == "Fintech").unwrap();
+        assert_eq!(fintech.2, vec!["100.0%", "50.0%"]);
+        assert_eq!(fintech.3, "-50.0pp");
+        let hiring = rows.iter().find(|r| r.1 == "Hiring rate").unwrap();
+        assert_eq!((hiring.2[1].as_str(), hiring.3.as_str()), ("50.0%", "+50.0pp"));
+    }
+
+    fn stale(slug: &str, batch: Option<&str>) -> StaleRow {
+        StaleRow {
+            slug: slug.to_string(),
+            batch: batch.map(str::to_string),
+            last_scraped: String::new(),
+            age_days: 100.0,
+        }
+    }
+
+    #[test]
+    fn stale_groups_newest_batch_first() {
+        let rows = [
+            stale("a", Some("Summer 2009")),
+            stale("b", None),
+            stale("c", Some("Winter 2024")),
+            stale("d", Some("Summer 2009")),
+            stale("e", Some("Fall 2024")),
+            stale("f", Some("IK12")),
+            stale("g", Some("Spring 2031?")),
+        ];
+        let groups = group_stale_by_batch(&rows);
+        let labels: Vec<&str> = groups.iter().map(|(l, _)| l.as_str()).collect();
+        assert_eq!(
+            labels,
+            vec!["Fall 2024", "Winter 2024", "Summer 2009", "IK12", "Spring 2031?", "Unknown batch"],
+            "unparseable batches keep their own groups"
+        );
+        assert_eq!(groups[2].1.len(), 2);
+    }
+
+    #[test]
+    fn role_buckets_from_titles() {
+        assert_eq!(role_bucket("Senior Software Engineer"), "Engineering");
+        assert_eq!(role_bucket("Founding Engineer (Rust)"), "Engineering");
+        assert_eq!(role_bucket("Account Executive"), "Sales");
+        assert_eq!(role_bucket("Sales Engineer"), "Sales");
+        assert_eq!(role_bucket("Head of Growth"), "Marketing");
+        assert_eq!(role_bucket("Staff Product Designer"), "Product");
+        assert_eq!(role_bucket("Chief of Staff"), "Other");
+    }
+
+    #[test]
+    fn job_stats_counts_emoji_and_keywords() {
+        let titles: Vec<String> = [
+            "🚀 Account Executive",
+            "Sales Development Rep ✨",
+            "Backend Engineer (Go, Postgres)",
+            "Senior Rust Engineer",
+            "Trust & Safety Engineer",
+            "LLM Engineer - OpenAI APIs",
+            "Operations Manager",
+        ]
+        .iter()
+        .map(|s| s.to_string())
+        .collect();
+        let s = job_stats(&titles);
+        assert_eq!(s.total, 7);
+        assert_eq!(s.emoji_jobs, 2);
+        assert_eq!(s.emoji_by_bucket[0], ("Sales", 2, 100.0));
+        assert_eq!(s.engineering, 4);
+        let stack: HashMap<_, _> = s.stack.iter().copied().collect();
+        assert_eq!(stack["rust"], 1, "'Trust' must not count as rust");
+        assert_eq!(stack["go"], 1);
+        assert_eq!(stack["postgres"], 1);
+        let memes: HashMap<_, _> = s.memes.iter().copied().collect();
+        assert_eq!(memes["gpt/llm"], 1);
+        assert_eq!(memes["openai"], 1);
+        assert_eq!(s.by_bucket[0], ("Engineering", 4));
+
+        let md = render_job_stats(&s);
+        let metrics = job_stat_metrics(&s, &md);
+        assert!(metrics.contains(&("emoji_bucket:Sales:count".to_string(), "2".to_string())));
+        assert!(metrics.contains(&("stack:rust".to_string(), "1".to_string())));
+        assert_eq!(metrics.last().unwrap().1, md);
+    }
+
+    #[test]
+    fn cohorts_by_batch_year_and_age() {
+        let row = |batch: &str, status: &str, team_size: Option<i32>, founded_year: Option<i32>| CohortRow {
+            batch: Some(batch.to_string()),
+            status: status.parse().ok(),
+            team_size,
+            founded_year,
+        };
+        let rows = vec![
+            row("Summer 2020", "Active", Some(10), Some(2019)),
+            row("Summer 2020", "Acquired", Some(4), Some(2020)),
+            row("Winter 2020", "Public", Some(900), Some(2019)),
+            row("Winter 2020", "Inactive", None, None),
+            row("Winter 2024", "Active", Some(3), Some(2023)),
+        ];
+
+        let batch = cohort_csv(&rows, CohortBy::Batch, 2025);
+        assert_eq!(batch.len(), 4);
+        assert_eq!(batch[1], "Winter 2020,2020,5,2,50.0,50.0,0.0,50.0,900");
+        assert_eq!(batch[2], "Summer 2020,2020,5,2,50.0,0.0,50.0,0.0,4");
+
+        let year = cohort_csv(&rows, CohortBy::Year, 2025);
+        assert_eq!(year[1], "2020,5,4,1,25.0,50.0,25.0,10");
+
+        let age = cohort_csv(&rows, CohortBy::Age, 2025);
+        assert_eq!(age[1..], ["2,2023,1,100.0,3", "5,2020,1,0.0,4", "6,2019,2,100.0,10"]);
+    }
+
+    #[test]
+    fn meeting_links_group_by_platform_and_batch() {
+        let row = |slug: &str, batch: Option<&str>, link_type: &str, live: Option<bool>| MeetingLinkStatus {
+            company_slug: slug.to_string(),
+            batch: batch.map(str::to_string),
+            link_type: link_type.to_string(),
+            live,
+        };
+        let rows = vec![
+            row("a", Some("Winter 2023"), "calendly", Some(false)),
+            row("a", Some("Winter 2023"), "cal.com", Some(true)),
+            row("b", Some("Summer 2024"), "calendly", Some(false)),
+            row("c", Some("Summer 2024"), "calendly", None),
+            row("d", None, "hubspot", Some(true)),
+            row("e", Some("IK12"), "calendly", None),
+            row("f", Some("Spring 2031?"), "calendly", None),
+        ];
+
+        let platforms = meeting_platforms(&rows);
+        let calendly = &platforms[0];
+        assert_eq!(calendly.label, "calendly");
+        assert_eq!((calendly.links, calendly.companies, calendly.checked, calendly.dead), (5, 5, 2, 2));
+        assert_eq!(dead_share(calendly), "100.0%");
+        assert_eq!(platforms.len(), 3);
+
+        let batches = meeting_batches(&rows);
+        let labels: Vec<&str> = batches.iter().map(|g| g.label.as_str()).collect();
+        assert_eq!(labels, ["Summer 2024", "Winter 2023", "IK12", "Spring 2031?", "Unknown batch"]);
+        assert_eq!((batches[1].links, batches[1].companies, batches[1].dead), (2, 1, 1));
+        assert_eq!(dead_share(&batches[0]), "100.0%");
+    }
+}
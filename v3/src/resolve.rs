@@ -0,0 +1,253 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use indicatif::ProgressStyle;
+use reqwest::redirect::Policy;
+use reqwest::{StatusCode, Url};
+use tokio::sync::Semaphore;
+use tokio::task::JoinSet;
+use tracing::{debug, info};
+
+use crate::output;
+
+const CONCURRENCY: usize = 16;
+const MAX_REDIRECTS: usize = 5;
+const TIMEOUT_SECS: u64 = 10;
+
+/// Query parameters that never change which article a URL points at.
+const TRACKING_PARAMS: &[&str] = &[
+    "fbclid", "gclid", "mc_cid", "mc_eid", "guccounter", "guce_referrer",
+    "guce_referrer_sig", "ref", "ref_src", "cmpid", "amp", "outputtype",
+];
+
+pub struct Resolved {
+    pub id: i64,
+    pub canonical_url: String,
+    /// Final URL differs from the stored one after canonicalization.
+    pub redirected: bool,
+    /// Request failed; `canonical_url` is the canonicalized original.
+    pub failed: bool,
+}
+
+/// Build a client that follows at most `MAX_REDIRECTS` hops.
+pub fn redirect_client() -> Result<reqwest::Client> {
+    Ok(reqwest::Client::builder()
+        .redirect(Policy::limited(MAX_REDIRECTS))
+        .timeout(Duration::from_secs(TIMEOUT_SECS))
+        .user_agent("Mozilla/5.0 (compatible; yc_scraper)")
+        .build()?)
+}
+
+/// Resolve `(id, url)` pairs concurrently, returning the canonical URL for each.
+pub async fn resolve_urls(items: Vec<(i64, String)>) -> Result<Vec<Resolved>> {
+    let client = redirect_client()?;
+    let semaphore = Arc::new(Semaphore::new(CONCURRENCY));
+
+    let pb = output::progress_bar(items.len() as u64);
+    pb.set_style(
+        ProgressStyle::default_bar()
+            .template("[{elapsed_precise}] {bar:40} {pos}/{len} ({per_sec}, eta {eta})")?
+            .progress_chars("=> "),
+    );
+
+    let mut tasks = JoinSet::new();
+    for (id, url) in items {
+        let client = client.clone();
+        let sem = Arc::clone(&semaphore);
+        tasks.spawn(async move {
+            let _permit = sem.acquire().await.unwrap();
+            let original = canonicalize_url(&url);
+            match follow_redirects(&client, &url).await {
+                Ok(final_url) => {
+                    let canonical = canonicalize_url(final_url.as_str());
+                    Resolved {
+                        id,
+                        redirected: canonical != original,
+                        canonical_url: canonical,
+                        failed: false,
+                    }
+                }
+                Err(e) => {
+                    debug!("Resolve failed for {}: {}", url, e);
+                    Resolved {
+                        id,
+                        canonical_url: original,
+                        redirected: false,
+                        failed: true,
+                    }
+                }
+            }
+        });
+    }
+
+    let mut out = Vec::new();
+    while let Some(res) = tasks.join_next().await {
+        out.push(res?);
+        pb.inc(1);
+    }
+    pb.finish_and_clear();
+
+    let failed = out.iter().filter(|r| r.failed).count();
+    info!("Resolved {} URLs ({} failed)", out.len(), failed);
+    Ok(out)
+}
+
+/// Follow redirects with a HEAD request, falling back to GET for servers
+/// that refuse HEAD. Only headers are read; bodies are dropped unread.
+pub async fn follow_redirects(client: &reqwest::Client, url: &str) -> Result<Url> {
+    let resp = client.head(url).send().await?;
+    if matches!(
+        resp.status(),
+        StatusCode::METHOD_NOT_ALLOWED
+            | StatusCode::FORBIDDEN
+            | StatusCode::NOT_IMPLEMENTED
+            | StatusCode::BAD_REQUEST
+    ) {
+        let resp = client.get(url).send().await?;
+        return Ok(resp.url().clone());
+    }
+    Ok(resp.url().clone())
+}
+
+/// Normalize an article URL: unwrap AMP caches, drop tracking params, AMP
+/// markers, fragments and trailing slashes. Unparseable input is returned trimmed.
+pub fn canonicalize_url(raw: &str) -> String {
+    let raw = raw.trim();
+    let Ok(mut url) = Url::parse(raw) else {
+        return raw.to_string();
+    };
+
+    if let Some(inner) = unwrap_amp_cache(&url) {
+        url = inner;
+    }
+
+    url.set_fragment(None);
+
+    let kept: Vec<(String, String)> = url
+        .query_pairs()
+        .filter(|(k, _)| {
+            let k = k.to_lowercase();
+            !k.starts_with("utm_") && !TRACKING_PARAMS.contains(&k.as_str())
+        })
+        .map(|(k, v)| (k.into_owned(), v.into_owned()))
+        .collect();
+    if kept.is_empty() {
+        url.set_query(None);
+    } else {
+        url.query_pairs_mut().clear().extend_pairs(kept);
+    }
+
+    let path = url.path().to_string();
+    let mut trimmed = path.trim_end_matches('/');
+    for suffix in ["/amp", "/amp.html"] {
+        if let Some(p) = trimmed.strip_suffix(suffix) {
+            trimmed = p;
+        }
+    }
+    let trimmed = if trimmed.is_empty() { "/" } else { trimmed };
+    url.set_path(trimmed);
+
+    let s = url.to_string();
+    // Url always renders an empty path as "/"; drop it for bare domains.
+    match s.strip_suffix('/') {
+        Some(stripped) if url.path() == "/" && url.query().is_none() => stripped.to_string(),
+        _ => s,
+    }
+}
+
+/// Applicant tracking system behind a resolved apply URL. Greenhouse boards
+/// embedded on a company's own careers page keep their `gh_jid` parameter.
+pub fn ats_vendor(url: &str) -> Option<&'static str> {
+    let url = Url::parse(url).ok()?;
+    let host = url.host_str()?.to_ascii_lowercase();
+    let on = |domain: &str| host == domain || host.ends_with(&format!(".{}", domain));
+    if on("greenhouse.io") || url.query_pairs().any(|(k, _)| k == "gh_jid") {
+        Some("greenhouse")
+    } else if on("lever.co") {
+        Some("lever")
+    } else if on("ashbyhq.com") {
+        Some("ashby")
+    } else if on("workable.com") {
+        Some("workable")
+    } else {
+        None
+    }
+}
+
+/// `https://www-foo-com.cdn.ampproject.org/c/s/www.foo.com/x` and
+/// `https://www.google.com/amp/s/www.foo.com/x` → `https://www.foo.com/x`.
+fn unwrap_amp_cache(url: &Url) -> Option<Url> {
+    let host = url.host_str()?;
+    let path = url.path();
+    let rest = if host.ends_with(".cdn.ampproject.org") {
+        path.strip_prefix("/c/s/")
+            .or_else(|| path.strip_prefix("/v/s/"))
+            .or_else(|| path.strip_prefix("/c/"))
+    } else if matches!(host, "google.com" | "www.google.com") {
+        path.strip_prefix("/amp/s/").or_else(|| path.strip_prefix("/amp/"))
+    } else {
+        None
+    }?;
+    let mut inner = Url::parse(&format!("https://{}", rest)).ok()?;
+    inner.set_query(url.query());
+    Some(inner)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn strips_tracking_and_fragment() {
+        assert_eq!(
+            canonicalize_url("https://techcrunch.com/2023/01/01/story/?utm_source=x&id=3#top"),
+            "https://techcrunch.com/2023/01/01/story?id=3"
+        );
+    }
+
+    #[test]
+    fn strips_amp_markers() {
+        assert_eq!(
+            canonicalize_url("https://www.cnbc.com/2023/05/09/stripe.html/amp/"),
+            "https://www.cnbc.com/2023/05/09/stripe.html"
+        );
+        assert_eq!(
+            canonicalize_url("https://example.com/news/story?outputType=amp"),
+            "https://example.com/news/story"
+        );
+    }
+
+    #[test]
+    fn unwraps_amp_caches() {
+        assert_eq!(
+            canonicalize_url("https://www-axios-com.cdn.ampproject.org/c/s/www.axios.com/2023/03/15/stripe"),
+            "https://www.axios.com/2023/03/15/stripe"
+        );
+        assert_eq!(
+            canonicalize_url("https://www.google.com/amp/s/www.forbes.com/sites/x/amp/"),
+            "https://www.forbes.com/sites/x"
+        );
+        assert_eq!(
+            canonicalize_url("https://evilgoogle.com/amp/s/www.forbes.com/sites/x"),
+            "https://evilgoogle.com/amp/s/www.forbes.com/sites/x"
+        );
+    }
+
+    #[test]
+    fn detects_ats_vendors() {
+        assert_eq!(ats_vendor("https://boards.greenhouse.io/stripe/jobs/123"), Some("greenhouse"));
+        assert_eq!(ats_vendor("https://stripe.com/jobs/listing?gh_jid=42"), Some("greenhouse"));
+        assert_eq!(ats_vendor("https://jobs.lever.co/acme/abc"), Some("lever"));
+        assert_eq!(ats_vendor("https://jobs.ashbyhq.com/acme/1"), Some("ashby"));
+        assert_eq!(ats_vendor("https://apply.workable.com/acme/j/ABC"), Some("workable"));
+        assert_eq!(ats_vendor("https://www.workatastartup.com/jobs/1"), None);
+        assert_eq!(ats_vendor("https://notlever.co/x"), None);
+    }
+
+    #[test]
+    fn bare_domain_and_garbage() {
+        assert_eq!(canonicalize_url("https://Stripe.com/"), "https://stripe.com");
+        assert_eq!(canonicalize_url(" not a url "), "not a url");
+    }
+}
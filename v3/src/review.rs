@@ -0,0 +1,210 @@
+use std::collections::HashMap;
+use std::io::{BufRead, Write};
+
+use anyhow::Result;
+use rusqlite::Connection;
+
+use crate::db::{self, Annotation, QualityRow};
+use crate::parser::blocks::Span;
+
+const EXCERPT_LINES: usize = 25;
+const HELP: &str = "a = accept (extraction is right)   r = reject (wrong, no fix)   \
+                    f <field>=<value> = correction   n <text> = note   s = skip   q = quit";
+
+enum Command {
+    Accept,
+    Reject,
+    Fix { field: String, value: String },
+    Note(String),
+    Skip,
+    Quit,
+}
+
+fn parse_command(line: &str) -> Option<Command> {
+    let line = line.trim();
+    let (cmd, rest) = line.split_once(' ').unwrap_or((line, ""));
+    let rest = rest.trim();
+    match cmd {
+        "a" => Some(Command::Accept),
+        "r" => Some(Command::Reject),
+        "s" | "" => Some(Command::Skip),
+        "q" => Some(Command::Quit),
+        "n" if !rest.is_empty() => Some(Command::Note(rest.to_string())),
+        "f" => {
+            let (field, value) = rest.split_once('=')?;
+            let field = field.trim();
+            (!field.is_empty()).then(|| Command::Fix {
+                field: field.to_string(),
+                value: value.trim().to_string(),
+            })
+        }
+        _ => None,
+    }
+}
+
+/// Page section whose lines explain a flag.
+fn section_for(check_name: &str) -> &'static str {
+    match check_name {
+        "founder_count" | "suspicious_founder_name" => "founders",
+        "ambiguous_partner" => "footer_meta",
+        _ => "header",
+    }
+}
+
+/// Numbered markdown lines for `span`, or the top of the page without one.
+fn excerpt(markdown: &str, span: Option<Span>) -> String {
+    let span = span.unwrap_or(Span { start_line: 1, end_line: EXCERPT_LINES });
+    let end = span.end_line.min(span.start_line + EXCERPT_LINES - 1);
+    let mut out: Vec<String> = markdown
+        .lines()
+        .enumerate()
+        .skip(span.start_line.saturating_sub(1))
+        .take_while(|(i, _)| *i < end)
+        .map(|(i, line)| format!("{:>5} │ {}", i + 1, line))
+        .collect();
+    if span.end_line > end {
+        out.push(format!("      │ … {} more lines", span.end_line - end));
+    }
+    out.join("\n")
+}
+
+fn source_excerpt(conn: &Connection, item: &QualityRow) -> Result<String> {
+    let Some((markdown, spans)) = db::fetch_review_source(conn, &item.company_slug)? else {
+        return Ok("      (no scraped markdown)".to_string());
+    };
+    let span = spans
+        .and_then(|json| serde_json::from_str::<HashMap<String, String>>(&json).ok())
+        .and_then(|spans| spans.get(section_for(&item.check_name))?.parse().ok());
+    Ok(excerpt(&markdown, span))
+}
+
+#[derive(Default)]
+pub struct ReviewStats {
+    pub annotated: usize,
+    pub skipped: usize,
+    pub remaining: usize,
+}
+
+/// Walk `items`, reading one command per line from `input`. Accept, reject
+/// and fix close an item; notes are stored and the item stays open. End of
+/// input behaves like `q`.
+pub fn run(
+    conn: &Connection,
+    items: &[QualityRow],
+    input: &mut impl BufRead,
+    out: &mut impl Write,
+) -> Result<ReviewStats> {
+    let mut stats = ReviewStats::default();
+    writeln!(out, "{}", HELP)?;
+    'items: for (i, item) in items.iter().enumerate() {
+        writeln!(
+            out,
+            "\n[{}/{}] {} · {} (expected {}, actual {})",
+            i + 1,
+            items.len(),
+            item.company_slug,
+            item.check_name,
+            item.expected,
+            item.actual
+        )?;
+        if let Some(detail) = &item.detail {
+            writeln!(out, "  {}", detail)?;
+        }
+        writeln!(out, "{}", source_excerpt(conn, item)?)?;
+
+        loop {
+            write!(out, "> ")?;
+            out.flush()?;
+            let mut line = String::new();
+            if input.read_line(&mut line)? == 0 {
+                stats.remaining = items.len() - i;
+                break 'items;
+            }
+            let Some(cmd) = parse_command(&line) else {
+                writeln!(out, "{}", HELP)?;
+                continue;
+            };
+            let mut annotation = Annotation {
+                company_slug: &item.company_slug,
+                check_name: &item.check_name,
+                action: "note",
+                field: None,
+                value: None,
+                note: None,
+            };
+            match &cmd {
+                Command::Skip => {
+                    stats.skipped += 1;
+                    continue 'items;
+                }
+                Command::Quit => {
+                    stats.remaining = items.len() - i;
+                    break 'items;
+                }
+                Command::Accept => annotation.action = "accept",
+                Command::Reject => annotation.action = "reject",
+                Command::Fix { field, value } => {
+                    annotation.action = "fix";
+                    annotation.field = Some(field);
+                    annotation.value = Some(value);
+                }
+                Command::Note(text) => annotation.note = Some(text),
+            }
+            db::save_annotation(conn, &annotation)?;
+            stats.annotated += 1;
+            if !matches!(cmd, Command::Note(_)) {
+                continue 'items;
+            }
+        }
+    }
+    Ok(stats)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn excerpt_numbers_and_caps_lines() {
+        let md: String = (1..=60).map(|i| format!("line {}\n", i)).collect();
+        let e = excerpt(&md, Some(Span { start_line: 3, end_line: 4 }));
+        assert_eq!(e, "    3 │ line 3\n    4 │ line 4");
+        let e = excerpt(&md, Some(Span { start_line: 10, end_line: 50 }));
+        assert!(e.ends_with("… 16 more lines"), "{}", e);
+        assert!(e.starts_with("   10 │ line 10"));
+    }
+
+    #[test]
+    fn commands_are_stored_as_annotations() {
+        let conn = Connection::open_in_memory().unwrap();
+        db::init_schema(&conn).unwrap();
+        conn.execute_batch(
+            "INSERT INTO pages (id, url, slug) VALUES (1, 'https://x/acme', 'acme');
+             INSERT INTO page_data (id, page_id, url, slug, markdown) VALUES (1, 1, 'https://x/acme', 'acme', '# Acme\nFounders\nJane');
+             INSERT INTO company_sections (page_id, slug, url, spans) VALUES (1, 'acme', 'x', '{\"founders\":\"2-3\"}');
+             INSERT INTO companies (slug, url) VALUES ('acme', 'https://x/acme');
+             INSERT INTO extraction_quality (company_slug, check_name, expected, actual, detail)
+                 VALUES ('acme', 'suspicious_founder_name', 0, 1, 'Jane (single word)');",
+        )
+        .unwrap();
+        let items = db::fetch_review_queue(&conn, 10).unwrap();
+        assert_eq!(items.len(), 1);
+
+        let mut input = "x\nn goes by one name\nf name=Jane Doe\n".as_bytes();
+        let mut out = Vec::new();
+        let stats = run(&conn, &items, &mut input, &mut out).unwrap();
+        assert_eq!((stats.annotated, stats.remaining), (2, 0));
+        let shown = String::from_utf8(out).unwrap();
+        assert!(shown.contains("    3 │ Jane"), "{}", shown);
+
+        let fix: (String, String, String) = conn
+            .query_row(
+                "SELECT action, field, value FROM review_annotations WHERE action = 'fix'",
+                [],
+                |r| Ok((r.get(0)?, r.get(1)?, r.get(2)?)),
+            )
+            .unwrap();
+        assert_eq!(fix, ("fix".into(), "name".into(), "Jane Doe".into()));
+        assert!(db::fetch_review_queue(&conn, 10).unwrap().is_empty());
+    }
+}
@@ -1,59 +1,260 @@
+use std::collections::{HashMap, VecDeque};
+use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::Arc;
 use std::time::{Duration, Instant};
 
 use anyhow::Result;
-use indicatif::{ProgressBar, ProgressStyle};
+use indicatif::ProgressStyle;
 use regex::Regex;
-use rusqlite::Connection;
-use spider_client::shapes::request::{ReturnFormat, ReturnFormatHandling};
+use spider_client::shapes::request::{RequestType, ReturnFormat, ReturnFormatHandling};
 use spider_client::{RequestParams, Spider};
 use tokio::sync::Semaphore;
 use tracing::{info, warn};
 
-use crate::db::ScrapeRow;
+use crate::cassette::{self, Cassette};
+use crate::db::{self, ScrapeRow};
+use crate::output;
+use crate::writer::DbWriter;
 
 const CONCURRENCY: usize = 10;
 const MAX_RETRIES: u32 = 3;
 const BASE_BACKOFF_MS: u64 = 2000;
+/// Window for the progress bar's request rate; indicatif's own `per_sec`
+/// averages over the whole run and hides a throttled stretch.
+const RATE_WINDOW: Duration = Duration::from_secs(30);
 
 /// Scrape stats returned after completion.
 pub struct ScrapeStats {
     pub total: usize,
     pub ok: usize,
     pub errors: usize,
+    /// Saved, but not a company page (consent wall, empty shell, 404, listing).
+    pub invalid: usize,
+    /// Redirected to another company's page; the new slug was queued.
+    pub renamed: usize,
+    /// Failed requests per `db::error_category`, most frequent first.
+    pub error_categories: Vec<(&'static str, usize)>,
+}
+
+/// How pages are requested from spider.cloud.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, clap::ValueEnum)]
+pub enum ScrapeMode {
+    /// One scrape_url request per page
+    #[default]
+    Single,
+    /// Crawl jobs seeded with the queued pages; pages they miss are scraped singly
+    Crawl,
+}
+
+/// How `--keep-raw` stores the unstripped page in `page_data.markdown_raw`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, clap::ValueEnum)]
+pub enum RawFormat {
+    /// As text
+    Plain,
+    /// gzip-compressed, read back with the `raw_markdown()` SQL function
+    Gzip,
+}
+
+/// What spider.cloud returns for each page (`--return-format`).
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, clap::ValueEnum)]
+pub enum PageFormat {
+    /// spider.cloud's markdown conversion
+    #[default]
+    Markdown,
+    /// The raw HTML, read by `parser::html` instead of the markdown lexer
+    Html,
+}
+
+impl PageFormat {
+    fn request(self) -> ReturnFormatHandling {
+        ReturnFormatHandling::Single(match self {
+            PageFormat::Markdown => ReturnFormat::Markdown,
+            PageFormat::Html => ReturnFormat::Raw,
+        })
+    }
+
+    fn name(self) -> &'static str {
+        match self {
+            PageFormat::Markdown => "markdown",
+            PageFormat::Html => "html",
+        }
+    }
+}
+
+/// spider.cloud, or a cassette standing in for it: `--record` saves every
+/// response, `--replay` serves them back without network or an API key.
+struct Client {
+    /// `None` while replaying.
+    spider: Option<Spider>,
+    cassette: Option<Arc<Cassette>>,
+}
+
+impl Client {
+    fn new(cassette: Option<Arc<Cassette>>) -> Result<Client> {
+        if cassette.as_ref().is_some_and(|c| c.replaying()) {
+            return Ok(Client { spider: None, cassette });
+        }
+        let api_key = std::env::var("SPIDER_API_KEY")
+            .map_err(|_| anyhow::anyhow!("SPIDER_API_KEY environment variable must be set"))?;
+        let spider =
+            Spider::new(Some(api_key)).map_err(|e| anyhow::anyhow!("Failed to create Spider client: {}", e))?;
+        Ok(Client { spider: Some(spider), cassette })
+    }
+
+    fn replaying(&self) -> bool {
+        self.spider.is_none()
+    }
+
+    /// One page, as spider.cloud's JSON response or the client error.
+    async fn scrape(&self, url: &str, render: bool, format: PageFormat) -> cassette::Response {
+        let request = cassette::Request {
+            kind: "scrape".to_string(),
+            url: url.to_string(),
+            render,
+            format: format.name().to_string(),
+        };
+        let Some(spider) = &self.spider else { return self.replay(&request) };
+        let params = RequestParams {
+            return_format: Some(format.request()),
+            request: render.then_some(RequestType::Chrome),
+            metadata: Some(true),
+            ..Default::default()
+        };
+        let response = spider.scrape_url(url, Some(params), "application/json").await.map_err(transport_error);
+        self.record(request, &response);
+        response
+    }
+
+    /// A crawl seeded with `urls`, each fetched on its own without following
+    /// its links.
+    async fn crawl(&self, urls: &[&str], format: PageFormat) -> cassette::Response {
+        // spider.cloud takes several start URLs as one comma-separated list
+        let seeds = urls.join(",");
+        let request = cassette::Request {
+            kind: "crawl".to_string(),
+            url: seeds.clone(),
+            render: false,
+            format: format.name().to_string(),
+        };
+        let Some(spider) = &self.spider else { return self.replay(&request) };
+        let params = RequestParams {
+            return_format: Some(format.request()),
+            // Per start URL: the seed page itself
+            limit: Some(1),
+            metadata: Some(true),
+            ..Default::default()
+        };
+        let response = spider
+            .crawl_url(&seeds, Some(params), false, "application/json", None)
+            .await
+            .map_err(transport_error);
+        self.record(request, &response);
+        response
+    }
+
+    fn replay(&self, request: &cassette::Request) -> cassette::Response {
+        match &self.cassette {
+            Some(cassette) => cassette.take(request),
+            None => Err("no spider.cloud client".to_string()),
+        }
+    }
+
+    fn record(&self, request: cassette::Request, response: &cassette::Response) {
+        if let Some(cassette) = &self.cassette {
+            cassette.add(request, response);
+        }
+    }
+}
+
+/// How `scrape_pages_streaming` requests pages and treats failures.
+#[derive(Clone, Copy, Default)]
+pub struct ScrapeOptions {
+    /// Leave pages whose request failed unvisited.
+    pub retry_errors: bool,
+    /// Re-fetch a page that came back as a block page with browser
+    /// rendering straight away, instead of on the next run.
+    pub failover: bool,
+    pub mode: ScrapeMode,
+    /// Also store each page as it came back, before `strip_images`.
+    pub keep_raw: Option<RawFormat>,
+    pub return_format: PageFormat,
+}
+
+/// Queued pages seeding one crawl job, keeping each request body small.
+const CRAWL_SEEDS: usize = 200;
+
+/// Counters shared with the scrape tasks, read by the progress bar.
+#[derive(Default)]
+struct Live {
+    in_flight: AtomicUsize,
+    retries: AtomicUsize,
+    failovers: AtomicUsize,
 }
 
 /// Scrape pages concurrently, saving each result to DB as it arrives.
+/// Markdown that looks like a block page (see `validate::block_reason`) is
+/// saved as a `blocked` error and re-queued. With a `cassette`, responses
+/// are recorded into it, or replayed from it instead of requested.
 pub async fn scrape_pages_streaming(
-    conn: &Connection,
+    writer: &DbWriter,
     pages: Vec<(i64, String, String)>,
+    options: ScrapeOptions,
+    cassette: Option<Arc<Cassette>>,
 ) -> Result<ScrapeStats> {
-    let api_key =
-        std::env::var("SPIDER_API_KEY").expect("SPIDER_API_KEY environment variable must be set");
-
-    let spider = Arc::new(Spider::new(Some(api_key)).expect("Failed to create Spider client"));
+    let client = Arc::new(Client::new(cassette)?);
     let semaphore = Arc::new(Semaphore::new(CONCURRENCY));
     let total = pages.len();
 
-    let pb = ProgressBar::new(total as u64);
+    let pb = output::progress_bar(total as u64);
     pb.set_style(
         ProgressStyle::default_bar()
-            .template("[{elapsed_precise}] {bar:40} {pos}/{len} ({per_sec}, eta {eta})")?
+            .template("[{elapsed_precise}] {bar:40} {pos}/{len} eta {eta} {msg}")?
             .progress_chars("=> "),
     );
+    let live = Arc::new(Live::default());
+
+    // Pages that came back as interstitials last time get a real browser
+    let rerender = writer.call(db::fetch_rescrape_page_ids).await?;
+    let typical = Arc::new(writer.call(db::fetch_typical_lengths).await?);
 
     // Channel: workers send results, main loop saves to DB
     let (tx, mut rx) = tokio::sync::mpsc::channel::<ScrapeRow>(CONCURRENCY * 2);
 
+    let pages = match options.mode {
+        ScrapeMode::Single => pages,
+        ScrapeMode::Crawl => {
+            pb.set_message("crawling queued pages");
+            let (crawled, rest) = crawl_queued(&client, pages, &typical, options).await;
+            info!("Crawl returned {} of {} queued pages; scraping the rest singly", crawled.len(), total);
+            let tx = tx.clone();
+            tokio::spawn(async move {
+                for row in crawled {
+                    if tx.send(row).await.is_err() {
+                        break;
+                    }
+                }
+            });
+            rest
+        }
+    };
+
     // Spawn all scrape tasks
     for (page_id, url, slug) in pages {
-        let spider = Arc::clone(&spider);
+        let client = Arc::clone(&client);
         let sem = Arc::clone(&semaphore);
         let tx = tx.clone();
+        let render = rerender.contains(&page_id);
+        let live = Arc::clone(&live);
+        let typical = Arc::clone(&typical);
 
         tokio::spawn(async move {
             let _permit = sem.acquire().await.unwrap();
-            match scrape_with_retry(&spider, page_id, &url, &slug).await {
+            live.in_flight.fetch_add(1, Ordering::Relaxed);
+            let typical = typical.get(&page_id).copied();
+            let result =
+                scrape_checked(&client, page_id, &url, &slug, render, typical, options, &live).await;
+            live.in_flight.fetch_sub(1, Ordering::Relaxed);
+            match result {
                 Ok(row) => { let _ = tx.send(row).await; }
                 Err(e) => {
                     warn!("Task failed for {}: {}", slug, e);
@@ -66,6 +267,11 @@ pub async fn scrape_pages_streaming(
                         status: None,
                         error: Some(e.to_string()),
                         latency_ms: None,
+                        metadata: None,
+                        final_url: None,
+                        scrape_meta: None,
+                        markdown_raw: None,
+                        html: false,
                     }).await;
                 }
             }
@@ -78,61 +284,178 @@ pub async fn scrape_pages_streaming(
     // Receive and save each result immediately
     let mut ok = 0usize;
     let mut errors = 0usize;
+    let mut invalid = 0usize;
+    let mut renamed = 0usize;
+    let mut by_category: HashMap<&'static str, usize> = HashMap::new();
+    let mut recent: VecDeque<Instant> = VecDeque::new();
+    // Redraw while nothing completes, so backoff stalls show as a falling rate
+    let mut tick = tokio::time::interval(Duration::from_secs(1));
+
+    loop {
+        tokio::select! {
+            row = rx.recv() => {
+                let Some(row) = row else { break };
+                match &row.error {
+                    Some(e) => {
+                        errors += 1;
+                        *by_category.entry(db::error_category(row.status, e)).or_default() += 1;
+                    }
+                    None => ok += 1,
+                }
 
-    // Prepare statements once, reuse for each row
-    let mut insert_stmt = conn.prepare(
-        "INSERT INTO page_data (page_id, url, slug, markdown, status, error, latency_ms)
-         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
-    )?;
-    let mut update_stmt = conn.prepare(
-        "UPDATE pages SET visited = 1, visited_at = datetime('now') WHERE id = ?1",
-    )?;
-
-    while let Some(row) = rx.recv().await {
-        if row.error.is_some() {
-            errors += 1;
-        } else {
-            ok += 1;
+                // Save immediately on the writer thread
+                let kind = writer.call(move |conn| db::save_scrape(conn, &row, options.retry_errors)).await?;
+                match kind {
+                    Some(crate::validate::RENAMED) => renamed += 1,
+                    Some(k) if k != crate::validate::COMPANY => invalid += 1,
+                    _ => {}
+                }
+                recent.push_back(Instant::now());
+                pb.inc(1);
+            }
+            _ = tick.tick() => {}
         }
-
-        // Save immediately
-        save_one(&mut insert_stmt, &mut update_stmt, &row)?;
-        pb.inc(1);
+        while recent.front().is_some_and(|t| t.elapsed() > RATE_WINDOW) {
+            recent.pop_front();
+        }
+        pb.set_message(format!(
+            "ok {} | err {} | retry {} | failover {} | active {}/{} | {:.1}/s",
+            ok,
+            errors,
+            live.retries.load(Ordering::Relaxed),
+            live.failovers.load(Ordering::Relaxed),
+            live.in_flight.load(Ordering::Relaxed),
+            CONCURRENCY,
+            recent.len() as f64 / RATE_WINDOW.as_secs_f64(),
+        ));
     }
 
     pb.finish_and_clear();
-    info!("Scraped {} pages ({} ok, {} errors)", total, ok, errors);
+    info!(
+        "Scraped {} pages ({} ok, {} errors, {} not company pages, {} renamed)",
+        total, ok, errors, invalid, renamed
+    );
+
+    let mut error_categories: Vec<_> = by_category.into_iter().collect();
+    error_categories.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(b.0)));
+    Ok(ScrapeStats { total, ok, errors, invalid, renamed, error_categories })
+}
+
+/// Crawl jobs seeded with the queued URLs, `CRAWL_SEEDS` at a time, matched
+/// back with `match_crawled`. Every queued page is a seed, so a crawl that
+/// goes through covers the whole queue; what's left for single scrapes is
+/// pages that errored, came back non-2xx or look blocked, and every page of
+/// a job that failed outright. Returns (crawled rows, pages left).
+async fn crawl_queued(
+    client: &Client,
+    pages: Vec<(i64, String, String)>,
+    typical: &HashMap<i64, f64>,
+    options: ScrapeOptions,
+) -> (Vec<ScrapeRow>, Vec<(i64, String, String)>) {
+    let mut crawled = Vec::new();
+    let mut rest = Vec::new();
+    for chunk in pages.chunks(CRAWL_SEEDS) {
+        let urls: Vec<&str> = chunk.iter().map(|(_, url, _)| url.as_str()).collect();
+        match client.crawl(&urls, options.return_format).await {
+            Ok(value) => {
+                let (rows, missed) = match_crawled(&response_items(value), chunk.to_vec(), typical, options);
+                crawled.extend(rows);
+                rest.extend(missed);
+            }
+            Err(e) => {
+                warn!("Crawl of {} pages failed ({}); scraping them singly", chunk.len(), e);
+                rest.extend_from_slice(chunk);
+            }
+        }
+    }
+    (crawled, rest)
+}
 
-    Ok(ScrapeStats { total, ok, errors })
+/// Match crawl results to queued pages by canonical URL. Only clean company
+/// pages are kept: errors, non-2xx statuses and block pages go back with the
+/// pages the crawl missed, to be scraped singly with retries and failover.
+fn match_crawled(
+    items: &[serde_json::Value],
+    pages: Vec<(i64, String, String)>,
+    typical: &HashMap<i64, f64>,
+    options: ScrapeOptions,
+) -> (Vec<ScrapeRow>, Vec<(i64, String, String)>) {
+    let by_url: HashMap<String, &serde_json::Value> = items
+        .iter()
+        .filter_map(|item| {
+            let url = item.get("url").and_then(|u| u.as_str())?;
+            Some((crate::sitemap::canonicalize(url)?.0, item))
+        })
+        .collect();
+
+    let mut crawled = Vec::new();
+    let mut rest = Vec::new();
+    for (page_id, url, slug) in pages {
+        let item = by_url.get(&url).copied();
+        let failed = item.and_then(|i| i.get("error")).is_some_and(|e| e.as_str().is_some_and(|e| !e.is_empty()));
+        let row = item
+            .map(|item| row_from_item(Some(item), page_id, &url, &slug, None, options.return_format, options.keep_raw));
+        let clean = row.as_ref().is_some_and(|r| {
+            !failed
+                && r.status.is_none_or(|s| (200..300).contains(&s))
+                && r.text()
+                    .is_some_and(|text| crate::validate::block_reason(&text, typical.get(&page_id).copied()).is_none())
+        });
+        match row {
+            Some(row) if clean => crawled.push(row),
+            _ => rest.push((page_id, url, slug)),
+        }
+    }
+    (crawled, rest)
 }
 
-/// Save a single scrape result to DB using pre-prepared statements.
-fn save_one(
-    insert: &mut rusqlite::Statement,
-    update: &mut rusqlite::Statement,
-    row: &ScrapeRow,
-) -> Result<()> {
-    insert.execute(rusqlite::params![
-        row.page_id, row.url, row.slug, row.markdown, row.status, row.error, row.latency_ms,
-    ])?;
-    update.execute(rusqlite::params![row.page_id])?;
-    Ok(())
+/// `scrape_with_retry`, then the block-page check: a blocked page is tried
+/// once more with browser rendering when `options.failover` is on (and it
+/// wasn't rendered already), and otherwise comes back with a `blocked: ...`
+/// error.
+#[allow(clippy::too_many_arguments)]
+async fn scrape_checked(
+    client: &Client,
+    page_id: i64,
+    url: &str,
+    slug: &str,
+    render: bool,
+    typical_len: Option<f64>,
+    options: ScrapeOptions,
+    live: &Live,
+) -> Result<ScrapeRow> {
+    let blocked = |row: &ScrapeRow| row.text().and_then(|text| crate::validate::block_reason(&text, typical_len));
+    let mut row = scrape_with_retry(client, page_id, url, slug, render, options, &live.retries).await?;
+    let mut reason = blocked(&row);
+    if reason.is_some() && options.failover && !render {
+        warn!("{} looks blocked ({}), retrying with browser rendering", slug, reason.as_deref().unwrap_or(""));
+        live.failovers.fetch_add(1, Ordering::Relaxed);
+        row = scrape_with_retry(client, page_id, url, slug, true, options, &live.retries).await?;
+        reason = blocked(&row);
+    }
+    if let Some(reason) = reason {
+        warn!("Blocked on {}: {}", slug, reason);
+        row.error = Some(format!("blocked: {}", reason));
+    }
+    Ok(row)
 }
 
 async fn scrape_with_retry(
-    spider: &Spider,
+    client: &Client,
     page_id: i64,
     url: &str,
     slug: &str,
+    render: bool,
+    options: ScrapeOptions,
+    retries: &AtomicUsize,
 ) -> Result<ScrapeRow> {
     for attempt in 0..=MAX_RETRIES {
-        let row = scrape_one(spider, page_id, url, slug).await?;
+        let row = scrape_one(client, page_id, url, slug, render, options).await?;
 
-        let should_retry = match &row.error {
-            Some(e) if e.contains("429") || e.contains("rate") => true,
-            Some(e) if e.contains("500") || e.contains("502") || e.contains("503") => true,
-            _ => false,
-        };
+        let should_retry = row
+            .error
+            .as_deref()
+            .is_some_and(|e| matches!(crate::db::error_category(row.status, e), "rate_limit" | "server"));
 
         if !should_retry || attempt == MAX_RETRIES {
             return Ok(row);
@@ -146,63 +469,247 @@ async fn scrape_with_retry(
             MAX_RETRIES,
             backoff.as_secs_f64()
         );
-        tokio::time::sleep(backoff).await;
+        retries.fetch_add(1, Ordering::Relaxed);
+        // A replayed rate limit has nothing to wait out
+        if !client.replaying() {
+            tokio::time::sleep(backoff).await;
+        }
     }
 
-    scrape_one(spider, page_id, url, slug).await
+    scrape_one(client, page_id, url, slug, render, options).await
 }
 
-async fn scrape_one(spider: &Spider, page_id: i64, url: &str, slug: &str) -> Result<ScrapeRow> {
-    let params = RequestParams {
-        return_format: Some(ReturnFormatHandling::Single(ReturnFormat::Markdown)),
-        ..Default::default()
-    };
-
+#[tracing::instrument(
+    name = "scrape",
+    skip(client, page_id, url, options),
+    fields(format = ?options.return_format, latency_ms = tracing::field::Empty, status = tracing::field::Empty)
+)]
+async fn scrape_one(
+    client: &Client,
+    page_id: i64,
+    url: &str,
+    slug: &str,
+    render: bool,
+    options: ScrapeOptions,
+) -> Result<ScrapeRow> {
     let start = Instant::now();
-    let response = spider
-        .scrape_url(url, Some(params), "application/json")
-        .await;
+    let response = client.scrape(url, render, options.return_format).await;
     let elapsed = start.elapsed().as_millis() as i64;
+    tracing::Span::current().record("latency_ms", elapsed);
 
     match response {
         Ok(value) => {
-            let parsed: serde_json::Value = match value.as_str() {
-                Some(s) => serde_json::from_str(s).unwrap_or(value.clone()),
-                None => value,
-            };
-
-            let first = parsed.as_array().and_then(|arr| arr.first());
-
-            let content = first
-                .and_then(|obj| obj.get("content"))
-                .and_then(|c| c.as_str())
-                .map(strip_images);
-
-            let status = first
-                .and_then(|obj| obj.get("status"))
-                .and_then(|s| s.as_i64())
-                .map(|s| s as i32);
-
-            Ok(ScrapeRow {
-                page_id,
-                url: url.to_string(),
-                slug: slug.to_string(),
-                markdown: content,
-                status,
-                error: None,
-                latency_ms: Some(elapsed),
-            })
+            let items = response_items(value);
+            let row = row_from_item(items.first(), page_id, url, slug, Some(elapsed), options.return_format, options.keep_raw);
+            if let Some(s) = row.status {
+                tracing::Span::current().record("status", s);
+            }
+            Ok(row)
         }
         Err(e) => Ok(ScrapeRow {
             page_id,
             url: url.to_string(),
             slug: slug.to_string(),
             markdown: None,
-            status: None,
-            error: Some(e.to_string()),
+            status: error_status(&e),
+            error: Some(e),
             latency_ms: Some(elapsed),
+            metadata: None,
+            final_url: None,
+            scrape_meta: None,
+            markdown_raw: None,
+            html: false,
+        }),
+    }
+}
+
+/// A failed spider.cloud request as stored in `page_data.error`, tagged
+/// with what went wrong so `db::error_category` needn't read the message:
+/// `status <code>: …` for an HTTP error, else `timeout: …`, `connect: …`
+/// or `request: …`. The tag survives cassette recording.
+fn transport_error(e: impl Into<Box<dyn std::error::Error + Send + Sync>>) -> String {
+    let e = e.into();
+    let kind = match e.downcast_ref::<reqwest::Error>() {
+        Some(r) => match r.status() {
+            Some(status) => format!("status {}", status.as_u16()),
+            None if r.is_timeout() => "timeout".to_string(),
+            None if r.is_connect() => "connect".to_string(),
+            None => "request".to_string(),
+        },
+        None => "request".to_string(),
+    };
+    format!("{}: {}", kind, e)
+}
+
+/// HTTP status of a `transport_error` tagged with one.
+fn error_status(error: &str) -> Option<i32> {
+    error.strip_prefix("status ")?.split_once(':')?.0.parse().ok()
+}
+
+/// Result objects of a scrape or crawl response, which may arrive as a JSON
+/// string.
+fn response_items(value: serde_json::Value) -> Vec<serde_json::Value> {
+    let parsed: serde_json::Value = match value.as_str() {
+        Some(s) => serde_json::from_str(s).unwrap_or(value.clone()),
+        None => value,
+    };
+    match parsed {
+        serde_json::Value::Array(items) => items,
+        _ => Vec::new(),
+    }
+}
+
+/// A page's row from its result object (`None` when the response had none).
+/// HTML is kept as it came; `parser::html` leaves images out itself. The
+/// unstripped copy is only made with `keep_raw`.
+fn row_from_item(
+    item: Option<&serde_json::Value>,
+    page_id: i64,
+    url: &str,
+    slug: &str,
+    latency_ms: Option<i64>,
+    format: PageFormat,
+    keep_raw: Option<RawFormat>,
+) -> ScrapeRow {
+    let html = format == PageFormat::Html;
+    let raw = item.and_then(|obj| obj.get("content")).and_then(|c| c.as_str());
+    let content = raw.map(|content| if html { content.to_string() } else { strip_images(content) });
+
+    let metadata = item
+        .and_then(|obj| obj.get("metadata"))
+        .filter(|m| m.is_object())
+        .map(|m| m.to_string());
+
+    let status = item
+        .and_then(|obj| obj.get("status"))
+        .and_then(|s| s.as_i64())
+        .map(|s| s as i32);
+
+    // spider.cloud reports the URL it ended on after redirects
+    let final_url = item
+        .and_then(|obj| obj.get("url"))
+        .and_then(|u| u.as_str())
+        .filter(|u| u.trim_end_matches('/') != url.trim_end_matches('/'))
+        .map(str::to_string);
+
+    ScrapeRow {
+        page_id,
+        url: url.to_string(),
+        slug: slug.to_string(),
+        markdown: content,
+        status,
+        error: None,
+        latency_ms,
+        metadata,
+        final_url,
+        scrape_meta: item.and_then(scrape_meta),
+        markdown_raw: keep_raw.zip(raw).map(|(keep, md)| match keep {
+            RawFormat::Plain => md.as_bytes().to_vec(),
+            RawFormat::Gzip => db::gzip(md.as_bytes()),
         }),
+        html,
+    }
+}
+
+/// Response details worth keeping for debugging, as a JSON object: the URL
+/// spider.cloud ended on, status, content type, page title, costs and the
+/// error body. Absent and empty fields are left out.
+fn scrape_meta(item: &serde_json::Value) -> Option<String> {
+    let content_type = item
+        .get("content_type")
+        .or_else(|| item.get("headers").and_then(|h| h.get("content-type")));
+    let fields = [
+        ("final_url", item.get("url")),
+        ("status", item.get("status")),
+        ("content_type", content_type),
+        ("title", item.get("metadata").and_then(|m| m.get("title"))),
+        ("costs", item.get("costs")),
+        ("error", item.get("error")),
+    ];
+    let meta: serde_json::Map<String, serde_json::Value> = fields
+        .into_iter()
+        .filter_map(|(key, value)| {
+            let value = value.filter(|v| !v.is_null() && v.as_str() != Some(""))?;
+            Some((key.to_string(), value.clone()))
+        })
+        .collect();
+    (!meta.is_empty()).then(|| serde_json::Value::Object(meta).to_string())
+}
+
+/// Counts from `scrape_job_pages`.
+pub struct JobScrapeStats {
+    pub saved: usize,
+    /// Saved, but blank or a 404 rather than a job description.
+    pub skipped: usize,
+    pub failed: usize,
+    /// Tech stack chips over the saved pages, most frequent first.
+    pub stacks: Vec<(&'static str, usize)>,
+}
+
+/// Scrape job detail pages, (job id, URL) in, and save each page's
+/// `job_details` row as it arrives, like `scrape_pages_streaming`. Pages
+/// that fail after retries stay unscraped.
+pub async fn scrape_job_pages(writer: &DbWriter, jobs: Vec<(i64, String)>) -> Result<JobScrapeStats> {
+    let client = Arc::new(Client::new(None)?);
+    let semaphore = Arc::new(Semaphore::new(CONCURRENCY));
+
+    let pb = output::progress_bar(jobs.len() as u64);
+    pb.set_style(
+        ProgressStyle::default_bar()
+            .template("[{elapsed_precise}] {bar:40} {pos}/{len} ({per_sec}, eta {eta})")?
+            .progress_chars("=> "),
+    );
+
+    let (tx, mut rx) = tokio::sync::mpsc::channel::<(i64, Option<String>)>(CONCURRENCY * 2);
+    for (job_id, url) in jobs {
+        let client = Arc::clone(&client);
+        let sem = Arc::clone(&semaphore);
+        let pb = pb.clone();
+        let tx = tx.clone();
+        tokio::spawn(async move {
+            let _permit = sem.acquire().await.unwrap();
+            let url = if url.starts_with('/') { format!("https://www.ycombinator.com{}", url) } else { url };
+            let label = format!("job {}", job_id);
+            let retries = AtomicUsize::new(0);
+            let markdown = match scrape_with_retry(&client, job_id, &url, &label, false, ScrapeOptions::default(), &retries).await {
+                Ok(row) => {
+                    if let Some(e) = &row.error {
+                        warn!("Job page {} failed: {}", url, e);
+                    }
+                    row.markdown
+                }
+                Err(e) => {
+                    warn!("Job page {} failed: {}", url, e);
+                    None
+                }
+            };
+            pb.inc(1);
+            let _ = tx.send((job_id, markdown)).await;
+        });
+    }
+    drop(tx);
+
+    let mut stats = JobScrapeStats { saved: 0, skipped: 0, failed: 0, stacks: Vec::new() };
+    let mut stacks: HashMap<&'static str, usize> = HashMap::new();
+    while let Some((job_id, markdown)) = rx.recv().await {
+        let Some(markdown) = markdown else {
+            stats.failed += 1;
+            continue;
+        };
+        let detail = crate::parser::extract::job_body::extract(job_id, markdown);
+        if detail.content_kind != crate::validate::JOB {
+            stats.skipped += 1;
+        }
+        for &chip in &detail.tech_stack {
+            *stacks.entry(chip).or_default() += 1;
+        }
+        writer.call(move |conn| db::save_job_details(conn, &[detail])).await?;
+        stats.saved += 1;
     }
+    pb.finish_and_clear();
+    stats.stacks = stacks.into_iter().collect();
+    stats.stacks.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(b.0)));
+    Ok(stats)
 }
 
 /// Scrape a single URL and return its markdown content.
@@ -245,3 +752,156 @@ fn strip_images(md: &str) -> String {
     let blanks = Regex::new(r"\n{3,}").unwrap();
     blanks.replace_all(&cleaned, "\n\n").to_string()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn transport_errors_carry_their_status() {
+        let e = transport_error("something broke");
+        assert_eq!(e, "request: something broke");
+        assert_eq!(error_status(&e), None);
+        assert_eq!(error_status("status 429: Too Many Requests"), Some(429));
+        assert_eq!(crate::db::error_category(error_status("status 503: x"), "status 503: x"), "server");
+    }
+
+    #[test]
+    fn crawl_results_map_back_to_queued_pages() {
+        let base = "https://www.ycombinator.com/companies/";
+        let page = |id: i64, slug: &str| (id, format!("{}{}", base, slug), slug.to_string());
+        let body = format!("### Acme\n\n{}\n\nFounded:2020\nBatch:W20", "Acme builds rockets. ".repeat(20));
+        let items: Vec<serde_json::Value> = serde_json::from_value(serde_json::json!([
+            { "url": "https://www.ycombinator.com/companies", "content": "directory", "status": 200 },
+            { "url": format!("{}Acme/", base), "content": body, "status": 200 },
+            { "url": format!("{}gone", base), "content": "", "status": 404 },
+            { "url": format!("{}walled", base), "content": "Verify you are human", "status": 200 },
+            { "url": format!("{}broken", base), "content": body, "status": 200, "error": "timeout" },
+        ]))
+        .unwrap();
+        let pages = vec![page(1, "acme"), page(2, "gone"), page(3, "walled"), page(4, "broken"), page(5, "missed")];
+
+        let (crawled, rest) = match_crawled(&items, pages, &HashMap::new(), ScrapeOptions::default());
+        assert_eq!(crawled.len(), 1);
+        assert_eq!((crawled[0].page_id, crawled[0].slug.as_str()), (1, "acme"));
+        assert_eq!(crawled[0].markdown.as_deref(), Some(body.as_str()));
+        let left: Vec<i64> = rest.iter().map(|(id, _, _)| *id).collect();
+        assert_eq!(left, [2, 3, 4, 5]);
+        assert_eq!(response_items(serde_json::Value::String(serde_json::to_string(&items).unwrap())).len(), 5);
+    }
+
+    #[tokio::test]
+    async fn replayed_cassette_drives_the_scrape() {
+        let base = "https://www.ycombinator.com/companies/";
+        let body = format!("### Acme\n\n{}\n\nFounded:2020\nBatch:W20", "Acme builds rockets. ".repeat(20));
+        let path = std::env::temp_dir().join(format!("yc-scraper-cassette-test-{}.json", std::process::id()));
+        let request = |slug: &str| cassette::Request {
+            kind: "scrape".to_string(),
+            url: format!("{}{}", base, slug),
+            render: false,
+            format: "markdown".to_string(),
+        };
+        let recorder = Cassette::record(&path);
+        recorder.add(request("acme"), &Err("status 429: Too Many Requests".to_string()));
+        recorder.add(request("acme"), &Ok(serde_json::json!([{ "url": format!("{}acme", base), "content": body, "status": 200 }])));
+        recorder.save().unwrap();
+        let cassette = Arc::new(Cassette::replay(&path).unwrap());
+        std::fs::remove_file(&path).unwrap();
+
+        let conn = rusqlite::Connection::open_in_memory().unwrap();
+        db::init_schema(&conn).unwrap();
+        conn.execute_batch(&format!(
+            "INSERT INTO pages (id, url, slug) VALUES (1, '{base}acme', 'acme'), (2, '{base}globex', 'globex');"
+        ))
+        .unwrap();
+        let writer = DbWriter::with_connection(conn).unwrap();
+        let pages = vec![(1, format!("{}acme", base), "acme".to_string()), (2, format!("{}globex", base), "globex".to_string())];
+        let stats = scrape_pages_streaming(&writer, pages, ScrapeOptions::default(), Some(cassette)).await.unwrap();
+        assert_eq!((stats.total, stats.ok, stats.errors), (2, 1, 1));
+
+        let saved: Vec<(String, Option<String>, Option<String>)> = writer
+            .call(|c| {
+                let mut stmt = c.prepare(
+                    "SELECT p.slug, substr(pd.markdown, 1, 6), pd.error FROM page_data pd JOIN pages p ON p.id = pd.page_id ORDER BY p.id",
+                )?;
+                let rows = stmt.query_map([], |r| Ok((r.get(0)?, r.get(1)?, r.get(2)?)))?;
+                Ok(rows.collect::<Result<Vec<_>, _>>()?)
+            })
+            .await
+            .unwrap();
+        writer.close().await.unwrap();
+        assert_eq!(saved[0], ("acme".to_string(), Some("### Ac".to_string()), None));
+        assert_eq!(saved[1].0, "globex");
+        assert!(saved[1].2.as_deref().is_some_and(|e| e.starts_with("not in cassette")), "{:?}", saved[1]);
+    }
+
+    #[tokio::test]
+    async fn crawl_is_seeded_with_queued_pages() {
+        let base = "https://www.ycombinator.com/companies/";
+        let body = format!("### Acme\n\n{}\n\nFounded:2020\nBatch:W20", "Acme builds rockets. ".repeat(20));
+        let path = std::env::temp_dir().join(format!("yc-scraper-crawl-test-{}.json", std::process::id()));
+        let recorder = Cassette::record(&path);
+        recorder.add(
+            cassette::Request {
+                kind: "crawl".to_string(),
+                url: format!("{base}acme,{base}globex"),
+                render: false,
+                format: "markdown".to_string(),
+            },
+            &Ok(serde_json::json!([{ "url": format!("{}acme", base), "content": body, "status": 200 }])),
+        );
+        recorder.save().unwrap();
+        let cassette = Arc::new(Cassette::replay(&path).unwrap());
+        std::fs::remove_file(&path).unwrap();
+
+        let conn = rusqlite::Connection::open_in_memory().unwrap();
+        db::init_schema(&conn).unwrap();
+        conn.execute_batch(&format!(
+            "INSERT INTO pages (id, url, slug) VALUES (1, '{base}acme', 'acme'), (2, '{base}globex', 'globex');"
+        ))
+        .unwrap();
+        let writer = DbWriter::with_connection(conn).unwrap();
+        let pages = vec![(1, format!("{}acme", base), "acme".to_string()), (2, format!("{}globex", base), "globex".to_string())];
+        let options = ScrapeOptions { mode: ScrapeMode::Crawl, ..Default::default() };
+        let stats = scrape_pages_streaming(&writer, pages, options, Some(cassette)).await.unwrap();
+        writer.close().await.unwrap();
+        // globex, missing from the crawl, fell back to a single scrape the cassette doesn't hold
+        assert_eq!((stats.total, stats.ok, stats.errors), (2, 1, 1));
+    }
+
+    #[test]
+    fn response_details_go_to_scrape_meta() {
+        let item = serde_json::json!({
+            "url": "https://www.ycombinator.com/companies/acme-labs",
+            "content": "### Acme",
+            "status": 200,
+            "error": null,
+            "headers": { "content-type": "text/html; charset=utf-8" },
+            "costs": { "total_cost": 0.0003 },
+            "metadata": { "title": "Acme Labs: Rockets | Y Combinator", "description": "" },
+        });
+        let row = row_from_item(Some(&item), 1, "https://www.ycombinator.com/companies/acme", "acme", Some(12), PageFormat::Markdown, None);
+        let meta: serde_json::Value = serde_json::from_str(row.scrape_meta.as_deref().unwrap()).unwrap();
+        assert_eq!(meta["final_url"], "https://www.ycombinator.com/companies/acme-labs");
+        assert_eq!(meta["status"], 200);
+        assert_eq!(meta["content_type"], "text/html; charset=utf-8");
+        assert_eq!(meta["title"], "Acme Labs: Rockets | Y Combinator");
+        assert_eq!(meta["costs"]["total_cost"], 0.0003);
+        assert!(meta.get("error").is_none());
+
+        let failed = serde_json::json!({ "url": "https://x.test", "content": "", "error": "upstream 502" });
+        assert_eq!(scrape_meta(&failed).as_deref(), Some(r#"{"error":"upstream 502","final_url":"https://x.test"}"#));
+        assert_eq!(scrape_meta(&serde_json::json!({})), None);
+        assert_eq!(row_from_item(None, 1, "u", "s", None, PageFormat::Markdown, None).scrape_meta, None);
+
+        let pictured = serde_json::json!({ "content": "### Acme\n\n![logo](https://cdn.test/a.png)\n\n\nRockets" });
+        let row = row_from_item(Some(&pictured), 1, "u", "acme", None, PageFormat::Markdown, None);
+        assert_eq!(row.markdown.as_deref(), Some("### Acme\n\nRockets"));
+        assert_eq!(row.markdown_raw, None, "no copy without --keep-raw");
+        let raw = "### Acme\n\n![logo](https://cdn.test/a.png)\n\n\nRockets";
+        let row = row_from_item(Some(&pictured), 1, "u", "acme", None, PageFormat::Markdown, Some(RawFormat::Plain));
+        assert_eq!(row.markdown_raw.as_deref(), Some(raw.as_bytes()));
+        let row = row_from_item(Some(&pictured), 1, "u", "acme", None, PageFormat::Markdown, Some(RawFormat::Gzip));
+        assert_eq!(db::raw_markdown(row.markdown_raw.as_deref().unwrap()).unwrap(), raw);
+    }
+}
@@ -0,0 +1,311 @@
+use std::collections::HashMap;
+use std::io::Write;
+use std::str::FromStr;
+
+#[cfg(feature = "network")]
+use anyhow::Context;
+use anyhow::Result;
+use rusqlite::Connection;
+
+use crate::db;
+use crate::parser::extract::{tags, ExtractedData};
+
+/// Where `process` / `run` write extraction results. Non-SQLite sinks leave
+/// pages unprocessed locally, so the next run emits them again.
+#[derive(Debug, Clone)]
+pub enum SinkKind {
+    /// company_sections + extracted tables in the local DB (default).
+    Sqlite,
+    /// One JSON object per page on stdout.
+    Jsonl,
+    /// NDJSON POSTed to an ingest endpoint, one request per chunk.
+    Http(String),
+}
+
+impl SinkKind {
+    /// Sinks that own stdout; progress messages go to stderr instead.
+    pub fn uses_stdout(&self) -> bool {
+        matches!(self, SinkKind::Jsonl)
+    }
+}
+
+impl FromStr for SinkKind {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim() {
+            "sqlite" => Ok(SinkKind::Sqlite),
+            "jsonl" => Ok(SinkKind::Jsonl),
+            url if url.starts_with("http://") || url.starts_with("https://") => {
+                Ok(SinkKind::Http(url.to_string()))
+            }
+            other => Err(format!(
+                "unknown sink '{}' (expected sqlite, jsonl, or an http(s):// URL)",
+                other
+            )),
+        }
+    }
+}
+
+/// Line-delimited copy of the results for `process --emit`, written to
+/// stdout alongside the sink.
+#[derive(Debug, Clone, Copy, clap::ValueEnum)]
+pub enum Emit {
+    /// One JSON object per company
+    Jsonl,
+}
+
+/// Receives extraction results one chunk of pages at a time.
+pub trait ExtractSink {
+    fn write(&mut self, pages: Vec<ExtractedData>) -> Result<()>;
+
+    /// Flush anything buffered. Called once after the last chunk.
+    fn finish(&mut self) -> Result<()> {
+        Ok(())
+    }
+}
+
+pub fn open<'a>(kind: &SinkKind, conn: &'a Connection) -> Result<Box<dyn ExtractSink + 'a>> {
+    Ok(match kind {
+        SinkKind::Sqlite => Box::new(SqliteSink { conn, tag_catalog: db::fetch_tag_catalog(conn)? }),
+        SinkKind::Jsonl => Box::new(JsonlSink {
+            out: std::io::stdout().lock(),
+            tag_catalog: db::fetch_tag_catalog(conn)?,
+        }),
+        #[cfg(feature = "network")]
+        SinkKind::Http(url) => Box::new(HttpSink {
+            client: reqwest::Client::new(),
+            url: url.clone(),
+            tag_catalog: db::fetch_tag_catalog(conn)?,
+        }),
+        #[cfg(not(feature = "network"))]
+        SinkKind::Http(url) => crate::offline::http_sink(url)?,
+    })
+}
+
+/// Rewrite company tags to the catalog's spellings, so every sink emits
+/// the tags the DB stores.
+fn normalize_tags(data: &mut ExtractedData, tag_catalog: &HashMap<String, String>) {
+    if let Some(tags) = &data.company.tags {
+        data.company.tags = Some(tags::normalize_list(tags, tag_catalog));
+    }
+}
+
+// ── SQLite ──
+
+pub struct SqliteSink<'a> {
+    conn: &'a Connection,
+    /// From `tags`; company tags are rewritten to these spellings.
+    tag_catalog: HashMap<String, String>,
+}
+
+impl ExtractSink for SqliteSink<'_> {
+    fn write(&mut self, pages: Vec<ExtractedData>) -> Result<()> {
+        let mut sections = Vec::new();
+        let mut companies = Vec::new();
+        let mut founders = Vec::new();
+        let mut experience = Vec::new();
+        let mut news = Vec::new();
+        let mut jobs = Vec::new();
+        let mut links = Vec::new();
+        let mut meeting_links = Vec::new();
+        let mut qa = Vec::new();
+        let mut locations = Vec::new();
+        let mut references = Vec::new();
+        let mut quality = Vec::new();
+        let mut custom = Vec::new();
+
+        for mut data in pages {
+            normalize_tags(&mut data, &self.tag_catalog);
+            sections.push(data.sections);
+            companies.push(data.company);
+            founders.extend(data.founders);
+            experience.extend(data.experience);
+            news.extend(data.news);
+            jobs.extend(data.jobs);
+            links.extend(data.links);
+            meeting_links.extend(data.meeting_links);
+            qa.extend(data.qa);
+            locations.extend(data.locations);
+            references.extend(data.references);
+            quality.extend(data.quality);
+            custom.extend(data.custom);
+        }
+
+        db::save_sections(self.conn, &sections)?;
+        db::save_extracted(self.conn, &companies, &founders, &news, &jobs, &links)?;
+        let with_founders: Vec<&str> = companies
+            .iter()
+            .filter(|c| founders.iter().any(|f| f.company_slug == c.slug))
+            .map(|c| c.slug.as_str())
+            .collect();
+        db::save_experience(self.conn, &with_founders, &experience)?;
+        db::save_meeting_links(self.conn, &meeting_links)?;
+        db::save_qa(self.conn, &qa)?;
+        let located: Vec<&str> = companies
+            .iter()
+            .filter(|c| c.location.is_some())
+            .map(|c| c.slug.as_str())
+            .collect();
+        db::save_locations(self.conn, &located, &locations)?;
+        let slugs: Vec<&str> = companies.iter().map(|c| c.slug.as_str()).collect();
+        db::save_references(self.conn, &slugs, &references)?;
+        db::save_quality(self.conn, &slugs, &quality)?;
+        db::save_custom_rows(self.conn, &slugs, &custom)?;
+        db::refresh_quality_scores(self.conn, &slugs)?;
+        Ok(())
+    }
+
+    fn finish(&mut self) -> Result<()> {
+        db::rebuild_news_global(self.conn)?;
+        db::rebuild_funding_events(self.conn)?;
+        db::backfill_founder_linkedin(self.conn)?;
+        Ok(())
+    }
+}
+
+// ── JSONL ──
+
+pub struct JsonlSink<W: Write = std::io::StdoutLock<'static>> {
+    out: W,
+    /// As in `SqliteSink`.
+    tag_catalog: HashMap<String, String>,
+}
+
+fn write_jsonl(out: &mut impl Write, pages: &[ExtractedData]) -> Result<()> {
+    for data in pages {
+        serde_json::to_writer(&mut *out, data)?;
+        out.write_all(b"\n")?;
+    }
+    Ok(())
+}
+
+impl<W: Write> ExtractSink for JsonlSink<W> {
+    fn write(&mut self, mut pages: Vec<ExtractedData>) -> Result<()> {
+        pages.iter_mut().for_each(|data| normalize_tags(data, &self.tag_catalog));
+        write_jsonl(&mut self.out, &pages)
+    }
+
+    fn finish(&mut self) -> Result<()> {
+        Ok(self.out.flush()?)
+    }
+}
+
+// ── Tee ──
+
+/// `inner` plus a JSONL copy of each chunk, printed once `inner` has
+/// stored it, so a consumer never sees a company the DB doesn't have.
+pub struct Tee<'a, W: Write> {
+    inner: Box<dyn ExtractSink + 'a>,
+    jsonl: JsonlSink<W>,
+}
+
+/// Wrap `inner` for `process --emit jsonl`: every page also goes to stdout.
+pub fn tee_jsonl<'a>(inner: Box<dyn ExtractSink + 'a>, conn: &Connection) -> Result<Box<dyn ExtractSink + 'a>> {
+    let jsonl = JsonlSink { out: std::io::stdout().lock(), tag_catalog: db::fetch_tag_catalog(conn)? };
+    Ok(Box::new(Tee { inner, jsonl }))
+}
+
+impl<W: Write> ExtractSink for Tee<'_, W> {
+    fn write(&mut self, mut pages: Vec<ExtractedData>) -> Result<()> {
+        pages.iter_mut().for_each(|data| normalize_tags(data, &self.jsonl.tag_catalog));
+        let mut lines = Vec::new();
+        write_jsonl(&mut lines, &pages)?;
+        self.inner.write(pages)?;
+        self.jsonl.out.write_all(&lines)?;
+        Ok(())
+    }
+
+    fn finish(&mut self) -> Result<()> {
+        self.inner.finish()?;
+        self.jsonl.finish()
+    }
+}
+
+// ── HTTP ──
+
+#[cfg(feature = "network")]
+pub struct HttpSink {
+    client: reqwest::Client,
+    url: String,
+    /// As in `SqliteSink`.
+    tag_catalog: HashMap<String, String>,
+}
+
+#[cfg(feature = "network")]
+impl ExtractSink for HttpSink {
+    fn write(&mut self, mut pages: Vec<ExtractedData>) -> Result<()> {
+        pages.iter_mut().for_each(|data| normalize_tags(data, &self.tag_catalog));
+        let mut body = Vec::new();
+        for data in &pages {
+            serde_json::to_writer(&mut body, data)?;
+            body.push(b'\n');
+        }
+        // process_pages is synchronous; hop onto the runtime for the POST.
+        let request = self
+            .client
+            .post(&self.url)
+            .header(reqwest::header::CONTENT_TYPE, "application/x-ndjson")
+            .body(body);
+        let resp = tokio::task::block_in_place(|| {
+            tokio::runtime::Handle::current().block_on(request.send())
+        })
+        .with_context(|| format!("POST {} failed", self.url))?;
+        if !resp.status().is_success() {
+            anyhow::bail!("POST {} returned {}", self.url, resp.status());
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_sink_kinds() {
+        assert!(matches!("sqlite".parse(), Ok(SinkKind::Sqlite)));
+        assert!(matches!("jsonl".parse(), Ok(SinkKind::Jsonl)));
+        assert!(matches!(
+            "https://ingest.example.com/yc".parse(),
+            Ok(SinkKind::Http(u)) if u == "https://ingest.example.com/yc"
+        ));
+        assert!("parquet".parse::<SinkKind>().is_err());
+    }
+
+    #[test]
+    fn tee_stores_and_prints_each_company() {
+        let conn = Connection::open_in_memory().unwrap();
+        db::init_schema(&conn).unwrap();
+        conn.execute_batch(
+            "INSERT INTO pages (id, url, slug) VALUES (1, 'u/stripe', 'stripe');
+             INSERT INTO page_data (id, page_id, url, slug) VALUES (1, 1, 'u/stripe', 'stripe');",
+        )
+        .unwrap();
+        let page = db::ScrapedPage {
+            page_data_id: 1,
+            slug: "stripe".to_string(),
+            url: "https://www.ycombinator.com/companies/stripe".to_string(),
+            markdown: include_str!("../tests/fixtures/stripe.md").to_string(),
+            meta_description: None,
+            html: false,
+        };
+        let data = crate::parser::process_page(&page, &Default::default());
+
+        let tag_catalog = HashMap::from([("saas".to_string(), "SAAS".to_string())]);
+        let sqlite = SqliteSink { conn: &conn, tag_catalog: tag_catalog.clone() };
+        let mut tee = Tee { inner: Box::new(sqlite), jsonl: JsonlSink { out: Vec::new(), tag_catalog } };
+        tee.write(vec![data]).unwrap();
+        tee.finish().unwrap();
+
+        let out = String::from_utf8(tee.jsonl.out).unwrap();
+        assert_eq!(out.lines().count(), 1);
+        let line: serde_json::Value = serde_json::from_str(out.trim()).unwrap();
+        assert_eq!(line["company"]["slug"], "stripe");
+        assert!(line["company"]["tags"].as_str().unwrap().contains("Fintech, SAAS"), "{}", line);
+        let stored: i64 = conn.query_row("SELECT COUNT(*) FROM companies", [], |r| r.get(0)).unwrap();
+        assert_eq!(stored, 1);
+        let tags: String = conn.query_row("SELECT tags FROM companies", [], |r| r.get(0)).unwrap();
+        assert!(tags.contains("Banking as a Service, Fintech, SAAS"), "{}", tags);
+    }
+}
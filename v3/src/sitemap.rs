@@ -1,69 +1,287 @@
-use anyhow::{Context, Result};
+use std::collections::HashSet;
+use std::sync::LazyLock;
+
 use regex::Regex;
-use tracing::info;
-
-const COMPANIES_SITEMAP_URL: &str = "https://www.ycombinator.com/companies/sitemap";
-const COMPANY_PATTERN: &str =
-    r"^https://www\.ycombinator\.com/companies/([a-zA-Z0-9][a-zA-Z0-9_-]*)$";
-
-/// Fetch the YC companies sitemap and return filtered (url, slug) pairs.
-pub async fn fetch_company_urls() -> Result<Vec<(String, String)>> {
-    let client = reqwest::Client::new();
-    let re = Regex::new(COMPANY_PATTERN)?;
-
-    info!("Fetching companies sitemap: {}", COMPANIES_SITEMAP_URL);
-    let xml = client
-        .get(COMPANIES_SITEMAP_URL)
-        .send()
-        .await?
-        .text()
-        .await
-        .context("Failed to fetch companies sitemap")?;
-
-    let all_urls = parse_urlset(&xml)?;
-    info!("Total URLs in sitemap: {}", all_urls.len());
-
-    // Filter to company pages only (exclude /industry/, /location/, /batch/, etc.)
-    let filtered: Vec<(String, String)> = all_urls
-        .into_iter()
-        .filter_map(|url| {
-            let slug = re.captures(&url)?.get(1)?.as_str().to_string();
-            Some((url, slug))
-        })
-        .collect();
-
-    info!("Company pages after filtering: {}", filtered.len());
-    Ok(filtered)
+use unicode_normalization::UnicodeNormalization;
+
+use crate::model::Batch;
+
+const COMPANY_BASE: &str = "https://www.ycombinator.com/companies/";
+static SLUG_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^[\p{L}\p{N}][\p{L}\p{N}_-]*$").unwrap());
+
+/// Canonical (url, slug) for a YC company page URL; `None` for anything else.
+/// Scheme/host case, `www.`, trailing slashes, query, fragment, percent-encoding,
+/// Unicode normalization form and slug case all collapse, so `.../Airbnb/` and
+/// `.../airbnb` map to the same row.
+pub fn canonicalize(url: &str) -> Option<(String, String)> {
+    let url = url.trim();
+    let url = url.split(['?', '#']).next().unwrap_or(url);
+    let (scheme, rest) = url.split_once("://")?;
+    if !scheme.eq_ignore_ascii_case("https") && !scheme.eq_ignore_ascii_case("http") {
+        return None;
+    }
+    let (host, path) = rest.split_once('/')?;
+    let host = host.to_ascii_lowercase();
+    if host != "www.ycombinator.com" && host != "ycombinator.com" {
+        return None;
+    }
+    let path = path.trim_end_matches('/');
+    let segment = path.strip_prefix("companies/")?;
+    if segment.contains('/') {
+        return None;
+    }
+    let slug = canonical_slug(segment);
+    SLUG_RE
+        .is_match(&slug)
+        .then(|| (company_url(&slug), slug))
 }
 
-/// Parse a urlset XML and return all <loc> URLs.
-fn parse_urlset(xml: &str) -> Result<Vec<String>> {
-    let mut reader = quick_xml::Reader::from_str(xml);
-    let mut urls = Vec::new();
-    let mut in_url = false;
-    let mut in_loc = false;
-    let mut buf = Vec::new();
-
-    loop {
-        match reader.read_event_into(&mut buf) {
-            Ok(quick_xml::events::Event::Start(e)) => match e.name().as_ref() {
-                b"url" => in_url = true,
-                b"loc" if in_url => in_loc = true,
-                _ => {}
-            },
-            Ok(quick_xml::events::Event::Text(e)) if in_loc => {
-                urls.push(e.unescape()?.to_string());
+/// Page URL of a canonical slug.
+pub fn company_url(slug: &str) -> String {
+    format!("{}{}", COMPANY_BASE, slug)
+}
+
+/// The company directory, newest companies first.
+pub const DIRECTORY_URL: &str = "https://www.ycombinator.com/companies";
+
+/// Launch YC: companies announcing themselves, often before the sitemap lists them.
+pub const LAUNCHES_URL: &str = "https://www.ycombinator.com/launches";
+
+/// Company directory filtered to one batch.
+pub fn batch_url(batch: &Batch) -> String {
+    format!("https://www.ycombinator.com/companies?batch={}", batch.to_string().replace(' ', "%20"))
+}
+
+/// Link targets in scraped markdown that point at a company page, absolute
+/// or site-relative.
+static COMPANY_LINK_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"\]\(((?i:https?://(?:www\.)?ycombinator\.com)?/companies/[^)\s]+)\)").unwrap()
+});
+
+/// Canonical slugs of the company pages a directory listing links to.
+pub fn listed_slugs(md: &str) -> HashSet<String> {
+    linked_slugs(md).collect()
+}
+
+/// Canonical slugs of the company pages markdown links in `md` point to, in
+/// order and with repeats.
+pub fn linked_slugs(md: &str) -> impl Iterator<Item = String> + '_ {
+    COMPANY_LINK_RE.captures_iter(md).filter_map(|c| slug_of_link(c.get(1)?.as_str()))
+}
+
+/// Canonical slug of a company page link, absolute or site-relative
+/// (`/companies/acme`).
+pub fn slug_of_link(url: &str) -> Option<String> {
+    let url = if url.starts_with('/') {
+        format!("https://www.ycombinator.com{}", url)
+    } else {
+        url.to_string()
+    };
+    canonicalize(&url).map(|(_, slug)| slug)
+}
+
+/// Percent-decoded, NFC-normalized, lowercased slug.
+pub fn canonical_slug(slug: &str) -> String {
+    percent_decode(slug.trim()).to_lowercase().nfc().collect()
+}
+
+/// Decode `%XX` escapes; input that doesn't decode to UTF-8 is returned as is.
+fn percent_decode(s: &str) -> String {
+    if !s.contains('%') {
+        return s.to_string();
+    }
+    let bytes = s.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        let hex = (bytes[i] == b'%')
+            .then(|| s.get(i + 1..i + 3))
+            .flatten()
+            .and_then(|h| u8::from_str_radix(h, 16).ok());
+        match hex {
+            Some(b) => {
+                out.push(b);
+                i += 3;
             }
-            Ok(quick_xml::events::Event::End(e)) => match e.name().as_ref() {
-                b"loc" => in_loc = false,
-                b"url" => in_url = false,
+            None => {
+                out.push(bytes[i]);
+                i += 1;
+            }
+        }
+    }
+    String::from_utf8(out).unwrap_or_else(|_| s.to_string())
+}
+
+#[cfg(feature = "network")]
+pub use fetch::fetch_company_urls;
+#[cfg(not(feature = "network"))]
+pub use crate::offline::fetch_company_urls;
+
+#[cfg(feature = "network")]
+mod fetch {
+    use std::collections::HashSet;
+
+    use anyhow::{Context, Result};
+    use tracing::info;
+
+    use super::canonicalize;
+    use crate::db::PageUrl;
+
+    const COMPANIES_SITEMAP_URL: &str = "https://www.ycombinator.com/companies/sitemap";
+
+    /// Fetch the YC companies sitemap and return company pages, canonicalized
+    /// and deduplicated, with their `<lastmod>` when the sitemap has one.
+    pub async fn fetch_company_urls() -> Result<Vec<PageUrl>> {
+        let client = reqwest::Client::new();
+
+        info!("Fetching companies sitemap: {}", COMPANIES_SITEMAP_URL);
+        let xml = client
+            .get(COMPANIES_SITEMAP_URL)
+            .send()
+            .await?
+            .text()
+            .await
+            .context("Failed to fetch companies sitemap")?;
+
+        let all_urls = parse_urlset(&xml)?;
+        info!("Total URLs in sitemap: {}", all_urls.len());
+
+        // Filter to company pages only (exclude /industry/, /location/, /batch/, etc.)
+        let mut seen = HashSet::new();
+        let filtered: Vec<PageUrl> = all_urls
+            .iter()
+            .filter_map(|(url, lastmod)| {
+                let (url, slug) = canonicalize(url)?;
+                let lastmod = lastmod.as_deref().and_then(normalize_lastmod);
+                Some(PageUrl { url, slug, lastmod })
+            })
+            .filter(|p| seen.insert(p.url.clone()))
+            .collect();
+
+        info!("Company pages after filtering: {}", filtered.len());
+        Ok(filtered)
+    }
+
+    /// Parse a urlset XML and return each `<url>`'s `<loc>` and raw `<lastmod>`.
+    fn parse_urlset(xml: &str) -> Result<Vec<(String, Option<String>)>> {
+        let mut reader = quick_xml::Reader::from_str(xml);
+        let mut urls = Vec::new();
+        let mut in_url = false;
+        let mut field: Option<&'static str> = None;
+        let mut loc: Option<String> = None;
+        let mut lastmod: Option<String> = None;
+        let mut buf = Vec::new();
+
+        loop {
+            match reader.read_event_into(&mut buf) {
+                Ok(quick_xml::events::Event::Start(e)) => match e.name().as_ref() {
+                    b"url" => in_url = true,
+                    b"loc" if in_url => field = Some("loc"),
+                    b"lastmod" if in_url => field = Some("lastmod"),
+                    _ => {}
+                },
+                Ok(quick_xml::events::Event::Text(e)) => {
+                    let text = e.unescape()?.trim().to_string();
+                    match field {
+                        Some("loc") => loc = Some(text),
+                        Some("lastmod") => lastmod = Some(text),
+                        _ => {}
+                    }
+                }
+                Ok(quick_xml::events::Event::End(e)) => match e.name().as_ref() {
+                    b"loc" | b"lastmod" => field = None,
+                    b"url" => {
+                        in_url = false;
+                        if let Some(loc) = loc.take() {
+                            urls.push((loc, lastmod.take()));
+                        }
+                        lastmod = None;
+                    }
+                    _ => {}
+                },
+                Ok(quick_xml::events::Event::Eof) => break,
+                Err(e) => return Err(e.into()),
                 _ => {}
-            },
-            Ok(quick_xml::events::Event::Eof) => break,
-            Err(e) => return Err(e.into()),
-            _ => {}
+            }
+            buf.clear();
+        }
+        Ok(urls)
+    }
+
+    /// W3C datetime (`2024-05-01`, `2024-05-01T12:00:00+02:00`) → UTC in SQLite's
+    /// `datetime()` format, so it compares directly with `pages.visited_at`.
+    fn normalize_lastmod(raw: &str) -> Option<String> {
+        const SQLITE: &str = "%Y-%m-%d %H:%M:%S";
+        let raw = raw.trim();
+        if let Ok(dt) = chrono::DateTime::parse_from_rfc3339(raw) {
+            return Some(dt.with_timezone(&chrono::Utc).format(SQLITE).to_string());
         }
-        buf.clear();
+        chrono::NaiveDate::parse_from_str(raw, "%Y-%m-%d")
+            .ok()
+            .and_then(|d| d.and_hms_opt(0, 0, 0))
+            .map(|dt| dt.format(SQLITE).to_string())
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        #[test]
+        fn urlset_with_lastmod() {
+            let xml = "<urlset>\
+                <url><loc>https://www.ycombinator.com/companies/stripe</loc><lastmod>2024-05-01T12:00:00+02:00</lastmod></url>\
+                <url><loc>https://www.ycombinator.com/companies/airbnb</loc></url>\
+                </urlset>";
+            let urls = parse_urlset(xml).unwrap();
+            assert_eq!(urls.len(), 2);
+            assert_eq!(urls[0].1.as_deref(), Some("2024-05-01T12:00:00+02:00"));
+            assert_eq!(urls[1].1, None);
+            assert_eq!(normalize_lastmod("2024-05-01T12:00:00+02:00").as_deref(), Some("2024-05-01 10:00:00"));
+            assert_eq!(normalize_lastmod("2024-05-01").as_deref(), Some("2024-05-01 00:00:00"));
+            assert_eq!(normalize_lastmod("yesterday"), None);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn canonical_urls_collapse_variants() {
+        let airbnb = Some((
+            "https://www.ycombinator.com/companies/airbnb".to_string(),
+            "airbnb".to_string(),
+        ));
+        assert_eq!(canonicalize("https://www.ycombinator.com/companies/Airbnb"), airbnb);
+        assert_eq!(canonicalize("https://www.ycombinator.com/companies/airbnb/"), airbnb);
+        assert_eq!(canonicalize("HTTPS://YCombinator.com/companies/AIRBNB?ref=x#top"), airbnb);
+        assert_eq!(
+            canonicalize("https://www.ycombinator.com/companies/Cafe\u{301}").map(|(_, s)| s),
+            Some("caf\u{e9}".to_string())
+        );
+        assert_eq!(canonical_slug("caf%C3%A9"), "caf\u{e9}");
+    }
+
+    #[test]
+    fn non_company_urls_are_rejected() {
+        assert_eq!(canonicalize("https://www.ycombinator.com/companies/industry/Fintech"), None);
+        assert_eq!(canonicalize("https://www.ycombinator.com/companies/"), None);
+        assert_eq!(canonicalize("https://example.com/companies/acme"), None);
+        assert_eq!(canonicalize("https://www.ycombinator.com/people/acme"), None);
+    }
+
+    #[test]
+    fn batch_listing_links() {
+        let w24: Batch = "W24".parse().unwrap();
+        assert_eq!(batch_url(&w24), "https://www.ycombinator.com/companies?batch=Winter%202024");
+        let md = "[All companies](https://www.ycombinator.com/companies?batch=Winter%202024)\n\
+                  [Acme San Francisco Payroll for teams W24 Fintech](/companies/acme)\n\
+                  [Beta](https://www.ycombinator.com/companies/Beta?ref=batch)\n\
+                  [Fintech](/companies/industry/Fintech)\n";
+        let mut slugs: Vec<_> = listed_slugs(md).into_iter().collect();
+        slugs.sort();
+        assert_eq!(slugs, ["acme", "beta"]);
     }
-    Ok(urls)
 }
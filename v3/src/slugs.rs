@@ -0,0 +1,105 @@
+use std::collections::HashSet;
+use std::path::Path;
+
+use anyhow::{Context, Result};
+
+/// Read a `--slugs-file`: one slug per line, or a CSV whose `slug` column
+/// (else first column) holds slugs or company URLs. `#` lines are comments.
+/// Slugs are canonicalized the same way as sitemap URLs (`Airbnb` → `airbnb`).
+pub fn load(path: &Path) -> Result<Vec<String>> {
+    let text = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read slugs file {}", path.display()))?;
+    let slugs = parse(&text);
+    if slugs.is_empty() {
+        anyhow::bail!("No slugs found in {}", path.display());
+    }
+    Ok(slugs)
+}
+
+pub fn parse(text: &str) -> Vec<String> {
+    let mut lines = text
+        .lines()
+        .map(str::trim)
+        .filter(|l| !l.is_empty() && !l.starts_with('#'))
+        .peekable();
+
+    // Header row: pick the "slug" column if present, otherwise skip nothing.
+    let mut column = 0;
+    if let Some(first) = lines.peek() {
+        let fields = split_fields(first);
+        if let Some(idx) = fields.iter().position(|f| f.eq_ignore_ascii_case("slug")) {
+            column = idx;
+            lines.next();
+        }
+    }
+
+    let mut seen = HashSet::new();
+    lines
+        .filter_map(|l| split_fields(l).into_iter().nth(column))
+        .map(|f| crate::sitemap::canonical_slug(&slug_from_field(&f)))
+        .filter(|s| !s.is_empty() && seen.insert(s.clone()))
+        .collect()
+}
+
+/// Fields of one CSV line, trimmed. Quoted fields may hold commas, with
+/// `""` for a quote, as `report::csv_field` writes them.
+fn split_fields(line: &str) -> Vec<String> {
+    let mut fields = Vec::new();
+    let mut field = String::new();
+    let mut quoted = false;
+    let mut chars = line.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if quoted && chars.peek() == Some(&'"') => {
+                field.push('"');
+                chars.next();
+            }
+            '"' => quoted = !quoted,
+            ',' if !quoted => fields.push(std::mem::take(&mut field).trim().to_string()),
+            _ => field.push(c),
+        }
+    }
+    fields.push(field.trim().to_string());
+    fields
+}
+
+/// Accept bare slugs or full `/companies/<slug>` URLs.
+fn slug_from_field(field: &str) -> String {
+    match field.rsplit_once("/companies/") {
+        Some((_, rest)) => rest
+            .split(['/', '?', '#'])
+            .next()
+            .unwrap_or("")
+            .to_string(),
+        None => field.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn newline_list() {
+        assert_eq!(parse("stripe\n\n# comment\ndoordash\nstripe\n"), vec!["stripe", "doordash"]);
+    }
+
+    #[test]
+    fn csv_with_slug_header() {
+        let csv = "name,slug,batch\nStripe,stripe,S09\n\"DoorDash\",\"doordash\",S13\n";
+        assert_eq!(parse(csv), vec!["stripe", "doordash"]);
+    }
+
+    #[test]
+    fn quoted_fields_keep_their_commas() {
+        let csv = "name,slug\n\"Acme, Inc.\",acme\n\"The \"\"Best\"\" Co, LLC\",best-co\n";
+        assert_eq!(parse(csv), vec!["acme", "best-co"]);
+        assert_eq!(split_fields("\"Acme, Inc.\", acme"), vec!["Acme, Inc.", "acme"]);
+    }
+
+    #[test]
+    fn urls_are_reduced_to_slugs() {
+        let text = "https://www.ycombinator.com/companies/stripe\nhttps://www.ycombinator.com/companies/doordash/jobs?x=1";
+        assert_eq!(parse(text), vec!["stripe", "doordash"]);
+    }
+}
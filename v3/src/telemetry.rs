@@ -0,0 +1,86 @@
+use anyhow::Result;
+use tracing_subscriber::layer::SubscriberExt;
+use tracing_subscriber::util::SubscriberInitExt;
+use tracing_subscriber::EnvFilter;
+
+/// Keeps the span exporter alive for the whole run. Dropping it flushes
+/// any spans still buffered in the batch processor.
+pub struct TelemetryGuard {
+    #[cfg(feature = "otel")]
+    provider: Option<opentelemetry_sdk::trace::SdkTracerProvider>,
+}
+
+impl Drop for TelemetryGuard {
+    fn drop(&mut self) {
+        #[cfg(feature = "otel")]
+        if let Some(provider) = self.provider.take() {
+            if let Err(e) = provider.shutdown() {
+                eprintln!("OTLP exporter shutdown failed: {}", e);
+            }
+        }
+    }
+}
+
+/// Install the global tracing subscriber: fmt logging filtered by `RUST_LOG`,
+/// plus OTLP span export when built with `--features otel` and
+/// `OTEL_EXPORTER_OTLP_ENDPOINT` is set.
+pub fn init() -> Result<TelemetryGuard> {
+    let filter = EnvFilter::try_from_default_env().unwrap_or_else(|_| "info".into());
+    let registry = tracing_subscriber::registry()
+        .with(filter)
+        .with(tracing_subscriber::fmt::layer());
+
+    #[cfg(feature = "otel")]
+    {
+        use opentelemetry::trace::TracerProvider as _;
+
+        let provider = otlp_provider()?;
+        let layer = provider
+            .as_ref()
+            .map(|p| tracing_opentelemetry::layer().with_tracer(p.tracer("yc_scraper")));
+        registry.with(layer).init();
+        Ok(TelemetryGuard { provider })
+    }
+
+    #[cfg(not(feature = "otel"))]
+    {
+        registry.init();
+        Ok(TelemetryGuard {})
+    }
+}
+
+/// Build an OTLP/HTTP tracer provider from the standard `OTEL_*` env vars.
+/// Returns None when no endpoint is configured so local runs stay silent.
+#[cfg(feature = "otel")]
+fn otlp_provider() -> Result<Option<opentelemetry_sdk::trace::SdkTracerProvider>> {
+    use opentelemetry_otlp::{WithExportConfig, WithHttpConfig};
+
+    let Ok(endpoint) = std::env::var("OTEL_EXPORTER_OTLP_ENDPOINT") else {
+        return Ok(None);
+    };
+
+    // The blocking client must be built off the tokio runtime, otherwise
+    // reqwest panics when its internal runtime is created/dropped.
+    let client = std::thread::spawn(reqwest::blocking::Client::new)
+        .join()
+        .map_err(|_| anyhow::anyhow!("Failed to build OTLP HTTP client"))?;
+
+    let exporter = opentelemetry_otlp::SpanExporter::builder()
+        .with_http()
+        .with_http_client(client)
+        .with_endpoint(format!("{}/v1/traces", endpoint.trim_end_matches('/')))
+        .build()?;
+
+    let service_name =
+        std::env::var("OTEL_SERVICE_NAME").unwrap_or_else(|_| "yc_scraper".to_string());
+    let resource = opentelemetry_sdk::Resource::builder()
+        .with_service_name(service_name)
+        .build();
+
+    Ok(Some(
+        opentelemetry_sdk::trace::SdkTracerProvider::builder()
+            .with_batch_exporter(exporter)
+            .with_resource(resource)
+            .build(),
+    ))
+}
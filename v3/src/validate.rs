@@ -0,0 +1,156 @@
+use std::sync::LazyLock;
+
+use regex::Regex;
+
+/// `page_data.content_kind` of a page that looks like a YC company page.
+pub const COMPANY: &str = "company";
+
+/// Footer fields every company page has; their presence outweighs any
+/// banner text around them.
+static COMPANY_META_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"(?m)^(Founded|Batch|Team Size|Location):").unwrap());
+static JS_SHELL_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?i)(enable javascript|javascript is (disabled|required)|requires javascript)").unwrap()
+});
+static CONSENT_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?i)(we use cookies|cookie (consent|preferences|settings)|accept all cookies)").unwrap()
+});
+static NOT_FOUND_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?i)(page not found|\b404\b|this page (doesn't|does not) exist)").unwrap()
+});
+#[cfg_attr(not(feature = "network"), allow(dead_code))]
+static BLOCK_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(
+        r"(?i)(captcha|verify you are (a )?human|are you a robot|unusual traffic|access denied|attention required|checking your browser|pardon our interruption)",
+    )
+    .unwrap()
+});
+static DIRECTORY_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?i)(startups funded by y combinator|startup directory)").unwrap()
+});
+
+/// Markdown shorter than this with no company fields is an empty shell.
+const MIN_CONTENT_CHARS: usize = 200;
+
+/// A company page under this share of its usual length was most likely
+/// served a block page.
+#[cfg_attr(not(feature = "network"), allow(dead_code))]
+const BLOCK_SHRINK_RATIO: f64 = 0.25;
+
+/// Why scraped markdown looks like a block rather than the real page: bot
+/// check wording on a page without company fields, or a company page far
+/// shorter than `typical_len`, the average of earlier good scrapes.
+#[cfg_attr(not(feature = "network"), allow(dead_code))]
+pub fn block_reason(markdown: &str, typical_len: Option<f64>) -> Option<String> {
+    if !COMPANY_META_RE.is_match(markdown) {
+        if let Some(m) = BLOCK_RE.find(markdown) {
+            return Some(format!("block page ({})", m.as_str().to_lowercase()));
+        }
+    }
+    let len = markdown.len() as f64;
+    match typical_len {
+        Some(typical) if len < typical * BLOCK_SHRINK_RATIO => {
+            Some(format!("{} bytes, usually {:.0}", len, typical))
+        }
+        _ => None,
+    }
+}
+
+/// `page_data.content_kind` of a scrape that redirected to another
+/// company's page (a rename); set from the final URL, not the markdown.
+pub const RENAMED: &str = "renamed";
+
+/// `job_details.content_kind` of a page that looks like a job posting.
+pub const JOB: &str = "job";
+
+/// Job pages are shorter than company pages; below this there's no posting.
+const MIN_JOB_CHARS: usize = 80;
+
+/// A 404 notice sits in the page title; further down "404" may be body text.
+const NOT_FOUND_HEAD_CHARS: usize = 300;
+
+/// Classify scraped markdown: `company`, or what came back instead —
+/// `js_shell` (empty or "enable JavaScript"), `consent_wall`, `not_found`,
+/// or `directory` (the generic "Startups funded by Y Combinator" listing).
+/// Unrecognized pages count as `company` so thin real pages still process.
+pub fn content_kind(markdown: &str) -> &'static str {
+    if COMPANY_META_RE.is_match(markdown) {
+        COMPANY
+    } else if markdown.trim().chars().count() < MIN_CONTENT_CHARS || JS_SHELL_RE.is_match(markdown) {
+        "js_shell"
+    } else if CONSENT_RE.is_match(markdown) {
+        "consent_wall"
+    } else if NOT_FOUND_RE.is_match(markdown) {
+        "not_found"
+    } else if DIRECTORY_RE.is_match(markdown) {
+        "directory"
+    } else {
+        COMPANY
+    }
+}
+
+/// Classify a scraped job detail page: `job`, `js_shell` (blank or
+/// "enable JavaScript") or `not_found` (a 404 notice at the top).
+pub fn job_content_kind(markdown: &str) -> &'static str {
+    let text = markdown.trim();
+    let head: String = text.chars().take(NOT_FOUND_HEAD_CHARS).collect();
+    if text.chars().count() < MIN_JOB_CHARS || JS_SHELL_RE.is_match(&head) {
+        "js_shell"
+    } else if NOT_FOUND_RE.is_match(&head) {
+        "not_found"
+    } else {
+        JOB
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fixtures_are_company_pages() {
+        for md in [
+            include_str!("../tests/fixtures/stripe.md"),
+            include_str!("../tests/fixtures/doordash.md"),
+            include_str!("../tests/fixtures/groupahead.md"),
+        ] {
+            assert_eq!(content_kind(md), COMPANY);
+        }
+    }
+
+    #[test]
+    fn detects_interstitials() {
+        let pad = "Lorem ipsum dolor sit amet. ".repeat(10);
+        assert_eq!(content_kind(""), "js_shell");
+        assert_eq!(content_kind(&format!("You need to enable JavaScript to run this app.\n{}", pad)), "js_shell");
+        assert_eq!(
+            content_kind(&format!("We use cookies to improve your experience.\n\n[Accept all cookies](#)\n{}", pad)),
+            "consent_wall"
+        );
+        assert_eq!(content_kind(&format!("# 404\n\nPage not found\n{}", pad)), "not_found");
+        assert_eq!(
+            content_kind(&format!("# Startups funded by Y Combinator (YC) 2025\n{}", pad)),
+            "directory"
+        );
+        let banner_over_page = format!("We use cookies.\n\n### Acme\n\nFounded:2020\n{}", pad);
+        assert_eq!(content_kind(&banner_over_page), COMPANY);
+    }
+
+    #[test]
+    fn block_pages() {
+        let pad = "Lorem ipsum dolor sit amet. ".repeat(10);
+        let captcha = format!("# Attention Required! | Cloudflare\n\nPlease complete the CAPTCHA.\n{}", pad);
+        assert_eq!(block_reason(&captcha, None).as_deref(), Some("block page (attention required)"));
+        let captcha_startup = format!("### Acme\n\nCAPTCHA solving API\n\nFounded:2020\n{}", pad);
+        assert_eq!(block_reason(&captcha_startup, Some(400.0)), None);
+        assert_eq!(block_reason(&captcha_startup, Some(4000.0)).as_deref(), Some("324 bytes, usually 4000"));
+    }
+
+    #[test]
+    fn job_pages() {
+        let pad = "Build and run our billing service. ".repeat(20);
+        assert_eq!(job_content_kind("  \n"), "js_shell");
+        assert_eq!(job_content_kind(&format!("# Page not found\n\n{}", pad)), "not_found");
+        assert_eq!(job_content_kind(&format!("# Backend Engineer\n\n{}Map 404 errors to alerts.", pad)), JOB);
+    }
+}
@@ -0,0 +1,95 @@
+use std::thread::JoinHandle;
+
+use anyhow::{anyhow, Result};
+use rusqlite::Connection;
+use tokio::sync::{mpsc, oneshot};
+
+use crate::db;
+
+type Job = Box<dyn FnOnce(&Connection) + Send>;
+
+/// Queued jobs before `call` starts waiting for room.
+const QUEUE_DEPTH: usize = 64;
+
+/// A dedicated thread owning its own SQLite connection. Async code sends
+/// closures to it instead of holding a `Connection` across `.await`s, so
+/// writes never block the runtime. Dropping a `call` future does not abort
+/// its job: once queued, it runs to completion (commit or rollback).
+pub struct DbWriter {
+    jobs: mpsc::Sender<Job>,
+    thread: JoinHandle<()>,
+}
+
+impl DbWriter {
+    pub fn spawn() -> Result<Self> {
+        Self::with_connection(db::connect()?)
+    }
+
+    pub fn with_connection(conn: Connection) -> Result<Self> {
+        let (jobs, mut rx) = mpsc::channel::<Job>(QUEUE_DEPTH);
+        let thread = std::thread::Builder::new()
+            .name("db-writer".to_string())
+            .spawn(move || {
+                while let Some(job) = rx.blocking_recv() {
+                    job(&conn);
+                }
+            })?;
+        Ok(DbWriter { jobs, thread })
+    }
+
+    /// Run `f` on the writer thread and wait for its result.
+    #[cfg_attr(not(feature = "network"), allow(dead_code))]
+    pub async fn call<R, F>(&self, f: F) -> Result<R>
+    where
+        F: FnOnce(&Connection) -> Result<R> + Send + 'static,
+        R: Send + 'static,
+    {
+        let (reply, result) = oneshot::channel();
+        let job: Job = Box::new(move |conn| {
+            let _ = reply.send(f(conn));
+        });
+        self.jobs
+            .send(job)
+            .await
+            .map_err(|_| anyhow!("DB writer thread has stopped"))?;
+        result
+            .await
+            .map_err(|_| anyhow!("DB writer thread dropped a job"))?
+    }
+
+    /// Drain queued jobs and join the thread.
+    pub async fn close(self) -> Result<()> {
+        drop(self.jobs);
+        let thread = self.thread;
+        tokio::task::spawn_blocking(move || thread.join())
+            .await?
+            .map_err(|_| anyhow!("DB writer thread panicked"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn jobs_run_in_order_and_return_results() {
+        let writer = DbWriter::with_connection(Connection::open_in_memory().unwrap()).unwrap();
+        writer
+            .call(|c| Ok(c.execute_batch("CREATE TABLE t (x INTEGER)")?))
+            .await
+            .unwrap();
+        for i in 0..3 {
+            writer
+                .call(move |c| Ok(c.execute("INSERT INTO t VALUES (?1)", [i])?))
+                .await
+                .unwrap();
+        }
+        let sum: i64 = writer
+            .call(|c| Ok(c.query_row("SELECT SUM(x) FROM t", [], |r| r.get(0))?))
+            .await
+            .unwrap();
+        assert_eq!(sum, 3);
+        assert!(writer.call(|c| Ok(c.execute_batch("NOT SQL")?)).await.is_err());
+        writer.close().await.unwrap();
+    }
+}